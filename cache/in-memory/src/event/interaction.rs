@@ -81,6 +81,7 @@ mod tests {
                     resolved: Some(CommandInteractionDataResolved {
                         channels: Vec::new(),
                         members: vec![InteractionMember {
+                            communication_disabled_until: None,
                             hoisted_role: None,
                             id: UserId(7),
                             joined_at: Some("joined at date".into()),
@@ -120,6 +121,7 @@ mod tests {
                 id: InteractionId(4),
                 kind: InteractionType::ApplicationCommand,
                 member: Some(PartialMember {
+                    communication_disabled_until: None,
                     deaf: false,
                     joined_at: Some("joined at".into()),
                     mute: false,
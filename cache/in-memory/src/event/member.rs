@@ -32,6 +32,7 @@ impl InMemoryCache {
 
         self.cache_user(Cow::Owned(member.user), Some(guild_id));
         let cached = CachedMember {
+            communication_disabled_until: member.communication_disabled_until,
             deaf: Some(member.deaf),
             guild_id,
             joined_at: member.joined_at,
@@ -71,6 +72,7 @@ impl InMemoryCache {
             .insert(user_id);
 
         let cached = CachedMember {
+            communication_disabled_until: member.communication_disabled_until.to_owned(),
             deaf: Some(member.deaf),
             guild_id,
             joined_at: member.joined_at.to_owned(),
@@ -104,6 +106,7 @@ impl InMemoryCache {
             .insert(member.id);
 
         let cached = CachedMember {
+            communication_disabled_until: member.communication_disabled_until.to_owned(),
             deaf,
             guild_id,
             joined_at: member.joined_at.to_owned(),
@@ -194,6 +197,7 @@ impl UpdateCache for MemberUpdate {
             None => return,
         };
 
+        member.communication_disabled_until = self.communication_disabled_until.clone();
         member.deaf = self.deaf.or(member.deaf);
         member.mute = self.mute.or(member.mute);
         member.nick = self.nick.clone();
@@ -308,4 +312,35 @@ mod tests {
         });
         assert!(!cache.0.users.contains_key(&user_id));
     }
+
+    #[test]
+    fn test_member_update_communication_disabled_until() {
+        let cache = InMemoryCache::new();
+        let guild_id = GuildId(1);
+        let user_id = UserId(2);
+        cache.cache_member(guild_id, test::member(user_id, guild_id));
+
+        let update = MemberUpdate {
+            communication_disabled_until: Some("2021-12-31T23:59:59+00:00".to_owned()),
+            guild_id,
+            deaf: None,
+            joined_at: "timestamp".to_owned(),
+            mute: None,
+            nick: None,
+            pending: false,
+            premium_since: None,
+            roles: Vec::new(),
+            user: test::user(user_id),
+        };
+        cache.update(&update);
+
+        assert_eq!(
+            Some("2021-12-31T23:59:59+00:00"),
+            cache
+                .member(guild_id, user_id)
+                .unwrap()
+                .communication_disabled_until
+                .as_deref()
+        );
+    }
 }
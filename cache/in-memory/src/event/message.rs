@@ -160,6 +160,7 @@ mod tests {
             interaction: None,
             kind: MessageType::Regular,
             member: Some(PartialMember {
+                communication_disabled_until: None,
                 deaf: false,
                 joined_at: None,
                 mute: false,
@@ -283,6 +283,7 @@ mod tests {
             deaf: false,
             guild_id: Some(GuildId(2)),
             member: Some(Member {
+                communication_disabled_until: None,
                 deaf: false,
                 guild_id: GuildId(2),
                 hoisted_role: None,
@@ -543,6 +543,10 @@ impl UpdateCache for Event {
         use Event::*;
 
         match self {
+            AutoModerationActionExecution(_) => {}
+            AutoModerationRuleCreate(_) => {}
+            AutoModerationRuleDelete(_) => {}
+            AutoModerationRuleUpdate(_) => {}
             BanAdd(_) => {}
             BanRemove(_) => {}
             ChannelCreate(v) => c.update(v),
@@ -592,6 +596,8 @@ impl UpdateCache for Event {
             ShardReconnecting(_) => {}
             ShardPayload(_) => {}
             ShardResuming(_) => {}
+            ShardSessionStartLimitExhausted(_) => {}
+            ShardZombied(_) => {}
             StageInstanceCreate(v) => c.update(v),
             StageInstanceDelete(v) => c.update(v),
             StageInstanceUpdate(v) => c.update(v),
@@ -10,6 +10,8 @@ use twilight_model::{
 /// [`Member`]: twilight_model::guild::Member
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct CachedMember {
+    /// ISO 8601 timestamp until which the member is timed out.
+    pub communication_disabled_until: Option<String>,
     /// Whether the member is deafened in a voice channel.
     pub deaf: Option<bool>,
     /// ID of the guild this member is a part of.
@@ -33,6 +35,7 @@ pub struct CachedMember {
 impl PartialEq<Member> for CachedMember {
     fn eq(&self, other: &Member) -> bool {
         (
+            &self.communication_disabled_until,
             self.deaf,
             self.joined_at.as_ref(),
             self.mute,
@@ -42,6 +45,7 @@ impl PartialEq<Member> for CachedMember {
             &self.roles,
             self.user_id,
         ) == (
+            &other.communication_disabled_until,
             Some(other.deaf),
             other.joined_at.as_ref(),
             Some(other.mute),
@@ -57,6 +61,7 @@ impl PartialEq<Member> for CachedMember {
 impl PartialEq<&PartialMember> for CachedMember {
     fn eq(&self, other: &&PartialMember) -> bool {
         (
+            &self.communication_disabled_until,
             self.deaf,
             self.joined_at.as_ref(),
             self.mute,
@@ -64,6 +69,7 @@ impl PartialEq<&PartialMember> for CachedMember {
             &self.premium_since,
             &self.roles,
         ) == (
+            &other.communication_disabled_until,
             Some(other.deaf),
             other.joined_at.as_ref(),
             Some(other.mute),
@@ -77,11 +83,13 @@ impl PartialEq<&PartialMember> for CachedMember {
 impl PartialEq<&InteractionMember> for CachedMember {
     fn eq(&self, other: &&InteractionMember) -> bool {
         (
+            &self.communication_disabled_until,
             self.joined_at.as_ref(),
             &self.nick,
             &self.premium_since,
             &self.roles,
         ) == (
+            &other.communication_disabled_until,
             other.joined_at.as_ref(),
             &other.nick,
             &other.premium_since,
@@ -101,7 +109,8 @@ mod tests {
     };
 
     assert_fields!(
-        CachedMember: deaf,
+        CachedMember: communication_disabled_until,
+        deaf,
         guild_id,
         joined_at,
         mute,
@@ -114,6 +123,7 @@ mod tests {
 
     fn cached_member() -> CachedMember {
         CachedMember {
+            communication_disabled_until: None,
             deaf: Some(false),
             guild_id: GuildId(3),
             joined_at: None,
@@ -147,6 +157,7 @@ mod tests {
     #[test]
     fn test_eq_member() {
         let member = Member {
+            communication_disabled_until: None,
             deaf: false,
             guild_id: GuildId(3),
             hoisted_role: Some(RoleId(4)),
@@ -165,6 +176,7 @@ mod tests {
     #[test]
     fn test_eq_partial_member() {
         let member = PartialMember {
+            communication_disabled_until: None,
             deaf: false,
             joined_at: None,
             mute: true,
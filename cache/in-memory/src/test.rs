@@ -44,6 +44,7 @@ pub fn cache_with_message_and_reactions() -> InMemoryCache {
         interaction: None,
         kind: MessageType::Regular,
         member: Some(PartialMember {
+            communication_disabled_until: None,
             deaf: false,
             joined_at: None,
             mute: false,
@@ -76,6 +77,7 @@ pub fn cache_with_message_and_reactions() -> InMemoryCache {
         },
         guild_id: Some(GuildId(1)),
         member: Some(Member {
+            communication_disabled_until: None,
             deaf: false,
             guild_id: GuildId(1),
             hoisted_role: None,
@@ -108,6 +110,7 @@ pub fn cache_with_message_and_reactions() -> InMemoryCache {
     cache.update(&reaction);
 
     reaction.member.replace(Member {
+        communication_disabled_until: None,
         deaf: false,
         guild_id: GuildId(1),
         hoisted_role: None,
@@ -199,6 +202,7 @@ pub fn guild_channel_text() -> (GuildId, ChannelId, GuildChannel) {
 
 pub fn member(id: UserId, guild_id: GuildId) -> Member {
     Member {
+        communication_disabled_until: None,
         deaf: false,
         guild_id,
         hoisted_role: None,
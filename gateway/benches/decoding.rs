@@ -0,0 +1,128 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use serde::de::DeserializeSeed;
+use serde_json::{Deserializer, Value};
+use twilight_gateway::shard::etf;
+use twilight_model::gateway::{
+    event::GatewayEventDeserializer,
+    payload::{
+        identify::{Identify, IdentifyInfo, IdentifyProperties},
+        Heartbeat,
+    },
+    Intents,
+};
+
+const GUILD_ROLE_DELETE: &str = r#"{
+    "op": 0,
+    "s": 2,
+    "d": {
+        "guild_id": "1",
+        "role_id": "2"
+    },
+    "t": "GUILD_ROLE_DELETE"
+}"#;
+
+const MESSAGE_CREATE: &str = r#"{
+    "op": 0,
+    "s": 3,
+    "d": {
+        "attachments": [],
+        "author": {
+            "avatar": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "discriminator": "0001",
+            "id": "3",
+            "public_flags": 131072,
+            "username": "test"
+        },
+        "channel_id": "2",
+        "content": "ping",
+        "edited_timestamp": null,
+        "embeds": [],
+        "flags": 0,
+        "guild_id": "1",
+        "id": "4",
+        "member": {
+            "deaf": false,
+            "hoisted_role": null,
+            "joined_at": "2020-01-01T00:00:00.000000+00:00",
+            "mute": false,
+            "nick": null,
+            "roles": ["5"]
+        },
+        "mention_everyone": false,
+        "mention_roles": [],
+        "mentions": [],
+        "pinned": false,
+        "timestamp": "2021-01-01T00:00:00.000000+00:00",
+        "tts": false,
+        "type": 0
+    },
+    "t": "MESSAGE_CREATE"
+}"#;
+
+/// Encode a JSON payload as ETF.
+fn to_etf(json: &str) -> Vec<u8> {
+    let value = serde_json::from_str::<Value>(json).unwrap();
+
+    etf::to_vec(&value).unwrap()
+}
+
+fn json_event(input: &str) {
+    let gateway_deserializer = GatewayEventDeserializer::from_json(input).unwrap();
+    let mut json_deserializer = Deserializer::from_str(input);
+    gateway_deserializer
+        .deserialize(&mut json_deserializer)
+        .unwrap();
+}
+
+fn etf_event(input: &[u8]) {
+    let (op, seq, event_type) = etf::payload_header(input).unwrap();
+    etf::parse_gateway_event(op, seq, event_type, input).unwrap();
+}
+
+fn identify() -> Identify {
+    Identify::new(IdentifyInfo {
+        compress: false,
+        intents: Intents::GUILDS | Intents::GUILD_MESSAGES,
+        large_threshold: 250,
+        presence: None,
+        properties: IdentifyProperties::new("twilight.rs", "twilight.rs", "linux", "", ""),
+        shard: Some([0, 1]),
+        token: "Bot token".to_owned(),
+    })
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let role_delete = to_etf(GUILD_ROLE_DELETE);
+    let message_create = to_etf(MESSAGE_CREATE);
+    let identify = identify();
+    let heartbeat = Heartbeat::new(3);
+
+    c.bench_function("json gateway event role delete", |b| {
+        b.iter(|| json_event(GUILD_ROLE_DELETE))
+    });
+    c.bench_function("etf gateway event role delete", |b| {
+        b.iter(|| etf_event(&role_delete))
+    });
+    c.bench_function("json gateway event message create", |b| {
+        b.iter(|| json_event(MESSAGE_CREATE))
+    });
+    c.bench_function("etf gateway event message create", |b| {
+        b.iter(|| etf_event(&message_create))
+    });
+    c.bench_function("json identify", |b| {
+        b.iter(|| serde_json::to_vec(&identify).unwrap())
+    });
+    c.bench_function("etf identify", |b| {
+        b.iter(|| etf::to_vec(&identify).unwrap())
+    });
+    c.bench_function("json heartbeat", |b| {
+        b.iter(|| serde_json::to_vec(&heartbeat).unwrap())
+    });
+    c.bench_function("etf heartbeat", |b| {
+        b.iter(|| etf::to_vec(&heartbeat).unwrap())
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
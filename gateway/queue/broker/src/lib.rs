@@ -0,0 +1,165 @@
+//! HTTP broker coordinating the gateway session initializations of shards
+//! across multiple processes.
+//!
+//! Shards request permission to initialize a session via the
+//! [`HttpQueue`]'s protocol: a `GET` request with the shard ID and the total
+//! number of shards as the `shard` and `total` query parameters, such as
+//! `GET /?shard=3&total=16`. The broker responds once the request was
+//! released by its queue, which is usually a [`LargeBotQueue`] releasing one
+//! session per bucket of the bot's maximum concurrency every few seconds,
+//! within the bot's daily session start limit.
+//!
+//! Requests with missing or invalid query parameters are responded to with a
+//! `400 Bad Request` status code.
+//!
+//! Once the daily session start limit is exhausted, except for the sessions
+//! kept in reserve, requests are refused with a `429 Too Many Requests` status
+//! code, the `Retry-After` header containing the number of seconds until the
+//! limit is reset, and the `X-Session-Remaining` header containing the number
+//! of remaining, reserved, sessions.
+//!
+//! # Running
+//!
+//! The binary is configured via environment variables:
+//!
+//! - `DISCORD_TOKEN`: token of the bot, used to retrieve its session start
+//!   limit;
+//! - `HOST`: address to listen on, defaulting to `0.0.0.0`;
+//! - `PORT`: port to listen on, defaulting to `8080`;
+//! - `RESERVE`: number of sessions to keep in reserve, defaulting to `0`.
+//!
+//! [`HttpQueue`]: twilight_gateway_queue::HttpQueue
+//! [`LargeBotQueue`]: twilight_gateway_queue::LargeBotQueue
+
+#![deny(
+    clippy::all,
+    clippy::missing_const_for_fn,
+    clippy::pedantic,
+    future_incompatible,
+    missing_docs,
+    nonstandard_style,
+    rust_2018_idioms,
+    unused,
+    warnings
+)]
+
+use hyper::{
+    header::RETRY_AFTER,
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+use std::{convert::Infallible, net::TcpListener, sync::Arc};
+use twilight_gateway_queue::{Queue, QueueRequestError, QueueRequestErrorType};
+
+/// Header of a refused request containing the number of remaining, reserved,
+/// sessions.
+const SESSION_REMAINING: &str = "x-session-remaining";
+
+/// Serve requests of shards on the listener until the server fails.
+///
+/// # Errors
+///
+/// Returns an error if the listener can't be used by the server or serving
+/// connections failed.
+pub async fn serve(listener: TcpListener, queue: Arc<Box<dyn Queue>>) -> Result<(), hyper::Error> {
+    let make_service = make_service_fn(move |_| {
+        let queue = Arc::clone(&queue);
+
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                handle(request, Arc::clone(&queue))
+            }))
+        }
+    });
+
+    tracing::info!("listening on {:?}", listener.local_addr());
+
+    Server::from_tcp(listener)?.serve(make_service).await
+}
+
+async fn handle(
+    request: Request<Body>,
+    queue: Arc<Box<dyn Queue>>,
+) -> Result<Response<Body>, Infallible> {
+    let shard_id = if let Some(shard_id) = request.uri().query().and_then(parse_query) {
+        shard_id
+    } else {
+        let mut response = Response::new(Body::from("shard and total are required"));
+        *response.status_mut() = StatusCode::BAD_REQUEST;
+
+        return Ok(response);
+    };
+
+    tracing::debug!("shard {}/{} requested allowance", shard_id[0], shard_id[1]);
+
+    if let Err(source) = queue.try_request(shard_id).await {
+        tracing::warn!("shard {}/{} refused: {}", shard_id[0], shard_id[1], source);
+
+        return Ok(refused(&source));
+    }
+
+    tracing::debug!("shard {}/{} allowed", shard_id[0], shard_id[1]);
+
+    Ok(Response::new(Body::empty()))
+}
+
+/// Create the response to a refused request.
+fn refused(source: &QueueRequestError) -> Response<Body> {
+    let mut response = Response::new(Body::from(source.to_string()));
+
+    if let QueueRequestErrorType::SessionStartLimitExhausted {
+        remaining,
+        reset_after,
+    } = source.kind()
+    {
+        // Round up so that shards don't retry before the limit was reset.
+        let retry_after = reset_after.as_secs() + u64::from(reset_after.subsec_nanos() > 0);
+
+        *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, retry_after.into());
+        response
+            .headers_mut()
+            .insert(SESSION_REMAINING, (*remaining).into());
+    } else {
+        *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+    }
+
+    response
+}
+
+/// Parse the shard ID and total from a query string.
+fn parse_query(query: &str) -> Option<[u64; 2]> {
+    let mut shard = None;
+    let mut total = None;
+
+    for pair in query.split('&') {
+        let mut parts = pair.splitn(2, '=');
+
+        match (parts.next(), parts.next()) {
+            (Some("shard"), Some(value)) => shard = value.parse().ok(),
+            (Some("total"), Some(value)) => total = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    match (shard, total) {
+        (Some(shard), Some(total)) if shard < total => Some([shard, total]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_query;
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(Some([3, 16]), parse_query("shard=3&total=16"));
+        assert_eq!(Some([0, 1]), parse_query("total=1&other=a&shard=0"));
+        assert_eq!(None, parse_query("shard=3"));
+        assert_eq!(None, parse_query("shard=16&total=16"));
+        assert_eq!(None, parse_query("shard=a&total=16"));
+    }
+}
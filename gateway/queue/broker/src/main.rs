@@ -0,0 +1,47 @@
+use std::{
+    env,
+    error::Error,
+    net::{IpAddr, SocketAddr, TcpListener},
+    sync::Arc,
+    time::Duration,
+};
+use twilight_gateway_queue::{LargeBotQueue, Queue};
+use twilight_http::Client;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    tracing_subscriber::fmt::init();
+
+    let host = env::var("HOST").map_or(Ok(IpAddr::from([0, 0, 0, 0])), |host| host.parse())?;
+    let port = env::var("PORT").map_or(Ok(8080), |port| port.parse())?;
+    let reserve = env::var("RESERVE").map_or(Ok(0), |reserve| reserve.parse())?;
+    let http = Client::new(env::var("DISCORD_TOKEN")?);
+
+    let limit = http.gateway().authed().await?.session_start_limit;
+    tracing::info!(
+        "max concurrency of {}, {}/{} sessions remaining",
+        limit.max_concurrency,
+        limit.remaining,
+        limit.total
+    );
+
+    #[allow(clippy::cast_possible_truncation)]
+    let queue: Box<dyn Queue> = Box::new(
+        LargeBotQueue::with_session_start_limit(
+            limit.max_concurrency.max(1) as usize,
+            &http,
+            limit.total,
+            limit.remaining,
+            Duration::from_millis(limit.reset_after),
+        )
+        .await
+        .reserve(reserve),
+    );
+
+    let listener = TcpListener::bind(SocketAddr::new(host, port))?;
+    listener.set_nonblocking(true)?;
+
+    twilight_gateway_queue_broker::serve(listener, Arc::new(queue)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use hyper::{Client as HyperClient, StatusCode};
+use std::{future::Future, net::TcpListener, sync::Arc, time::Duration};
+use tokio::time::timeout;
+use twilight_gateway_queue::{HttpQueue, LargeBotQueue, Queue, QueueRequestErrorType};
+use twilight_http::Client;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Start a broker releasing sessions in the buckets within the daily limit,
+/// returning its URL.
+async fn broker(buckets: usize, total: u64, remaining: u64, reserve: u64) -> String {
+    let queue = LargeBotQueue::with_session_start_limit(
+        buckets,
+        &Client::new("token"),
+        total,
+        remaining,
+        DAY,
+    )
+    .await
+    .reserve(reserve);
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(twilight_gateway_queue_broker::serve(
+        listener,
+        Arc::new(Box::new(queue)),
+    ));
+
+    url
+}
+
+/// Request allowance for a shard from its own client, as if each shard ran in
+/// a separate process.
+fn request(url: &str, shard_id: [u64; 2]) -> impl Future<Output = ()> {
+    let queue = HttpQueue::new(url).unwrap();
+
+    async move { queue.request(shard_id).await }
+}
+
+#[tokio::test]
+async fn test_buckets() {
+    let url = broker(2, 1000, 1000, 0).await;
+
+    let first = tokio::spawn(request(&url, [0, 4]));
+    let second = tokio::spawn(request(&url, [1, 4]));
+    let third = tokio::spawn(request(&url, [2, 4]));
+
+    // Shards in distinct buckets are allowed concurrently.
+    timeout(Duration::from_secs(1), first)
+        .await
+        .unwrap()
+        .unwrap();
+    timeout(Duration::from_secs(1), second)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Shard 2 shares the first shard's bucket and has to wait for it to be
+    // released again.
+    let mut third = third;
+    assert!(timeout(Duration::from_secs(1), &mut third).await.is_err());
+    timeout(Duration::from_secs(10), third)
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_daily_limit() {
+    let url = broker(4, 1000, 1, 0).await;
+
+    timeout(Duration::from_secs(1), request(&url, [0, 4]))
+        .await
+        .unwrap();
+
+    // The remaining sessions are used up until the limit is reset.
+    assert!(timeout(Duration::from_secs(1), request(&url, [1, 4]))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_reserve_refused() {
+    let url = broker(4, 1000, 3, 2).await;
+    let queue = HttpQueue::new(&url).unwrap();
+
+    timeout(Duration::from_secs(1), queue.try_request([0, 4]))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Only the reserved sessions remain, so the broker refuses instead of
+    // waiting for the limit to be reset.
+    let error = timeout(Duration::from_secs(1), queue.try_request([1, 4]))
+        .await
+        .unwrap()
+        .unwrap_err();
+
+    assert!(matches!(
+        error.kind(),
+        QueueRequestErrorType::SessionStartLimitExhausted { remaining: 2, reset_after }
+            if *reset_after > DAY - Duration::from_secs(60) && *reset_after <= DAY
+    ));
+}
+
+#[tokio::test]
+async fn test_bad_request() {
+    let url = broker(1, 1000, 1000, 0).await;
+    let client = HyperClient::new();
+
+    for query in &["", "?shard=1", "?shard=4&total=4", "?shard=a&total=4"] {
+        let response = client
+            .get(format!("{}/{}", url, query).parse().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+}
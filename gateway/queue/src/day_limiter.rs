@@ -1,12 +1,11 @@
+use super::{QueueRequestError, QueueRequestErrorType};
 use std::{
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
-    time::Duration,
-};
-use tokio::{
     sync::Mutex,
-    time::{self, Instant},
+    time::Duration,
 };
+use tokio::time::Instant;
 
 /// Creating a day limiter queue failed.
 #[derive(Debug)]
@@ -42,16 +41,72 @@ pub enum DayLimiterErrorType {
     RetrievingSessionAvailability,
 }
 
+/// State of the bot's daily session start limit as tracked by a queue.
+///
+/// Obtained via [`Queue::session_availability`].
+///
+/// [`Queue::session_availability`]: crate::Queue::session_availability
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SessionAvailability {
+    remaining: u64,
+    reserve: u64,
+    reset_after: Duration,
+    total: u64,
+}
+
+impl SessionAvailability {
+    /// Number of sessions that can still be started before the limit is
+    /// reset.
+    ///
+    /// This includes the [`reserve`], which the queue doesn't use.
+    ///
+    /// [`reserve`]: Self::reserve
+    pub const fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Number of remaining sessions the queue keeps in reserve and refuses
+    /// to use.
+    pub const fn reserve(&self) -> u64 {
+        self.reserve
+    }
+
+    /// Duration until the limit is reset.
+    pub const fn reset_after(&self) -> Duration {
+        self.reset_after
+    }
+
+    /// Total number of sessions that can be started per day.
+    pub const fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Number of remaining sessions the queue can use, excluding the
+    /// [`reserve`].
+    ///
+    /// [`reserve`]: Self::reserve
+    pub const fn usable(&self) -> u64 {
+        self.remaining.saturating_sub(self.reserve)
+    }
+}
+
 #[derive(Debug)]
-pub(crate) struct DayLimiter(pub(crate) Mutex<DayLimiterInner>);
+pub(crate) struct DayLimiter(Mutex<DayLimiterInner>);
 
 #[derive(Debug)]
-pub(crate) struct DayLimiterInner {
-    pub http: twilight_http::Client,
-    pub last_check: Instant,
-    pub next_reset: Duration,
-    pub total: u64,
-    pub current: u64,
+struct DayLimiterInner {
+    http: twilight_http::Client,
+    last_check: Instant,
+    next_reset: Duration,
+    reserve: u64,
+    total: u64,
+    current: u64,
+}
+
+impl DayLimiterInner {
+    fn reset_at(&self) -> Instant {
+        self.last_check + self.next_reset
+    }
 }
 
 impl DayLimiter {
@@ -65,46 +120,163 @@ impl DayLimiter {
                 source: Some(Box::new(source)),
             })?;
 
-        let last_check = Instant::now();
+        Ok(Self::with_limit(
+            http,
+            info.session_start_limit.total,
+            info.session_start_limit.remaining,
+            Duration::from_millis(info.session_start_limit.reset_after),
+        ))
+    }
+
+    pub fn with_limit(
+        http: &twilight_http::Client,
+        total: u64,
+        remaining: u64,
+        next_reset: Duration,
+    ) -> Self {
+        // Clamp the number of remaining sessions to the total, in case the
+        // API reports more.
+        let current = total.saturating_sub(remaining);
 
-        let next_reset = Duration::from_millis(info.session_start_limit.reset_after);
-        let total = info.session_start_limit.total;
-        let remaining = info.session_start_limit.remaining;
-        debug_assert!(total >= remaining);
-        let current = total - remaining;
-        Ok(DayLimiter(Mutex::new(DayLimiterInner {
+        DayLimiter(Mutex::new(DayLimiterInner {
             http: http.clone(),
-            last_check,
+            last_check: Instant::now(),
             next_reset,
-            total: info.session_start_limit.total,
+            reserve: 0,
+            total,
             current,
-        })))
+        }))
+    }
+
+    pub fn availability(&self) -> SessionAvailability {
+        let lock = self.0.lock().expect("day limiter poisoned");
+
+        SessionAvailability {
+            remaining: lock.total.saturating_sub(lock.current),
+            reserve: lock.reserve,
+            reset_after: lock.reset_at().saturating_duration_since(Instant::now()),
+            total: lock.total,
+        }
     }
 
-    pub async fn get(&self) {
-        let mut lock = self.0.lock().await;
-        if lock.current < lock.total {
+    pub fn set_reserve(&self, reserve: u64) {
+        self.0.lock().expect("day limiter poisoned").reserve = reserve;
+    }
+
+    /// Use one of the remaining sessions, refreshing the limit if it has been
+    /// reset.
+    ///
+    /// Instead of waiting for the limit to be reset, this returns an error if
+    /// only the reserved sessions remain.
+    pub async fn get(&self) -> Result<(), QueueRequestError> {
+        self.refresh().await;
+
+        let mut lock = self.0.lock().expect("day limiter poisoned");
+
+        if lock.current + lock.reserve < lock.total {
             lock.current += 1;
-        } else {
-            let wait = lock.last_check + lock.next_reset;
-            time::sleep_until(wait).await;
-            if let Ok(info) = lock.http.gateway().authed().await {
-                let last_check = Instant::now();
-                let next_reset = Duration::from_millis(info.session_start_limit.remaining);
-                tracing::info!("next session start limit reset in: {:.2?}", next_reset);
-                let total = info.session_start_limit.total;
-                let remaining = info.session_start_limit.remaining;
-                assert!(total >= remaining);
-                let current = total - remaining;
-                lock.last_check = last_check;
-                lock.next_reset = next_reset;
-                lock.total = total;
-                lock.current = current + 1;
-            } else {
+
+            return Ok(());
+        }
+
+        Err(QueueRequestError {
+            kind: QueueRequestErrorType::SessionStartLimitExhausted {
+                remaining: lock.total.saturating_sub(lock.current),
+                reset_after: lock.reset_at().saturating_duration_since(Instant::now()),
+            },
+            source: None,
+        })
+    }
+
+    /// Retrieve the new session start limit if the previous one has been
+    /// reset.
+    async fn refresh(&self) {
+        let (http, last_check) = {
+            let lock = self.0.lock().expect("day limiter poisoned");
+
+            if lock.reset_at() > Instant::now() {
+                return;
+            }
+
+            (lock.http.clone(), lock.last_check)
+        };
+
+        let info = http.gateway().authed().await;
+        let mut lock = self.0.lock().expect("day limiter poisoned");
+
+        // Another request has refreshed the limit in the meantime.
+        if lock.last_check != last_check {
+            return;
+        }
+
+        lock.last_check = Instant::now();
+
+        if let Ok(info) = info {
+            let limit = info.session_start_limit;
+            lock.next_reset = Duration::from_millis(limit.reset_after);
+            tracing::info!("next session start limit reset in: {:.2?}", lock.next_reset);
+
+            if limit.remaining > limit.total {
                 tracing::warn!(
-                    "unable to get new session limits, skipping (this may cause bad things)"
-                )
+                    remaining = limit.remaining,
+                    total = limit.total,
+                    "more sessions remaining than the total, assuming all remain",
+                );
             }
+
+            lock.total = limit.total;
+            lock.current = limit.total.saturating_sub(limit.remaining);
+        } else {
+            tracing::warn!(
+                "unable to get new session limits, assuming the limit was reset \
+                 (this may cause bad things)"
+            );
+            lock.next_reset = Duration::from_secs(24 * 60 * 60);
+            lock.current = 0;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DayLimiter;
+    use crate::QueueRequestErrorType;
+    use std::time::Duration;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    #[tokio::test]
+    async fn test_reserve() {
+        let limiter = DayLimiter::with_limit(&twilight_http::Client::new("token"), 1000, 3, DAY);
+        limiter.set_reserve(1);
+
+        let availability = limiter.availability();
+        assert_eq!(1000, availability.total());
+        assert_eq!(3, availability.remaining());
+        assert_eq!(2, availability.usable());
+        assert!(availability.reset_after() <= DAY);
+
+        assert!(limiter.get().await.is_ok());
+        assert!(limiter.get().await.is_ok());
+        assert!(matches!(
+            limiter.get().await.unwrap_err().kind(),
+            QueueRequestErrorType::SessionStartLimitExhausted { remaining: 1, reset_after }
+                if *reset_after <= DAY
+        ));
+        assert_eq!(1, limiter.availability().remaining());
+        assert_eq!(0, limiter.availability().usable());
+    }
+
+    #[tokio::test]
+    async fn test_remaining_exceeding_total() {
+        let limiter = DayLimiter::with_limit(&twilight_http::Client::new("token"), 2, 5, DAY);
+
+        let availability = limiter.availability();
+        assert_eq!(2, availability.total());
+        assert_eq!(2, availability.remaining());
+
+        assert!(limiter.get().await.is_ok());
+        assert!(limiter.get().await.is_ok());
+        assert!(limiter.get().await.is_err());
+    }
+}
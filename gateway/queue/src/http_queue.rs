@@ -0,0 +1,271 @@
+use super::{Priority, Queue, QueueRequestError, QueueRequestErrorType};
+use hyper::{client::HttpConnector, header::RETRY_AFTER, Body, Client, Response, StatusCode, Uri};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    future::Future,
+    pin::Pin,
+    time::Duration,
+};
+use tokio::time::sleep;
+
+/// Initial duration to wait before retrying a failed request to the broker.
+const RETRY_INITIAL: Duration = Duration::from_millis(500);
+
+/// Maximum duration to wait before retrying a failed request to the broker.
+const RETRY_MAX: Duration = Duration::from_secs(30);
+
+/// Header of a refused request containing the number of remaining, reserved,
+/// sessions.
+const SESSION_REMAINING: &str = "x-session-remaining";
+
+/// Creating an HTTP queue failed.
+#[derive(Debug)]
+pub struct HttpQueueError {
+    kind: HttpQueueErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl HttpQueueError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &HttpQueueErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(self) -> (HttpQueueErrorType, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for HttpQueueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            HttpQueueErrorType::UrlInvalid { url } => {
+                f.write_str("broker url `")?;
+                f.write_str(url)?;
+
+                f.write_str("` is not a valid http url")
+            }
+        }
+    }
+}
+
+impl Error for HttpQueueError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`HttpQueueError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum HttpQueueErrorType {
+    /// Broker URL is not a valid URL with the `http` scheme.
+    UrlInvalid {
+        /// Provided URL.
+        url: String,
+    },
+}
+
+/// Queue requesting permission to initialize sessions from a broker via HTTP.
+///
+/// Shards in multiple processes can coordinate their session initializations
+/// by pointing them at the same broker, such as the
+/// `twilight-gateway-queue-broker` binary in the twilight repository.
+///
+/// # Protocol
+///
+/// For every session initialization a `GET` request is sent to the broker
+/// with the shard ID and the total number of shards as the `shard` and
+/// `total` query parameters, such as `GET /?shard=3&total=16`. The broker
+/// responds with a successful status code once the shard may initialize its
+/// session.
+///
+/// Once the session start limit is exhausted the broker responds with a
+/// `429 Too Many Requests` status code, the `Retry-After` header containing
+/// the number of seconds until the limit is reset, and the
+/// `X-Session-Remaining` header containing the number of remaining, reserved,
+/// sessions. [`Queue::try_request`] then returns a
+/// [`QueueRequestErrorType::SessionStartLimitExhausted`] error type, while
+/// [`Queue::request`] requests again after the limit was reset.
+///
+/// Other failed requests are retried with an exponential backoff, so shards wait
+/// for the broker to become available.
+///
+/// # Examples
+///
+/// ```no_run
+/// use twilight_gateway_queue::HttpQueue;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let queue = HttpQueue::new("http://queue-broker:8080")?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct HttpQueue {
+    client: Client<HttpConnector>,
+    url: Box<str>,
+}
+
+impl HttpQueue {
+    /// Create a new queue using the broker at the URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`HttpQueueErrorType::UrlInvalid`] error type if the URL is
+    /// not a valid URL with the `http` scheme.
+    pub fn new(url: impl Into<String>) -> Result<Self, HttpQueueError> {
+        let mut url = url.into();
+
+        if url.ends_with('/') {
+            url.pop();
+        }
+
+        let queue = Self {
+            client: Client::new(),
+            url: url.into_boxed_str(),
+        };
+
+        match queue.uri([0, 1]) {
+            Ok(uri) if uri.scheme_str() == Some("http") => Ok(queue),
+            Ok(_) => Err(HttpQueueError {
+                kind: HttpQueueErrorType::UrlInvalid {
+                    url: queue.url.into(),
+                },
+                source: None,
+            }),
+            Err(source) => Err(HttpQueueError {
+                kind: HttpQueueErrorType::UrlInvalid {
+                    url: queue.url.into(),
+                },
+                source: Some(Box::new(source)),
+            }),
+        }
+    }
+
+    /// URL of the broker.
+    pub const fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn uri(&self, [id, total]: [u64; 2]) -> Result<Uri, hyper::http::uri::InvalidUri> {
+        format!("{}/?shard={}&total={}", self.url, id, total).parse()
+    }
+}
+
+/// Parse the refusal of a broker whose session start limit is exhausted.
+fn exhausted(response: &Response<Body>) -> QueueRequestError {
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    };
+
+    QueueRequestError {
+        kind: QueueRequestErrorType::SessionStartLimitExhausted {
+            remaining: header(SESSION_REMAINING),
+            reset_after: Duration::from_secs(header(RETRY_AFTER.as_str())),
+        },
+        source: None,
+    }
+}
+
+impl Queue for HttpQueue {
+    /// Request to be able to identify with the gateway from the broker. The
+    /// returned future will resolve once the broker allowed the request.
+    ///
+    /// If the broker's session start limit is exhausted, this waits until
+    /// it's reset.
+    fn request(&'_ self, shard_id: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(super::request_waiting(self, shard_id, Priority::Normal))
+    }
+
+    fn try_request(
+        &'_ self,
+        shard_id: [u64; 2],
+    ) -> Pin<Box<dyn Future<Output = Result<(), QueueRequestError>> + Send + '_>> {
+        Box::pin(async move {
+            // The URL was validated when creating the queue.
+            let uri = match self.uri(shard_id) {
+                Ok(uri) => uri,
+                Err(source) => {
+                    tracing::warn!("skipping, broker url is invalid: {:?}", source);
+
+                    return Ok(());
+                }
+            };
+            let mut retry_after = RETRY_INITIAL;
+
+            tracing::info!(
+                "shard {}/{} waiting for allowance from broker",
+                shard_id[0],
+                shard_id[1]
+            );
+
+            loop {
+                match self.client.get(uri.clone()).await {
+                    Ok(response) if response.status().is_success() => return Ok(()),
+                    Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                        return Err(exhausted(&response));
+                    }
+                    Ok(response) => tracing::warn!(
+                        "broker responded with status {}, retrying in {:.2?}",
+                        response.status(),
+                        retry_after,
+                    ),
+                    Err(source) => tracing::warn!(
+                        "requesting allowance from broker failed, retrying in {:.2?}: {:?}",
+                        retry_after,
+                        source,
+                    ),
+                }
+
+                sleep(retry_after).await;
+                retry_after = (retry_after * 2).min(RETRY_MAX);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HttpQueue, HttpQueueError, HttpQueueErrorType};
+    use crate::Queue;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{error::Error, fmt::Debug};
+
+    assert_fields!(HttpQueueErrorType::UrlInvalid: url);
+    assert_impl_all!(HttpQueueErrorType: Debug, Send, Sync);
+    assert_impl_all!(HttpQueueError: Error, Send, Sync);
+    assert_impl_all!(HttpQueue: Clone, Debug, Queue, Send, Sync);
+
+    #[tokio::test]
+    async fn test_new() {
+        let queue = HttpQueue::new("http://localhost:8080/").unwrap();
+        assert_eq!("http://localhost:8080", queue.url());
+        assert_eq!(
+            "http://localhost:8080/?shard=3&total=16",
+            queue.uri([3, 16]).unwrap().to_string()
+        );
+
+        assert!(matches!(
+            HttpQueue::new("https://localhost").unwrap_err().kind(),
+            HttpQueueErrorType::UrlInvalid { url } if url == "https://localhost"
+        ));
+        assert!(HttpQueue::new("not a url").is_err());
+    }
+}
@@ -1,11 +1,8 @@
-use super::{DayLimiter, Queue};
+use super::{waiter, DayLimiter, Priority, Queue, QueueRequestError, SessionAvailability};
 use std::{fmt::Debug, future::Future, pin::Pin, time::Duration};
-use tokio::{
-    sync::{
-        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
-        oneshot::{self, Sender},
-    },
-    time::sleep,
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedSender},
+    oneshot::{self, Sender},
 };
 
 /// Queue built for single-process clusters that require identifying via
@@ -19,7 +16,7 @@ use tokio::{
 /// [module-level]: crate
 #[derive(Debug)]
 pub struct LargeBotQueue {
-    buckets: Vec<UnboundedSender<Sender<()>>>,
+    buckets: Vec<UnboundedSender<(Priority, Sender<()>)>>,
     limiter: DayLimiter,
 }
 
@@ -29,6 +26,37 @@ impl LargeBotQueue {
     /// You must provide the number of buckets Discord requires your bot to
     /// connect with.
     pub async fn new(buckets: usize, http: &twilight_http::Client) -> Self {
+        let limiter = DayLimiter::new(http).await.expect(
+            "Getting the first session limits failed, \
+             Is network connection available?",
+        );
+
+        Self::with_limiter(buckets, limiter).await
+    }
+
+    /// Create a new large bot queue with a known session start limit,
+    /// without retrieving it via the HTTP API.
+    ///
+    /// You must provide the number of buckets Discord requires your bot to
+    /// connect with, the total number of sessions that can be started per
+    /// day, the number of sessions remaining, and the duration until the
+    /// number of remaining sessions is reset. The HTTP client is used to
+    /// retrieve the new session start limit once it is reset.
+    pub async fn with_session_start_limit(
+        buckets: usize,
+        http: &twilight_http::Client,
+        total: u64,
+        remaining: u64,
+        reset_after: Duration,
+    ) -> Self {
+        Self::with_limiter(
+            buckets,
+            DayLimiter::with_limit(http, total, remaining, reset_after),
+        )
+        .await
+    }
+
+    async fn with_limiter(buckets: usize, limiter: DayLimiter) -> Self {
         let mut queues = Vec::with_capacity(buckets);
         for _ in 0..buckets {
             let (tx, rx) = unbounded_channel();
@@ -38,22 +66,17 @@ impl LargeBotQueue {
             queues.push(tx)
         }
 
-        let limiter = DayLimiter::new(http).await.expect(
-            "Getting the first session limits failed, \
-             Is network connection available?",
-        );
-
         // The level_enabled macro does not turn off with the dynamic
         // tracing levels. It is made for the static_max_level_xxx features
         // And will return false if you do not use those features of if
         // You use the feature but then dynamically set a lower feature.
         if tracing::level_enabled!(tracing::Level::INFO) {
-            let lock = limiter.0.lock().await;
+            let availability = limiter.availability();
             tracing::info!(
                 "{}/{} identifies used before next reset in {:.2?}",
-                lock.current,
-                lock.total,
-                lock.next_reset
+                availability.total() - availability.remaining(),
+                availability.total(),
+                availability.reset_after()
             );
         }
 
@@ -62,15 +85,15 @@ impl LargeBotQueue {
             limiter,
         }
     }
-}
 
-async fn waiter(mut rx: UnboundedReceiver<Sender<()>>) {
-    const DUR: Duration = Duration::from_secs(6);
-    while let Some(req) = rx.recv().await {
-        if let Err(err) = req.send(()) {
-            tracing::warn!("skipping, send failed with: {:?}", err);
-        }
-        sleep(DUR).await;
+    /// Set the number of remaining sessions to keep in reserve.
+    ///
+    /// Requests are refused or delayed once only the reserved sessions
+    /// remain, so that they are available for manual intervention.
+    pub fn reserve(self, reserve: u64) -> Self {
+        self.limiter.set_reserve(reserve);
+
+        self
     }
 }
 
@@ -78,30 +101,86 @@ impl Queue for LargeBotQueue {
     /// Request to be able to identify with the gateway. This will place this
     /// request behind all other requests, and the returned future will resolve
     /// once the request has been completed.
+    ///
+    /// If the session start limit is exhausted, this waits until it's reset.
     fn request(&'_ self, shard_id: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        self.request_with_priority(shard_id, Priority::Normal)
+    }
+
+    /// Request to be able to identify with the gateway. This will place this
+    /// request behind all other requests in the shard's bucket with the same
+    /// or a higher priority.
+    fn request_with_priority(
+        &'_ self,
+        shard_id: [u64; 2],
+        priority: Priority,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(super::request_waiting(self, shard_id, priority))
+    }
+
+    fn try_request(
+        &'_ self,
+        shard_id: [u64; 2],
+    ) -> Pin<Box<dyn Future<Output = Result<(), QueueRequestError>> + Send + '_>> {
+        self.try_request_with_priority(shard_id, Priority::Normal)
+    }
+
+    fn try_request_with_priority(
+        &'_ self,
+        shard_id: [u64; 2],
+        priority: Priority,
+    ) -> Pin<Box<dyn Future<Output = Result<(), QueueRequestError>> + Send + '_>> {
         #[allow(clippy::cast_possible_truncation)]
         let bucket = (shard_id[0] % (self.buckets.len() as u64)) as usize;
         let (tx, rx) = oneshot::channel();
 
         Box::pin(async move {
-            self.limiter.get().await;
-            if let Err(err) = self.buckets[bucket].clone().send(tx) {
+            self.limiter.get().await?;
+            if let Err(err) = self.buckets[bucket].clone().send((priority, tx)) {
                 tracing::warn!("skipping, send failed with: {:?}", err);
-                return;
+                return Ok(());
             }
 
             tracing::info!("waiting for allowance on shard {}", shard_id[0]);
 
             let _ = rx.await;
+
+            Ok(())
         })
     }
+
+    fn session_availability(&self) -> Option<SessionAvailability> {
+        Some(self.limiter.availability())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{LargeBotQueue, Queue};
+    use crate::QueueRequestErrorType;
     use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
+    use std::{fmt::Debug, time::Duration};
 
     assert_impl_all!(LargeBotQueue: Debug, Queue, Send, Sync);
+
+    #[tokio::test]
+    async fn test_reserve() {
+        let queue = LargeBotQueue::with_session_start_limit(
+            1,
+            &twilight_http::Client::new("token"),
+            1000,
+            2,
+            Duration::from_secs(60),
+        )
+        .await
+        .reserve(1);
+
+        assert_eq!(1, queue.session_availability().unwrap().usable());
+        assert!(queue.try_request([0, 1]).await.is_ok());
+        assert!(matches!(
+            queue.try_request([0, 1]).await.unwrap_err().kind(),
+            QueueRequestErrorType::SessionStartLimitExhausted { remaining: 1, .. }
+        ));
+        assert_eq!(1, queue.session_availability().unwrap().remaining());
+    }
 }
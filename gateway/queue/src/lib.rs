@@ -18,10 +18,32 @@
 //! single-process [Sharding for Very Large Bots] through the use of bucket
 //! releasing.
 //!
+//! Bots running shards in multiple processes can use the [`HttpQueue`], which
+//! requests permission from a broker via HTTP. The
+//! `twilight-gateway-queue-broker` binary in the twilight repository is such
+//! a broker, releasing sessions per bucket within the daily session start
+//! limit.
+//!
 //! By default, the gateway's `Cluster` and `Shard`s use the [`LocalQueue`]. You
 //! can override this in the `ClusterBuilder::queue` and `ShardBuilder::queue`
 //! configuration methods.
 //!
+//! # Session start limit
+//!
+//! Bots can only start a limited number of sessions per day. The
+//! [`LargeBotQueue`] and a [`LocalQueue`] created with
+//! [`LocalQueue::with_session_start_limit`] keep track of the limit, which
+//! can be inspected via [`Queue::session_availability`]. They can keep a
+//! number of sessions in reserve so that a crash loop doesn't use up the
+//! last ones.
+//!
+//! Once the limit is exhausted [`Queue::request`] waits until it's reset,
+//! which can take up to a day. [`Queue::try_request`] instead returns a
+//! [`QueueRequestErrorType::SessionStartLimitExhausted`] error type, leaving
+//! it up to the caller to delay or give up. Shards use the latter, emitting
+//! a `ShardSessionStartLimitExhausted` event each time before waiting for the
+//! limit to be reset.
+//!
 //! # Advanced use cases
 //!
 //! Large bots, and smaller bots out of design, may need to implement their own
@@ -33,12 +55,27 @@
 //! [Sharding for Very Large Bots]: https://discord.com/developers/docs/topics/gateway#sharding-for-very-large-bots
 
 mod day_limiter;
+mod http_queue;
 mod large_bot_queue;
+mod request;
 
-pub use large_bot_queue::LargeBotQueue;
+pub use self::{
+    day_limiter::SessionAvailability,
+    http_queue::{HttpQueue, HttpQueueError, HttpQueueErrorType},
+    large_bot_queue::LargeBotQueue,
+    request::{QueueRequestError, QueueRequestErrorType},
+};
 
 use day_limiter::DayLimiter;
-use std::{fmt::Debug, future::Future, pin::Pin, time::Duration};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
 use tokio::{
     sync::{
         mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
@@ -62,6 +99,132 @@ pub trait Queue: Debug + Send + Sync {
     /// The returned future must resolve only when the shard can initiate the
     /// session.
     fn request<'a>(&'a self, shard_id: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+    /// A shard has requested the ability to request a session initialization
+    /// with the gateway, hinting at the priority of the request.
+    ///
+    /// Queues may release requests with a higher priority before requests
+    /// with a lower priority that were made earlier.
+    ///
+    /// The default implementation ignores the priority and calls
+    /// [`request`].
+    ///
+    /// [`request`]: Self::request
+    fn request_with_priority<'a>(
+        &'a self,
+        shard_id: [u64; 2],
+        priority: Priority,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        let _ = priority;
+
+        self.request(shard_id)
+    }
+
+    /// A shard has requested the ability to request a session initialization
+    /// with the gateway, without waiting for the session start limit to be
+    /// reset.
+    ///
+    /// The returned future must resolve when the shard can initiate the
+    /// session or when the request was refused.
+    ///
+    /// The default implementation waits for [`request`] and never refuses.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QueueRequestErrorType::SessionStartLimitExhausted`] error
+    /// type if all sessions except for the reserved ones have been used.
+    ///
+    /// [`request`]: Self::request
+    fn try_request<'a>(
+        &'a self,
+        shard_id: [u64; 2],
+    ) -> Pin<Box<dyn Future<Output = Result<(), QueueRequestError>> + Send + 'a>> {
+        Box::pin(async move {
+            self.request(shard_id).await;
+
+            Ok(())
+        })
+    }
+
+    /// A shard has requested the ability to request a session initialization
+    /// with the gateway, hinting at the priority of the request and without
+    /// waiting for the session start limit to be reset.
+    ///
+    /// The default implementation ignores the priority and calls
+    /// [`try_request`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QueueRequestErrorType::SessionStartLimitExhausted`] error
+    /// type if all sessions except for the reserved ones have been used.
+    ///
+    /// [`try_request`]: Self::try_request
+    fn try_request_with_priority<'a>(
+        &'a self,
+        shard_id: [u64; 2],
+        priority: Priority,
+    ) -> Pin<Box<dyn Future<Output = Result<(), QueueRequestError>> + Send + 'a>> {
+        let _ = priority;
+
+        self.try_request(shard_id)
+    }
+
+    /// State of the session start limit, if the queue keeps track of it.
+    ///
+    /// The default implementation returns `None`.
+    fn session_availability(&self) -> Option<SessionAvailability> {
+        None
+    }
+}
+
+/// Priority of a request to initialize a session.
+///
+/// Shards hosting the most important guilds of a bot, such as its support
+/// server, can request with a [`High`] priority to initialize their sessions
+/// before other shards.
+///
+/// [`High`]: Self::High
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum Priority {
+    /// Request is released in the order it was made.
+    Normal,
+    /// Request is released before requests with a [`Normal`] priority.
+    ///
+    /// [`Normal`]: Self::Normal
+    High,
+}
+
+impl Default for Priority {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// Wait for the queue to allow a request, waiting for the session start limit
+/// to be reset whenever it's exhausted.
+async fn request_waiting(queue: &(impl Queue + ?Sized), shard_id: [u64; 2], priority: Priority) {
+    /// Minimum duration to wait before requesting again, in case the limit
+    /// was reported to be reset already.
+    const MIN_WAIT: Duration = Duration::from_secs(1);
+
+    loop {
+        match queue.try_request_with_priority(shard_id, priority).await {
+            Ok(()) => return,
+            Err(source) => match source.kind() {
+                QueueRequestErrorType::SessionStartLimitExhausted { reset_after, .. } => {
+                    tracing::warn!(
+                        "shard {}/{} waiting for session start limit reset: {}",
+                        shard_id[0],
+                        shard_id[1],
+                        source,
+                    );
+
+                    sleep((*reset_after).max(MIN_WAIT)).await;
+                }
+            },
+        }
+    }
 }
 
 /// A local, in-process implementation of a [`Queue`] which manages the
@@ -86,11 +249,12 @@ pub trait Queue: Debug + Send + Sync {
 /// [`LargeBotQueue`] can be used.
 ///
 /// If you can't use this, look into an alternative implementation of the
-/// [`Queue`], such as the [`gateway-queue`] broker.
-///
-/// [`gateway-queue`]: https://github.com/twilight-rs/gateway-queue
+/// [`Queue`], such as the [`HttpQueue`] paired with a broker.
 #[derive(Clone, Debug)]
-pub struct LocalQueue(UnboundedSender<Sender<()>>);
+pub struct LocalQueue {
+    limiter: Option<Arc<DayLimiter>>,
+    tx: UnboundedSender<(Priority, Sender<()>)>,
+}
 
 impl Default for LocalQueue {
     fn default() -> Self {
@@ -100,21 +264,122 @@ impl Default for LocalQueue {
 
 impl LocalQueue {
     /// Creates a new local queue.
+    ///
+    /// The queue doesn't keep track of the daily session start limit. Use
+    /// [`with_session_start_limit`] to do so.
+    ///
+    /// [`with_session_start_limit`]: Self::with_session_start_limit
     pub fn new() -> Self {
+        Self::with_limiter(None)
+    }
+
+    /// Create a new local queue keeping track of a known session start limit.
+    ///
+    /// You must provide the total number of sessions that can be started per
+    /// day, the number of sessions remaining, and the duration until the
+    /// number of remaining sessions is reset. The HTTP client is used to
+    /// retrieve the new session start limit once it is reset.
+    pub fn with_session_start_limit(
+        http: &twilight_http::Client,
+        total: u64,
+        remaining: u64,
+        reset_after: Duration,
+    ) -> Self {
+        Self::with_limiter(Some(Arc::new(DayLimiter::with_limit(
+            http,
+            total,
+            remaining,
+            reset_after,
+        ))))
+    }
+
+    fn with_limiter(limiter: Option<Arc<DayLimiter>>) -> Self {
         let (tx, rx) = unbounded_channel();
 
         tokio::spawn(waiter(rx));
 
-        Self(tx)
+        Self { limiter, tx }
+    }
+
+    /// Set the number of remaining sessions to keep in reserve.
+    ///
+    /// Requests are refused or delayed once only the reserved sessions
+    /// remain, so that they are available for manual intervention.
+    ///
+    /// Has no effect if the queue doesn't keep track of the session start
+    /// limit.
+    pub fn reserve(self, reserve: u64) -> Self {
+        if let Some(limiter) = &self.limiter {
+            limiter.set_reserve(reserve);
+        }
+
+        self
+    }
+}
+
+/// Request waiting in a queue, ordered by its priority and then by the order
+/// in which it was made.
+#[derive(Debug)]
+struct Pending {
+    priority: Priority,
+    sequence: Reverse<u64>,
+    tx: Sender<()>,
+}
+
+impl Eq for Pending {}
+
+impl Ord for Pending {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.priority, self.sequence).cmp(&(other.priority, other.sequence))
+    }
+}
+
+impl PartialEq for Pending {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
 }
 
-async fn waiter(mut rx: UnboundedReceiver<Sender<()>>) {
+impl PartialOrd for Pending {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Release one of the queued requests every 6 seconds, releasing requests
+/// with a higher priority first.
+async fn waiter(mut rx: UnboundedReceiver<(Priority, Sender<()>)>) {
     const DUR: Duration = Duration::from_secs(6);
-    while let Some(req) = rx.recv().await {
-        if let Err(err) = req.send(()) {
-            tracing::warn!("skipping, send failed: {:?}", err);
+
+    let mut pending = BinaryHeap::new();
+    let mut sequence = 0;
+    let mut push = |pending: &mut BinaryHeap<Pending>, (priority, tx)| {
+        pending.push(Pending {
+            priority,
+            sequence: Reverse(sequence),
+            tx,
+        });
+        sequence += 1;
+    };
+
+    loop {
+        if pending.is_empty() {
+            match rx.recv().await {
+                Some(req) => push(&mut pending, req),
+                None => return,
+            }
+        }
+
+        while let Ok(req) = rx.try_recv() {
+            push(&mut pending, req);
         }
+
+        if let Some(req) = pending.pop() {
+            if let Err(err) = req.tx.send(()) {
+                tracing::warn!("skipping, send failed: {:?}", err);
+            }
+        }
+
         sleep(DUR).await;
     }
 }
@@ -123,29 +388,121 @@ impl Queue for LocalQueue {
     /// Request to be able to identify with the gateway. This will place this
     /// request behind all other requests, and the returned future will resolve
     /// once the request has been completed.
-    fn request(&'_ self, [id, total]: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+    ///
+    /// If the session start limit is exhausted, this waits until it's reset.
+    fn request(&'_ self, shard_id: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        self.request_with_priority(shard_id, Priority::Normal)
+    }
+
+    /// Request to be able to identify with the gateway. This will place this
+    /// request behind all other requests with the same or a higher priority.
+    fn request_with_priority(
+        &'_ self,
+        shard_id: [u64; 2],
+        priority: Priority,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(request_waiting(self, shard_id, priority))
+    }
+
+    fn try_request(
+        &'_ self,
+        shard_id: [u64; 2],
+    ) -> Pin<Box<dyn Future<Output = Result<(), QueueRequestError>> + Send + '_>> {
+        self.try_request_with_priority(shard_id, Priority::Normal)
+    }
+
+    fn try_request_with_priority(
+        &'_ self,
+        [id, total]: [u64; 2],
+        priority: Priority,
+    ) -> Pin<Box<dyn Future<Output = Result<(), QueueRequestError>> + Send + '_>> {
         Box::pin(async move {
+            if let Some(limiter) = &self.limiter {
+                limiter.get().await?;
+            }
+
             let (tx, rx) = oneshot::channel();
 
-            if let Err(err) = self.0.clone().send(tx) {
+            if let Err(err) = self.tx.clone().send((priority, tx)) {
                 tracing::warn!("skipping, send failed: {:?}", err);
-                return;
+
+                return Ok(());
             }
 
             tracing::info!("shard {}/{} waiting for allowance", id, total);
 
             let _ = rx.await;
+
+            Ok(())
         })
     }
+
+    fn session_availability(&self) -> Option<SessionAvailability> {
+        self.limiter.as_ref().map(|limiter| limiter.availability())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{LocalQueue, Queue};
+    use super::{waiter, LocalQueue, Priority, Queue};
     use static_assertions::{assert_impl_all, assert_obj_safe};
-    use std::fmt::Debug;
+    use std::{fmt::Debug, time::Duration};
+    use tokio::{
+        sync::{mpsc::unbounded_channel, oneshot},
+        time::sleep,
+    };
 
     assert_impl_all!(LocalQueue: Clone, Debug, Queue, Send, Sync);
     assert_impl_all!(dyn Queue: Debug, Send, Sync);
+    assert_impl_all!(Priority: Clone, Copy, Debug, Default, Eq, Ord, Send, Sync);
     assert_obj_safe!(Queue);
+
+    #[tokio::test(start_paused = true)]
+    async fn test_priority() {
+        let (tx, rx) = unbounded_channel();
+        tokio::spawn(waiter(rx));
+
+        let mut requests = Vec::new();
+
+        for priority in &[Priority::Normal, Priority::Normal, Priority::High] {
+            let (req_tx, req_rx) = oneshot::channel();
+            tx.send((*priority, req_tx)).unwrap();
+            requests.push(req_rx);
+        }
+
+        let mut released = Vec::new();
+
+        while released.len() < requests.len() {
+            sleep(Duration::from_secs(1)).await;
+
+            for (idx, request) in requests.iter_mut().enumerate() {
+                if !released.contains(&idx) && request.try_recv().is_ok() {
+                    released.push(idx);
+                }
+            }
+        }
+
+        // All requests are queued before the waiter first runs.
+        assert_eq!(vec![2, 0, 1], released);
+    }
+
+    #[tokio::test]
+    async fn test_session_availability() {
+        assert!(LocalQueue::new().session_availability().is_none());
+
+        let queue = LocalQueue::with_session_start_limit(
+            &twilight_http::Client::new("token"),
+            1000,
+            10,
+            Duration::from_secs(60),
+        )
+        .reserve(2);
+        let availability = queue.session_availability().unwrap();
+        assert_eq!(10, availability.remaining());
+        assert_eq!(2, availability.reserve());
+        assert_eq!(8, availability.usable());
+
+        assert!(queue.try_request([0, 1]).await.is_ok());
+        assert_eq!(9, queue.session_availability().unwrap().remaining());
+    }
 }
@@ -0,0 +1,101 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::Duration,
+};
+
+/// Requesting the ability to initialize a session via
+/// [`Queue::try_request`] was refused.
+///
+/// [`Queue::try_request`]: crate::Queue::try_request
+#[derive(Debug)]
+pub struct QueueRequestError {
+    pub(crate) kind: QueueRequestErrorType,
+    pub(crate) source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl QueueRequestError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &QueueRequestErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(self) -> (QueueRequestErrorType, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for QueueRequestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            QueueRequestErrorType::SessionStartLimitExhausted {
+                remaining,
+                reset_after,
+            } => {
+                f.write_str("session start limit is exhausted with ")?;
+                Display::fmt(remaining, f)?;
+                f.write_str(" reserved sessions remaining, resetting in ")?;
+
+                write!(f, "{:.2?}", reset_after)
+            }
+        }
+    }
+}
+
+impl Error for QueueRequestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`QueueRequestError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum QueueRequestErrorType {
+    /// All sessions of the daily session start limit, except for the
+    /// configured reserve, have been used.
+    SessionStartLimitExhausted {
+        /// Number of remaining sessions, which are all reserved.
+        remaining: u64,
+        /// Duration until the limit is reset.
+        reset_after: Duration,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueueRequestError, QueueRequestErrorType};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{error::Error, fmt::Debug, time::Duration};
+
+    assert_fields!(QueueRequestErrorType::SessionStartLimitExhausted: remaining, reset_after);
+    assert_impl_all!(QueueRequestErrorType: Debug, Send, Sync);
+    assert_impl_all!(QueueRequestError: Error, Send, Sync);
+
+    #[test]
+    fn test_display() {
+        let error = QueueRequestError {
+            kind: QueueRequestErrorType::SessionStartLimitExhausted {
+                remaining: 2,
+                reset_after: Duration::from_secs(3),
+            },
+            source: None,
+        };
+
+        assert_eq!(
+            "session start limit is exhausted with 2 reserved sessions remaining, resetting in 3.00s",
+            error.to_string()
+        );
+    }
+}
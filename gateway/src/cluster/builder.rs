@@ -1,17 +1,31 @@
 use super::{
     config::Config as ClusterConfig,
+    event::ClusterEvents,
     r#impl::{Cluster, ClusterStartError},
     scheme::ShardScheme,
+    session_store::SessionStore,
 };
 use crate::{
-    shard::{LargeThresholdError, ResumeSession, ShardBuilder},
-    Event, EventTypeFlags,
+    shard::{
+        replay::Recorder, EventBuffer, HeartbeatPolicy, LargeThresholdError, Proxy, ResumeSession,
+        ShardBuilder,
+    },
+    EventTypeFlags,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
 };
-use futures_util::stream::Stream;
-use std::{collections::HashMap, sync::Arc};
 use twilight_gateway_queue::{LocalQueue, Queue};
 use twilight_http::Client;
-use twilight_model::gateway::{payload::update_presence::UpdatePresencePayload, Intents};
+use twilight_model::{
+    gateway::{
+        payload::{identify::IdentifyProperties, update_presence::UpdatePresencePayload},
+        Intents,
+    },
+    id::GuildId,
+};
 
 /// Builder to configure and construct a [`Cluster`].
 ///
@@ -61,8 +75,12 @@ impl ClusterBuilder {
                 http_client,
                 shard_config: shard_config.0,
                 shard_scheme: ShardScheme::Auto,
+                priority_guilds: HashSet::new(),
                 queue: Arc::new(Box::new(LocalQueue::new())),
+                reshard_interval: None,
+                reshard_timeout: Duration::from_secs(30 * 60),
                 resume_sessions: HashMap::new(),
+                session_store: None,
             },
             ShardBuilder::new(token, intents),
         )
@@ -70,21 +88,21 @@ impl ClusterBuilder {
 
     /// Consume the builder and create the cluster.
     ///
+    /// A warning is logged if any of the configured [event types] can never
+    /// be received with the configured intents. The default event types are
+    /// not checked, as they include the events of every intent. Use
+    /// [`unreachable_event_types`] to reject such configurations instead.
+    ///
+    /// [event types]: Self::event_types
+    /// [`unreachable_event_types`]: Self::unreachable_event_types
+    ///
     /// # Errors
     ///
     /// Returns a [`ClusterStartErrorType::RetrievingGatewayInfo`] error type if
     /// there was an HTTP error Retrieving the gateway information.
     ///
     /// [`ClusterStartErrorType::RetrievingGatewayInfo`]: super::ClusterStartErrorType::RetrievingGatewayInfo
-    pub async fn build(
-        mut self,
-    ) -> Result<
-        (
-            Cluster,
-            impl Stream<Item = (u64, Event)> + Send + Sync + Unpin + 'static,
-        ),
-        ClusterStartError,
-    > {
+    pub async fn build(mut self) -> Result<(Cluster, ClusterEvents), ClusterStartError> {
         if (self.1).0.gateway_url.is_none() {
             let gateway_url = (self.1)
                 .0
@@ -98,11 +116,24 @@ impl ClusterBuilder {
             self = self.gateway_url(gateway_url);
         }
 
+        (self.1).0.warn_unreachable_event_types();
         self.0.shard_config = (self.1).0;
 
         Cluster::new_with_config(self.0).await
     }
 
+    /// Set the bounded buffer of events of each shard that have not yet been
+    /// consumed by the event stream.
+    ///
+    /// Refer to the shard's [`ShardBuilder::event_buffer`] for more
+    /// information.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn event_buffer(mut self, event_buffer: Option<EventBuffer>) -> Self {
+        self.1 = self.1.event_buffer(event_buffer);
+
+        self
+    }
+
     /// Set the event types to process.
     ///
     /// This is an optimization technique; all events not included in the
@@ -118,6 +149,17 @@ impl ClusterBuilder {
         self
     }
 
+    /// Configured [event types] that can never be received with the
+    /// configured intents.
+    ///
+    /// Refer to [`ShardBuilder::unreachable_event_types`] for more
+    /// information.
+    ///
+    /// [event types]: Self::event_types
+    pub fn unreachable_event_types(&self) -> EventTypeFlags {
+        self.1.unreachable_event_types()
+    }
+
     /// Set the URL that will be used to connect to the gateway.
     pub fn gateway_url(mut self, gateway_url: Option<String>) -> Self {
         self.1 = self.1.gateway_url(gateway_url);
@@ -125,6 +167,18 @@ impl ClusterBuilder {
         self
     }
 
+    /// Set the policy of sending heartbeats and detecting zombie
+    /// connections of each shard.
+    ///
+    /// Refer to the shard's [`ShardBuilder::heartbeat_policy`] for more
+    /// information.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn heartbeat_policy(mut self, heartbeat_policy: HeartbeatPolicy) -> Self {
+        self.1 = self.1.heartbeat_policy(heartbeat_policy);
+
+        self
+    }
+
     /// Set the `twilight_http` Client used by the cluster and the shards it
     /// manages.
     ///
@@ -138,6 +192,17 @@ impl ClusterBuilder {
         self
     }
 
+    /// Set the properties to send when identifying with the gateway.
+    ///
+    /// Refer to the shard's [`ShardBuilder::identify_properties`] for more
+    /// information.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn identify_properties(mut self, identify_properties: IdentifyProperties) -> Self {
+        self.1 = self.1.identify_properties(identify_properties);
+
+        self
+    }
+
     /// Set the "large threshold" of shards.
     ///
     /// Refer to the shard's [`ShardBuilder::large_threshold`] for more
@@ -159,6 +224,28 @@ impl ClusterBuilder {
         Ok(self)
     }
 
+    /// Set the maximum size of a single websocket frame of each shard.
+    ///
+    /// Refer to the shard's [`ShardBuilder::max_frame_size`] for more
+    /// information.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn max_frame_size(mut self, max_frame_size: Option<usize>) -> Self {
+        self.1 = self.1.max_frame_size(max_frame_size);
+
+        self
+    }
+
+    /// Set the maximum size of a single websocket message of each shard.
+    ///
+    /// Refer to the shard's [`ShardBuilder::max_message_size`] for more
+    /// information.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn max_message_size(mut self, max_message_size: Option<usize>) -> Self {
+        self.1 = self.1.max_message_size(max_message_size);
+
+        self
+    }
+
     /// Set the presence to use when identifying with the gateway.
     ///
     /// Refer to the shard's [`ShardBuilder::presence`] for more information.
@@ -168,6 +255,16 @@ impl ClusterBuilder {
         self
     }
 
+    /// Set the proxy to tunnel the shards' gateway connections through.
+    ///
+    /// Refer to the shard's [`ShardBuilder::proxy`] for more information.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn proxy(mut self, proxy: Option<Proxy>) -> Self {
+        self.1 = self.1.proxy(proxy);
+
+        self
+    }
+
     /// Set the scheme to use for shard managing.
     ///
     /// For example, [`ShardScheme::Auto`] means that the cluster will
@@ -206,6 +303,49 @@ impl ClusterBuilder {
         self
     }
 
+    /// Set the guilds whose shards request to initialize their sessions with
+    /// a high priority.
+    ///
+    /// Shards receiving the events of any of the guilds are started first and
+    /// request to initialize their sessions from the [`queue`] with a
+    /// [`Priority::High`], so that the most important guilds, such as a
+    /// support server, are available first. The [`LocalQueue`] and
+    /// [`LargeBotQueue`] let them initialize their sessions first within each
+    /// of their buckets. This also applies to shards started while
+    /// [resharding].
+    ///
+    /// Defaults to no guilds.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::{collections::HashSet, env};
+    /// use twilight_gateway::{Cluster, Intents};
+    /// use twilight_model::id::GuildId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let token = env::var("DISCORD_TOKEN")?;
+    /// let support_server = GuildId(81384788765712384);
+    ///
+    /// let (cluster, _events) = Cluster::builder(token, Intents::GUILD_MESSAGES)
+    ///     .priority_guilds(vec![support_server].into_iter().collect::<HashSet<_>>())
+    ///     .build()
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`LargeBotQueue`]: crate::queue::LargeBotQueue
+    /// [`LocalQueue`]: crate::queue::LocalQueue
+    /// [`Priority::High`]: crate::queue::Priority::High
+    /// [`queue`]: Self::queue
+    /// [resharding]: Self::reshard_interval
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn priority_guilds(mut self, priority_guilds: HashSet<GuildId>) -> Self {
+        self.0.priority_guilds = priority_guilds;
+
+        self
+    }
+
     /// Set the queue to use for queueing shard connections.
     ///
     /// This is useful when you have a very large bot or when you have a more
@@ -221,6 +361,84 @@ impl ClusterBuilder {
         self
     }
 
+    /// Token the shards of the cluster authenticate with, prefixed with
+    /// `"Bot "`.
+    pub(crate) fn token(&self) -> &str {
+        (self.1).0.token()
+    }
+
+    /// Set the recorder of the raw inbound payloads of each shard.
+    ///
+    /// The payloads of all shards are written to the same recorder. Refer to
+    /// the shard's [`ShardBuilder::recorder`] for more information.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn recorder(mut self, recorder: Option<Recorder>) -> Self {
+        self.1 = self.1.recorder(recorder);
+
+        self
+    }
+
+    /// Set the interval at which to check whether the cluster needs to
+    /// reshard.
+    ///
+    /// Every interval the cluster retrieves the number of shards recommended
+    /// by Discord. If it differs from the number of shards currently in use,
+    /// a new set of shards with the recommended total is started in the
+    /// background through the [`queue`]. Once every new shard is connected the
+    /// event stream switches over to the new shards and the old shards are
+    /// shut down.
+    ///
+    /// Resharding is only done with a [`ShardScheme::Auto`] scheme, since
+    /// other schemes manage a fixed subset of the bot's shards.
+    ///
+    /// Defaults to `None`, disabling resharding.
+    ///
+    /// # Examples
+    ///
+    /// Check whether to reshard every 8 hours:
+    ///
+    /// ```no_run
+    /// use std::{env, time::Duration};
+    /// use twilight_gateway::{Cluster, Intents};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let token = env::var("DISCORD_TOKEN")?;
+    ///
+    /// let (cluster, _events) = Cluster::builder(token, Intents::GUILD_MESSAGES)
+    ///     .reshard_interval(Some(Duration::from_secs(8 * 60 * 60)))
+    ///     .build()
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`queue`]: Self::queue
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn reshard_interval(mut self, reshard_interval: Option<Duration>) -> Self {
+        self.0.reshard_interval = reshard_interval;
+
+        self
+    }
+
+    /// Set how long to wait for every new shard to be connected while
+    /// resharding.
+    ///
+    /// If the new shards are not all connected in time the reshard is
+    /// aborted, shutting down the new shards and keeping the old ones. The
+    /// next check of the [reshard interval] tries again. Since shards are
+    /// started through the [`queue`], bots with many shards may need a longer
+    /// timeout.
+    ///
+    /// Defaults to 30 minutes.
+    ///
+    /// [`queue`]: Self::queue
+    /// [reshard interval]: Self::reshard_interval
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn reshard_timeout(mut self, reshard_timeout: Duration) -> Self {
+        self.0.reshard_timeout = reshard_timeout;
+
+        self
+    }
+
     /// Set the session information to resume shards with.
     ///
     /// This requires having recovered the resume data when shutting down the
@@ -234,6 +452,29 @@ impl ClusterBuilder {
         self.0.resume_sessions = resume_sessions;
         self
     }
+
+    /// Set the store to save and load the sessions of the shards with.
+    ///
+    /// When the cluster is built, the sessions saved in the store are loaded
+    /// and resumed, in addition to the sessions set via [`resume_sessions`].
+    /// Save the sessions with [`Cluster::down_resumable_and_save`] when
+    /// shutting down.
+    ///
+    /// Failing to load the sessions is logged, and the shards identify
+    /// instead. The same applies to shards whose sessions are no longer valid
+    /// or were saved with a different total number of shards.
+    ///
+    /// Defaults to no session store.
+    ///
+    /// Refer to [`FileSessionStore`] for an example.
+    ///
+    /// [`FileSessionStore`]: super::FileSessionStore
+    /// [`resume_sessions`]: Self::resume_sessions
+    pub fn session_store(mut self, session_store: Arc<dyn SessionStore>) -> Self {
+        self.0.session_store.replace(session_store);
+
+        self
+    }
 }
 
 impl<T: Into<String>> From<(T, Intents)> for ClusterBuilder {
@@ -245,9 +486,22 @@ impl<T: Into<String>> From<(T, Intents)> for ClusterBuilder {
 #[cfg(test)]
 mod tests {
     use super::ClusterBuilder;
-    use crate::Intents;
+    use crate::{EventTypeFlags, Intents};
     use static_assertions::assert_impl_all;
     use std::fmt::Debug;
 
     assert_impl_all!(ClusterBuilder: Debug, From<(String, Intents)>, Send, Sync);
+
+    #[tokio::test]
+    async fn test_unreachable_event_types() {
+        let builder = ClusterBuilder::new("token", Intents::GUILD_MESSAGES)
+            .event_types(EventTypeFlags::MESSAGE_CREATE | EventTypeFlags::MEMBER_ADD);
+        assert_eq!(
+            EventTypeFlags::MEMBER_ADD,
+            builder.unreachable_event_types()
+        );
+
+        let builder = builder.event_types(EventTypeFlags::MESSAGE_CREATE);
+        assert!(builder.unreachable_event_types().is_empty());
+    }
 }
@@ -1,11 +1,16 @@
-use super::scheme::ShardScheme;
+use super::{scheme::ShardScheme, session_store::SessionStore};
 use crate::{
     shard::{Config as ShardConfig, ResumeSession},
     EventTypeFlags,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 use twilight_gateway_queue::Queue;
 use twilight_http::Client;
+use twilight_model::id::GuildId;
 
 /// Built configuration for a [`Cluster`].
 ///
@@ -15,8 +20,12 @@ pub struct Config {
     pub(super) http_client: Client,
     pub(super) shard_config: ShardConfig,
     pub(super) shard_scheme: ShardScheme,
+    pub(super) priority_guilds: HashSet<GuildId>,
     pub(super) queue: Arc<Box<dyn Queue>>,
+    pub(super) reshard_interval: Option<Duration>,
+    pub(super) reshard_timeout: Duration,
     pub(super) resume_sessions: HashMap<u64, ResumeSession>,
+    pub(super) session_store: Option<Arc<dyn SessionStore>>,
 }
 
 impl Config {
@@ -54,11 +63,50 @@ impl Config {
         &self.shard_scheme
     }
 
+    /// Immutable reference to the guilds whose shards request to initialize
+    /// their sessions with a high priority.
+    ///
+    /// Refer to [`ClusterBuilder::priority_guilds`] for the default value.
+    ///
+    /// [`ClusterBuilder::priority_guilds`]: super::ClusterBuilder::priority_guilds
+    pub const fn priority_guilds(&self) -> &HashSet<GuildId> {
+        &self.priority_guilds
+    }
+
     /// Return an immutable reference to the queue used for initiating shard
     /// sessions.
     pub fn queue(&self) -> &Arc<Box<dyn Queue>> {
         &self.queue
     }
+
+    /// Interval at which the recommended number of shards is checked to
+    /// determine whether the cluster needs to reshard.
+    ///
+    /// Refer to [`ClusterBuilder::reshard_interval`] for the default value.
+    ///
+    /// [`ClusterBuilder::reshard_interval`]: super::ClusterBuilder::reshard_interval
+    pub const fn reshard_interval(&self) -> Option<Duration> {
+        self.reshard_interval
+    }
+
+    /// Time to wait for the new shards to be connected while resharding.
+    ///
+    /// Refer to [`ClusterBuilder::reshard_timeout`] for the default value.
+    ///
+    /// [`ClusterBuilder::reshard_timeout`]: super::ClusterBuilder::reshard_timeout
+    pub const fn reshard_timeout(&self) -> Duration {
+        self.reshard_timeout
+    }
+
+    /// Return an immutable reference to the store used to save and load the
+    /// sessions of the shards.
+    ///
+    /// Refer to [`ClusterBuilder::session_store`] for the default value.
+    ///
+    /// [`ClusterBuilder::session_store`]: super::ClusterBuilder::session_store
+    pub fn session_store(&self) -> Option<&Arc<dyn SessionStore>> {
+        self.session_store.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,223 @@
+//! Streams merging the events of the shards managed by a cluster.
+
+use crate::{fan_out::FanOut, shard::Events};
+use futures_util::stream::{SelectAll, Stream, StreamExt};
+use std::{
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc::UnboundedReceiver;
+use twilight_model::gateway::event::Event;
+
+/// Stream of events from a single shard, tagged with the shard's ID.
+pub(super) struct ShardEvents {
+    events: Events,
+    id: u64,
+}
+
+impl ShardEvents {
+    pub const fn new(id: u64, events: Events) -> Self {
+        Self { events, id }
+    }
+}
+
+impl Stream for ShardEvents {
+    type Item = (u64, Event);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let id = self.id;
+
+        self.events
+            .poll_next_unpin(cx)
+            .map(|maybe_event| maybe_event.map(|event| (id, event)))
+    }
+}
+
+/// Stream of events from all shards of a cluster, tagged with the ID of the
+/// shard.
+///
+/// Created along with the cluster via [`ClusterBuilder::build`] or
+/// [`Cluster::new`].
+///
+/// [`Cluster::new`]: super::Cluster::new
+/// [`ClusterBuilder::build`]: super::ClusterBuilder::build
+pub struct ClusterEvents(ShardSets<ShardEvents>);
+
+impl ClusterEvents {
+    pub(super) const fn new(
+        streams: SelectAll<ShardEvents>,
+        swaps: UnboundedReceiver<SelectAll<ShardEvents>>,
+    ) -> Self {
+        Self(ShardSets::new(streams, swaps))
+    }
+
+    /// Share the events between multiple consumers, each subscribing to the
+    /// event types it needs.
+    ///
+    /// Refer to [`FanOut`] for more information.
+    pub fn fan_out(self) -> FanOut<(u64, Event)> {
+        FanOut::new(self)
+    }
+}
+
+impl Stream for ClusterEvents {
+    type Item = (u64, Event);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_next_unpin(cx)
+    }
+}
+
+/// Streams of the current and previous sets of shards of a cluster.
+///
+/// When the cluster reshards it sends the streams of the new set of shards
+/// over a channel. The stream swaps over to them the next time it is polled,
+/// while continuing to pass on the events of the old set of shards ahead of
+/// the events of the new set until the old streams end, so that events
+/// buffered by the old shards are not lost.
+struct ShardSets<S> {
+    /// Streams of previous sets of shards, oldest first.
+    previous: Vec<SelectAll<S>>,
+    streams: SelectAll<S>,
+    swaps: UnboundedReceiver<SelectAll<S>>,
+}
+
+impl<S> ShardSets<S> {
+    const fn new(streams: SelectAll<S>, swaps: UnboundedReceiver<SelectAll<S>>) -> Self {
+        Self {
+            previous: Vec::new(),
+            streams,
+            swaps,
+        }
+    }
+}
+
+impl<S: Stream<Item = (u64, Event)> + Unpin> Stream for ShardSets<S> {
+    type Item = (u64, Event);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        while let Poll::Ready(Some(streams)) = self.swaps.poll_recv(cx) {
+            let previous = mem::replace(&mut self.streams, streams);
+            self.previous.push(previous);
+        }
+
+        let mut idx = 0;
+
+        while idx < self.previous.len() {
+            match self.previous[idx].poll_next_unpin(cx) {
+                Poll::Ready(Some(item)) => return Poll::Ready(Some(item)),
+                Poll::Ready(None) => {
+                    drop(self.previous.remove(idx));
+                }
+                Poll::Pending => idx += 1,
+            }
+        }
+
+        match self.streams.poll_next_unpin(cx) {
+            // The stream only ends once the streams of previous sets of
+            // shards have ended too.
+            Poll::Ready(None) if !self.previous.is_empty() => Poll::Pending,
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClusterEvents, ShardEvents, ShardSets};
+    use futures_util::{
+        future::{self, FutureExt},
+        stream::{self, SelectAll, Stream, StreamExt},
+    };
+    use static_assertions::assert_impl_all;
+    use std::pin::Pin;
+    use tokio::sync::mpsc::{self, UnboundedSender};
+    use twilight_model::gateway::event::Event;
+
+    assert_impl_all!(ClusterEvents: Send, Stream, Sync, Unpin);
+    assert_impl_all!(ShardEvents: Send, Stream, Sync, Unpin);
+
+    type Shard = Pin<Box<dyn Stream<Item = (u64, Event)> + Send>>;
+
+    /// Create the streams of a set of shards, returning the senders of their
+    /// events.
+    fn shards(ids: &[u64]) -> (Vec<UnboundedSender<(u64, Event)>>, SelectAll<Shard>) {
+        let mut senders = Vec::new();
+        let mut streams = SelectAll::new();
+
+        for _ in ids {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            senders.push(tx);
+            streams.push(Box::pin(stream::poll_fn(move |cx| rx.poll_recv(cx))) as Shard);
+        }
+
+        (senders, streams)
+    }
+
+    #[tokio::test]
+    async fn test_swap_keeps_buffered_events() {
+        let (old, streams) = shards(&[0, 1]);
+        let (swaps, swaps_rx) = mpsc::unbounded_channel();
+        let mut events = ShardSets::new(streams, swaps_rx);
+
+        old[0].send((0, Event::GatewayHeartbeat(1))).unwrap();
+        old[1].send((1, Event::GatewayHeartbeat(2))).unwrap();
+
+        let (new, streams) = shards(&[0, 1, 2]);
+        new[2].send((2, Event::GatewayHeartbeat(3))).unwrap();
+        swaps.send(streams).unwrap();
+
+        // The buffered events of the old shards come first.
+        let mut first = vec![
+            events.next().await.unwrap().0,
+            events.next().await.unwrap().0,
+        ];
+        first.sort_unstable();
+        assert_eq!(vec![0, 1], first);
+        assert_eq!(Some((2, Event::GatewayHeartbeat(3))), events.next().await);
+
+        // Old shards may still emit events until they are shut down.
+        old[1].send((1, Event::GatewayHeartbeat(4))).unwrap();
+        assert_eq!(Some((1, Event::GatewayHeartbeat(4))), events.next().await);
+
+        drop(old);
+        assert!(events.next().now_or_never().is_none());
+
+        drop(new);
+        assert!(events.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_swap_no_events_lost() {
+        let (old, streams) = shards(&[0]);
+        let (swaps, swaps_rx) = mpsc::unbounded_channel();
+        let events = ShardSets::new(streams, swaps_rx);
+
+        for seq in 0..100 {
+            old[0].send((0, Event::GatewayHeartbeat(seq))).unwrap();
+        }
+
+        // Swap twice before the stream is polled again.
+        let (first, streams) = shards(&[0]);
+        swaps.send(streams).unwrap();
+        let (second, streams) = shards(&[0]);
+        swaps.send(streams).unwrap();
+
+        first[0].send((0, Event::GatewayHeartbeat(100))).unwrap();
+        second[0].send((0, Event::GatewayHeartbeat(101))).unwrap();
+        drop((old, first, second, swaps));
+
+        let received = events
+            .filter_map(|(_, event)| {
+                future::ready(match event {
+                    Event::GatewayHeartbeat(seq) => Some(seq),
+                    _ => None,
+                })
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!((0..102).collect::<Vec<_>>(), received);
+    }
+}
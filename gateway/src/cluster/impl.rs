@@ -1,21 +1,147 @@
-use super::{builder::ClusterBuilder, config::Config, scheme::ShardScheme};
+use super::{
+    builder::ClusterBuilder,
+    config::Config,
+    event::{ClusterEvents, ShardEvents},
+    presence::{PresenceManager, PresenceState},
+    scheme::ShardScheme,
+    session_store,
+};
 use crate::{
-    shard::{raw_message::Message, Events, Information, ResumeSession, Shard},
+    shard::{
+        raw_message::Message, CollectGuildMembersError, CollectGuildMembersErrorType,
+        Config as ShardConfig, GuildMembers, Information, ResumeSession, Shard, ShardFatalError,
+        Stage,
+    },
     Intents,
 };
-use futures_util::{
-    future,
-    stream::{SelectAll, Stream, StreamExt},
-};
+use futures_util::{future, stream::SelectAll};
 use std::{
-    collections::HashMap,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
-    iter::FromIterator,
-    sync::{Arc, Mutex},
+    mem,
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc::{self, UnboundedSender},
+    task::JoinHandle,
 };
+use twilight_gateway_queue::Priority;
 use twilight_http::Client as HttpClient;
-use twilight_model::gateway::event::Event;
+use twilight_model::{
+    gateway::payload::{update_presence::UpdatePresencePayload, RequestGuildMembers},
+    id::GuildId,
+};
+
+/// Collecting the members of a guild failed.
+#[derive(Debug)]
+pub struct ClusterCollectGuildMembersError {
+    kind: ClusterCollectGuildMembersErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl ClusterCollectGuildMembersError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &ClusterCollectGuildMembersErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(
+        self,
+    ) -> (
+        ClusterCollectGuildMembersErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, self.source)
+    }
+
+    fn from_collect(error: CollectGuildMembersError) -> Self {
+        let (kind, source) = error.into_parts();
+
+        let kind = match kind {
+            CollectGuildMembersErrorType::Sending => ClusterCollectGuildMembersErrorType::Sending,
+            CollectGuildMembersErrorType::TimedOut { received, total } => {
+                ClusterCollectGuildMembersErrorType::TimedOut { received, total }
+            }
+        };
+
+        Self { kind, source }
+    }
+}
+
+impl Display for ClusterCollectGuildMembersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            ClusterCollectGuildMembersErrorType::Sending => {
+                f.write_str("sending the request for guild members failed")
+            }
+            ClusterCollectGuildMembersErrorType::ShardNotManaged { guild_id, shard_id } => {
+                fmt_shard_not_managed(f, *guild_id, *shard_id)
+            }
+            ClusterCollectGuildMembersErrorType::TimedOut { received, total } => {
+                f.write_str("timed out after receiving ")?;
+                Display::fmt(received, f)?;
+                f.write_str(" of ")?;
+
+                if let Some(total) = total {
+                    Display::fmt(total, f)?;
+                } else {
+                    f.write_str("an unknown number of")?;
+                }
+
+                f.write_str(" member chunks")
+            }
+        }
+    }
+}
+
+impl Error for ClusterCollectGuildMembersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`ClusterCollectGuildMembersError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ClusterCollectGuildMembersErrorType {
+    /// Sending the request for guild members failed.
+    ///
+    /// The source error is a [`CommandError`].
+    ///
+    /// [`CommandError`]: crate::shard::CommandError
+    Sending,
+    /// Shard of the provided guild is not managed by the cluster.
+    ///
+    /// This can occur when the cluster only manages a subset of the bot's
+    /// shards, such as with a [`ShardScheme::Bucket`] or [`ShardScheme::Range`].
+    ShardNotManaged {
+        /// Provided guild ID.
+        guild_id: GuildId,
+        /// ID of the shard the guild belongs to.
+        shard_id: u64,
+    },
+    /// Not every member chunk was received before the timeout elapsed.
+    TimedOut {
+        /// Number of member chunks received.
+        received: u32,
+        /// Total number of member chunks, if any chunk was received.
+        total: Option<u32>,
+    },
+}
 
 /// Sending a command to a shard failed.
 #[derive(Debug)]
@@ -76,6 +202,9 @@ impl Display for ClusterCommandError {
 
                 f.write_str(" does not exist")
             }
+            ClusterCommandErrorType::ShardNotManaged { guild_id, shard_id } => {
+                fmt_shard_not_managed(f, *guild_id, *shard_id)
+            }
         }
     }
 }
@@ -99,6 +228,158 @@ pub enum ClusterCommandErrorType {
         /// Provided shard ID.
         id: u64,
     },
+    /// Shard of the provided guild is not managed by the cluster.
+    ///
+    /// This can occur when the cluster only manages a subset of the bot's
+    /// shards, such as with a [`ShardScheme::Bucket`] or [`ShardScheme::Range`].
+    ShardNotManaged {
+        /// Provided guild ID.
+        guild_id: GuildId,
+        /// ID of the shard the guild belongs to.
+        shard_id: u64,
+    },
+}
+
+/// Retrieving the shard of a guild failed.
+#[derive(Debug)]
+pub struct ClusterGuildShardError {
+    kind: ClusterGuildShardErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl ClusterGuildShardError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &ClusterGuildShardErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(
+        self,
+    ) -> (
+        ClusterGuildShardErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for ClusterGuildShardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            ClusterGuildShardErrorType::ShardNotManaged { guild_id, shard_id } => {
+                fmt_shard_not_managed(f, *guild_id, *shard_id)
+            }
+        }
+    }
+}
+
+impl Error for ClusterGuildShardError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`ClusterGuildShardError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ClusterGuildShardErrorType {
+    /// Shard of the provided guild is not managed by the cluster.
+    ///
+    /// This can occur when the cluster only manages a subset of the bot's
+    /// shards, such as with a [`ShardScheme::Bucket`] or [`ShardScheme::Range`].
+    ShardNotManaged {
+        /// Provided guild ID.
+        guild_id: GuildId,
+        /// ID of the shard the guild belongs to.
+        shard_id: u64,
+    },
+}
+
+/// Saving the sessions of the shards to the session store failed.
+#[derive(Debug)]
+pub struct ClusterSaveSessionsError {
+    kind: ClusterSaveSessionsErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl ClusterSaveSessionsError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &ClusterSaveSessionsErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(
+        self,
+    ) -> (
+        ClusterSaveSessionsErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for ClusterSaveSessionsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            ClusterSaveSessionsErrorType::Saving => {
+                f.write_str("saving the sessions to the session store failed")
+            }
+            ClusterSaveSessionsErrorType::SessionStoreMissing => {
+                f.write_str("no session store is configured")
+            }
+        }
+    }
+}
+
+impl Error for ClusterSaveSessionsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`ClusterSaveSessionsError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ClusterSaveSessionsErrorType {
+    /// Session store failed to save the sessions.
+    ///
+    /// The source error is the error returned by the session store.
+    Saving,
+    /// No [session store] is configured.
+    ///
+    /// [session store]: super::ClusterBuilder::session_store
+    SessionStoreMissing,
+}
+
+fn fmt_shard_not_managed(f: &mut Formatter<'_>, guild_id: GuildId, shard_id: u64) -> FmtResult {
+    f.write_str("shard ")?;
+    Display::fmt(&shard_id, f)?;
+    f.write_str(" of guild ")?;
+    Display::fmt(&guild_id, f)?;
+
+    f.write_str(" is not managed by this cluster")
 }
 
 /// Sending a raw websocket message via a shard failed.
@@ -223,12 +504,27 @@ pub enum ClusterStartErrorType {
     RetrievingGatewayInfo,
 }
 
+/// Interval at which the stages of new shards are checked while resharding.
+const RESHARD_CONNECTED_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Calculate the ID of the shard that receives the events of a guild, out of
+/// the provided total number of shards.
+const fn guild_shard_id(guild_id: GuildId, total: u64) -> u64 {
+    (guild_id.0 >> 22) % total
+}
+
 #[derive(Debug)]
-struct ClusterRef {
+pub(super) struct ClusterRef {
     config: Config,
-    shard_from: u64,
-    shard_to: u64,
+    /// Presence applied to the shards and its rotation.
+    pub(super) presence: PresenceState,
+    /// Task of the resharding loop, if it is running.
+    reshard_task: Mutex<Option<JoinHandle<()>>>,
+    /// Resolved scheme of the shards currently in use.
+    scheme: Mutex<ShardScheme>,
     shards: Mutex<HashMap<u64, Shard>>,
+    /// Sender for the event streams of a new set of shards after resharding.
+    swaps: UnboundedSender<SelectAll<ShardEvents>>,
 }
 
 /// A manager for multiple shards.
@@ -245,7 +541,7 @@ struct ClusterRef {
 ///
 /// Refer to the module-level documentation for examples.
 #[derive(Clone, Debug)]
-pub struct Cluster(Arc<ClusterRef>);
+pub struct Cluster(pub(super) Arc<ClusterRef>);
 
 impl Cluster {
     /// Create a new unconfigured cluster.
@@ -294,78 +590,102 @@ impl Cluster {
     pub async fn new(
         token: impl Into<String>,
         intents: Intents,
-    ) -> Result<
-        (
-            Self,
-            impl Stream<Item = (u64, Event)> + Send + Sync + Unpin + 'static,
-        ),
-        ClusterStartError,
-    > {
+    ) -> Result<(Self, ClusterEvents), ClusterStartError> {
         Self::builder(token, intents).build().await
     }
 
     pub(super) async fn new_with_config(
         mut config: Config,
-    ) -> Result<
-        (
-            Self,
-            impl Stream<Item = (u64, Event)> + Send + Sync + Unpin + 'static,
-        ),
-        ClusterStartError,
-    > {
-        #[derive(Default)]
-        struct ShardFold {
-            shards: HashMap<u64, Shard>,
-            streams: Vec<(u64, Events)>,
-        }
-
+    ) -> Result<(Self, ClusterEvents), ClusterStartError> {
         let scheme = match config.shard_scheme() {
             ShardScheme::Auto => Self::retrieve_shard_count(&config.http_client).await?,
             other => other.clone(),
         };
 
-        let iter = scheme.iter().expect("shard scheme is not auto");
-        let total = scheme.total().expect("shard scheme is not auto");
-
         #[cfg(feature = "metrics")]
         #[allow(clippy::cast_precision_loss)]
         {
-            metrics::gauge!("Cluster-Shard-Count", total as f64);
+            let total = scheme.total().expect("shard scheme is not auto");
+            metrics::gauge!(crate::metrics::CLUSTER_SHARD_COUNT, total as f64);
         }
 
-        let ShardFold { shards, streams } = iter.fold(ShardFold::default(), |mut fold, idx| {
-            let mut shard_config = config.shard_config().clone();
+        if let Some(store) = config.session_store.clone() {
+            let total = scheme.total().expect("shard scheme is not auto");
+            session_store::load_sessions(&*store, total, &mut config.resume_sessions).await;
+        }
+
+        let (shards, streams) = Self::create_shards(
+            &config.shard_config,
+            &scheme,
+            &config.priority_guilds,
+            None,
+            &mut config.resume_sessions,
+        );
+        let (swaps, swaps_rx) = mpsc::unbounded_channel();
+        let presence = PresenceState::new(config.shard_config.presence().cloned());
+
+        Ok((
+            Self(Arc::new(ClusterRef {
+                config,
+                presence,
+                reshard_task: Mutex::new(None),
+                scheme: Mutex::new(scheme),
+                shards: Mutex::new(shards),
+                swaps,
+            })),
+            ClusterEvents::new(streams, swaps_rx),
+        ))
+    }
+
+    /// Create the shards of a resolved shard scheme along with a combined
+    /// stream of their events.
+    ///
+    /// Shards with an entry in the provided resume sessions are configured to
+    /// resume their session, and shards receiving the events of any of the
+    /// priority guilds request to initialize their sessions with a high
+    /// priority. If a presence is provided, it replaces the configured one.
+    fn create_shards(
+        shard_config: &ShardConfig,
+        scheme: &ShardScheme,
+        priority_guilds: &HashSet<GuildId>,
+        presence: Option<&UpdatePresencePayload>,
+        resume_sessions: &mut HashMap<u64, ResumeSession>,
+    ) -> (HashMap<u64, Shard>, SelectAll<ShardEvents>) {
+        let iter = scheme.iter().expect("shard scheme is not auto");
+        let total = scheme.total().expect("shard scheme is not auto");
+
+        let mut shards = HashMap::new();
+        let mut streams = SelectAll::new();
+
+        for idx in iter {
+            let mut shard_config = shard_config.clone();
             shard_config.shard = [idx, total];
 
-            if let Some(data) = config.resume_sessions.remove(&idx) {
+            if let Some(presence) = presence {
+                shard_config.presence = Some(presence.clone());
+            }
+
+            if priority_guilds
+                .iter()
+                .any(|guild_id| guild_shard_id(*guild_id, total) == idx)
+            {
+                shard_config.queue_priority = Priority::High;
+            }
+
+            if let Some(data) = resume_sessions.remove(&idx) {
+                shard_config.resume_gateway_url =
+                    data.resume_gateway_url.map(String::into_boxed_str);
                 shard_config.session_id = Some(data.session_id.into_boxed_str());
                 shard_config.sequence = Some(data.sequence);
             }
 
             let (shard, stream) = Shard::new_with_config(shard_config);
 
-            fold.shards.insert(idx, shard);
-            fold.streams.push((idx, stream));
-
-            fold
-        });
-
-        let combined = streams
-            .into_iter()
-            .map(|(id, stream)| stream.map(move |e| (id, e)));
-
-        #[allow(clippy::from_iter_instead_of_collect)]
-        let select_all = SelectAll::from_iter(combined);
+            shards.insert(idx, shard);
+            streams.push(ShardEvents::new(idx, stream));
+        }
 
-        Ok((
-            Self(Arc::new(ClusterRef {
-                config,
-                shard_from: scheme.from().expect("shard scheme is not auto"),
-                shard_to: scheme.to().expect("shard scheme is not auto"),
-                shards: Mutex::new(shards),
-            })),
-            select_all,
-        ))
+        (shards, streams)
     }
 
     /// Retrieve the recommended number of shards from the HTTP API.
@@ -460,15 +780,58 @@ impl Cluster {
     /// cluster.up().await;
     /// # Ok(()) }
     /// ```
+    ///
+    /// If a [reshard interval] is configured, this also starts checking
+    /// whether the cluster needs to reshard.
+    ///
+    /// [reshard interval]: super::ClusterBuilder::reshard_interval
     pub async fn up(&self) {
+        let mut ids = self
+            .0
+            .scheme
+            .lock()
+            .expect("scheme poisoned")
+            .iter()
+            .expect("shard scheme is not auto")
+            .collect::<Vec<_>>();
+
+        // Shards are queued in the order they are started, so start shards
+        // with a high queue priority first.
+        {
+            let shards = self.0.shards.lock().expect("shards poisoned");
+            ids.sort_by_key(|id| {
+                Reverse(shards.get(id).map(|shard| shard.config().queue_priority()))
+            });
+        }
+
         future::join_all(
-            (self.0.shard_from..=self.0.shard_to).map(|id| Self::start(Arc::clone(&self.0), id)),
+            ids.into_iter()
+                .map(|id| Self::start(Arc::clone(&self.0), id)),
         )
         .await;
+
+        if let Some(interval) = self.0.config.reshard_interval() {
+            if *self.0.config.shard_scheme() == ShardScheme::Auto {
+                let mut task = self.0.reshard_task.lock().expect("reshard task poisoned");
+
+                if task.is_none() {
+                    task.replace(tokio::spawn(Self::reshard_loop(
+                        Arc::downgrade(&self.0),
+                        interval,
+                    )));
+                }
+            }
+        }
     }
 
     /// Bring down the cluster, stopping all of the shards that it's managing.
+    ///
+    /// This also stops checking whether the cluster needs to reshard and
+    /// stops any presence rotation.
     pub fn down(&self) {
+        self.stop_resharding();
+        self.0.presence.stop_rotation();
+
         for shard in self.0.shards.lock().expect("shards poisoned").values() {
             shard.shutdown();
         }
@@ -484,16 +847,95 @@ impl Cluster {
     /// disconnection. You may also not be able to resume if you missed too many
     /// events already.
     pub fn down_resumable(&self) -> HashMap<u64, ResumeSession> {
+        self.down_resumable_shards()
+            .into_iter()
+            .map(|([id, _], session)| (id, session))
+            .collect()
+    }
+
+    /// Bring down the cluster in a resumable way, returning the sessions
+    /// keyed by shard ID and total number of shards.
+    fn down_resumable_shards(&self) -> HashMap<[u64; 2], ResumeSession> {
+        self.stop_resharding();
+        self.0.presence.stop_rotation();
+
         self.0
             .shards
             .lock()
             .expect("shards poisoned")
             .values()
-            .map(Shard::shutdown_resumable)
-            .filter_map(|(id, session)| session.map(|s| (id, s)))
+            .filter_map(|shard| {
+                let [_, total] = shard.config().shard();
+                let (id, session) = shard.shutdown_resumable();
+
+                session.map(|session| ([id, total], session))
+            })
             .collect()
     }
 
+    /// Bring down the cluster in a resumable way and save the sessions of the
+    /// shards to the configured [session store].
+    ///
+    /// The next cluster built with the session store resumes the saved
+    /// sessions. Refer to [`down_resumable`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClusterSaveSessionsErrorType::Saving`] error type if the
+    /// session store failed to save the sessions.
+    ///
+    /// Returns a [`ClusterSaveSessionsErrorType::SessionStoreMissing`] error
+    /// type if no session store is configured. The cluster is still brought
+    /// down.
+    ///
+    /// [`down_resumable`]: Self::down_resumable
+    /// [session store]: super::ClusterBuilder::session_store
+    pub async fn down_resumable_and_save(&self) -> Result<(), ClusterSaveSessionsError> {
+        let sessions = self.down_resumable_shards();
+
+        let store = self
+            .0
+            .config
+            .session_store()
+            .ok_or(ClusterSaveSessionsError {
+                kind: ClusterSaveSessionsErrorType::SessionStoreMissing,
+                source: None,
+            })?;
+
+        store
+            .save(sessions)
+            .await
+            .map_err(|source| ClusterSaveSessionsError {
+                kind: ClusterSaveSessionsErrorType::Saving,
+                source: Some(source),
+            })
+    }
+
+    /// Return the errors of the shards that stopped because the gateway closed
+    /// their connection with a fatal close code, keyed by shard ID.
+    ///
+    /// Stopped shards do not reconnect. The last event a stopped shard sends
+    /// to the cluster's event stream is an [`Event::ShardDisconnected`] whose
+    /// kind is [`CloseCodeKind::Fatal`], emitted after the error is set.
+    ///
+    /// [`CloseCodeKind::Fatal`]: twilight_model::gateway::CloseCodeKind::Fatal
+    pub fn fatal_errors(&self) -> HashMap<u64, ShardFatalError> {
+        self.0
+            .shards
+            .lock()
+            .expect("shards poisoned")
+            .iter()
+            .filter_map(|(id, shard)| shard.fatal_error().map(|error| (*id, error.clone())))
+            .collect()
+    }
+
+    /// Return a manager for the presence of all shards.
+    ///
+    /// Refer to [`PresenceManager`] for more information.
+    pub fn presence(&self) -> PresenceManager {
+        PresenceManager::new(self.clone())
+    }
+
     /// Return a Shard by its ID.
     pub fn shard(&self, id: u64) -> Option<Shard> {
         self.0
@@ -515,6 +957,38 @@ impl Cluster {
             .collect()
     }
 
+    /// Calculate the ID of the shard that receives the events of a guild.
+    ///
+    /// This is `(guild_id >> 22) % total`, where `total` is the total number
+    /// of shards used by the bot across all clusters. The shard is not
+    /// necessarily managed by this cluster.
+    pub fn guild_shard_id(&self, guild_id: GuildId) -> u64 {
+        let total = self
+            .0
+            .scheme
+            .lock()
+            .expect("scheme poisoned")
+            .total()
+            .expect("shard scheme is not auto");
+
+        guild_shard_id(guild_id, total)
+    }
+
+    /// Return the shard that receives the events of a guild.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClusterGuildShardErrorType::ShardNotManaged`] error type if
+    /// the guild's shard is not managed by this cluster.
+    pub fn guild_shard(&self, guild_id: GuildId) -> Result<Shard, ClusterGuildShardError> {
+        let shard_id = self.guild_shard_id(guild_id);
+
+        self.shard(shard_id).ok_or(ClusterGuildShardError {
+            kind: ClusterGuildShardErrorType::ShardNotManaged { guild_id, shard_id },
+            source: None,
+        })
+    }
+
     /// Return information about all shards.
     ///
     /// # Examples
@@ -580,6 +1054,135 @@ impl Cluster {
             })
     }
 
+    /// Send a command to the shard that receives the events of a guild.
+    ///
+    /// This is useful for commands that are tied to a guild, such as
+    /// [`RequestGuildMembers`] and [`UpdateVoiceState`].
+    ///
+    /// # Examples
+    ///
+    /// Request the members of a guild whose name starts with "tw":
+    ///
+    /// ```no_run
+    /// use std::env;
+    /// use twilight_gateway::{Cluster, Intents};
+    /// use twilight_model::{gateway::payload::RequestGuildMembers, id::GuildId};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let token = env::var("DISCORD_TOKEN")?;
+    /// let (cluster, _) = Cluster::new(token, Intents::GUILD_MEMBERS).await?;
+    /// cluster.up().await;
+    ///
+    /// let guild_id = GuildId(1);
+    /// let request = RequestGuildMembers::builder(guild_id).query("tw", None);
+    /// cluster.command_guild(guild_id, &request).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClusterCommandErrorType::Sending`] error type if the shard
+    /// exists, but sending it failed.
+    ///
+    /// Returns a [`ClusterCommandErrorType::ShardNotManaged`] error type if
+    /// the guild's shard is not managed by this cluster.
+    ///
+    /// [`RequestGuildMembers`]: twilight_model::gateway::payload::RequestGuildMembers
+    /// [`UpdateVoiceState`]: twilight_model::gateway::payload::UpdateVoiceState
+    pub async fn command_guild(
+        &self,
+        guild_id: GuildId,
+        value: &impl serde::Serialize,
+    ) -> Result<(), ClusterCommandError> {
+        let shard = self.guild_shard(guild_id).map_err(|source| {
+            let (kind, source) = source.into_parts();
+
+            match kind {
+                ClusterGuildShardErrorType::ShardNotManaged { guild_id, shard_id } => {
+                    ClusterCommandError {
+                        kind: ClusterCommandErrorType::ShardNotManaged { guild_id, shard_id },
+                        source,
+                    }
+                }
+            }
+        })?;
+
+        shard
+            .command(value)
+            .await
+            .map_err(|source| ClusterCommandError {
+                kind: ClusterCommandErrorType::Sending,
+                source: Some(Box::new(source)),
+            })
+    }
+
+    /// Request the members of a guild from the shard that receives its
+    /// events and collect all of the member chunks sent in response.
+    ///
+    /// Refer to [`Shard::collect_guild_members`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Collect all members of a guild:
+    ///
+    /// ```no_run
+    /// use std::{env, time::Duration};
+    /// use twilight_gateway::{Cluster, Intents};
+    /// use twilight_model::{gateway::payload::RequestGuildMembers, id::GuildId};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let token = env::var("DISCORD_TOKEN")?;
+    /// let (cluster, _) = Cluster::new(token, Intents::GUILD_MEMBERS).await?;
+    /// cluster.up().await;
+    ///
+    /// let request = RequestGuildMembers::builder(GuildId(1)).query("", None);
+    /// let members = cluster
+    ///     .collect_guild_members(request, Duration::from_secs(30))
+    ///     .await?;
+    ///
+    /// println!("guild has {} members", members.members.len());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClusterCollectGuildMembersErrorType::Sending`] error type
+    /// if the request could not be sent.
+    ///
+    /// Returns a [`ClusterCollectGuildMembersErrorType::ShardNotManaged`]
+    /// error type if the guild's shard is not managed by this cluster.
+    ///
+    /// Returns a [`ClusterCollectGuildMembersErrorType::TimedOut`] error type
+    /// if not every member chunk was received within the provided timeout.
+    pub async fn collect_guild_members(
+        &self,
+        request: RequestGuildMembers,
+        timeout: Duration,
+    ) -> Result<GuildMembers, ClusterCollectGuildMembersError> {
+        let guild_id = request.d.guild_id;
+
+        let shard = self.guild_shard(guild_id).map_err(|source| {
+            let (kind, source) = source.into_parts();
+
+            match kind {
+                ClusterGuildShardErrorType::ShardNotManaged { guild_id, shard_id } => {
+                    ClusterCollectGuildMembersError {
+                        kind: ClusterCollectGuildMembersErrorType::ShardNotManaged {
+                            guild_id,
+                            shard_id,
+                        },
+                        source,
+                    }
+                }
+            }
+        })?;
+
+        shard
+            .collect_guild_members(request, timeout)
+            .await
+            .map_err(ClusterCollectGuildMembersError::from_collect)
+    }
+
     /// Send a raw command to the specified shard.
     ///
     /// # Errors
@@ -664,24 +1267,337 @@ impl Cluster {
 
         Some(shard)
     }
+
+    /// Stop the resharding loop, shutting down the new shards of a reshard in
+    /// progress.
+    fn stop_resharding(&self) {
+        if let Some(handle) = self
+            .0
+            .reshard_task
+            .lock()
+            .expect("reshard task poisoned")
+            .take()
+        {
+            handle.abort();
+        }
+    }
+
+    /// Periodically retrieve the recommended number of shards, resharding
+    /// when it differs from the number of shards in use.
+    ///
+    /// Accepts a weak reference to the cluster so that the loop ends once the
+    /// cluster is dropped.
+    async fn reshard_loop(cluster: Weak<ClusterRef>, interval: Duration) {
+        loop {
+            tokio::time::sleep(interval).await;
+
+            let (http, current) = match cluster.upgrade() {
+                Some(cluster) => (
+                    cluster.config.http_client.clone(),
+                    cluster.scheme.lock().expect("scheme poisoned").total(),
+                ),
+                None => return,
+            };
+
+            let scheme = match Self::retrieve_shard_count(&http).await {
+                Ok(scheme) => scheme,
+                Err(source) => {
+                    tracing::warn!("failed to retrieve recommended shard count: {}", source);
+
+                    continue;
+                }
+            };
+
+            if scheme.total() != current {
+                Self::reshard(&cluster, scheme).await;
+            }
+        }
+    }
+
+    /// Start a new set of shards with the provided scheme, swapping the shards
+    /// and event stream of the cluster over to them once all of them are
+    /// connected.
+    ///
+    /// The old shards are shut down after the swap. If any of the new shards
+    /// fail to start, stop with a fatal error, or are not connected within
+    /// the [reshard timeout], the new shards are shut down instead. This is
+    /// also the case if the cluster is dropped or the future is aborted by
+    /// bringing down the cluster.
+    ///
+    /// Accepts a weak reference to the cluster so that a reshard in progress
+    /// doesn't keep the cluster alive.
+    ///
+    /// [reshard timeout]: super::ClusterBuilder::reshard_timeout
+    async fn reshard(cluster: &Weak<ClusterRef>, scheme: ShardScheme) {
+        let total = scheme.total().expect("shard scheme is not auto");
+        tracing::info!("resharding to {} shards", total);
+
+        let (shards, streams, timeout) = {
+            let cluster = match cluster.upgrade() {
+                Some(cluster) => cluster,
+                None => return,
+            };
+
+            let presence = cluster.presence.current();
+            let (shards, streams) = Self::create_shards(
+                &cluster.config.shard_config,
+                &scheme,
+                &cluster.config.priority_guilds,
+                presence.as_ref(),
+                &mut HashMap::new(),
+            );
+
+            (shards, streams, cluster.config.reshard_timeout())
+        };
+
+        let shards = ReshardShards(Some(shards));
+
+        let connected = tokio::time::timeout(timeout, Self::connect_shards(cluster, &shards)).await;
+
+        match connected {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(_) => {
+                tracing::warn!(
+                    "new shards did not connect within {:?} while resharding",
+                    timeout
+                );
+
+                return;
+            }
+        }
+
+        let cluster = match cluster.upgrade() {
+            Some(cluster) => cluster,
+            None => return,
+        };
+
+        let old = {
+            let mut current = cluster.shards.lock().expect("shards poisoned");
+            *cluster.scheme.lock().expect("scheme poisoned") = scheme;
+
+            // The receiving half is only gone if the event stream was dropped,
+            // in which case there is nothing to swap over.
+            let _res = cluster.swaps.send(streams);
+
+            mem::replace(&mut *current, shards.into_inner())
+        };
+
+        old.values().for_each(Shard::shutdown);
+
+        #[cfg(feature = "metrics")]
+        #[allow(clippy::cast_precision_loss)]
+        {
+            metrics::gauge!(crate::metrics::CLUSTER_SHARD_COUNT, total as f64);
+        }
+
+        tracing::info!("resharded to {} shards", total);
+    }
+
+    /// Start the new shards of a reshard and wait for all of them to be
+    /// connected.
+    ///
+    /// Returns whether all of them connected, which is not the case if any
+    /// of them failed to start or stopped with a fatal error, or the cluster
+    /// was dropped.
+    async fn connect_shards(cluster: &Weak<ClusterRef>, shards: &ReshardShards) -> bool {
+        let shards = shards.0.as_ref().expect("shards taken");
+
+        let mut starting = shards.values().collect::<Vec<_>>();
+        starting.sort_by_key(|shard| {
+            let config = shard.config();
+
+            (Reverse(config.queue_priority()), config.shard()[0])
+        });
+
+        let started = future::join_all(starting.into_iter().map(Shard::start)).await;
+
+        if let Some(source) = started.into_iter().find_map(Result::err) {
+            tracing::warn!("failed to start shard while resharding: {}", source);
+
+            return false;
+        }
+
+        loop {
+            if cluster.strong_count() == 0 {
+                return false;
+            }
+
+            if let Some(error) = shards.values().find_map(Shard::fatal_error) {
+                tracing::warn!("shard stopped while resharding: {}", error);
+
+                return false;
+            }
+
+            let connected = shards.values().all(|shard| {
+                shard
+                    .info()
+                    .map_or(false, |info| info.stage() == Stage::Connected)
+            });
+
+            if connected {
+                return true;
+            }
+
+            tokio::time::sleep(RESHARD_CONNECTED_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// New shards of a reshard in progress, which are shut down when dropped
+/// unless the cluster swapped over to them.
+struct ReshardShards(Option<HashMap<u64, Shard>>);
+
+impl ReshardShards {
+    fn into_inner(mut self) -> HashMap<u64, Shard> {
+        self.0.take().expect("shards taken")
+    }
+}
+
+impl Drop for ReshardShards {
+    fn drop(&mut self) {
+        if let Some(shards) = self.0.take() {
+            shards.values().for_each(Shard::shutdown);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        Cluster, ClusterCommandError, ClusterCommandErrorType, ClusterSendError,
-        ClusterSendErrorType, ClusterStartError, ClusterStartErrorType,
+        Cluster, ClusterCollectGuildMembersError, ClusterCollectGuildMembersErrorType,
+        ClusterCommandError, ClusterCommandErrorType, ClusterGuildShardError,
+        ClusterGuildShardErrorType, ClusterSaveSessionsError, ClusterSaveSessionsErrorType,
+        ClusterSendError, ClusterSendErrorType, ClusterStartError, ClusterStartErrorType,
+        ShardScheme,
     };
+    use crate::{shard::ShardBuilder, Intents};
     use static_assertions::{assert_fields, assert_impl_all};
-    use std::{error::Error, fmt::Debug};
+    use std::{collections::HashMap, error::Error, fmt::Debug, time::Duration};
+    use twilight_gateway_queue::Priority;
+    use twilight_model::{gateway::payload::RequestGuildMembers, id::GuildId};
 
+    assert_impl_all!(ClusterCollectGuildMembersErrorType: Debug, Send, Sync);
+    assert_fields!(ClusterCollectGuildMembersErrorType::ShardNotManaged: guild_id, shard_id);
+    assert_fields!(ClusterCollectGuildMembersErrorType::TimedOut: received, total);
+    assert_impl_all!(ClusterCollectGuildMembersError: Error, Send, Sync);
     assert_impl_all!(ClusterCommandErrorType: Debug, Send, Sync);
     assert_fields!(ClusterCommandErrorType::ShardNonexistent: id);
+    assert_fields!(ClusterCommandErrorType::ShardNotManaged: guild_id, shard_id);
     assert_impl_all!(ClusterCommandError: Error, Send, Sync);
+    assert_impl_all!(ClusterGuildShardErrorType: Debug, Send, Sync);
+    assert_fields!(ClusterGuildShardErrorType::ShardNotManaged: guild_id, shard_id);
+    assert_impl_all!(ClusterGuildShardError: Error, Send, Sync);
+    assert_impl_all!(ClusterSaveSessionsErrorType: Debug, Send, Sync);
+    assert_impl_all!(ClusterSaveSessionsError: Error, Send, Sync);
     assert_impl_all!(ClusterSendErrorType: Debug, Send, Sync);
     assert_fields!(ClusterSendErrorType::ShardNonexistent: id);
     assert_impl_all!(ClusterSendError: Error, Send, Sync);
     assert_impl_all!(ClusterStartErrorType: Debug, Send, Sync);
     assert_impl_all!(ClusterStartError: Error, Send, Sync);
     assert_impl_all!(Cluster: Clone, Debug, Send, Sync);
+
+    #[tokio::test]
+    async fn test_create_shards_priority() {
+        let shard_config = ShardBuilder::new("token", Intents::empty()).0;
+        let scheme = ShardScheme::Range {
+            from: 0,
+            to: 3,
+            total: 4,
+        };
+        let priority_guilds = vec![GuildId(6 << 22)].into_iter().collect();
+
+        let (shards, _) = Cluster::create_shards(
+            &shard_config,
+            &scheme,
+            &priority_guilds,
+            None,
+            &mut HashMap::new(),
+        );
+
+        for (id, shard) in &shards {
+            let expected = if *id == 2 {
+                Priority::High
+            } else {
+                Priority::Normal
+            };
+
+            assert_eq!(expected, shard.config().queue_priority());
+        }
+    }
+
+    /// Build a cluster managing the shards 0 and 1 of a total of 4 shards.
+    async fn cluster() -> Cluster {
+        let scheme = ShardScheme::Range {
+            from: 0,
+            to: 1,
+            total: 4,
+        };
+
+        Cluster::builder("token", Intents::empty())
+            .gateway_url(Some("ws://localhost".to_owned()))
+            .shard_scheme(scheme)
+            .build()
+            .await
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn test_guild_shard_id() {
+        let cluster = cluster().await;
+
+        assert_eq!(0, cluster.guild_shard_id(GuildId(0)));
+        // The lower 22 bits are not part of the timestamp.
+        assert_eq!(0, cluster.guild_shard_id(GuildId((1 << 22) - 1)));
+        assert_eq!(1, cluster.guild_shard_id(GuildId(1 << 22)));
+        assert_eq!(3, cluster.guild_shard_id(GuildId(3 << 22)));
+        assert_eq!(0, cluster.guild_shard_id(GuildId(4 << 22)));
+        assert_eq!(3, cluster.guild_shard_id(GuildId(u64::MAX)));
+        assert_eq!(2, cluster.guild_shard_id(GuildId(81_384_788_765_712_384)));
+    }
+
+    #[tokio::test]
+    async fn test_guild_shard_managed() {
+        let cluster = cluster().await;
+
+        let shard = cluster.guild_shard(GuildId(1 << 22)).unwrap();
+        assert_eq!([1, 4], shard.config().shard());
+
+        let shard = cluster.guild_shard(GuildId(5 << 22)).unwrap();
+        assert_eq!([1, 4], shard.config().shard());
+    }
+
+    #[tokio::test]
+    async fn test_guild_shard_not_managed() {
+        let cluster = cluster().await;
+
+        let error = cluster.guild_shard(GuildId(3 << 22)).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ClusterGuildShardErrorType::ShardNotManaged {
+                guild_id: GuildId(id),
+                shard_id: 3,
+            } if *id == 3 << 22
+        ));
+
+        let error = cluster
+            .command_guild(GuildId(u64::MAX), &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ClusterCommandErrorType::ShardNotManaged { shard_id: 3, .. }
+        ));
+
+        let request = RequestGuildMembers::builder(GuildId(3 << 22)).query("", None);
+        let error = cluster
+            .collect_guild_members(request, Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ClusterCollectGuildMembersErrorType::ShardNotManaged { shard_id: 3, .. }
+        ));
+    }
 }
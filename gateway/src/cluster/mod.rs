@@ -8,6 +8,7 @@
 //!
 //! ```no_run
 //! use twilight_gateway::{Cluster, Event, Intents};
+//! use twilight_model::gateway::CloseCodeKind;
 //! use futures::StreamExt;
 //! use std::env;
 //!
@@ -33,6 +34,13 @@
 //!         Event::ShardConnected { .. } => {
 //!             println!("Shard {} is now connected", shard_id);
 //!         },
+//!         Event::ShardDisconnected(disconnected)
+//!             if disconnected.kind == Some(CloseCodeKind::Fatal) =>
+//!         {
+//!             if let Some(error) = cluster.fatal_errors().remove(&shard_id) {
+//!                 println!("Shard {} stopped: {}", shard_id, error);
+//!             }
+//!         },
 //!         Event::ShardDisconnected { .. } => {
 //!             println!("Shard {} is now disconnected", shard_id);
 //!         },
@@ -87,14 +95,25 @@ pub mod scheme;
 
 mod builder;
 mod config;
+mod event;
 mod r#impl;
+mod presence;
+mod session_store;
 
 pub use self::{
     builder::ClusterBuilder,
     config::Config,
+    event::ClusterEvents,
+    presence::{
+        PresenceManager, PresenceRotationError, PresenceRotationErrorType,
+        MINIMUM_ROTATION_INTERVAL, PRESENCE_UPDATE_PACE,
+    },
     r#impl::{
-        Cluster, ClusterCommandError, ClusterCommandErrorType, ClusterStartError,
-        ClusterStartErrorType,
+        Cluster, ClusterCollectGuildMembersError, ClusterCollectGuildMembersErrorType,
+        ClusterCommandError, ClusterCommandErrorType, ClusterGuildShardError,
+        ClusterGuildShardErrorType, ClusterSaveSessionsError, ClusterSaveSessionsErrorType,
+        ClusterStartError, ClusterStartErrorType,
     },
     scheme::{ShardScheme, ShardSchemeRangeError, ShardSchemeRangeErrorType},
+    session_store::{FileSessionStore, SessionStore},
 };
@@ -0,0 +1,389 @@
+//! Manage the presence of all shards of a cluster.
+
+use super::r#impl::{Cluster, ClusterRef};
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+use tokio::{sync::Mutex as AsyncMutex, task::JoinHandle};
+use twilight_model::gateway::payload::update_presence::UpdatePresencePayload;
+
+/// Delay between updating the presence of one shard and the next.
+///
+/// Updates are sent to the shards one after the other instead of all at once
+/// to avoid a burst of commands across all connections.
+pub const PRESENCE_UPDATE_PACE: Duration = Duration::from_millis(100);
+
+/// Minimum interval between presences in a rotation.
+///
+/// The gateway only allows a few presence updates per minute on each
+/// connection, ignoring any sent beyond that.
+pub const MINIMUM_ROTATION_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Starting a presence rotation failed.
+#[derive(Debug)]
+pub struct PresenceRotationError {
+    kind: PresenceRotationErrorType,
+}
+
+impl PresenceRotationError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &PresenceRotationErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[allow(clippy::unused_self)]
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        None
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(
+        self,
+    ) -> (
+        PresenceRotationErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, None)
+    }
+}
+
+impl Display for PresenceRotationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            PresenceRotationErrorType::IntervalTooShort { interval } => {
+                f.write_str("rotation interval of ")?;
+                Debug::fmt(interval, f)?;
+                f.write_str(" is shorter than the minimum of ")?;
+
+                Debug::fmt(&MINIMUM_ROTATION_INTERVAL, f)
+            }
+            PresenceRotationErrorType::NoPresences => {
+                f.write_str("at least one presence must be provided")
+            }
+        }
+    }
+}
+
+impl Error for PresenceRotationError {}
+
+/// Type of [`PresenceRotationError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PresenceRotationErrorType {
+    /// Provided interval is shorter than [`MINIMUM_ROTATION_INTERVAL`].
+    IntervalTooShort {
+        /// Provided interval.
+        interval: Duration,
+    },
+    /// No presences were provided.
+    NoPresences,
+}
+
+/// Presence state of a cluster.
+#[derive(Debug)]
+pub(super) struct PresenceState {
+    /// Lock held while the presence is applied to the shards, so that
+    /// updates do not interleave.
+    applying: AsyncMutex<()>,
+    /// Presence most recently applied to the shards.
+    current: Mutex<Option<UpdatePresencePayload>>,
+    /// Task rotating through presences, if any.
+    rotation: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl PresenceState {
+    pub(super) fn new(presence: Option<UpdatePresencePayload>) -> Self {
+        Self {
+            applying: AsyncMutex::new(()),
+            current: Mutex::new(presence),
+            rotation: Mutex::new(None),
+        }
+    }
+
+    /// Presence most recently applied to the shards.
+    pub(super) fn current(&self) -> Option<UpdatePresencePayload> {
+        self.current.lock().expect("presence poisoned").clone()
+    }
+
+    /// Stop the rotation task, if there is one.
+    pub(super) fn stop_rotation(&self) {
+        if let Some(handle) = self.rotation.lock().expect("rotation poisoned").take() {
+            handle.abort();
+        }
+    }
+}
+
+/// Manager for the presence of all shards of a cluster.
+///
+/// Presences are applied to the shards one after the other, paced by
+/// [`PRESENCE_UPDATE_PACE`]. Shards remember the last presence applied to
+/// them and set it when identifying, so it is kept when a shard reconnects
+/// with a new session, as well as by the shards started when resharding.
+///
+/// Obtained via [`Cluster::presence`].
+///
+/// # Examples
+///
+/// Rotate between two activities every minute:
+///
+/// ```no_run
+/// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::{env, time::Duration};
+/// use twilight_gateway::{Cluster, Intents};
+/// use twilight_model::gateway::{
+///     payload::update_presence::UpdatePresencePayload,
+///     presence::{ActivityType, MinimalActivity, Status},
+/// };
+///
+/// let token = env::var("DISCORD_TOKEN")?;
+/// let (cluster, _events) = Cluster::new(token, Intents::empty()).await?;
+/// cluster.up().await;
+///
+/// let presences = ["with shards", "with sessions"]
+///     .iter()
+///     .map(|name| {
+///         UpdatePresencePayload::new(
+///             vec![MinimalActivity {
+///                 kind: ActivityType::Playing,
+///                 name: (*name).to_owned(),
+///                 url: None,
+///             }
+///             .into()],
+///             false,
+///             None,
+///             Status::Online,
+///         )
+///     })
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// cluster.presence().rotate(presences, Duration::from_secs(60))?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PresenceManager {
+    cluster: Cluster,
+}
+
+impl PresenceManager {
+    pub(super) const fn new(cluster: Cluster) -> Self {
+        Self { cluster }
+    }
+
+    /// Return the presence most recently applied to the shards.
+    ///
+    /// This is the presence configured via [`ClusterBuilder::presence`] until
+    /// a presence is applied.
+    ///
+    /// [`ClusterBuilder::presence`]: super::ClusterBuilder::presence
+    pub fn current(&self) -> Option<UpdatePresencePayload> {
+        self.cluster.0.presence.current()
+    }
+
+    /// Apply a presence to all shards, stopping any rotation.
+    ///
+    /// Resolves once the presence has been sent to all shards. Shards that
+    /// fail to send the update, such as because they are reconnecting, set
+    /// the presence when they identify.
+    pub async fn set(&self, presence: UpdatePresencePayload) {
+        self.cluster.0.presence.stop_rotation();
+
+        Self::apply(&self.cluster, presence).await;
+    }
+
+    /// Rotate through a list of presences, applying the next one to all
+    /// shards every interval.
+    ///
+    /// The first presence is applied immediately. This replaces any running
+    /// rotation, which is stopped by [`set`], [`stop_rotation`], or bringing
+    /// the cluster down.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PresenceRotationErrorType::IntervalTooShort`] error type if
+    /// the interval is shorter than [`MINIMUM_ROTATION_INTERVAL`].
+    ///
+    /// Returns a [`PresenceRotationErrorType::NoPresences`] error type if the
+    /// list of presences is empty.
+    ///
+    /// [`set`]: Self::set
+    /// [`stop_rotation`]: Self::stop_rotation
+    pub fn rotate(
+        &self,
+        presences: Vec<UpdatePresencePayload>,
+        interval: Duration,
+    ) -> Result<(), PresenceRotationError> {
+        if interval < MINIMUM_ROTATION_INTERVAL {
+            return Err(PresenceRotationError {
+                kind: PresenceRotationErrorType::IntervalTooShort { interval },
+            });
+        }
+
+        if presences.is_empty() {
+            return Err(PresenceRotationError {
+                kind: PresenceRotationErrorType::NoPresences,
+            });
+        }
+
+        let state = &self.cluster.0.presence;
+        let mut rotation = state.rotation.lock().expect("rotation poisoned");
+
+        if let Some(handle) = rotation.take() {
+            handle.abort();
+        }
+
+        rotation.replace(tokio::spawn(Self::rotation_loop(
+            Arc::downgrade(&self.cluster.0),
+            presences,
+            interval,
+        )));
+
+        Ok(())
+    }
+
+    /// Stop the running rotation, if any.
+    ///
+    /// The presence currently applied is kept.
+    pub fn stop_rotation(&self) {
+        self.cluster.0.presence.stop_rotation();
+    }
+
+    /// Send a presence to all shards of the cluster one after the other.
+    async fn apply(cluster: &Cluster, presence: UpdatePresencePayload) {
+        let state = &cluster.0.presence;
+        let _guard = state.applying.lock().await;
+
+        state
+            .current
+            .lock()
+            .expect("presence poisoned")
+            .replace(presence.clone());
+
+        let mut shards = cluster.shards();
+        shards.sort_by_key(|shard| shard.config().shard()[0]);
+
+        for (idx, shard) in shards.into_iter().enumerate() {
+            if idx > 0 {
+                tokio::time::sleep(PRESENCE_UPDATE_PACE).await;
+            }
+
+            if let Err(source) = shard.update_presence(presence.clone()).await {
+                tracing::debug!(
+                    shard_id = shard.config().shard()[0],
+                    "presence is set on identify, failed to update it: {}",
+                    source,
+                );
+            }
+        }
+    }
+
+    /// Apply the presences in turn, waiting the interval in between.
+    ///
+    /// Accepts a weak reference to the cluster so that the loop ends once the
+    /// cluster is dropped.
+    async fn rotation_loop(
+        cluster: Weak<ClusterRef>,
+        presences: Vec<UpdatePresencePayload>,
+        interval: Duration,
+    ) {
+        for presence in presences.into_iter().cycle() {
+            let cluster = match cluster.upgrade() {
+                Some(cluster) => Cluster(cluster),
+                None => return,
+            };
+
+            Self::apply(&cluster, presence).await;
+            drop(cluster);
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        PresenceManager, PresenceRotationError, PresenceRotationErrorType,
+        MINIMUM_ROTATION_INTERVAL,
+    };
+    use crate::{cluster::ShardScheme, Cluster, Intents};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{error::Error, fmt::Debug, time::Duration};
+    use twilight_model::gateway::{
+        payload::update_presence::UpdatePresencePayload,
+        presence::{ActivityType, MinimalActivity, Status},
+    };
+
+    assert_impl_all!(PresenceManager: Clone, Debug, Send, Sync);
+    assert_fields!(PresenceRotationErrorType::IntervalTooShort: interval);
+    assert_impl_all!(PresenceRotationErrorType: Debug, Send, Sync);
+    assert_impl_all!(PresenceRotationError: Error, Send, Sync);
+
+    fn presence(name: &str) -> UpdatePresencePayload {
+        UpdatePresencePayload::new(
+            vec![MinimalActivity {
+                kind: ActivityType::Playing,
+                name: name.to_owned(),
+                url: None,
+            }
+            .into()],
+            false,
+            None,
+            Status::Online,
+        )
+        .unwrap()
+    }
+
+    async fn cluster() -> Cluster {
+        Cluster::builder("token", Intents::empty())
+            .shard_scheme(ShardScheme::Range {
+                from: 0,
+                to: 1,
+                total: 2,
+            })
+            .build()
+            .await
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn test_rotate_invalid() {
+        let cluster = cluster().await;
+        let manager = cluster.presence();
+
+        assert!(matches!(
+            manager.rotate(vec![presence("a")], Duration::from_secs(1)).unwrap_err().kind(),
+            PresenceRotationErrorType::IntervalTooShort { interval }
+            if *interval == Duration::from_secs(1)
+        ));
+        assert!(matches!(
+            manager
+                .rotate(Vec::new(), MINIMUM_ROTATION_INTERVAL)
+                .unwrap_err()
+                .kind(),
+            PresenceRotationErrorType::NoPresences
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_set_unstarted() {
+        let cluster = cluster().await;
+        let manager = cluster.presence();
+        assert!(manager.current().is_none());
+
+        manager.set(presence("a")).await;
+
+        assert_eq!(Some(presence("a")), manager.current());
+
+        for shard in cluster.shards() {
+            assert_eq!(Some(presence("a")), shard.presence());
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Persist the sessions of shards so they can be resumed after a restart.
+
+use crate::shard::ResumeSession;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Debug,
+    future::Future,
+    io::ErrorKind as IoErrorKind,
+    path::{Path, PathBuf},
+    pin::Pin,
+};
+
+/// Storage for the sessions of a cluster's shards.
+///
+/// The cluster saves the sessions of its shards to the store when brought
+/// down via [`Cluster::down_resumable_and_save`] and loads them when it is
+/// built, resuming the sessions instead of identifying new ones. Shards whose
+/// sessions are no longer valid fall back to identifying.
+///
+/// Sessions are keyed by the ID of their shard and the total number of shards,
+/// as a session can only be resumed by a shard with the same total. Loaded
+/// sessions saved with a different total, such as after the cluster was
+/// resharded, are dropped.
+///
+/// [`Cluster::down_resumable_and_save`]: super::Cluster::down_resumable_and_save
+pub trait SessionStore: Debug + Send + Sync {
+    /// Load the saved sessions, keyed by shard ID and total number of shards.
+    ///
+    /// Returns an empty map if no sessions have been saved.
+    #[allow(clippy::type_complexity)]
+    fn load(
+        &self,
+    ) -> Pin<
+        Box<
+            dyn Future<
+                    Output = Result<HashMap<[u64; 2], ResumeSession>, Box<dyn Error + Send + Sync>>,
+                > + Send
+                + '_,
+        >,
+    >;
+
+    /// Save the sessions, keyed by shard ID and total number of shards,
+    /// replacing any previously saved sessions.
+    fn save(
+        &self,
+        sessions: HashMap<[u64; 2], ResumeSession>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Box<dyn Error + Send + Sync>>> + Send + '_>>;
+}
+
+/// Load the sessions saved in a session store into the sessions to resume,
+/// clearing the store so that the sessions are only used once.
+///
+/// Sessions saved with a total number of shards other than the provided one
+/// are dropped. Explicitly provided sessions take precedence over loaded
+/// ones. Failing to load the sessions is not fatal, as the shards can
+/// identify instead.
+pub(super) async fn load_sessions(
+    store: &dyn SessionStore,
+    total: u64,
+    resume_sessions: &mut HashMap<u64, ResumeSession>,
+) {
+    let sessions = match store.load().await {
+        Ok(sessions) => sessions,
+        Err(source) => {
+            tracing::warn!("failed to load sessions, identifying instead: {}", source);
+
+            return;
+        }
+    };
+
+    if sessions.is_empty() {
+        return;
+    }
+
+    tracing::debug!("loaded {} sessions to resume", sessions.len());
+
+    for ([id, session_total], session) in sessions {
+        if session_total != total {
+            tracing::debug!(
+                shard_id = id,
+                shard_total = session_total,
+                "dropping session saved with a different total number of shards",
+            );
+
+            continue;
+        }
+
+        resume_sessions.entry(id).or_insert(session);
+    }
+
+    if let Err(source) = store.save(HashMap::new()).await {
+        tracing::warn!("failed to clear loaded sessions: {}", source);
+    }
+}
+
+/// Session saved to a file by the [`FileSessionStore`].
+#[derive(Deserialize, Serialize)]
+struct FileSession {
+    /// ID of the shard and total number of shards.
+    shard: [u64; 2],
+    #[serde(flatten)]
+    session: ResumeSession,
+}
+
+/// Session store saving the sessions as JSON to a file.
+///
+/// The file is replaced as a whole when saving by writing to a temporary file
+/// next to it, so a failed save never leaves a partially written file behind.
+///
+/// # Examples
+///
+/// Resume the sessions saved when the process last stopped:
+///
+/// ```no_run
+/// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::{env, sync::Arc};
+/// use twilight_gateway::{cluster::FileSessionStore, Cluster, Intents};
+///
+/// let token = env::var("DISCORD_TOKEN")?;
+/// let (cluster, _events) = Cluster::builder(token, Intents::GUILD_MESSAGES)
+///     .session_store(Arc::new(FileSessionStore::new("sessions.json")))
+///     .build()
+///     .await?;
+///
+/// cluster.up().await;
+///
+/// // Later, when the process is stopping.
+/// cluster.down_resumable_and_save().await?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FileSessionStore {
+    path: PathBuf,
+}
+
+impl FileSessionStore {
+    /// Create a new session store saving to the file at the provided path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Return an immutable reference to the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl SessionStore for FileSessionStore {
+    fn load(
+        &self,
+    ) -> Pin<
+        Box<
+            dyn Future<
+                    Output = Result<HashMap<[u64; 2], ResumeSession>, Box<dyn Error + Send + Sync>>,
+                > + Send
+                + '_,
+        >,
+    > {
+        Box::pin(async move {
+            let bytes = match tokio::fs::read(&self.path).await {
+                Ok(bytes) => bytes,
+                Err(source) if source.kind() == IoErrorKind::NotFound => return Ok(HashMap::new()),
+                Err(source) => return Err(Box::new(source) as Box<dyn Error + Send + Sync>),
+            };
+
+            let sessions: Vec<FileSession> = serde_json::from_slice(&bytes)?;
+
+            Ok(sessions
+                .into_iter()
+                .map(|saved| (saved.shard, saved.session))
+                .collect())
+        })
+    }
+
+    fn save(
+        &self,
+        sessions: HashMap<[u64; 2], ResumeSession>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Box<dyn Error + Send + Sync>>> + Send + '_>> {
+        Box::pin(async move {
+            let sessions = sessions
+                .into_iter()
+                .map(|(shard, session)| FileSession { shard, session })
+                .collect::<Vec<_>>();
+            let bytes = serde_json::to_vec(&sessions)?;
+
+            let mut temporary = self.path.clone().into_os_string();
+            temporary.push(".tmp");
+
+            tokio::fs::write(&temporary, bytes).await?;
+            tokio::fs::rename(&temporary, &self.path).await?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_sessions, FileSessionStore, SessionStore};
+    use crate::shard::ResumeSession;
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::{collections::HashMap, env, fmt::Debug, process};
+
+    fn session(session_id: &str) -> ResumeSession {
+        ResumeSession {
+            resume_gateway_url: Some("wss://resume.discord.gg".to_owned()),
+            session_id: session_id.to_owned(),
+            sequence: 42,
+        }
+    }
+
+    assert_impl_all!(FileSessionStore: Clone, Debug, Send, Sync);
+    assert_obj_safe!(SessionStore);
+
+    #[tokio::test]
+    async fn test_file_roundtrip() {
+        let path = env::temp_dir().join(format!("twilight-sessions-{}.json", process::id()));
+        let store = FileSessionStore::new(&path);
+
+        assert!(store.load().await.unwrap().is_empty());
+
+        let mut sessions = HashMap::new();
+        sessions.insert([3, 4], session("session"));
+        store.save(sessions).await.unwrap();
+
+        let loaded = store.load().await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(1, loaded.len());
+        let session = &loaded[&[3, 4]];
+        assert_eq!(
+            Some("wss://resume.discord.gg"),
+            session.resume_gateway_url.as_deref()
+        );
+        assert_eq!("session", session.session_id);
+        assert_eq!(42, session.sequence);
+    }
+
+    #[tokio::test]
+    async fn test_load_sessions_total_mismatch() {
+        let path =
+            env::temp_dir().join(format!("twilight-sessions-mismatch-{}.json", process::id()));
+        let store = FileSessionStore::new(&path);
+
+        let mut sessions = HashMap::new();
+        sessions.insert([0, 2], session("matching"));
+        sessions.insert([1, 4], session("mismatched"));
+        store.save(sessions).await.unwrap();
+
+        let mut resume_sessions = HashMap::new();
+        load_sessions(&store, 2, &mut resume_sessions).await;
+        let remaining = store.load().await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(1, resume_sessions.len());
+        assert_eq!("matching", resume_sessions[&0].session_id);
+        assert!(remaining.is_empty());
+    }
+}
@@ -1,11 +1,22 @@
 use bitflags::bitflags;
 use std::convert::TryFrom;
-use twilight_model::gateway::event::EventType;
+use twilight_model::gateway::{event::EventType, Intents};
 
 bitflags! {
     /// Bitflags representing all of the possible types of events.
+    //
+    // Bits are assigned in the order flags are added. Bits 0 to 63 are in use,
+    // new flags take the next free bit starting at 64.
     #[non_exhaustive]
-    pub struct EventTypeFlags: u64 {
+    pub struct EventTypeFlags: u128 {
+        /// An auto moderation rule was triggered and an action was executed.
+        const AUTO_MODERATION_ACTION_EXECUTION = 1 << 50;
+        /// An auto moderation rule has been created.
+        const AUTO_MODERATION_RULE_CREATE = 1 << 51;
+        /// An auto moderation rule has been deleted.
+        const AUTO_MODERATION_RULE_DELETE = 1 << 52;
+        /// An auto moderation rule has been updated.
+        const AUTO_MODERATION_RULE_UPDATE = 1 << 53;
         /// User has been banned from a guild.
         const BAN_ADD = 1;
         /// User has been unbanned from a guild.
@@ -28,7 +39,7 @@ bitflags! {
         ///
         /// A payload containing a boolean is included. If `true` the session is
         /// resumable. If not, then the shard must initialize a new session.
-        const GATEWAY_INVALIDATE_SESSION = 1 << 8;
+        const GATEWAY_INVALIDATE_SESSION = 1 << 55;
         /// Gateway is indicating that a shard should perform a reconnect.
         const GATEWAY_RECONNECT = 1 << 9;
         /// Gift code sent in a channel has been updated.
@@ -113,6 +124,10 @@ bitflags! {
         const SHARD_RECONNECTING = 1 << 37;
         /// Shard is resuming a session with the gateway.
         const SHARD_RESUMING = 1 << 38;
+        /// Shard is waiting for the session start limit to be reset.
+        const SHARD_SESSION_START_LIMIT_EXHAUSTED = 1 << 63;
+        /// Shard's connection stopped responding and is being replaced.
+        const SHARD_ZOMBIED = 1 << 54;
         /// Stage instance was created in a stage channel.
         const STAGE_INSTANCE_CREATE = 1 << 57;
         /// Stage instance was deleted in a stage channel.
@@ -134,9 +149,166 @@ bitflags! {
     }
 }
 
+/// Event types gated by intents, and the intents gating them.
+///
+/// An event type is received if any of its intents are enabled. Event types
+/// not listed are received regardless of the enabled intents.
+const GATED_EVENT_TYPES: &[(EventTypeFlags, Intents)] = &[
+    (
+        EventTypeFlags::AUTO_MODERATION_ACTION_EXECUTION,
+        Intents::AUTO_MODERATION_EXECUTION,
+    ),
+    (
+        EventTypeFlags::AUTO_MODERATION_RULE_CREATE,
+        Intents::AUTO_MODERATION_CONFIGURATION,
+    ),
+    (
+        EventTypeFlags::AUTO_MODERATION_RULE_DELETE,
+        Intents::AUTO_MODERATION_CONFIGURATION,
+    ),
+    (
+        EventTypeFlags::AUTO_MODERATION_RULE_UPDATE,
+        Intents::AUTO_MODERATION_CONFIGURATION,
+    ),
+    (EventTypeFlags::BAN_ADD, Intents::GUILD_BANS),
+    (EventTypeFlags::BAN_REMOVE, Intents::GUILD_BANS),
+    (EventTypeFlags::CHANNEL_CREATE, Intents::GUILDS),
+    (EventTypeFlags::CHANNEL_DELETE, Intents::GUILDS),
+    (
+        EventTypeFlags::CHANNEL_PINS_UPDATE,
+        Intents::from_bits_truncate(Intents::GUILDS.bits() | Intents::DIRECT_MESSAGES.bits()),
+    ),
+    (EventTypeFlags::CHANNEL_UPDATE, Intents::GUILDS),
+    (EventTypeFlags::GUILD_CREATE, Intents::GUILDS),
+    (EventTypeFlags::GUILD_DELETE, Intents::GUILDS),
+    (EventTypeFlags::GUILD_EMOJIS_UPDATE, Intents::GUILD_EMOJIS),
+    (
+        EventTypeFlags::GUILD_INTEGRATIONS_UPDATE,
+        Intents::GUILD_INTEGRATIONS,
+    ),
+    (EventTypeFlags::GUILD_UPDATE, Intents::GUILDS),
+    (
+        EventTypeFlags::INTEGRATION_CREATE,
+        Intents::GUILD_INTEGRATIONS,
+    ),
+    (
+        EventTypeFlags::INTEGRATION_DELETE,
+        Intents::GUILD_INTEGRATIONS,
+    ),
+    (
+        EventTypeFlags::INTEGRATION_UPDATE,
+        Intents::GUILD_INTEGRATIONS,
+    ),
+    (EventTypeFlags::INVITE_CREATE, Intents::GUILD_INVITES),
+    (EventTypeFlags::INVITE_DELETE, Intents::GUILD_INVITES),
+    (EventTypeFlags::MEMBER_ADD, Intents::GUILD_MEMBERS),
+    (EventTypeFlags::MEMBER_REMOVE, Intents::GUILD_MEMBERS),
+    (EventTypeFlags::MEMBER_UPDATE, Intents::GUILD_MEMBERS),
+    (EventTypeFlags::MESSAGE_CREATE, MESSAGES),
+    (EventTypeFlags::MESSAGE_DELETE, MESSAGES),
+    (EventTypeFlags::MESSAGE_DELETE_BULK, Intents::GUILD_MESSAGES),
+    (EventTypeFlags::MESSAGE_UPDATE, MESSAGES),
+    (EventTypeFlags::PRESENCE_UPDATE, Intents::GUILD_PRESENCES),
+    (EventTypeFlags::REACTION_ADD, REACTIONS),
+    (EventTypeFlags::REACTION_REMOVE, REACTIONS),
+    (EventTypeFlags::REACTION_REMOVE_ALL, REACTIONS),
+    (EventTypeFlags::REACTION_REMOVE_EMOJI, REACTIONS),
+    (EventTypeFlags::ROLE_CREATE, Intents::GUILDS),
+    (EventTypeFlags::ROLE_DELETE, Intents::GUILDS),
+    (EventTypeFlags::ROLE_UPDATE, Intents::GUILDS),
+    (EventTypeFlags::STAGE_INSTANCE_CREATE, Intents::GUILDS),
+    (EventTypeFlags::STAGE_INSTANCE_DELETE, Intents::GUILDS),
+    (EventTypeFlags::STAGE_INSTANCE_UPDATE, Intents::GUILDS),
+    (
+        EventTypeFlags::TYPING_START,
+        Intents::from_bits_truncate(
+            Intents::GUILD_MESSAGE_TYPING.bits() | Intents::DIRECT_MESSAGE_TYPING.bits(),
+        ),
+    ),
+    (EventTypeFlags::UNAVAILABLE_GUILD, Intents::GUILDS),
+    (
+        EventTypeFlags::VOICE_STATE_UPDATE,
+        Intents::GUILD_VOICE_STATES,
+    ),
+    (EventTypeFlags::WEBHOOKS_UPDATE, Intents::GUILD_WEBHOOKS),
+];
+
+/// Intents gating message events in guilds and direct messages.
+const MESSAGES: Intents =
+    Intents::from_bits_truncate(Intents::GUILD_MESSAGES.bits() | Intents::DIRECT_MESSAGES.bits());
+
+/// Intents gating reaction events in guilds and direct messages.
+const REACTIONS: Intents = Intents::from_bits_truncate(
+    Intents::GUILD_MESSAGE_REACTIONS.bits() | Intents::DIRECT_MESSAGE_REACTIONS.bits(),
+);
+
+impl EventTypeFlags {
+    /// Intents required to receive all of the event types.
+    ///
+    /// Some event types are gated by multiple intents, such as
+    /// [`MESSAGE_CREATE`] by both [`Intents::GUILD_MESSAGES`] and
+    /// [`Intents::DIRECT_MESSAGES`], in which case all of them are required
+    /// to receive the event type from every source. Event types not gated by
+    /// any intent, such as [`READY`], don't require any intents.
+    ///
+    /// Use this on a single event type to retrieve the intents gating it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_gateway::{EventType, EventTypeFlags, Intents};
+    ///
+    /// let flags = EventTypeFlags::MEMBER_ADD | EventTypeFlags::READY;
+    /// assert_eq!(Intents::GUILD_MEMBERS, flags.intents());
+    ///
+    /// assert_eq!(
+    ///     Intents::GUILD_MESSAGES | Intents::DIRECT_MESSAGES,
+    ///     EventTypeFlags::from(EventType::MessageCreate).intents(),
+    /// );
+    /// ```
+    ///
+    /// [`MESSAGE_CREATE`]: Self::MESSAGE_CREATE
+    /// [`READY`]: Self::READY
+    pub fn intents(self) -> Intents {
+        GATED_EVENT_TYPES
+            .iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .fold(Intents::empty(), |intents, (_, gating)| intents | *gating)
+    }
+
+    /// Event types that can never be received with the intents.
+    ///
+    /// An event type can't be received if none of the intents gating it are
+    /// enabled.
+    ///
+    /// # Examples
+    ///
+    /// Member events require the privileged [`Intents::GUILD_MEMBERS`]
+    /// intent:
+    ///
+    /// ```
+    /// use twilight_gateway::{EventTypeFlags, Intents};
+    ///
+    /// let flags = EventTypeFlags::GUILD_CREATE | EventTypeFlags::MEMBER_ADD;
+    /// assert_eq!(EventTypeFlags::MEMBER_ADD, flags.unreachable(Intents::GUILDS));
+    /// ```
+    pub fn unreachable(self, intents: Intents) -> Self {
+        GATED_EVENT_TYPES
+            .iter()
+            .filter(|(flag, gating)| self.contains(*flag) && !intents.intersects(*gating))
+            .fold(Self::empty(), |flags, (flag, _)| flags | *flag)
+    }
+}
+
 impl From<EventType> for EventTypeFlags {
     fn from(event_type: EventType) -> Self {
         match event_type {
+            EventType::AutoModerationActionExecution => {
+                EventTypeFlags::AUTO_MODERATION_ACTION_EXECUTION
+            }
+            EventType::AutoModerationRuleCreate => EventTypeFlags::AUTO_MODERATION_RULE_CREATE,
+            EventType::AutoModerationRuleDelete => EventTypeFlags::AUTO_MODERATION_RULE_DELETE,
+            EventType::AutoModerationRuleUpdate => EventTypeFlags::AUTO_MODERATION_RULE_UPDATE,
             EventType::BanAdd => EventTypeFlags::BAN_ADD,
             EventType::BanRemove => EventTypeFlags::BAN_REMOVE,
             EventType::ChannelCreate => EventTypeFlags::CHANNEL_CREATE,
@@ -186,6 +358,10 @@ impl From<EventType> for EventTypeFlags {
             EventType::ShardReconnecting => EventTypeFlags::SHARD_RECONNECTING,
             EventType::ShardPayload => EventTypeFlags::SHARD_PAYLOAD,
             EventType::ShardResuming => EventTypeFlags::SHARD_RESUMING,
+            EventType::ShardSessionStartLimitExhausted => {
+                EventTypeFlags::SHARD_SESSION_START_LIMIT_EXHAUSTED
+            }
+            EventType::ShardZombied => EventTypeFlags::SHARD_ZOMBIED,
             EventType::StageInstanceCreate => EventTypeFlags::STAGE_INSTANCE_CREATE,
             EventType::StageInstanceDelete => EventTypeFlags::STAGE_INSTANCE_DELETE,
             EventType::StageInstanceUpdate => EventTypeFlags::STAGE_INSTANCE_UPDATE,
@@ -230,9 +406,10 @@ impl Default for EventTypeFlags {
 
 #[cfg(test)]
 mod tests {
-    use super::{EventType, EventTypeFlags};
+    use super::{EventType, EventTypeFlags, GATED_EVENT_TYPES};
     use static_assertions::assert_impl_all;
     use std::{convert::TryFrom, fmt::Debug, hash::Hash};
+    use twilight_model::gateway::Intents;
 
     assert_impl_all!(
         EventTypeFlags: Copy,
@@ -246,4 +423,56 @@ mod tests {
         Sync,
         TryFrom<(u8, Option<&'static str>)>
     );
+
+    #[test]
+    fn test_flags_distinct() {
+        let flags = [
+            EventTypeFlags::GATEWAY_HEARTBEAT,
+            EventTypeFlags::GATEWAY_HEARTBEAT_ACK,
+            EventTypeFlags::GATEWAY_HELLO,
+            EventTypeFlags::GATEWAY_INVALIDATE_SESSION,
+            EventTypeFlags::GATEWAY_RECONNECT,
+        ];
+
+        for (idx, flag) in flags.iter().enumerate() {
+            assert_eq!(1, flag.bits().count_ones());
+
+            for other in &flags[idx + 1..] {
+                assert!(!flag.intersects(*other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_gated_event_types() {
+        for (flag, intents) in GATED_EVENT_TYPES {
+            assert_eq!(1, flag.bits().count_ones());
+            assert!(!intents.is_empty());
+            assert_eq!(*intents, flag.intents());
+        }
+
+        assert!(EventTypeFlags::READY.intents().is_empty());
+        assert!(EventTypeFlags::INTERACTION_CREATE.intents().is_empty());
+        assert_eq!(
+            Intents::GUILD_MESSAGE_REACTIONS | Intents::DIRECT_MESSAGE_REACTIONS,
+            EventTypeFlags::from(EventType::ReactionAdd).intents()
+        );
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let flags = EventTypeFlags::MESSAGE_CREATE
+            | EventTypeFlags::MESSAGE_DELETE_BULK
+            | EventTypeFlags::READY;
+
+        assert_eq!(
+            EventTypeFlags::MESSAGE_DELETE_BULK,
+            flags.unreachable(Intents::DIRECT_MESSAGES)
+        );
+        assert!(flags.unreachable(flags.intents()).is_empty());
+        assert_eq!(
+            EventTypeFlags::MESSAGE_CREATE | EventTypeFlags::MESSAGE_DELETE_BULK,
+            flags.unreachable(Intents::empty())
+        );
+    }
 }
@@ -0,0 +1,408 @@
+//! Share the events of a shard or cluster between multiple consumers.
+//!
+//! A [`FanOut`] takes over the stream of events of a [`Shard`], [`Cluster`],
+//! or [`Multiplexer`] and passes each event on to every [`Subscription`]
+//! whose event types include it. Events are wrapped in an [`Arc`] once and
+//! shared between all subscriptions, so that large events such as
+//! `GuildCreate` are not cloned for every consumer.
+//!
+//! A fan-out is created from the event stream via [`Events::fan_out`],
+//! [`ClusterEvents::fan_out`], or [`MultiplexerEvents::fan_out`], or from any
+//! other stream via [`FanOut::new`].
+//!
+//! Each subscription has its own bounded [`EventBuffer`], whose
+//! [`BackpressurePolicy`] is applied once it is full. Subscriptions that must
+//! see every event, such as caches, can use [`BackpressurePolicy::Block`],
+//! which holds back the other subscriptions until they have consumed an
+//! event. Other subscriptions can drop events instead, so that a slow
+//! consumer never holds back the others. The number of events a subscription
+//! missed this way is returned by [`Subscription::dropped`]. Dropped
+//! subscriptions are removed.
+//!
+//! [`BackpressurePolicy`]: crate::shard::BackpressurePolicy
+//! [`BackpressurePolicy::Block`]: crate::shard::BackpressurePolicy::Block
+//! [`Cluster`]: crate::Cluster
+//! [`EventBuffer`]: crate::shard::EventBuffer
+//! [`ClusterEvents::fan_out`]: crate::cluster::ClusterEvents::fan_out
+//! [`Events::fan_out`]: crate::shard::Events::fan_out
+//! [`Multiplexer`]: crate::multiplexer::Multiplexer
+//! [`MultiplexerEvents::fan_out`]: crate::multiplexer::MultiplexerEvents::fan_out
+//! [`Shard`]: crate::Shard
+
+use crate::{
+    multiplexer::BotId,
+    shard::{
+        buffer::{self, BoundedReceiver, BoundedSender},
+        EventBuffer,
+    },
+    EventTypeFlags,
+};
+use futures_util::stream::{Stream, StreamExt};
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use twilight_model::gateway::event::Event;
+
+/// Item of an event stream that can be shared via a [`FanOut`].
+///
+/// This is implemented for the items of the event streams of shards,
+/// clusters, and multiplexers.
+pub trait FanOutEvent: Send + Sync + 'static {
+    /// Return an immutable reference to the event.
+    fn event(&self) -> &Event;
+}
+
+impl FanOutEvent for Event {
+    fn event(&self) -> &Event {
+        self
+    }
+}
+
+impl FanOutEvent for (u64, Event) {
+    fn event(&self) -> &Event {
+        &self.1
+    }
+}
+
+impl FanOutEvent for (BotId, u64, Event) {
+    fn event(&self) -> &Event {
+        &self.2
+    }
+}
+
+type Source<T> = Pin<Box<dyn Stream<Item = T> + Send>>;
+
+/// Subscription registered with a fan-out.
+struct Subscriber<T> {
+    event_types: EventTypeFlags,
+    tx: Arc<BoundedSender<Arc<T>>>,
+}
+
+/// Subscriptions of a fan-out.
+struct Subscribers<T> {
+    /// Whether the source stream has ended, in which case new subscriptions
+    /// end immediately.
+    ended: bool,
+    list: Vec<Subscriber<T>>,
+}
+
+struct FanOutRef<T> {
+    /// Source stream, until the fan-out is started.
+    source: Mutex<Option<Source<T>>>,
+    subscribers: Mutex<Subscribers<T>>,
+}
+
+/// Fan-out of an event stream to multiple subscriptions.
+///
+/// Refer to the [module-level] documentation for more information.
+///
+/// The fan-out is cheap to clone, with clones sharing the subscriptions.
+///
+/// # Examples
+///
+/// Feed the messages of a shard to one consumer and all events to another:
+///
+/// ```no_run
+/// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::StreamExt;
+/// use std::env;
+/// use twilight_gateway::{
+///     shard::{BackpressurePolicy, EventBuffer},
+///     EventTypeFlags, Intents, Shard,
+/// };
+///
+/// let token = env::var("DISCORD_TOKEN")?;
+/// let (shard, events) = Shard::new(token, Intents::GUILDS | Intents::GUILD_MESSAGES);
+///
+/// let fan_out = events.fan_out();
+/// let mut messages = fan_out.subscribe(
+///     EventTypeFlags::MESSAGE_CREATE,
+///     EventBuffer::new(16, BackpressurePolicy::Block),
+/// );
+/// // Logging events may fall behind without holding back the messages.
+/// let mut everything = fan_out.subscribe(
+///     EventTypeFlags::all(),
+///     EventBuffer::new(256, BackpressurePolicy::DropOldest),
+/// );
+/// fan_out.start();
+///
+/// tokio::spawn(async move {
+///     while let Some(event) = everything.next().await {
+///         println!("event: {:?}", event.kind());
+///     }
+/// });
+///
+/// shard.start().await?;
+///
+/// while let Some(event) = messages.next().await {
+///     println!("message: {:?}", event);
+/// }
+/// # Ok(()) }
+/// ```
+///
+/// [module-level]: self
+pub struct FanOut<T>(Arc<FanOutRef<T>>);
+
+impl<T: FanOutEvent> FanOut<T> {
+    /// Create a new fan-out of a stream of events.
+    ///
+    /// Events are not taken from the stream until the fan-out is
+    /// [started], so that subscriptions created before do not miss any.
+    ///
+    /// [started]: Self::start
+    pub fn new(stream: impl Stream<Item = T> + Send + 'static) -> Self {
+        Self(Arc::new(FanOutRef {
+            source: Mutex::new(Some(Box::pin(stream))),
+            subscribers: Mutex::new(Subscribers {
+                ended: false,
+                list: Vec::new(),
+            }),
+        }))
+    }
+
+    /// Subscribe to events of the provided types, buffering them in the
+    /// provided buffer.
+    ///
+    /// The subscription receives the events taken from the stream after it
+    /// was created, and ends once the stream has ended. Once the buffer is
+    /// full its [`BackpressurePolicy`] is applied.
+    ///
+    /// [`BackpressurePolicy`]: crate::shard::BackpressurePolicy
+    pub fn subscribe(&self, event_types: EventTypeFlags, buffer: EventBuffer) -> Subscription<T> {
+        let (tx, rx) = buffer::channel(buffer);
+
+        let mut subscribers = self.0.subscribers.lock().expect("subscribers poisoned");
+
+        // Dropping the sender ends the subscription right away.
+        if !subscribers.ended {
+            subscribers.list.push(Subscriber {
+                event_types,
+                tx: Arc::new(tx),
+            });
+        }
+
+        Subscription { event_types, rx }
+    }
+
+    /// Number of subscriptions that have not been dropped.
+    pub fn subscriptions(&self) -> usize {
+        let mut subscribers = self.0.subscribers.lock().expect("subscribers poisoned");
+        subscribers
+            .list
+            .retain(|subscriber| !subscriber.tx.is_closed());
+
+        subscribers.list.len()
+    }
+
+    /// Start taking events from the stream and passing them on to the
+    /// subscriptions in a background task.
+    ///
+    /// Calling this again has no effect.
+    pub fn start(&self) {
+        let source = self.0.source.lock().expect("source poisoned").take();
+
+        if let Some(source) = source {
+            tokio::spawn(Self::dispatch(Arc::clone(&self.0), source));
+        }
+    }
+
+    async fn dispatch(fan_out: Arc<FanOutRef<T>>, mut source: Source<T>) {
+        while let Some(item) = source.next().await {
+            let event_type = EventTypeFlags::from(item.event().kind());
+            let item = Arc::new(item);
+
+            let senders = {
+                let mut subscribers = fan_out.subscribers.lock().expect("subscribers poisoned");
+                subscribers
+                    .list
+                    .retain(|subscriber| !subscriber.tx.is_closed());
+
+                subscribers
+                    .list
+                    .iter()
+                    .filter(|subscriber| subscriber.event_types.contains(event_type))
+                    .map(|subscriber| Arc::clone(&subscriber.tx))
+                    .collect::<Vec<_>>()
+            };
+
+            // Only subscriptions blocking once their buffer is full are
+            // waited for.
+            for tx in senders {
+                tx.send(Arc::clone(&item)).await;
+            }
+        }
+
+        tracing::debug!("event stream ended, ending subscriptions");
+
+        let mut subscribers = fan_out.subscribers.lock().expect("subscribers poisoned");
+        subscribers.ended = true;
+        subscribers.list.clear();
+    }
+}
+
+impl<T> Clone for FanOut<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Debug for FanOut<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let subscribers = self.0.subscribers.lock().expect("subscribers poisoned");
+
+        f.debug_struct("FanOut")
+            .field("ended", &subscribers.ended)
+            .field(
+                "started",
+                &self.0.source.lock().expect("source poisoned").is_none(),
+            )
+            .field("subscriptions", &subscribers.list.len())
+            .finish()
+    }
+}
+
+/// Stream of the events passed on to a subscription of a [`FanOut`].
+///
+/// Created via [`FanOut::subscribe`].
+pub struct Subscription<T> {
+    event_types: EventTypeFlags,
+    rx: BoundedReceiver<Arc<T>>,
+}
+
+impl<T> Subscription<T> {
+    /// Number of events dropped by the [`BackpressurePolicy`] of the
+    /// subscription's buffer.
+    ///
+    /// This is always 0 for subscriptions using [`BackpressurePolicy::Block`].
+    ///
+    /// [`BackpressurePolicy`]: crate::shard::BackpressurePolicy
+    /// [`BackpressurePolicy::Block`]: crate::shard::BackpressurePolicy::Block
+    pub fn dropped(&self) -> u64 {
+        self.rx.dropped()
+    }
+
+    /// Return the event types passed on to the subscription.
+    pub const fn event_types(&self) -> EventTypeFlags {
+        self.event_types
+    }
+}
+
+impl<T> Debug for Subscription<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Subscription")
+            .field("dropped", &self.dropped())
+            .field("event_types", &self.event_types)
+            .finish()
+    }
+}
+
+impl<T> Stream for Subscription<T> {
+    type Item = Arc<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FanOut, FanOutEvent, Subscription};
+    use crate::{
+        shard::{BackpressurePolicy, EventBuffer},
+        EventTypeFlags,
+    };
+    use futures::{stream, Stream, StreamExt};
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::{fmt::Debug, sync::Arc};
+    use twilight_model::gateway::event::Event;
+
+    assert_impl_all!(FanOut<Event>: Clone, Debug, Send, Sync);
+    assert_impl_all!(Subscription<(u64, Event)>: Debug, Send, Stream, Sync, Unpin);
+    assert_obj_safe!(FanOutEvent);
+
+    fn block(capacity: usize) -> EventBuffer {
+        EventBuffer::new(capacity, BackpressurePolicy::Block)
+    }
+
+    fn events() -> Vec<(u64, Event)> {
+        vec![
+            (0, Event::GatewayHeartbeat(1)),
+            (1, Event::GatewayHeartbeatAck),
+            (0, Event::GatewayReconnect),
+            (1, Event::GatewayHeartbeat(2)),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_filters() {
+        let fan_out = FanOut::new(stream::iter(events()));
+
+        let heartbeats = fan_out.subscribe(EventTypeFlags::GATEWAY_HEARTBEAT, block(1));
+        let all = fan_out.subscribe(EventTypeFlags::all(), block(1));
+        drop(fan_out.subscribe(EventTypeFlags::all(), block(1)));
+        assert_eq!(2, fan_out.subscriptions());
+
+        fan_out.start();
+
+        let (heartbeats, all) =
+            tokio::join!(heartbeats.collect::<Vec<_>>(), all.collect::<Vec<_>>());
+
+        assert_eq!(
+            vec![
+                (0, Event::GatewayHeartbeat(1)),
+                (1, Event::GatewayHeartbeat(2))
+            ],
+            heartbeats
+                .iter()
+                .map(|item| (**item).clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            events(),
+            all.iter().map(|item| (**item).clone()).collect::<Vec<_>>()
+        );
+
+        // Events are shared instead of cloned.
+        assert!(Arc::ptr_eq(&heartbeats[0], &all[0]));
+        assert!(Arc::ptr_eq(&heartbeats[1], &all[3]));
+    }
+
+    #[tokio::test]
+    async fn test_ended() {
+        let fan_out = FanOut::new(stream::iter(events()));
+        let all = fan_out.subscribe(EventTypeFlags::all(), block(4));
+        fan_out.start();
+        fan_out.start();
+
+        assert_eq!(4, all.count().await);
+        assert_eq!(0, fan_out.subscriptions());
+
+        let mut late = fan_out.subscribe(EventTypeFlags::all(), block(1));
+        assert!(late.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stalled_subscription() {
+        let fan_out = FanOut::new(stream::iter(events()));
+        let mut stalled = fan_out.subscribe(
+            EventTypeFlags::all(),
+            EventBuffer::new(1, BackpressurePolicy::DropOldest),
+        );
+        let all = fan_out.subscribe(EventTypeFlags::all(), block(1));
+        fan_out.start();
+
+        // The stalled subscription isn't consumed until the others are done,
+        // which would never happen if the fan-out waited for it.
+        assert_eq!(4, all.count().await);
+
+        assert_eq!(3, stalled.dropped());
+        assert_eq!(
+            Some(&(1, Event::GatewayHeartbeat(2))),
+            stalled.next().await.as_deref()
+        );
+        assert!(stalled.next().await.is_none());
+    }
+}
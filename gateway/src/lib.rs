@@ -17,6 +17,14 @@
 //! manages and proxies all of their events under one unified stream. This is
 //! useful to use if you have a large bot in over 1000 or 2000 guilds.
 //!
+//! The Multiplexer runs the clusters of multiple bots side by side, such as
+//! white-label bots with different tokens, and proxies all of their events
+//! under one stream tagged with the bot and shard they were received on.
+//!
+//! The events of any of these can be shared between multiple consumers, such
+//! as a cache and event handlers, by subscribing to a FanOut with the event
+//! types each consumer needs.
+//!
 //! ## Features
 //!
 //! ### Deserialization
@@ -44,6 +52,18 @@
 //! twilight-gateway = { default-features = false, features = ["rustls", "simd-json"], version = "0.2" }
 //! ```
 //!
+//! #### `etf`
+//!
+//! The `etf` feature makes shards use Discord's [External Term Format] instead
+//! of JSON as the encoding of gateway payloads, which are smaller and faster
+//! to decode. Payloads are decoded into the same events. It is not enabled by
+//! default.
+//!
+//! ```toml
+//! [dependencies]
+//! twilight-gateway = { features = ["etf"], version = "0.5" }
+//! ```
+//!
 //! ### TLS
 //!
 //! `twilight-gateway` has features to enable [`async-tungstenite`] and
@@ -87,12 +107,14 @@
 //! ### Metrics
 //!
 //! The `metrics` feature provides metrics information via the `metrics` crate.
-//! Some of the metrics logged are counters about received event counts and
-//! their types and gauges about the capacity and efficiency of the inflater of
-//! each shard.
+//! Metrics are labeled by shard and include heartbeat latencies, reconnects
+//! and resumes by reason, received close codes, received bytes, dispatch
+//! events by type, and time spent in each connection stage. Refer to the
+//! `metrics` module for the catalogue of metrics.
 //!
 //! This is disabled by default.
 //!
+//! [External Term Format]: https://erlang.org/doc/apps/erts/erl_ext_dist.html
 //! [`async-tungstenite`]: https://crates.io/crates/async-tungstenite
 //! [`native-tls`]: https://crates.io/crates/native-tls
 //! [`rustls`]: https://crates.io/crates/rustls
@@ -124,6 +146,10 @@
 #![allow(clippy::module_name_repetitions, clippy::must_use_candidate)]
 
 pub mod cluster;
+pub mod fan_out;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod multiplexer;
 pub mod shard;
 
 mod event;
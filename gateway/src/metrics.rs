@@ -0,0 +1,340 @@
+//! Catalogue of the metrics recorded with the `metrics` feature.
+//!
+//! Metrics are recorded via the [`metrics`] crate, so any of its recorders
+//! can be installed to export them. The names and labels of the metrics
+//! listed here are stable: metrics are only added, but not renamed or
+//! removed, outside of breaking releases.
+//!
+//! Every shard metric is labeled with the `shard` label, which is the ID of
+//! the shard recording it.
+//!
+//! | Name                        | Type      | Labels                 |
+//! | --------------------------- | --------- | ---------------------- |
+//! | [`CLUSTER_SHARD_COUNT`]     | gauge     |                        |
+//! | [`GATEWAY_EVENT`]           | counter   | `shard`, `GatewayEvent`|
+//! | [`INFLATER_CAPACITY`]       | gauge     | `shard`                |
+//! | [`INFLATER_IN`]             | gauge     | `shard`                |
+//! | [`INFLATER_OUT`]            | gauge     | `shard`                |
+//! | [`SHARD_BYTES_RECEIVED`]    | counter   | `shard`, `kind`        |
+//! | [`SHARD_CLOSE_CODES`]       | counter   | `shard`, `code`        |
+//! | [`SHARD_DISPATCH_EVENTS`]   | counter   | `shard`, `event_type`  |
+//! | [`SHARD_HEARTBEAT_LATENCY`] | histogram | `shard`                |
+//! | [`SHARD_INVALID_SESSIONS`]  | counter   | `shard`, `resumable`   |
+//! | [`SHARD_RECONNECTS`]        | counter   | `shard`, `reason`      |
+//! | [`SHARD_RESUMES`]           | counter   | `shard`, `reason`      |
+//! | [`SHARD_STAGE_DURATION`]    | histogram | `shard`, `stage`       |
+//!
+//! # Reasons
+//!
+//! The `reason` label of [`SHARD_RECONNECTS`] and [`SHARD_RESUMES`] is one
+//! of:
+//!
+//! - `closed`: the gateway closed the connection;
+//! - `decompressing`: a message could not be decompressed;
+//! - `event_stream_ended`: the connection was dropped;
+//! - `invalid_session`: the gateway invalidated the session;
+//! - `processing`: an event could not be processed;
+//! - `reconnect_requested`: the gateway requested a reconnect;
+//! - `resume_failed`: resuming the session failed;
+//! - `sending`: a message could not be sent;
+//! - `sequence_skipped`: the gateway skipped a sequence number;
+//! - `session_unavailable`: there is no session to resume;
+//! - `startup`: the shard was started with a session to resume;
+//! - `zombied`: the connection was detected to be a zombie.
+//!
+//! [`metrics`]: ::metrics
+
+use crate::shard::Stage;
+use std::time::Duration;
+
+/// Number of shards managed by a cluster.
+pub const CLUSTER_SHARD_COUNT: &str = "Cluster-Shard-Count";
+
+/// Number of received non-dispatch gateway events and `READY` and `RESUMED`
+/// dispatch events.
+///
+/// The `GatewayEvent` label is one of `Dispatch`, `Heartbeat`,
+/// `HeartbeatAck`, `Hello`, `InvalidateSessionFalse`,
+/// `InvalidateSessionTrue`, or `Reconnect`.
+pub const GATEWAY_EVENT: &str = "GatewayEvent";
+
+/// Capacity of the buffer of decompressed payloads, in bytes.
+///
+/// Only recorded with the `compression` feature.
+pub const INFLATER_CAPACITY: &str = "Inflater-Capacity";
+
+/// Total number of compressed bytes read by the inflater of the current
+/// connection.
+///
+/// Only recorded with the `compression` feature.
+pub const INFLATER_IN: &str = "Inflater-In";
+
+/// Total number of decompressed bytes written by the inflater of the current
+/// connection.
+///
+/// Only recorded with the `compression` feature.
+pub const INFLATER_OUT: &str = "Inflater-Out";
+
+/// Number of bytes received from the gateway.
+///
+/// The `kind` label is `compressed` for bytes of compressed messages as
+/// received, and `decompressed` for bytes of complete payloads after
+/// decompression, or as received if they were not compressed.
+pub const SHARD_BYTES_RECEIVED: &str = "Shard-Bytes-Received";
+
+/// Number of close frames received from the gateway.
+///
+/// The `code` label is the close code, or `none` if the close frame had no
+/// code.
+pub const SHARD_CLOSE_CODES: &str = "Shard-Close-Codes";
+
+/// Number of received dispatch events.
+///
+/// The `event_type` label is the name of the event type as sent by the
+/// gateway, such as `MESSAGE_CREATE`.
+pub const SHARD_DISPATCH_EVENTS: &str = "Shard-Dispatch-Events";
+
+/// Duration between sending a heartbeat and receiving its acknowledgement.
+pub const SHARD_HEARTBEAT_LATENCY: &str = "Shard-Heartbeat-Latency";
+
+/// Number of sessions invalidated by the gateway.
+///
+/// The `resumable` label is `true` or `false`, depending on whether the
+/// session could be resumed.
+pub const SHARD_INVALID_SESSIONS: &str = "Shard-Invalid-Sessions";
+
+/// Number of full reconnects, initializing a new session.
+///
+/// Refer to the [module-level] documentation for the values of the `reason`
+/// label.
+///
+/// [module-level]: self#reasons
+pub const SHARD_RECONNECTS: &str = "Shard-Reconnects";
+
+/// Number of attempts to resume a session.
+///
+/// Refer to the [module-level] documentation for the values of the `reason`
+/// label.
+///
+/// [module-level]: self#reasons
+pub const SHARD_RESUMES: &str = "Shard-Resumes";
+
+/// Duration a shard spent in a [`Stage`] before moving on to another stage.
+///
+/// The `stage` label is the displayed stage, such as `Identifying`.
+pub const SHARD_STAGE_DURATION: &str = "Shard-Stage-Duration";
+
+pub(crate) fn bytes_received(shard_id: u64, kind: &'static str, bytes: usize) {
+    ::metrics::counter!(
+        SHARD_BYTES_RECEIVED,
+        bytes as u64,
+        "shard" => shard_id.to_string(),
+        "kind" => kind,
+    );
+}
+
+pub(crate) fn close_code(shard_id: u64, code: Option<u16>) {
+    let code = code.map_or_else(|| "none".to_owned(), |code| code.to_string());
+
+    ::metrics::counter!(
+        SHARD_CLOSE_CODES,
+        1,
+        "shard" => shard_id.to_string(),
+        "code" => code,
+    );
+}
+
+pub(crate) fn dispatch_event(shard_id: u64, event_type: &str) {
+    ::metrics::counter!(
+        SHARD_DISPATCH_EVENTS,
+        1,
+        "shard" => shard_id.to_string(),
+        "event_type" => event_type.to_owned(),
+    );
+}
+
+pub(crate) fn gateway_event(shard_id: u64, event: &'static str) {
+    ::metrics::counter!(
+        GATEWAY_EVENT,
+        1,
+        "shard" => shard_id.to_string(),
+        "GatewayEvent" => event,
+    );
+}
+
+pub(crate) fn heartbeat_latency(shard_id: u64, latency: Duration) {
+    ::metrics::histogram!(
+        SHARD_HEARTBEAT_LATENCY,
+        latency,
+        "shard" => shard_id.to_string(),
+    );
+}
+
+pub(crate) fn invalid_session(shard_id: u64, resumable: bool) {
+    let resumable = if resumable { "true" } else { "false" };
+
+    ::metrics::counter!(
+        SHARD_INVALID_SESSIONS,
+        1,
+        "shard" => shard_id.to_string(),
+        "resumable" => resumable,
+    );
+}
+
+pub(crate) fn reconnect(shard_id: u64, reason: &'static str) {
+    ::metrics::counter!(
+        SHARD_RECONNECTS,
+        1,
+        "shard" => shard_id.to_string(),
+        "reason" => reason,
+    );
+}
+
+pub(crate) fn resume(shard_id: u64, reason: &'static str) {
+    ::metrics::counter!(
+        SHARD_RESUMES,
+        1,
+        "shard" => shard_id.to_string(),
+        "reason" => reason,
+    );
+}
+
+pub(crate) fn stage_duration(shard_id: u64, stage: Stage, duration: Duration) {
+    ::metrics::histogram!(
+        SHARD_STAGE_DURATION,
+        duration,
+        "shard" => shard_id.to_string(),
+        "stage" => stage.to_string(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shard::Stage;
+    use metrics::{GaugeValue, Key, Recorder, Unit};
+    use std::{sync::Mutex, time::Duration};
+
+    /// ID of the shard recording the metrics, distinguishing them from the
+    /// metrics recorded by other tests.
+    const SHARD_ID: u64 = 4242;
+
+    /// Name and labels of a recorded metric.
+    type Metric = (String, Vec<(String, String)>);
+
+    /// Recorder collecting the names and labels of the recorded metrics.
+    struct CatalogueRecorder(Mutex<Vec<Metric>>);
+
+    impl CatalogueRecorder {
+        fn record(&self, key: &Key) {
+            let labels = key
+                .labels()
+                .map(|label| (label.key().to_owned(), label.value().to_owned()))
+                .collect::<Vec<_>>();
+
+            let shard_id = SHARD_ID.to_string();
+
+            if labels
+                .iter()
+                .any(|(key, value)| key == "shard" && *value == shard_id)
+            {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((key.name().to_string(), labels));
+            }
+        }
+    }
+
+    impl Recorder for CatalogueRecorder {
+        fn register_counter(&self, _: Key, _: Option<Unit>, _: Option<&'static str>) {}
+
+        fn register_gauge(&self, _: Key, _: Option<Unit>, _: Option<&'static str>) {}
+
+        fn register_histogram(&self, _: Key, _: Option<Unit>, _: Option<&'static str>) {}
+
+        fn increment_counter(&self, key: Key, _: u64) {
+            self.record(&key);
+        }
+
+        fn update_gauge(&self, key: Key, _: GaugeValue) {
+            self.record(&key);
+        }
+
+        fn record_histogram(&self, key: Key, _: f64) {
+            self.record(&key);
+        }
+    }
+
+    fn metric(name: &str, labels: &[(&str, &str)]) -> Metric {
+        let labels = labels
+            .iter()
+            .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+            .collect();
+
+        (name.to_owned(), labels)
+    }
+
+    /// Metrics not recorded via the helper functions.
+    #[test]
+    fn test_names() {
+        assert_eq!("Cluster-Shard-Count", super::CLUSTER_SHARD_COUNT);
+        assert_eq!("Inflater-Capacity", super::INFLATER_CAPACITY);
+        assert_eq!("Inflater-In", super::INFLATER_IN);
+        assert_eq!("Inflater-Out", super::INFLATER_OUT);
+    }
+
+    #[test]
+    fn test_catalogue() {
+        let recorder: &'static CatalogueRecorder =
+            Box::leak(Box::new(CatalogueRecorder(Mutex::new(Vec::new()))));
+        metrics::set_recorder(recorder).unwrap();
+
+        super::bytes_received(SHARD_ID, "compressed", 10);
+        super::close_code(SHARD_ID, Some(4000));
+        super::close_code(SHARD_ID, None);
+        super::dispatch_event(SHARD_ID, "MESSAGE_CREATE");
+        super::gateway_event(SHARD_ID, "Hello");
+        super::heartbeat_latency(SHARD_ID, Duration::from_millis(10));
+        super::invalid_session(SHARD_ID, true);
+        super::reconnect(SHARD_ID, "zombied");
+        super::resume(SHARD_ID, "closed");
+        super::stage_duration(SHARD_ID, Stage::Identifying, Duration::from_secs(1));
+
+        let shard = SHARD_ID.to_string();
+        let shard = shard.as_str();
+
+        assert_eq!(
+            vec![
+                metric(
+                    "Shard-Bytes-Received",
+                    &[("shard", shard), ("kind", "compressed")]
+                ),
+                metric("Shard-Close-Codes", &[("shard", shard), ("code", "4000")]),
+                metric("Shard-Close-Codes", &[("shard", shard), ("code", "none")]),
+                metric(
+                    "Shard-Dispatch-Events",
+                    &[("shard", shard), ("event_type", "MESSAGE_CREATE")]
+                ),
+                metric(
+                    "GatewayEvent",
+                    &[("shard", shard), ("GatewayEvent", "Hello")]
+                ),
+                metric("Shard-Heartbeat-Latency", &[("shard", shard)]),
+                metric(
+                    "Shard-Invalid-Sessions",
+                    &[("shard", shard), ("resumable", "true")]
+                ),
+                metric(
+                    "Shard-Reconnects",
+                    &[("shard", shard), ("reason", "zombied")]
+                ),
+                metric("Shard-Resumes", &[("shard", shard), ("reason", "closed")]),
+                metric(
+                    "Shard-Stage-Duration",
+                    &[("shard", shard), ("stage", "Identifying")]
+                ),
+            ],
+            *recorder.0.lock().unwrap()
+        );
+    }
+}
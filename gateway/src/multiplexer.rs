@@ -0,0 +1,567 @@
+//! Run the clusters of multiple bots side by side in one process.
+//!
+//! A [`Multiplexer`] manages one [`Cluster`] per bot, each identified by a
+//! [`BotId`], and combines the events of all of them into a single stream
+//! tagged with the bot and shard they were received on. Bots can be added
+//! and removed at runtime without affecting the others.
+//!
+//! Bots sharing a token, such as several deployments of the same
+//! application, share a single HTTP client and gateway queue when their
+//! clusters are configured via [`Multiplexer::cluster_builder`], so that
+//! ratelimits and identify concurrency are respected across them.
+
+use crate::{
+    cluster::{Cluster, ClusterBuilder},
+    fan_out::FanOut,
+    Event,
+};
+use futures_util::stream::{SelectAll, Stream, StreamExt};
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, Weak},
+    task::{Context, Poll},
+};
+use tokio::sync::{
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
+use twilight_gateway_queue::{LocalQueue, Queue};
+use twilight_http::Client;
+use twilight_model::gateway::Intents;
+
+/// Adding a bot to a multiplexer failed.
+#[derive(Debug)]
+pub struct MultiplexerAddError {
+    kind: MultiplexerAddErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl MultiplexerAddError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &MultiplexerAddErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(
+        self,
+    ) -> (
+        MultiplexerAddErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for MultiplexerAddError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            MultiplexerAddErrorType::BotExists { bot } => {
+                f.write_str("bot ")?;
+                Display::fmt(bot, f)?;
+
+                f.write_str(" has already been added")
+            }
+            MultiplexerAddErrorType::Starting => f.write_str("failed to start the cluster"),
+        }
+    }
+}
+
+impl Error for MultiplexerAddError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`MultiplexerAddError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MultiplexerAddErrorType {
+    /// A bot with the same ID has already been added.
+    BotExists {
+        /// ID of the bot.
+        bot: BotId,
+    },
+    /// Starting the cluster of the bot failed.
+    ///
+    /// The source is a [`ClusterStartError`].
+    ///
+    /// [`ClusterStartError`]: crate::cluster::ClusterStartError
+    Starting,
+}
+
+/// ID of a bot managed by a [`Multiplexer`].
+///
+/// This is chosen by the user when adding the bot, such as the name of the
+/// customer a white-label bot is run for, and is cheap to clone.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BotId(Arc<str>);
+
+impl BotId {
+    /// Return an immutable reference to the ID as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for BotId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for BotId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for BotId {
+    fn from(id: &str) -> Self {
+        Self(Arc::from(id))
+    }
+}
+
+impl From<String> for BotId {
+    fn from(id: String) -> Self {
+        Self(Arc::from(id))
+    }
+}
+
+/// Bot added to a multiplexer.
+#[derive(Debug)]
+struct Bot {
+    cluster: Cluster,
+    /// Sender ending the bot's stream of events when the bot is removed.
+    removed: oneshot::Sender<()>,
+}
+
+/// HTTP client and queue shared between the bots using a token.
+#[derive(Debug)]
+struct Resources {
+    http_client: Client,
+    /// Queue, shared until the last builder or cluster using it is dropped.
+    queue: Weak<Box<dyn Queue>>,
+}
+
+#[derive(Debug)]
+struct MultiplexerRef {
+    additions: UnboundedSender<BotEvents>,
+    bots: Mutex<HashMap<BotId, Bot>>,
+    resources: Mutex<HashMap<Box<str>, Resources>>,
+}
+
+/// Manager running the clusters of multiple bots side by side.
+///
+/// Refer to the [module-level] documentation for more information.
+///
+/// The multiplexer is cheap to clone, with clones managing the same bots.
+///
+/// # Examples
+///
+/// Run two bots and print the events they receive:
+///
+/// ```no_run
+/// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::StreamExt;
+/// use std::env;
+/// use twilight_gateway::{multiplexer::Multiplexer, Intents};
+///
+/// let (multiplexer, mut events) = Multiplexer::new();
+///
+/// for bot in &["alpha", "beta"] {
+///     let token = env::var(format!("DISCORD_TOKEN_{}", bot.to_uppercase()))?;
+///     let builder = multiplexer.cluster_builder(token, Intents::GUILD_MESSAGES);
+///     let cluster = multiplexer.add(*bot, builder).await?;
+///
+///     tokio::spawn(async move { cluster.up().await });
+/// }
+///
+/// while let Some((bot, shard_id, event)) = events.next().await {
+///     println!("bot {} shard {}: {:?}", bot, shard_id, event.kind());
+/// }
+/// # Ok(()) }
+/// ```
+///
+/// [module-level]: self
+#[derive(Clone, Debug)]
+pub struct Multiplexer(Arc<MultiplexerRef>);
+
+impl Multiplexer {
+    /// Create a new multiplexer without any bots.
+    ///
+    /// Returns the multiplexer and a stream of the events of all of its bots,
+    /// tagged with the ID of the bot and shard. The stream ends once all
+    /// clones of the multiplexer have been dropped and the streams of all
+    /// bots have ended.
+    pub fn new() -> (Self, MultiplexerEvents) {
+        let (additions, rx) = mpsc::unbounded_channel();
+
+        let multiplexer = Self(Arc::new(MultiplexerRef {
+            additions,
+            bots: Mutex::new(HashMap::new()),
+            resources: Mutex::new(HashMap::new()),
+        }));
+
+        let events = MultiplexerEvents {
+            additions: Some(rx),
+            streams: SelectAll::new(),
+        };
+
+        (multiplexer, events)
+    }
+
+    /// Create a builder for the cluster of a bot.
+    ///
+    /// The builder is configured with the HTTP client and gateway queue
+    /// shared between all bots using the same token. They are shared for as
+    /// long as any builder or cluster configured with them exists, including
+    /// builders that have not been added yet and clusters of removed bots,
+    /// and are created anew otherwise.
+    pub fn cluster_builder(&self, token: impl Into<String>, intents: Intents) -> ClusterBuilder {
+        let builder = ClusterBuilder::new(token, intents);
+
+        let mut resources = self.0.resources.lock().expect("resources poisoned");
+
+        // Release the resources of tokens no builder or cluster uses anymore.
+        resources.retain(|_, resources| resources.queue.strong_count() > 0);
+
+        let shared = resources.get(builder.token()).and_then(|resources| {
+            let queue = resources.queue.upgrade()?;
+
+            Some((resources.http_client.clone(), queue))
+        });
+
+        let (http_client, queue) = if let Some(shared) = shared {
+            shared
+        } else {
+            let http_client = Client::new(builder.token());
+            let queue: Arc<Box<dyn Queue>> = Arc::new(Box::new(LocalQueue::new()));
+
+            resources.insert(
+                builder.token().into(),
+                Resources {
+                    http_client: http_client.clone(),
+                    queue: Arc::downgrade(&queue),
+                },
+            );
+
+            (http_client, queue)
+        };
+
+        drop(resources);
+
+        builder.http_client(http_client).queue(queue)
+    }
+
+    /// Build the cluster of a bot and add its events to the stream.
+    ///
+    /// The cluster is not brought up. Call [`Cluster::up`] on the returned
+    /// cluster to start its shards.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MultiplexerAddErrorType::BotExists`] error type if a bot
+    /// with the same ID has already been added.
+    ///
+    /// Returns a [`MultiplexerAddErrorType::Starting`] error type if building
+    /// the cluster failed.
+    pub async fn add(
+        &self,
+        bot: impl Into<BotId>,
+        builder: ClusterBuilder,
+    ) -> Result<Cluster, MultiplexerAddError> {
+        let bot = bot.into();
+
+        if self.contains(bot.as_str()) {
+            return Err(MultiplexerAddError {
+                kind: MultiplexerAddErrorType::BotExists { bot },
+                source: None,
+            });
+        }
+
+        let (cluster, events) = builder
+            .build()
+            .await
+            .map_err(|source| MultiplexerAddError {
+                kind: MultiplexerAddErrorType::Starting,
+                source: Some(Box::new(source)),
+            })?;
+
+        let (removed, removed_rx) = oneshot::channel();
+
+        {
+            let mut bots = self.0.bots.lock().expect("bots poisoned");
+
+            // The bot may have been added while the cluster was being built.
+            if bots.contains_key(&bot) {
+                drop(bots);
+                cluster.down();
+
+                return Err(MultiplexerAddError {
+                    kind: MultiplexerAddErrorType::BotExists { bot },
+                    source: None,
+                });
+            }
+
+            bots.insert(
+                bot.clone(),
+                Bot {
+                    cluster: cluster.clone(),
+                    removed,
+                },
+            );
+        }
+
+        // The receiver is only dropped along with the stream of events, in
+        // which case there is nothing to add the bot's events to.
+        let _res = self.0.additions.send(BotEvents {
+            bot,
+            events: Box::pin(events),
+            removed: removed_rx,
+        });
+
+        Ok(cluster)
+    }
+
+    /// Remove a bot, bringing its cluster down and ending its events.
+    ///
+    /// Returns the cluster of the bot, or `None` if no bot with the ID has
+    /// been added. To resume the sessions of the bot's shards later, call
+    /// [`Cluster::down_resumable`] on the bot's cluster before removing it.
+    pub fn remove(&self, bot: &str) -> Option<Cluster> {
+        let removed = self.0.bots.lock().expect("bots poisoned").remove(bot)?;
+
+        removed.cluster.down();
+        let _res = removed.removed.send(());
+
+        Some(removed.cluster)
+    }
+
+    /// Return the cluster of a bot, if it has been added.
+    pub fn cluster(&self, bot: &str) -> Option<Cluster> {
+        self.0
+            .bots
+            .lock()
+            .expect("bots poisoned")
+            .get(bot)
+            .map(|bot| bot.cluster.clone())
+    }
+
+    /// Return the IDs of the added bots, sorted.
+    pub fn bots(&self) -> Vec<BotId> {
+        let mut bots = self
+            .0
+            .bots
+            .lock()
+            .expect("bots poisoned")
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        bots.sort();
+
+        bots
+    }
+
+    /// Whether a bot with the ID has been added.
+    pub fn contains(&self, bot: &str) -> bool {
+        self.0.bots.lock().expect("bots poisoned").contains_key(bot)
+    }
+
+    /// Bring down the clusters of all bots.
+    ///
+    /// The bots are kept, and their clusters can be brought up again.
+    pub fn down(&self) {
+        for bot in self.0.bots.lock().expect("bots poisoned").values() {
+            bot.cluster.down();
+        }
+    }
+}
+
+/// Stream of the events of a bot, ending when the bot is removed.
+struct BotEvents {
+    bot: BotId,
+    events: Pin<Box<dyn Stream<Item = (u64, Event)> + Send + Sync>>,
+    removed: oneshot::Receiver<()>,
+}
+
+impl Stream for BotEvents {
+    type Item = (BotId, u64, Event);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // The sender being dropped along with the multiplexer also ends the
+        // stream.
+        if Pin::new(&mut self.removed).poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+
+        match self.events.poll_next_unpin(cx) {
+            Poll::Ready(Some((shard_id, event))) => {
+                Poll::Ready(Some((self.bot.clone(), shard_id, event)))
+            }
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Stream of the events of all bots of a multiplexer, tagged with the ID of
+/// the bot and shard.
+///
+/// Created along with the multiplexer via [`Multiplexer::new`].
+pub struct MultiplexerEvents {
+    /// Receiver of the streams of added bots, until all clones of the
+    /// multiplexer have been dropped.
+    additions: Option<UnboundedReceiver<BotEvents>>,
+    streams: SelectAll<BotEvents>,
+}
+
+impl MultiplexerEvents {
+    /// Share the events between multiple consumers, each subscribing to the
+    /// event types it needs.
+    ///
+    /// Refer to [`FanOut`] for more information.
+    pub fn fan_out(self) -> FanOut<(BotId, u64, Event)> {
+        FanOut::new(self)
+    }
+}
+
+impl Stream for MultiplexerEvents {
+    type Item = (BotId, u64, Event);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        while let Some(additions) = self.additions.as_mut() {
+            match additions.poll_recv(cx) {
+                Poll::Ready(Some(events)) => self.streams.push(events),
+                Poll::Ready(None) => self.additions = None,
+                Poll::Pending => break,
+            }
+        }
+
+        match self.streams.poll_next_unpin(cx) {
+            // Bots may still be added, so the stream is not over yet.
+            Poll::Ready(None) if self.additions.is_some() => Poll::Pending,
+            poll => poll,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BotId, Multiplexer, MultiplexerAddError, MultiplexerAddErrorType, MultiplexerEvents,
+    };
+    use crate::{cluster::ShardScheme, Intents};
+    use futures::{Stream, StreamExt};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{error::Error, fmt::Debug, hash::Hash, sync::Arc};
+
+    assert_impl_all!(BotId: Clone, Debug, Eq, Hash, Ord, Send, Sync);
+    assert_impl_all!(Multiplexer: Clone, Debug, Send, Sync);
+    assert_fields!(MultiplexerAddErrorType::BotExists: bot);
+    assert_impl_all!(MultiplexerAddErrorType: Debug, Send, Sync);
+    assert_impl_all!(MultiplexerAddError: Error, Send, Sync);
+    assert_impl_all!(MultiplexerEvents: Send, Stream, Sync, Unpin);
+
+    const SCHEME: ShardScheme = ShardScheme::Range {
+        from: 0,
+        to: 0,
+        total: 1,
+    };
+
+    #[tokio::test]
+    async fn test_add_remove() {
+        let (multiplexer, _events) = Multiplexer::new();
+
+        for (bot, token) in &[("a", "first"), ("b", "first"), ("c", "second")] {
+            let builder = multiplexer
+                .cluster_builder(*token, Intents::empty())
+                .shard_scheme(SCHEME);
+            multiplexer.add(*bot, builder).await.unwrap();
+        }
+
+        assert_eq!(
+            vec![BotId::from("a"), BotId::from("b"), BotId::from("c")],
+            multiplexer.bots()
+        );
+        assert_eq!(2, multiplexer.0.resources.lock().unwrap().len());
+
+        let builder = multiplexer
+            .cluster_builder("second", Intents::empty())
+            .shard_scheme(SCHEME);
+        assert!(matches!(
+            multiplexer.add("c", builder).await.unwrap_err().kind(),
+            MultiplexerAddErrorType::BotExists { bot } if bot.as_str() == "c"
+        ));
+
+        assert!(multiplexer.remove("a").is_some());
+        assert!(multiplexer.remove("a").is_none());
+        assert!(multiplexer.cluster("a").is_none());
+
+        assert!(multiplexer.remove("c").is_some());
+        assert!(multiplexer.cluster("b").is_some());
+
+        // Resources of tokens no builder or cluster uses are released.
+        drop(multiplexer.cluster_builder("third", Intents::empty()));
+        let resources = multiplexer.0.resources.lock().unwrap();
+        assert_eq!(2, resources.len());
+        assert!(resources.contains_key("Bot first"));
+        assert!(resources.contains_key("Bot third"));
+    }
+
+    #[tokio::test]
+    async fn test_pending_builder() {
+        let (multiplexer, _events) = Multiplexer::new();
+
+        let pending = multiplexer
+            .cluster_builder("token", Intents::empty())
+            .shard_scheme(SCHEME);
+        let builder = multiplexer
+            .cluster_builder("token", Intents::empty())
+            .shard_scheme(SCHEME);
+        multiplexer.add("a", builder).await.unwrap();
+        multiplexer.remove("a");
+
+        // The pending builder still uses the queue, so it is shared with
+        // builders created after all bots using the token were removed.
+        let later = multiplexer
+            .cluster_builder("token", Intents::empty())
+            .shard_scheme(SCHEME);
+        let pending = multiplexer.add("b", pending).await.unwrap();
+        let later = multiplexer.add("c", later).await.unwrap();
+
+        assert!(Arc::ptr_eq(
+            pending.config().queue(),
+            later.config().queue()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_events_end() {
+        let (multiplexer, mut events) = Multiplexer::new();
+
+        let builder = multiplexer
+            .cluster_builder("token", Intents::empty())
+            .shard_scheme(SCHEME);
+        multiplexer.add("a", builder).await.unwrap();
+        multiplexer.remove("a");
+        drop(multiplexer);
+
+        assert!(events.next().await.is_none());
+    }
+}
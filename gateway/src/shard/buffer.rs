@@ -0,0 +1,313 @@
+//! Bounded buffering of events between a shard and its event stream.
+//!
+//! By default the events of a shard are buffered without limit until the
+//! event stream consumes them. A bounded [`EventBuffer`] caps the number of
+//! buffered events, applying a [`BackpressurePolicy`] once it is full.
+//!
+//! The same bounded channel buffers the events of the subscriptions of a
+//! [`FanOut`].
+//!
+//! [`FanOut`]: crate::fan_out::FanOut
+
+use crate::{fan_out::FanOutEvent, Event, EventTypeFlags};
+use futures_util::task::AtomicWaker;
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+use tokio::sync::Notify;
+use twilight_model::gateway::event::EventType;
+
+/// Policy applied when a bounded event buffer is full.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum BackpressurePolicy {
+    /// Stop reading from the gateway connection until the event stream has
+    /// consumed an event.
+    ///
+    /// No events are dropped, but the shard may miss heartbeats if the event
+    /// stream is not consumed for a long time. For a subscription of a
+    /// fan-out, this holds back the other subscriptions too.
+    Block,
+    /// Drop the oldest buffered event to make room for the new event.
+    DropOldest,
+    /// Drop new events of the provided low-priority event types.
+    ///
+    /// Events of other types are always buffered, even if this exceeds the
+    /// capacity of the buffer.
+    DropNewest(EventTypeFlags),
+}
+
+/// Configuration of a bounded buffer of events.
+///
+/// Refer to [`ShardBuilder::event_buffer`] and [`FanOut::subscribe`] for more
+/// information.
+///
+/// [`FanOut::subscribe`]: crate::fan_out::FanOut::subscribe
+/// [`ShardBuilder::event_buffer`]: super::ShardBuilder::event_buffer
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct EventBuffer {
+    capacity: usize,
+    policy: BackpressurePolicy,
+}
+
+impl EventBuffer {
+    /// Create a new bounded buffer configuration.
+    ///
+    /// A capacity of 0 is treated as a capacity of 1.
+    pub fn new(capacity: usize, policy: BackpressurePolicy) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            policy,
+        }
+    }
+
+    /// Maximum number of buffered events.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Policy applied when the buffer is full.
+    pub const fn policy(&self) -> BackpressurePolicy {
+        self.policy
+    }
+}
+
+/// Item of a bounded channel, which can be dropped depending on its event
+/// type.
+pub(crate) trait BufferedEvent {
+    /// Type of the event.
+    fn kind(&self) -> EventType;
+}
+
+impl BufferedEvent for Event {
+    fn kind(&self) -> EventType {
+        Event::kind(self)
+    }
+}
+
+impl<T: FanOutEvent> BufferedEvent for Arc<T> {
+    fn kind(&self) -> EventType {
+        self.event().kind()
+    }
+}
+
+#[derive(Debug)]
+struct Shared<T> {
+    buffer: EventBuffer,
+    dropped: AtomicU64,
+    queue: Mutex<VecDeque<T>>,
+    receiver_closed: AtomicBool,
+    sender_closed: AtomicBool,
+    /// Notified when the receiver takes an event out of the queue.
+    space: Notify,
+    /// Waker of the receiver waiting for an event.
+    waker: AtomicWaker,
+}
+
+/// Create a new bounded channel of events.
+pub(crate) fn channel<T>(buffer: EventBuffer) -> (BoundedSender<T>, BoundedReceiver<T>) {
+    let shared = Arc::new(Shared {
+        buffer,
+        dropped: AtomicU64::new(0),
+        queue: Mutex::new(VecDeque::with_capacity(buffer.capacity())),
+        receiver_closed: AtomicBool::new(false),
+        sender_closed: AtomicBool::new(false),
+        space: Notify::new(),
+        waker: AtomicWaker::new(),
+    });
+
+    (BoundedSender(Arc::clone(&shared)), BoundedReceiver(shared))
+}
+
+/// Sending half of a bounded channel of events.
+///
+/// The receiver is notified that the channel is closed once this is dropped.
+#[derive(Debug)]
+pub(crate) struct BoundedSender<T = Event>(Arc<Shared<T>>);
+
+impl<T: BufferedEvent> BoundedSender<T> {
+    /// Number of events dropped due to the buffer being full.
+    pub fn dropped(&self) -> u64 {
+        self.0.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Whether the receiver has been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.0.receiver_closed.load(Ordering::Relaxed)
+    }
+
+    /// Buffer an event, applying the backpressure policy if the buffer is
+    /// full.
+    ///
+    /// Events are discarded if the receiver has been dropped.
+    pub async fn send(&self, event: T) {
+        loop {
+            if self.0.receiver_closed.load(Ordering::Relaxed) {
+                return;
+            }
+
+            {
+                let mut queue = self.0.queue.lock().expect("event queue poisoned");
+
+                if queue.len() < self.0.buffer.capacity() {
+                    queue.push_back(event);
+                    self.0.waker.wake();
+
+                    return;
+                }
+
+                match self.0.buffer.policy() {
+                    BackpressurePolicy::Block => {}
+                    BackpressurePolicy::DropOldest => {
+                        queue.pop_front();
+                        queue.push_back(event);
+                        self.0.dropped.fetch_add(1, Ordering::Relaxed);
+                        self.0.waker.wake();
+
+                        return;
+                    }
+                    BackpressurePolicy::DropNewest(low_priority) => {
+                        if low_priority.contains(EventTypeFlags::from(event.kind())) {
+                            self.0.dropped.fetch_add(1, Ordering::Relaxed);
+                        } else {
+                            queue.push_back(event);
+                            self.0.waker.wake();
+                        }
+
+                        return;
+                    }
+                }
+            }
+
+            self.0.space.notified().await;
+        }
+    }
+}
+
+impl<T> Drop for BoundedSender<T> {
+    fn drop(&mut self) {
+        self.0.sender_closed.store(true, Ordering::Release);
+        self.0.waker.wake();
+    }
+}
+
+/// Receiving half of a bounded channel of events.
+#[derive(Debug)]
+pub(crate) struct BoundedReceiver<T = Event>(Arc<Shared<T>>);
+
+impl<T> BoundedReceiver<T> {
+    /// Number of events dropped due to the buffer being full.
+    pub fn dropped(&self) -> u64 {
+        self.0.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        if let Some(event) = self.pop() {
+            return Poll::Ready(Some(event));
+        }
+
+        self.0.waker.register(cx.waker());
+
+        // An event may have been sent between the first check and
+        // registering the waker.
+        if let Some(event) = self.pop() {
+            return Poll::Ready(Some(event));
+        }
+
+        if self.0.sender_closed.load(Ordering::Acquire) {
+            return Poll::Ready(None);
+        }
+
+        Poll::Pending
+    }
+
+    fn pop(&self) -> Option<T> {
+        let event = self
+            .0
+            .queue
+            .lock()
+            .expect("event queue poisoned")
+            .pop_front()?;
+        self.0.space.notify_one();
+
+        Some(event)
+    }
+}
+
+impl<T> Drop for BoundedReceiver<T> {
+    fn drop(&mut self) {
+        self.0.receiver_closed.store(true, Ordering::Relaxed);
+        self.0.space.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{channel, BackpressurePolicy, BoundedReceiver, BoundedSender, EventBuffer};
+    use crate::{Event, EventTypeFlags};
+    use futures_util::future;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash, time::Duration};
+    use twilight_model::gateway::event::shard::Payload;
+
+    assert_impl_all!(BackpressurePolicy: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(BoundedReceiver: Debug, Send, Sync);
+    assert_impl_all!(BoundedSender: Debug, Send, Sync);
+    assert_impl_all!(EventBuffer: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    fn payload(byte: u8) -> Event {
+        Event::ShardPayload(Payload { bytes: vec![byte] })
+    }
+
+    async fn recv(rx: &BoundedReceiver) -> Option<Event> {
+        future::poll_fn(|cx| rx.poll_recv(cx)).await
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest() {
+        let (tx, rx) = channel(EventBuffer::new(2, BackpressurePolicy::DropOldest));
+        tx.send(payload(1)).await;
+        tx.send(payload(2)).await;
+        tx.send(payload(3)).await;
+        assert_eq!(1, tx.dropped());
+        drop(tx);
+
+        assert!(matches!(recv(&rx).await, Some(Event::ShardPayload(p)) if p.bytes == [2]));
+        assert!(matches!(recv(&rx).await, Some(Event::ShardPayload(p)) if p.bytes == [3]));
+        assert!(recv(&rx).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_drop_newest() {
+        let policy = BackpressurePolicy::DropNewest(EventTypeFlags::SHARD_PAYLOAD);
+        let (tx, rx) = channel(EventBuffer::new(1, policy));
+        tx.send(payload(1)).await;
+        tx.send(payload(2)).await;
+        tx.send(Event::GatewayReconnect).await;
+        assert_eq!(1, tx.dropped());
+
+        assert!(matches!(recv(&rx).await, Some(Event::ShardPayload(p)) if p.bytes == [1]));
+        assert!(matches!(recv(&rx).await, Some(Event::GatewayReconnect)));
+    }
+
+    #[tokio::test]
+    async fn test_block() {
+        let (tx, rx) = channel(EventBuffer::new(1, BackpressurePolicy::Block));
+        tx.send(payload(1)).await;
+
+        let blocked = tokio::time::timeout(Duration::from_millis(10), tx.send(payload(2))).await;
+        assert!(blocked.is_err());
+
+        assert!(recv(&rx).await.is_some());
+        tx.send(payload(3)).await;
+        assert_eq!(0, tx.dropped());
+
+        drop(rx);
+        tx.send(payload(4)).await;
+    }
+}
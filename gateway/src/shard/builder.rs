@@ -1,13 +1,20 @@
-use super::{config::Config, Events, Shard};
+use super::{
+    buffer::EventBuffer, config::Config, processor::heartbeat::HeartbeatPolicy, proxy::Proxy,
+    replay::Recorder, Events, Shard,
+};
 use crate::EventTypeFlags;
 use std::{
+    env::consts::OS,
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
     sync::Arc,
 };
-use twilight_gateway_queue::{LocalQueue, Queue};
+use twilight_gateway_queue::{LocalQueue, Priority, Queue};
 use twilight_http::Client as HttpClient;
-use twilight_model::gateway::{payload::update_presence::UpdatePresencePayload, Intents};
+use twilight_model::gateway::{
+    payload::{identify::IdentifyProperties, update_presence::UpdatePresencePayload},
+    Intents,
+};
 
 /// Large threshold configuration is invalid.
 ///
@@ -174,25 +181,83 @@ impl ShardBuilder {
         }
 
         Self(Config {
+            event_buffer: None,
             event_types: EventTypeFlags::default(),
             gateway_url: None,
+            heartbeat_policy: HeartbeatPolicy::default(),
             http_client: HttpClient::new(token.clone()),
+            identify_properties: IdentifyProperties::new("twilight.rs", "twilight.rs", OS, "", ""),
             intents,
             large_threshold: 250,
+            max_frame_size: None,
+            max_message_size: None,
             presence: None,
+            proxy: None,
             queue: Arc::new(Box::new(LocalQueue::new())),
+            queue_priority: Priority::Normal,
+            recorder: None,
             shard: [0, 1],
             token: token.into_boxed_str(),
+            resume_gateway_url: None,
             session_id: None,
             sequence: None,
         })
     }
 
     /// Consume the builder, constructing a shard.
+    ///
+    /// A warning is logged if any of the configured [event types] can never
+    /// be received with the configured intents. The default event types are
+    /// not checked, as they include the events of every intent. Use
+    /// [`unreachable_event_types`] to reject such configurations instead.
+    ///
+    /// [event types]: Self::event_types
+    /// [`unreachable_event_types`]: Self::unreachable_event_types
     pub fn build(self) -> (Shard, Events) {
+        self.0.warn_unreachable_event_types();
+
         Shard::new_with_config(self.0)
     }
 
+    /// Set the bounded buffer of events that have not yet been consumed by
+    /// the event stream.
+    ///
+    /// Once the buffer is full the buffer's [`BackpressurePolicy`] is applied.
+    /// The number of events dropped due to the policy is available via
+    /// [`Information::dropped_events`].
+    ///
+    /// Defaults to `None`, buffering events without limit.
+    ///
+    /// # Examples
+    ///
+    /// Buffer up to 10000 events, dropping new presence updates and typing
+    /// events once the buffer is full:
+    ///
+    /// ```no_run
+    /// use std::env;
+    /// use twilight_gateway::{
+    ///     shard::{BackpressurePolicy, EventBuffer},
+    ///     EventTypeFlags, Intents, Shard,
+    /// };
+    ///
+    /// let token = env::var("DISCORD_TOKEN")?;
+    /// let low_priority = EventTypeFlags::PRESENCE_UPDATE | EventTypeFlags::TYPING_START;
+    /// let buffer = EventBuffer::new(10_000, BackpressurePolicy::DropNewest(low_priority));
+    ///
+    /// let (shard, events) = Shard::builder(token, Intents::GUILD_PRESENCES)
+    ///     .event_buffer(Some(buffer))
+    ///     .build();
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`BackpressurePolicy`]: super::BackpressurePolicy
+    /// [`Information::dropped_events`]: super::Information::dropped_events
+    pub const fn event_buffer(mut self, event_buffer: Option<EventBuffer>) -> Self {
+        self.0.event_buffer = event_buffer;
+
+        self
+    }
+
     /// Set the event types to process.
     ///
     /// This is an optimization technique; all events not included in the
@@ -207,6 +272,33 @@ impl ShardBuilder {
         self
     }
 
+    /// Configured [event types] that can never be received with the
+    /// configured intents.
+    ///
+    /// Unlike the warning logged when building, this also checks the default
+    /// event types.
+    ///
+    /// # Examples
+    ///
+    /// Refuse to build a shard receiving member events without the
+    /// [`Intents::GUILD_MEMBERS`] intent:
+    ///
+    /// ```
+    /// use twilight_gateway::{EventTypeFlags, Intents, Shard};
+    ///
+    /// # #[tokio::main] async fn main() {
+    /// let builder = Shard::builder("token", Intents::GUILDS)
+    ///     .event_types(EventTypeFlags::GUILD_CREATE | EventTypeFlags::MEMBER_ADD);
+    ///
+    /// assert_eq!(EventTypeFlags::MEMBER_ADD, builder.unreachable_event_types());
+    /// # }
+    /// ```
+    ///
+    /// [event types]: Self::event_types
+    pub fn unreachable_event_types(&self) -> EventTypeFlags {
+        self.0.unreachable_event_types()
+    }
+
     /// Set the URL used for connecting to Discord's gateway
     pub fn gateway_url(mut self, gateway_url: Option<String>) -> Self {
         self.0.gateway_url = gateway_url.map(String::into_boxed_str);
@@ -214,6 +306,23 @@ impl ShardBuilder {
         self
     }
 
+    /// Set the policy of sending heartbeats and detecting zombie
+    /// connections.
+    ///
+    /// When the gateway stops acknowledging heartbeats the connection is
+    /// considered a zombie once the policy's threshold of missed
+    /// acknowledgements is reached. The shard then emits a [`ShardZombied`]
+    /// event and resumes the session on a new connection.
+    ///
+    /// Defaults to [`HeartbeatPolicy::default`].
+    ///
+    /// [`ShardZombied`]: twilight_model::gateway::event::Event::ShardZombied
+    pub const fn heartbeat_policy(mut self, heartbeat_policy: HeartbeatPolicy) -> Self {
+        self.0.heartbeat_policy = heartbeat_policy;
+
+        self
+    }
+
     /// Set the HTTP client to be used by the shard for getting gateway
     /// information.
     ///
@@ -225,6 +334,38 @@ impl ShardBuilder {
         self
     }
 
+    /// Set the properties to send when identifying with the gateway.
+    ///
+    /// Discord shows the bot with a mobile status when the browser is set to
+    /// that of a mobile client.
+    ///
+    /// Defaults to a browser and device of `twilight.rs` and the current
+    /// operating system.
+    ///
+    /// # Examples
+    ///
+    /// Show the bot with a mobile status:
+    ///
+    /// ```no_run
+    /// use std::env;
+    /// use twilight_gateway::{Intents, Shard};
+    /// use twilight_model::gateway::payload::identify::IdentifyProperties;
+    ///
+    /// let token = env::var("DISCORD_TOKEN")?;
+    /// let properties = IdentifyProperties::new("Discord iOS", "twilight.rs", "iOS", "", "");
+    ///
+    /// let (shard, events) = Shard::builder(token, Intents::GUILDS)
+    ///     .identify_properties(properties)
+    ///     .build();
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn identify_properties(mut self, identify_properties: IdentifyProperties) -> Self {
+        self.0.identify_properties = identify_properties;
+
+        self
+    }
+
     /// Set the maximum number of members in a guild to load the member list.
     ///
     /// Default value is `250`. The minimum value is `50` and the maximum is
@@ -268,6 +409,29 @@ impl ShardBuilder {
         Ok(self)
     }
 
+    /// Set the maximum size of a single websocket frame.
+    ///
+    /// Connections receiving a larger frame are closed and reconnected.
+    ///
+    /// Defaults to `None`, not limiting the frame size.
+    pub const fn max_frame_size(mut self, max_frame_size: Option<usize>) -> Self {
+        self.0.max_frame_size = max_frame_size;
+
+        self
+    }
+
+    /// Set the maximum size of a single websocket message, which may consist
+    /// of multiple frames.
+    ///
+    /// Connections receiving a larger message are closed and reconnected.
+    ///
+    /// Defaults to `None`, not limiting the message size.
+    pub const fn max_message_size(mut self, max_message_size: Option<usize>) -> Self {
+        self.0.max_message_size = max_message_size;
+
+        self
+    }
+
     /// Set the presence to use automatically when starting a new session.
     ///
     /// Default is no presence, which defaults to strictly being "online"
@@ -307,6 +471,18 @@ impl ShardBuilder {
         self
     }
 
+    /// Set the proxy to tunnel the gateway connection through.
+    ///
+    /// Refer to [`Proxy`] for an example.
+    ///
+    /// Defaults to `None`, connecting to the gateway directly.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn proxy(mut self, proxy: Option<Proxy>) -> Self {
+        self.0.proxy = proxy;
+
+        self
+    }
+
     /// Set the queue to use for queueing shard connections.
     ///
     /// You probably don't need to set this yourself, because the [`Cluster`]
@@ -324,6 +500,40 @@ impl ShardBuilder {
         self
     }
 
+    /// Set the priority with which the shard requests to initialize a session
+    /// from the [`queue`].
+    ///
+    /// Queues may let shards with a higher priority initialize their sessions
+    /// first. The [`LocalQueue`] and [`LargeBotQueue`] do so within each of
+    /// their buckets.
+    ///
+    /// Defaults to [`Priority::Normal`].
+    ///
+    /// [`LargeBotQueue`]: crate::queue::LargeBotQueue
+    /// [`LocalQueue`]: crate::queue::LocalQueue
+    /// [`queue`]: Self::queue
+    pub const fn queue_priority(mut self, queue_priority: Priority) -> Self {
+        self.0.queue_priority = queue_priority;
+
+        self
+    }
+
+    /// Set the recorder of the shard's raw inbound payloads.
+    ///
+    /// Every payload received from the gateway is written to the recorder
+    /// before it is processed. Recordings can be replayed without a
+    /// connection using a [`Replayer`].
+    ///
+    /// Defaults to `None`, not recording any payloads.
+    ///
+    /// [`Replayer`]: super::replay::Replayer
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn recorder(mut self, recorder: Option<Recorder>) -> Self {
+        self.0.recorder = recorder;
+
+        self
+    }
+
     /// Set the shard ID to connect as, and the total number of shards used by
     /// the bot.
     ///
@@ -384,7 +594,7 @@ mod tests {
     use super::{
         LargeThresholdError, LargeThresholdErrorType, ShardBuilder, ShardIdError, ShardIdErrorType,
     };
-    use crate::Intents;
+    use crate::{EventTypeFlags, Intents};
     use static_assertions::{assert_fields, assert_impl_all};
     use std::{error::Error, fmt::Debug};
 
@@ -402,4 +612,19 @@ mod tests {
     assert_impl_all!(ShardIdErrorType: Debug, Send, Sync);
     assert_fields!(ShardIdErrorType::IdTooLarge: id, total);
     assert_impl_all!(ShardIdError: Error, Send, Sync);
+
+    #[tokio::test]
+    async fn test_unreachable_event_types() {
+        let builder = ShardBuilder::new("token".to_owned(), Intents::GUILDS);
+        assert_eq!(
+            EventTypeFlags::default().unreachable(Intents::GUILDS),
+            builder.unreachable_event_types()
+        );
+        assert!(builder
+            .unreachable_event_types()
+            .contains(EventTypeFlags::MESSAGE_CREATE));
+
+        let builder = builder.event_types(EventTypeFlags::GUILD_CREATE | EventTypeFlags::READY);
+        assert!(builder.unreachable_event_types().is_empty());
+    }
 }
@@ -1,8 +1,14 @@
+use super::{
+    buffer::EventBuffer, processor::heartbeat::HeartbeatPolicy, proxy::Proxy, replay::Recorder,
+};
 use crate::EventTypeFlags;
 use std::sync::Arc;
-use twilight_gateway_queue::Queue;
+use twilight_gateway_queue::{Priority, Queue};
 use twilight_http::Client;
-use twilight_model::gateway::{payload::update_presence::UpdatePresencePayload, Intents};
+use twilight_model::gateway::{
+    payload::{identify::IdentifyProperties, update_presence::UpdatePresencePayload},
+    Intents,
+};
 
 /// The configuration used by the shard to identify with the gateway and
 /// operate.
@@ -12,25 +18,44 @@ use twilight_model::gateway::{payload::update_presence::UpdatePresencePayload, I
 /// [`Shard::builder`]: super::Shard::builder
 #[derive(Clone, Debug)]
 pub struct Config {
+    pub(super) event_buffer: Option<EventBuffer>,
     pub(crate) event_types: EventTypeFlags,
     pub(crate) gateway_url: Option<Box<str>>,
+    pub(super) heartbeat_policy: HeartbeatPolicy,
     pub(crate) http_client: Client,
+    pub(super) identify_properties: IdentifyProperties,
     pub(super) intents: Intents,
     pub(super) large_threshold: u64,
-    pub(super) presence: Option<UpdatePresencePayload>,
+    pub(super) max_frame_size: Option<usize>,
+    pub(super) max_message_size: Option<usize>,
+    pub(crate) presence: Option<UpdatePresencePayload>,
+    pub(super) proxy: Option<Proxy>,
     pub(super) queue: Arc<Box<dyn Queue>>,
+    pub(crate) queue_priority: Priority,
+    pub(super) recorder: Option<Recorder>,
     pub(crate) shard: [u64; 2],
     pub(super) token: Box<str>,
+    pub(crate) resume_gateway_url: Option<Box<str>>,
     pub(crate) session_id: Option<Box<str>>,
     pub(crate) sequence: Option<u64>,
 }
 
 impl Config {
+    /// Bounded buffer of events, if events are not buffered without limit.
+    pub const fn event_buffer(&self) -> Option<EventBuffer> {
+        self.event_buffer
+    }
+
     /// Copy of the event type flags.
     pub const fn event_types(&self) -> EventTypeFlags {
         self.event_types
     }
 
+    /// Policy of sending heartbeats and detecting zombie connections.
+    pub const fn heartbeat_policy(&self) -> HeartbeatPolicy {
+        self.heartbeat_policy
+    }
+
     /// Return an immutable reference to the url used to connect to the gateway.
     pub fn gateway_url(&self) -> Option<&str> {
         self.gateway_url.as_deref()
@@ -42,6 +67,12 @@ impl Config {
         &self.http_client
     }
 
+    /// Immutable reference to the properties sent when identifying with the
+    /// gateway.
+    pub const fn identify_properties(&self) -> &IdentifyProperties {
+        &self.identify_properties
+    }
+
     /// Return a copy of the intents that the gateway is using.
     pub const fn intents(&self) -> Intents {
         self.intents
@@ -53,6 +84,16 @@ impl Config {
         self.large_threshold
     }
 
+    /// Maximum size of a single websocket frame, if limited.
+    pub const fn max_frame_size(&self) -> Option<usize> {
+        self.max_frame_size
+    }
+
+    /// Maximum size of a single websocket message, if limited.
+    pub const fn max_message_size(&self) -> Option<usize> {
+        self.max_message_size
+    }
+
     /// Return an immutable reference to the presence to set when identifying
     /// with the gateway.
     ///
@@ -62,6 +103,22 @@ impl Config {
         self.presence.as_ref()
     }
 
+    /// Proxy the gateway connection is tunneled through, if any.
+    pub const fn proxy(&self) -> Option<&Proxy> {
+        self.proxy.as_ref()
+    }
+
+    /// Priority with which the shard requests to initialize a session from
+    /// the queue.
+    pub const fn queue_priority(&self) -> Priority {
+        self.queue_priority
+    }
+
+    /// Recorder of the shard's raw inbound payloads, if any.
+    pub const fn recorder(&self) -> Option<&Recorder> {
+        self.recorder.as_ref()
+    }
+
     /// The shard's ID and the total number of shards used by the bot.
     pub const fn shard(&self) -> [u64; 2] {
         self.shard
@@ -72,6 +129,35 @@ impl Config {
     pub const fn token(&self) -> &str {
         &self.token
     }
+
+    /// Event types that can never be received with the configured intents.
+    ///
+    /// Refer to [`EventTypeFlags::unreachable`] for more information.
+    pub fn unreachable_event_types(&self) -> EventTypeFlags {
+        self.event_types.unreachable(self.intents)
+    }
+
+    /// Warn about configured event types that can never be received with the
+    /// configured intents.
+    ///
+    /// Nothing is checked if the event types were not configured, as the
+    /// default event types include the events of every intent.
+    pub(crate) fn warn_unreachable_event_types(&self) {
+        if self.event_types == EventTypeFlags::default() {
+            return;
+        }
+
+        let unreachable = self.unreachable_event_types();
+
+        if !unreachable.is_empty() {
+            tracing::warn!(
+                event_types = ?unreachable,
+                intents = ?self.intents,
+                missing_intents = ?(unreachable.intents() - self.intents),
+                "event types can never be received with the configured intents",
+            );
+        }
+    }
 }
 
 #[cfg(test)]
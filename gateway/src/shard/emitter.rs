@@ -1,9 +1,17 @@
-use super::json;
+#[cfg(feature = "etf")]
+use super::etf;
+use super::{
+    buffer::{self, BoundedReceiver, BoundedSender, EventBuffer},
+    json,
+    members::MemberChunkRegistry,
+};
 use crate::{Event, EventTypeFlags};
 use std::{
     convert::TryFrom,
     error::Error,
     fmt::{Debug, Display, Formatter, Result as FmtResult},
+    sync::Arc,
+    task::{Context, Poll},
 };
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use twilight_model::gateway::event::shard::Payload;
@@ -60,20 +68,81 @@ pub enum EmitJsonErrorType {
     Parsing,
 }
 
+/// Sending half of the channel of events to the listener.
+#[derive(Clone, Debug)]
+enum EventSender {
+    Bounded(Arc<BoundedSender>),
+    Unbounded(UnboundedSender<Event>),
+}
+
+/// Receiving half of the channel of events to the listener.
+#[derive(Debug)]
+pub enum EventReceiver {
+    Bounded(BoundedReceiver),
+    Unbounded(UnboundedReceiver<Event>),
+}
+
+impl EventReceiver {
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        match self {
+            Self::Bounded(rx) => rx.poll_recv(cx),
+            Self::Unbounded(rx) => rx.poll_recv(cx),
+        }
+    }
+}
+
 /// Emitter over a listener with some useful things on top to abstract common
 /// operations.
 #[derive(Clone, Debug)]
 pub struct Emitter {
     event_types: EventTypeFlags,
-    tx: UnboundedSender<Event>,
+    member_chunks: Arc<MemberChunkRegistry>,
+    tx: EventSender,
 }
 
 impl Emitter {
     /// Create a new emitter for events and bytes.
-    pub fn new(event_types: EventTypeFlags) -> (Self, UnboundedReceiver<Event>) {
-        let (tx, rx) = mpsc::unbounded_channel();
+    ///
+    /// Events are buffered without limit unless a bounded event buffer is
+    /// provided.
+    pub fn new(
+        event_types: EventTypeFlags,
+        event_buffer: Option<EventBuffer>,
+    ) -> (Self, EventReceiver) {
+        let (tx, rx) = if let Some(event_buffer) = event_buffer {
+            let (tx, rx) = buffer::channel(event_buffer);
+
+            (
+                EventSender::Bounded(Arc::new(tx)),
+                EventReceiver::Bounded(rx),
+            )
+        } else {
+            let (tx, rx) = mpsc::unbounded_channel();
+
+            (EventSender::Unbounded(tx), EventReceiver::Unbounded(rx))
+        };
+
+        (
+            Self {
+                event_types,
+                member_chunks: Arc::default(),
+                tx,
+            },
+            rx,
+        )
+    }
 
-        (Self { event_types, tx }, rx)
+    /// Number of events dropped due to the event buffer being full.
+    pub fn dropped(&self) -> u64 {
+        match &self.tx {
+            EventSender::Bounded(tx) => tx.dropped(),
+            EventSender::Unbounded(_) => 0,
+        }
+    }
+
+    /// Registry of in-flight guild member requests.
+    pub const fn member_chunks(&self) -> &Arc<MemberChunkRegistry> {
+        &self.member_chunks
     }
 
     /// Whether the configured event types include an individual event type.
@@ -91,21 +160,29 @@ impl Emitter {
     ///
     /// [`EventTypeFlags::SHARD_PAYLOAD`]: crate::EventTypeFlags::SHARD_PAYLOAD
     #[tracing::instrument(level = "trace")]
-    pub fn bytes(&self, bytes: &[u8]) {
+    pub async fn bytes(&self, bytes: &[u8]) {
         if self.wants(EventTypeFlags::SHARD_PAYLOAD) {
             self.send(Event::ShardPayload(Payload {
                 bytes: bytes.to_vec(),
             }))
+            .await;
         }
     }
 
     /// Send an event to the listener if it has subscribed to its event type.
+    ///
+    /// Member chunks are additionally forwarded to the in-flight guild member
+    /// request with their nonce, if there is one.
     #[tracing::instrument(level = "trace")]
-    pub fn event(&self, event: Event) {
+    pub async fn event(&self, event: Event) {
+        if let Event::MemberChunk(chunk) = &event {
+            self.member_chunks.forward(chunk);
+        }
+
         let event_type = EventTypeFlags::from(event.kind());
 
         if self.wants(event_type) {
-            self.send(event);
+            self.send(event).await;
         }
     }
 
@@ -120,24 +197,14 @@ impl Emitter {
     /// Returns a [`EmitJsonError::Parsing`] error type if the combination of
     /// the provided opcode, sequence, event type, and JSON could not be parsed
     /// into an event.
-    pub fn json(
+    pub async fn json(
         &self,
         op: u8,
         seq: Option<u64>,
         event_type: Option<&str>,
         json: &mut str,
     ) -> Result<(), EmitJsonError> {
-        let flag = EventTypeFlags::try_from((op, event_type)).map_err(|(op, event_type)| {
-            EmitJsonError {
-                kind: EmitJsonErrorType::EventTypeUnknown {
-                    event_type: event_type.map(ToOwned::to_owned),
-                    op,
-                },
-                source: None,
-            }
-        })?;
-
-        if self.wants(flag) {
+        if self.wants_payload(op, event_type)? {
             let gateway_event =
                 json::parse_gateway_event(op, seq, event_type, json).map_err(|source| {
                     EmitJsonError {
@@ -145,43 +212,122 @@ impl Emitter {
                         source: Some(Box::new(source)),
                     }
                 })?;
-            self.event(Event::from(gateway_event));
+            self.event(Event::from(gateway_event)).await;
         }
 
         Ok(())
     }
 
-    fn send(&self, event: Event) {
-        let _res = self.tx.send(event);
+    /// Emit an ETF payload that hasn't been decoded yet, but only if the
+    /// listener wants the event type.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`EmitJsonError::EventTypeUnknown`] error type if the
+    /// event type is unknown.
+    ///
+    /// Returns a [`EmitJsonError::Parsing`] error type if the combination of
+    /// the provided opcode, sequence, event type, and term could not be parsed
+    /// into an event.
+    #[cfg(feature = "etf")]
+    pub async fn etf(
+        &self,
+        op: u8,
+        seq: Option<u64>,
+        event_type: Option<&str>,
+        bytes: &[u8],
+    ) -> Result<(), EmitJsonError> {
+        if self.wants_payload(op, event_type)? {
+            let gateway_event =
+                etf::parse_gateway_event(op, seq, event_type, bytes).map_err(|source| {
+                    EmitJsonError {
+                        kind: EmitJsonErrorType::Parsing,
+                        source: Some(Box::new(source)),
+                    }
+                })?;
+            self.event(Event::from(gateway_event)).await;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the listener wants the event of a payload that hasn't been
+    /// parsed yet.
+    fn wants_payload(&self, op: u8, event_type: Option<&str>) -> Result<bool, EmitJsonError> {
+        let flag = EventTypeFlags::try_from((op, event_type)).map_err(|(op, event_type)| {
+            EmitJsonError {
+                kind: EmitJsonErrorType::EventTypeUnknown {
+                    event_type: event_type.map(ToOwned::to_owned),
+                    op,
+                },
+                source: None,
+            }
+        })?;
+
+        // Member chunks need to be parsed if there are in-flight guild member
+        // requests waiting for them.
+        Ok(self.wants(flag)
+            || (flag == EventTypeFlags::MEMBER_CHUNK && !self.member_chunks.is_empty()))
+    }
+
+    async fn send(&self, event: Event) {
+        match &self.tx {
+            EventSender::Bounded(tx) => tx.send(event).await,
+            EventSender::Unbounded(tx) => {
+                let _res = tx.send(event);
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Emitter;
-    use crate::{Event, EventTypeFlags};
+    use super::{Emitter, EventReceiver};
+    use crate::{
+        shard::buffer::{BackpressurePolicy, EventBuffer},
+        Event, EventTypeFlags,
+    };
+    use futures_util::future;
     use tokio::time::{self, Duration};
 
+    async fn recv(rx: &mut EventReceiver) -> Option<Event> {
+        future::poll_fn(|cx| rx.poll_recv(cx)).await
+    }
+
     #[tokio::test]
     async fn test_bytes_send() {
-        let (emitter, mut rx) = Emitter::new(EventTypeFlags::SHARD_PAYLOAD);
-        emitter.bytes(&[1]);
+        let (emitter, mut rx) = Emitter::new(EventTypeFlags::SHARD_PAYLOAD, None);
+        emitter.bytes(&[1]).await;
 
-        assert!(rx.recv().await.is_some());
-        assert!(time::timeout(Duration::from_millis(10), rx.recv())
+        assert!(recv(&mut rx).await.is_some());
+        assert!(time::timeout(Duration::from_millis(10), recv(&mut rx))
             .await
             .is_err());
     }
 
     #[tokio::test]
     async fn test_event_sends_to_rx() {
-        let (emitter, mut rx) = Emitter::new(EventTypeFlags::default());
-        emitter.event(Event::GatewayReconnect);
+        let (emitter, mut rx) = Emitter::new(EventTypeFlags::default(), None);
+        emitter.event(Event::GatewayReconnect).await;
 
-        assert!(rx.recv().await.is_some());
+        assert!(recv(&mut rx).await.is_some());
 
         // now check that the event didn't send the event twice
-        assert!(time::timeout(Duration::from_millis(10), rx.recv())
+        assert!(time::timeout(Duration::from_millis(10), recv(&mut rx))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bounded_dropped() {
+        let buffer = EventBuffer::new(1, BackpressurePolicy::DropOldest);
+        let (emitter, mut rx) = Emitter::new(EventTypeFlags::default(), Some(buffer));
+        emitter.event(Event::GatewayReconnect).await;
+        emitter.event(Event::GatewayReconnect).await;
+
+        assert_eq!(1, emitter.dropped());
+        assert!(recv(&mut rx).await.is_some());
+        assert!(time::timeout(Duration::from_millis(10), recv(&mut rx))
             .await
             .is_err());
     }
@@ -0,0 +1,35 @@
+//! Encoding of payloads sent to the gateway.
+//!
+//! Payloads are encoded as JSON unless the `etf` feature is enabled, in which
+//! case they're encoded in the External Term Format.
+
+#[cfg(feature = "etf")]
+pub use super::etf::to_vec;
+#[cfg(not(feature = "etf"))]
+pub use super::json::to_vec;
+
+/// Add the encoding to a gateway connection URL depending on whether the
+/// `etf` feature is enabled.
+///
+/// If it is enabled then the `encoding` query parameter is appended with a
+/// value of `etf`, otherwise Discord's default JSON encoding is used.
+#[cfg_attr(not(feature = "etf"), allow(unused_variables))]
+pub fn add_url_feature(buf: &mut String) {
+    #[cfg(feature = "etf")]
+    buf.push_str("&encoding=etf");
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_add_url_feature() {
+        let mut buf = String::new();
+        super::add_url_feature(&mut buf);
+
+        #[cfg(feature = "etf")]
+        assert_eq!("&encoding=etf", buf);
+
+        #[cfg(not(feature = "etf"))]
+        assert!(buf.is_empty());
+    }
+}
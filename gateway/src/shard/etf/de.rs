@@ -0,0 +1,410 @@
+use super::{
+    EtfError, EtfErrorType, ATOM_EXT, ATOM_UTF8_EXT, BINARY_EXT, FLOAT_EXT, INTEGER_EXT,
+    LARGE_BIG_EXT, LARGE_TUPLE_EXT, LIST_EXT, MAP_EXT, NEW_FLOAT_EXT, NIL_EXT, SMALL_ATOM_EXT,
+    SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT, SMALL_INTEGER_EXT, SMALL_TUPLE_EXT, STRING_EXT, VERSION,
+};
+use serde::{
+    de::{
+        value::SeqDeserializer, DeserializeSeed, Deserializer as _, EnumAccess, IgnoredAny,
+        IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
+    },
+    Deserialize as _,
+};
+use std::{convert::TryFrom, str};
+
+/// Deserializer of a term borrowing strings and bytes from the input.
+#[derive(Debug)]
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+}
+
+impl<'de> Deserializer<'de> {
+    /// Create a deserializer over an encoded term, checking its version byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EtfErrorType::VersionInvalid`] error type if the term does
+    /// not start with the version byte.
+    ///
+    /// Returns an [`EtfErrorType::Eof`] error type if the input is empty.
+    pub fn from_slice(input: &'de [u8]) -> Result<Self, EtfError> {
+        match input.split_first() {
+            Some((&VERSION, input)) => Ok(Self { input }),
+            Some((&version, _)) => Err(EtfError::new(EtfErrorType::VersionInvalid { version })),
+            None => Err(EtfError::new(EtfErrorType::Eof)),
+        }
+    }
+
+    /// Ensure that the whole input has been deserialized.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EtfErrorType::TrailingBytes`] error type if bytes remain.
+    pub fn end(&self) -> Result<(), EtfError> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(EtfError::new(EtfErrorType::TrailingBytes))
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'de [u8], EtfError> {
+        if self.input.len() < len {
+            return Err(EtfError::new(EtfErrorType::Eof));
+        }
+
+        let (bytes, input) = self.input.split_at(len);
+        self.input = input;
+
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, EtfError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, EtfError> {
+        let bytes = self.take(2)?;
+
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, EtfError> {
+        let bytes = self.take(4)?;
+
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, EtfError> {
+        let bytes = self.take(4)?;
+
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_len(&mut self) -> Result<usize, EtfError> {
+        // Lengths are at most `u32::MAX`, which fits into a `usize` on
+        // supported platforms.
+        self.read_u32().map(|len| len as usize)
+    }
+
+    fn read_f64(&mut self) -> Result<f64, EtfError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+
+        Ok(f64::from_be_bytes(bytes))
+    }
+
+    /// Consume the atom `nil`, returning whether the next term was one.
+    fn take_nil(&mut self) -> bool {
+        let len = match self.input {
+            [SMALL_ATOM_EXT | SMALL_ATOM_UTF8_EXT, 3, b'n', b'i', b'l', ..] => 5,
+            [ATOM_EXT | ATOM_UTF8_EXT, 0, 3, b'n', b'i', b'l', ..] => 6,
+            _ => return false,
+        };
+
+        self.input = &self.input[len..];
+
+        true
+    }
+
+    fn parse_atom<V: Visitor<'de>>(
+        &mut self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, EtfError> {
+        let atom = str::from_utf8(self.take(len)?)
+            .map_err(|_| EtfError::new(EtfErrorType::AtomInvalid))?;
+
+        match atom {
+            "nil" => visitor.visit_unit(),
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            atom => visitor.visit_borrowed_str(atom),
+        }
+    }
+
+    fn parse_big<V: Visitor<'de>>(&mut self, len: usize, visitor: V) -> Result<V::Value, EtfError> {
+        let negative = self.read_u8()? != 0;
+        let digits = self.take(len)?;
+
+        // Digits are little endian and may be zero padded.
+        if digits.iter().skip(8).any(|digit| *digit != 0) {
+            return Err(EtfError::new(EtfErrorType::IntegerTooLarge));
+        }
+
+        let value = digits
+            .iter()
+            .take(8)
+            .rev()
+            .fold(0_u64, |value, digit| value << 8 | u64::from(*digit));
+
+        if negative {
+            let value = i64::try_from(-i128::from(value))
+                .map_err(|_| EtfError::new(EtfErrorType::IntegerTooLarge))?;
+
+            visitor.visit_i64(value)
+        } else {
+            visitor.visit_u64(value)
+        }
+    }
+
+    fn parse_float<V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value, EtfError> {
+        // Old floats are zero padded strings formatted with `%.20e`.
+        let value = str::from_utf8(self.take(31)?)
+            .ok()
+            .and_then(|float| float.trim_end_matches('\0').parse().ok())
+            .ok_or_else(|| EtfError::new(EtfErrorType::FloatInvalid))?;
+
+        visitor.visit_f64(value)
+    }
+
+    fn parse_seq<V: Visitor<'de>>(&mut self, len: usize, visitor: V) -> Result<V::Value, EtfError> {
+        let mut access = Access {
+            de: self,
+            remaining: len,
+        };
+        let value = visitor.visit_seq(&mut access)?;
+
+        if access.remaining == 0 {
+            Ok(value)
+        } else {
+            Err(EtfError::new(EtfErrorType::TrailingElements))
+        }
+    }
+
+    fn parse_map<V: Visitor<'de>>(&mut self, len: usize, visitor: V) -> Result<V::Value, EtfError> {
+        let mut access = Access {
+            de: self,
+            remaining: len,
+        };
+        let value = visitor.visit_map(&mut access)?;
+
+        if access.remaining == 0 {
+            Ok(value)
+        } else {
+            Err(EtfError::new(EtfErrorType::TrailingElements))
+        }
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = EtfError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.read_u8()? {
+            SMALL_INTEGER_EXT => visitor.visit_u8(self.read_u8()?),
+            INTEGER_EXT => visitor.visit_i32(self.read_i32()?),
+            SMALL_BIG_EXT => {
+                let len = self.read_u8()?;
+
+                self.parse_big(usize::from(len), visitor)
+            }
+            LARGE_BIG_EXT => {
+                let len = self.read_len()?;
+
+                self.parse_big(len, visitor)
+            }
+            NEW_FLOAT_EXT => visitor.visit_f64(self.read_f64()?),
+            FLOAT_EXT => self.parse_float(visitor),
+            ATOM_EXT | ATOM_UTF8_EXT => {
+                let len = self.read_u16()?;
+
+                self.parse_atom(usize::from(len), visitor)
+            }
+            SMALL_ATOM_EXT | SMALL_ATOM_UTF8_EXT => {
+                let len = self.read_u8()?;
+
+                self.parse_atom(usize::from(len), visitor)
+            }
+            SMALL_TUPLE_EXT => {
+                let len = self.read_u8()?;
+
+                self.parse_seq(usize::from(len), visitor)
+            }
+            LARGE_TUPLE_EXT => {
+                let len = self.read_len()?;
+
+                self.parse_seq(len, visitor)
+            }
+            NIL_EXT => self.parse_seq(0, visitor),
+            STRING_EXT => {
+                // Lists of small integers are encoded as strings of bytes.
+                let len = self.read_u16()?;
+                let bytes = self.take(usize::from(len))?;
+                let mut access = SeqDeserializer::new(bytes.iter().copied());
+                let value = visitor.visit_seq(&mut access)?;
+                access.end()?;
+
+                Ok(value)
+            }
+            LIST_EXT => {
+                let len = self.read_len()?;
+                let value = self.parse_seq(len, visitor)?;
+
+                // Proper lists end with an empty list.
+                if self.read_u8()? == NIL_EXT {
+                    Ok(value)
+                } else {
+                    Err(EtfError::new(EtfErrorType::ListImproper))
+                }
+            }
+            BINARY_EXT => {
+                let len = self.read_len()?;
+                let bytes = self.take(len)?;
+
+                match str::from_utf8(bytes) {
+                    Ok(string) => visitor.visit_borrowed_str(string),
+                    Err(_) => visitor.visit_borrowed_bytes(bytes),
+                }
+            }
+            MAP_EXT => {
+                let len = self.read_len()?;
+
+                self.parse_map(len, visitor)
+            }
+            tag => Err(EtfError::new(EtfErrorType::TagUnsupported { tag })),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.take_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        // Variants with values are maps with the variant as the only key,
+        // while unit variants are only the variant.
+        if let [MAP_EXT, 0, 0, 0, 1, ..] = self.input {
+            self.input = &self.input[5..];
+
+            visitor.visit_enum(Enum { de: self })
+        } else {
+            let variant = <&str>::deserialize(&mut *self)?;
+
+            visitor.visit_enum(variant.into_deserializer())
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Access to the elements of a sequence or the entries of a map.
+struct Access<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> SeqAccess<'de> for Access<'_, 'de> {
+    type Error = EtfError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> MapAccess<'de> for Access<'_, 'de> {
+    type Error = EtfError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// Access to an enum variant encoded as a map with a single entry.
+struct Enum<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de> EnumAccess<'de> for Enum<'_, 'de> {
+    type Error = EtfError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(&mut *self.de)?;
+
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Enum<'_, 'de> {
+    type Error = EtfError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        IgnoredAny::deserialize(self.de).map(|_| ())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.de.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.de.deserialize_any(visitor)
+    }
+}
@@ -0,0 +1,483 @@
+//! Encoding and decoding of gateway payloads in the External Term Format.
+//!
+//! The [External Term Format] (ETF) is the binary format of Erlang terms,
+//! which Discord can use instead of JSON for gateway payloads. With the
+//! `etf` feature enabled shards request the ETF encoding when connecting and
+//! use this module to decode incoming payloads and encode outgoing commands.
+//!
+//! Terms are mapped to and from the serde data model so that payloads are
+//! decoded into the same [`GatewayEvent`] and model types as JSON payloads:
+//!
+//! | Term                         | Serde                               |
+//! |------------------------------|-------------------------------------|
+//! | integer and big integer      | `i64` or `u64`                      |
+//! | float                        | `f64`                               |
+//! | atom `nil`                   | unit or `None`                      |
+//! | atoms `true` and `false`     | `bool`                              |
+//! | other atoms                  | borrowed `str`                      |
+//! | binary                       | borrowed `str`, or bytes if invalid UTF-8 |
+//! | list, tuple, and string      | sequence                            |
+//! | map                          | map                                 |
+//!
+//! Strings are encoded as binaries, sequences as lists, and structs as maps
+//! keyed by binaries, which is what Discord expects of sent payloads.
+//!
+//! # Examples
+//!
+//! Encode a heartbeat and decode it again:
+//!
+//! ```
+//! use twilight_gateway::shard::etf;
+//! use twilight_model::gateway::payload::Heartbeat;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let bytes = etf::to_vec(&Heartbeat::new(5))?;
+//! let value = etf::from_slice::<serde_json::Value>(&bytes)?;
+//!
+//! assert_eq!(1, value["op"]);
+//! assert_eq!(5, value["d"]);
+//! # Ok(()) }
+//! ```
+//!
+//! [External Term Format]: https://erlang.org/doc/apps/erts/erl_ext_dist.html
+//! [`GatewayEvent`]: twilight_model::gateway::event::GatewayEvent
+
+mod de;
+mod ser;
+
+pub use self::{de::Deserializer, ser::Serializer};
+
+use serde::{
+    de::{DeserializeSeed, Error as DeError},
+    ser::Error as SerError,
+    Deserialize, Serialize,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::gateway::event::{GatewayEvent, GatewayEventDeserializer};
+
+/// Version byte every encoded term starts with.
+const VERSION: u8 = 131;
+
+const NEW_FLOAT_EXT: u8 = 70;
+const SMALL_INTEGER_EXT: u8 = 97;
+const INTEGER_EXT: u8 = 98;
+const FLOAT_EXT: u8 = 99;
+const ATOM_EXT: u8 = 100;
+const SMALL_TUPLE_EXT: u8 = 104;
+const LARGE_TUPLE_EXT: u8 = 105;
+const NIL_EXT: u8 = 106;
+const STRING_EXT: u8 = 107;
+const LIST_EXT: u8 = 108;
+const BINARY_EXT: u8 = 109;
+const SMALL_BIG_EXT: u8 = 110;
+const LARGE_BIG_EXT: u8 = 111;
+const SMALL_ATOM_EXT: u8 = 115;
+const MAP_EXT: u8 = 116;
+const ATOM_UTF8_EXT: u8 = 118;
+const SMALL_ATOM_UTF8_EXT: u8 = 119;
+
+/// Encoding or decoding a term failed.
+#[derive(Debug)]
+pub struct EtfError {
+    kind: EtfErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl EtfError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &EtfErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(self) -> (EtfErrorType, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, self.source)
+    }
+
+    pub(super) const fn new(kind: EtfErrorType) -> Self {
+        Self { kind, source: None }
+    }
+}
+
+impl Display for EtfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            EtfErrorType::AtomInvalid => f.write_str("atom is not valid UTF-8"),
+            EtfErrorType::Custom { message } => f.write_str(message),
+            EtfErrorType::Eof => f.write_str("term ended unexpectedly"),
+            EtfErrorType::FloatInvalid => f.write_str("float term is invalid"),
+            EtfErrorType::IntegerTooLarge => f.write_str("integer term does not fit into 64 bits"),
+            EtfErrorType::LengthTooLarge => {
+                f.write_str("value is too long to be encoded as a term")
+            }
+            EtfErrorType::ListImproper => f.write_str("list term is not a proper list"),
+            EtfErrorType::TagUnsupported { tag } => {
+                f.write_str("term tag ")?;
+                Display::fmt(tag, f)?;
+
+                f.write_str(" is unsupported")
+            }
+            EtfErrorType::TrailingBytes => f.write_str("bytes remain after the term"),
+            EtfErrorType::TrailingElements => {
+                f.write_str("elements of a list, tuple, or map term remain unread")
+            }
+            EtfErrorType::VersionInvalid { version } => {
+                f.write_str("term version ")?;
+                Display::fmt(version, f)?;
+
+                f.write_str(" is invalid")
+            }
+        }
+    }
+}
+
+impl Error for EtfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+impl DeError for EtfError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::new(EtfErrorType::Custom {
+            message: msg.to_string(),
+        })
+    }
+}
+
+impl SerError for EtfError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::new(EtfErrorType::Custom {
+            message: msg.to_string(),
+        })
+    }
+}
+
+/// Type of [`EtfError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EtfErrorType {
+    /// Atom is not valid UTF-8.
+    AtomInvalid,
+    /// Value failed to serialize or deserialize.
+    Custom {
+        /// Message of the serializer or deserializer.
+        message: String,
+    },
+    /// Term ended unexpectedly.
+    Eof,
+    /// Float term is not a valid float.
+    FloatInvalid,
+    /// Integer term does not fit into a 64 bit integer.
+    IntegerTooLarge,
+    /// Value is too long to be encoded as a term.
+    LengthTooLarge,
+    /// List term does not end with an empty list.
+    ListImproper,
+    /// Term tag is unknown or can not be represented in the serde data model.
+    TagUnsupported {
+        /// Tag of the term.
+        tag: u8,
+    },
+    /// Bytes remain after the term.
+    TrailingBytes,
+    /// Elements of a list, tuple, or map remain after the value was
+    /// deserialized.
+    TrailingElements,
+    /// Version byte of the term is not 131.
+    VersionInvalid {
+        /// Provided version.
+        version: u8,
+    },
+}
+
+/// Deserialize an instance of a type from an encoded term.
+///
+/// # Errors
+///
+/// Returns an [`EtfErrorType::VersionInvalid`] error type if the term does
+/// not start with the version byte.
+///
+/// Returns an [`EtfErrorType::TrailingBytes`] error type if bytes remain after
+/// the term.
+///
+/// Returns other error types if the term is invalid or does not match the
+/// type.
+pub fn from_slice<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<T, EtfError> {
+    let mut deserializer = Deserializer::from_slice(bytes)?;
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+
+    Ok(value)
+}
+
+/// Serialize a value as an encoded term.
+///
+/// # Errors
+///
+/// Returns an [`EtfErrorType::LengthTooLarge`] error type if a string or
+/// collection is too long to be encoded.
+///
+/// Returns an [`EtfErrorType::Custom`] error type if the value failed to
+/// serialize.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, EtfError> {
+    let mut serializer = Serializer::new();
+    value.serialize(&mut serializer)?;
+
+    Ok(serializer.into_inner())
+}
+
+/// Parse a gateway event from an encoded term with its headers.
+///
+/// The headers can be retrieved from the term via [`payload_header`].
+///
+/// # Errors
+///
+/// Returns an error if the term is invalid or does not match the headers.
+pub fn parse_gateway_event(
+    op: u8,
+    sequence: Option<u64>,
+    event_type: Option<&str>,
+    bytes: &[u8],
+) -> Result<GatewayEvent, EtfError> {
+    let mut deserializer = Deserializer::from_slice(bytes)?;
+    let event =
+        GatewayEventDeserializer::new(op, sequence, event_type).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+
+    Ok(event)
+}
+
+/// Retrieve the opcode, sequence, and event type of an encoded payload.
+///
+/// Returns `None` if the term is not a payload with an opcode.
+pub fn payload_header(bytes: &[u8]) -> Option<(u8, Option<u64>, Option<&str>)> {
+    #[derive(Deserialize)]
+    struct Header<'a> {
+        op: u8,
+        s: Option<u64>,
+        #[serde(borrow)]
+        t: Option<&'a str>,
+    }
+
+    let header = from_slice::<Header<'_>>(bytes).ok()?;
+
+    Some((header.op, header.s, header.t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EtfError, EtfErrorType, VERSION};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{error::Error, fmt::Debug};
+    use twilight_model::{
+        gateway::{
+            event::{DispatchEvent, GatewayEvent},
+            payload::{
+                identify::{Identify, IdentifyInfo, IdentifyProperties},
+                Heartbeat, UpdatePresence,
+            },
+            presence::{ActivityType, MinimalActivity, Status},
+            Intents, OpCode,
+        },
+        id::GuildId,
+    };
+
+    assert_fields!(EtfErrorType::Custom: message);
+    assert_fields!(EtfErrorType::TagUnsupported: tag);
+    assert_fields!(EtfErrorType::VersionInvalid: version);
+    assert_impl_all!(EtfErrorType: Debug, Send, Sync);
+    assert_impl_all!(EtfError: Error, Send, Sync);
+
+    /// `%{d: %{guild_id: 123, unavailable: true}, op: 0, s: 7, t: "GUILD_DELETE"}`
+    /// as encoded by Discord, with atom keys and an integer ID.
+    const GUILD_DELETE: &[u8] = &[
+        131, 116, 0, 0, 0, 4, 119, 1, b'd', 116, 0, 0, 0, 2, 119, 2, b'i', b'd', 97, 123, 119, 11,
+        b'u', b'n', b'a', b'v', b'a', b'i', b'l', b'a', b'b', b'l', b'e', 119, 4, b't', b'r', b'u',
+        b'e', 119, 2, b'o', b'p', 97, 0, 119, 1, b's', 97, 7, 119, 1, b't', 109, 0, 0, 0, 12, b'G',
+        b'U', b'I', b'L', b'D', b'_', b'D', b'E', b'L', b'E', b'T', b'E',
+    ];
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    enum Kind {
+        Unit,
+        Newtype(u8),
+        Struct { value: i64 },
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Sample {
+        big: u64,
+        bytes: Vec<u8>,
+        empty: Vec<u8>,
+        float: f64,
+        kinds: Vec<Kind>,
+        negative: i64,
+        none: Option<String>,
+        pair: (bool, String),
+    }
+
+    #[test]
+    fn test_roundtrip() -> Result<(), EtfError> {
+        let sample = Sample {
+            big: u64::MAX,
+            bytes: vec![1, 2, 3],
+            empty: Vec::new(),
+            float: 1.5,
+            kinds: vec![Kind::Unit, Kind::Newtype(5), Kind::Struct { value: -300 }],
+            negative: i64::MIN,
+            none: None,
+            pair: (true, "twilight".to_owned()),
+        };
+
+        let bytes = super::to_vec(&sample)?;
+        assert_eq!(Some(&VERSION), bytes.first());
+        assert_eq!(sample, super::from_slice(&bytes)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_integers() -> Result<(), EtfError> {
+        for value in &[
+            0,
+            255,
+            256,
+            -1,
+            i64::from(i32::MAX),
+            i64::from(i32::MIN) - 1,
+        ] {
+            assert_eq!(*value, super::from_slice::<i64>(&super::to_vec(value)?)?);
+        }
+
+        // Small integer, integer, and small big integer.
+        assert_eq!(vec![VERSION, 97, 5], super::to_vec(&5_u8)?);
+        assert_eq!(
+            vec![VERSION, 98, 255, 255, 255, 255],
+            super::to_vec(&-1_i8)?
+        );
+        assert_eq!(
+            vec![VERSION, 110, 5, 0, 0, 0, 0, 0, 1],
+            super::to_vec(&(1_u64 << 32))?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_ext_is_sequence() -> Result<(), EtfError> {
+        // Lists of small integers such as the shard are encoded as strings.
+        let bytes = [VERSION, 107, 0, 2, 0, 1];
+        assert_eq!([0_u64, 1], super::from_slice::<[u64; 2]>(&bytes)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            super::from_slice::<u8>(&[130, 97, 1]).unwrap_err().kind(),
+            EtfErrorType::VersionInvalid { version: 130 }
+        ));
+        assert!(matches!(
+            super::from_slice::<u8>(&[VERSION, 97, 1, 0])
+                .unwrap_err()
+                .kind(),
+            EtfErrorType::TrailingBytes
+        ));
+        assert!(matches!(
+            super::from_slice::<String>(&[VERSION, 109, 0, 0, 0, 3, b'a'])
+                .unwrap_err()
+                .kind(),
+            EtfErrorType::Eof
+        ));
+        assert!(matches!(
+            super::from_slice::<u8>(&[VERSION, 77]).unwrap_err().kind(),
+            EtfErrorType::TagUnsupported { tag: 77 }
+        ));
+        assert!(matches!(
+            super::from_slice::<Vec<u8>>(&[VERSION, 108, 0, 0, 0, 1, 97, 1, 97, 2])
+                .unwrap_err()
+                .kind(),
+            EtfErrorType::ListImproper
+        ));
+    }
+
+    #[test]
+    fn test_payload_header() {
+        assert_eq!(
+            Some((0, Some(7), Some("GUILD_DELETE"))),
+            super::payload_header(GUILD_DELETE)
+        );
+        assert!(super::payload_header(&[VERSION, 116, 0, 0, 0, 0]).is_none());
+    }
+
+    #[test]
+    fn test_parse_gateway_event() -> Result<(), EtfError> {
+        let event = super::parse_gateway_event(0, Some(7), Some("GUILD_DELETE"), GUILD_DELETE)?;
+
+        assert!(matches!(
+            event,
+            GatewayEvent::Dispatch(7, dispatch)
+                if matches!(&*dispatch, DispatchEvent::GuildDelete(guild)
+                    if guild.id == GuildId(123) && guild.unavailable)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_commands() -> Result<(), EtfError> {
+        let heartbeat = super::from_slice::<Value>(&super::to_vec(&Heartbeat::new(5))?)?;
+        assert_eq!(json!({ "d": 5, "op": 1 }), heartbeat);
+
+        let identify = Identify::new(IdentifyInfo {
+            compress: false,
+            intents: Intents::GUILDS,
+            large_threshold: 250,
+            presence: None,
+            properties: IdentifyProperties::new("twilight.rs", "twilight.rs", "linux", "", ""),
+            shard: Some([0, 1]),
+            token: "Bot token".to_owned(),
+        });
+        let value = super::from_slice::<Value>(&super::to_vec(&identify)?)?;
+
+        assert_eq!(OpCode::Identify as u8, value["op"]);
+        assert_eq!("Bot token", value["d"]["token"]);
+        assert_eq!(json!([0, 1]), value["d"]["shard"]);
+        assert_eq!(Value::Null, value["d"]["presence"]);
+        assert_eq!(serde_json::to_value(&identify).unwrap(), value);
+
+        let activity = MinimalActivity {
+            kind: ActivityType::Playing,
+            name: "twilight".to_owned(),
+            url: None,
+        };
+        let presence =
+            UpdatePresence::new(vec![activity.into()], false, None, Status::Online).unwrap();
+        let bytes = super::to_vec(&presence)?;
+
+        assert_eq!(
+            serde_json::to_value(&presence).unwrap(),
+            super::from_slice::<Value>(&bytes)?
+        );
+        assert_eq!(presence, super::from_slice(&bytes)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,432 @@
+use super::{
+    EtfError, EtfErrorType, BINARY_EXT, INTEGER_EXT, LIST_EXT, MAP_EXT, NEW_FLOAT_EXT, NIL_EXT,
+    SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT, SMALL_INTEGER_EXT, VERSION,
+};
+use serde::ser::{
+    Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant,
+};
+use std::convert::TryFrom;
+
+/// Serializer of values into a term.
+#[derive(Debug)]
+pub struct Serializer {
+    output: Vec<u8>,
+}
+
+impl Serializer {
+    /// Create a serializer, writing the version byte.
+    pub fn new() -> Self {
+        Self {
+            output: vec![VERSION],
+        }
+    }
+
+    /// Consume the serializer, returning the encoded term.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn into_inner(self) -> Vec<u8> {
+        self.output
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn write_atom(&mut self, atom: &str) {
+        // Only short, known atoms are written.
+        self.output.push(SMALL_ATOM_UTF8_EXT);
+        self.output.push(atom.len() as u8);
+        self.output.extend_from_slice(atom.as_bytes());
+    }
+
+    fn write_binary(&mut self, bytes: &[u8]) -> Result<(), EtfError> {
+        let len =
+            u32::try_from(bytes.len()).map_err(|_| EtfError::new(EtfErrorType::LengthTooLarge))?;
+
+        self.output.push(BINARY_EXT);
+        self.output.extend_from_slice(&len.to_be_bytes());
+        self.output.extend_from_slice(bytes);
+
+        Ok(())
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn write_integer(&mut self, value: i128) {
+        if let Ok(value) = u8::try_from(value) {
+            self.output.push(SMALL_INTEGER_EXT);
+            self.output.push(value);
+        } else if let Ok(value) = i32::try_from(value) {
+            self.output.push(INTEGER_EXT);
+            self.output.extend_from_slice(&value.to_be_bytes());
+        } else {
+            // Big integers are a sign and little endian digits without
+            // trailing zeros. 64 bit integers have at most 8 digits, so the
+            // magnitude fits in a u64.
+            let digits = (value.abs() as u64).to_le_bytes();
+            let len = digits.len() - digits.iter().rev().take_while(|d| **d == 0).count();
+
+            self.output.push(SMALL_BIG_EXT);
+            self.output.push(len as u8);
+            self.output.push(u8::from(value < 0));
+            self.output.extend_from_slice(&digits[..len]);
+        }
+    }
+
+    /// Write the header of a list or map with a placeholder length.
+    fn begin(&mut self, kind: Kind) -> Compound<'_> {
+        let header = self.output.len();
+
+        self.output.push(match kind {
+            Kind::List => LIST_EXT,
+            Kind::Map => MAP_EXT,
+        });
+        self.output.extend_from_slice(&[0; 4]);
+
+        Compound {
+            header,
+            kind,
+            len: 0,
+            ser: self,
+        }
+    }
+
+    /// Write the header of a map with the variant as its only key.
+    fn begin_variant(&mut self, variant: &str) -> Result<(), EtfError> {
+        self.output.push(MAP_EXT);
+        self.output.extend_from_slice(&1_u32.to_be_bytes());
+
+        self.write_binary(variant.as_bytes())
+    }
+}
+
+impl Default for Serializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> serde::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = EtfError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), EtfError> {
+        self.write_atom(if v { "true" } else { "false" });
+
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), EtfError> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), EtfError> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), EtfError> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), EtfError> {
+        self.write_integer(i128::from(v));
+
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), EtfError> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), EtfError> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), EtfError> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), EtfError> {
+        self.write_integer(i128::from(v));
+
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), EtfError> {
+        self.serialize_f64(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), EtfError> {
+        self.output.push(NEW_FLOAT_EXT);
+        self.output.extend_from_slice(&v.to_be_bytes());
+
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), EtfError> {
+        self.write_binary(v.encode_utf8(&mut [0; 4]).as_bytes())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), EtfError> {
+        self.write_binary(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), EtfError> {
+        self.write_binary(v)
+    }
+
+    fn serialize_none(self) -> Result<(), EtfError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), EtfError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), EtfError> {
+        self.write_atom("nil");
+
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), EtfError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), EtfError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), EtfError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), EtfError> {
+        self.begin_variant(variant)?;
+
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Compound<'a>, EtfError> {
+        Ok(self.begin(Kind::List))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Compound<'a>, EtfError> {
+        Ok(self.begin(Kind::List))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Compound<'a>, EtfError> {
+        Ok(self.begin(Kind::List))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Compound<'a>, EtfError> {
+        self.begin_variant(variant)?;
+
+        Ok(self.begin(Kind::List))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Compound<'a>, EtfError> {
+        Ok(self.begin(Kind::Map))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Compound<'a>, EtfError> {
+        Ok(self.begin(Kind::Map))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Compound<'a>, EtfError> {
+        self.begin_variant(variant)?;
+
+        Ok(self.begin(Kind::Map))
+    }
+}
+
+/// Kind of collection being serialized.
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    List,
+    Map,
+}
+
+/// Serializer of the elements of a list or the entries of a map.
+///
+/// The number of elements isn't always known beforehand, or may differ from
+/// the hint when fields are skipped, so it is counted and written to the
+/// header once the collection ends.
+#[derive(Debug)]
+pub struct Compound<'a> {
+    header: usize,
+    kind: Kind,
+    len: u32,
+    ser: &'a mut Serializer,
+}
+
+impl Compound<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        self.len = self
+            .len
+            .checked_add(1)
+            .ok_or_else(|| EtfError::new(EtfErrorType::LengthTooLarge))?;
+
+        value.serialize(&mut *self.ser)
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn finish(self) -> Result<(), EtfError> {
+        let output = &mut self.ser.output;
+
+        match self.kind {
+            // Empty lists are written as such instead of a list without
+            // elements.
+            Kind::List if self.len == 0 => {
+                output.truncate(self.header);
+                output.push(NIL_EXT);
+            }
+            Kind::List => {
+                output[self.header + 1..self.header + 5].copy_from_slice(&self.len.to_be_bytes());
+                output.push(NIL_EXT);
+            }
+            Kind::Map => {
+                output[self.header + 1..self.header + 5].copy_from_slice(&self.len.to_be_bytes());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        self.finish()
+    }
+}
+
+impl SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        self.finish()
+    }
+}
+
+impl SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        self.finish()
+    }
+}
+
+impl SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        self.finish()
+    }
+}
+
+impl SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), EtfError> {
+        self.element(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EtfError> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        self.finish()
+    }
+}
+
+impl SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), EtfError> {
+        self.element(key)?;
+
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        self.finish()
+    }
+}
+
+impl SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = EtfError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), EtfError> {
+        self.element(key)?;
+
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), EtfError> {
+        self.finish()
+    }
+}
@@ -13,13 +13,13 @@
 //! [`EventType`]: ::twilight_model::gateway::event::EventType
 //! [`ShardBuilder::event_types`]: crate::shard::ShardBuilder::event_types
 
-use crate::EventTypeFlags;
+use super::emitter::EventReceiver;
+use crate::{fan_out::FanOut, EventTypeFlags};
 use futures_util::stream::Stream;
 use std::{
     pin::Pin,
     task::{Context, Poll},
 };
-use tokio::sync::mpsc::UnboundedReceiver;
 use twilight_model::gateway::event::Event;
 
 /// A stream of events from a [`Shard`].
@@ -39,11 +39,11 @@ use twilight_model::gateway::event::Event;
 /// [`futures::stream::Stream`]: https://docs.rs/futures/*/futures/stream/trait.Stream.html
 pub struct Events {
     event_types: EventTypeFlags,
-    rx: UnboundedReceiver<Event>,
+    rx: EventReceiver,
 }
 
 impl Events {
-    pub(super) const fn new(event_types: EventTypeFlags, rx: UnboundedReceiver<Event>) -> Self {
+    pub(super) const fn new(event_types: EventTypeFlags, rx: EventReceiver) -> Self {
         Self { event_types, rx }
     }
 
@@ -51,6 +51,14 @@ impl Events {
     pub const fn event_types(&self) -> EventTypeFlags {
         self.event_types
     }
+
+    /// Share the events between multiple consumers, each subscribing to the
+    /// event types it needs.
+    ///
+    /// Refer to [`FanOut`] for more information.
+    pub fn fan_out(self) -> FanOut<Event> {
+        FanOut::new(self)
+    }
 }
 
 impl Stream for Events {
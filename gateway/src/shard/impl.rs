@@ -2,8 +2,11 @@ use super::{
     builder::ShardBuilder,
     config::Config,
     emitter::Emitter,
+    encoding,
     event::Events,
-    json,
+    members::{
+        CollectGuildMembersError, CollectGuildMembersErrorType, GuildMembers, MemberChunkCollector,
+    },
     processor::{ConnectingErrorType, Latency, Session, ShardProcessor},
     raw_message::Message,
     stage::Stage,
@@ -15,13 +18,18 @@ use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
     error::Error,
-    fmt::{Display, Formatter, Result as FmtResult},
-    sync::{atomic::Ordering, Arc},
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    sync::{atomic::Ordering, Arc, Mutex},
+    time::Duration,
 };
 use tokio::{sync::watch::Receiver as WatchReceiver, task::JoinHandle};
 use tokio_tungstenite::tungstenite::protocol::{
     frame::coding::CloseCode, CloseFrame as TungsteniteCloseFrame,
 };
+use twilight_model::gateway::{
+    payload::{update_presence::UpdatePresencePayload, RequestGuildMembers, UpdatePresence},
+    CloseCode as GatewayCloseCode, OpCode,
+};
 
 /// Sending a command failed.
 #[derive(Debug)]
@@ -72,7 +80,7 @@ impl Display for CommandError {
             CommandErrorType::Sending => {
                 f.write_str("sending the message over the websocket failed")
             }
-            CommandErrorType::Serializing => f.write_str("serializing the value as json failed"),
+            CommandErrorType::Serializing => f.write_str("serializing the value failed"),
             CommandErrorType::SessionInactive => Display::fmt(&SessionInactiveError, f),
         }
     }
@@ -93,7 +101,7 @@ pub enum CommandErrorType {
     /// Sending the payload over the WebSocket failed. This is indicative of a
     /// shutdown shard.
     Sending,
-    /// Serializing the payload as JSON failed.
+    /// Serializing the payload failed.
     Serializing,
     /// Shard's session is inactive because the shard hasn't been started.
     SessionInactive,
@@ -171,6 +179,134 @@ pub enum SendErrorType {
     SessionInactive,
 }
 
+/// Shard stopped because the gateway closed the connection with a fatal
+/// close code.
+///
+/// The shard does not reconnect, as connecting again with the same
+/// configuration would fail the same way.
+#[derive(Clone, Debug)]
+pub struct ShardFatalError {
+    close_code: GatewayCloseCode,
+    kind: ShardFatalErrorType,
+}
+
+impl ShardFatalError {
+    /// Create an error from a fatal close code, returning `None` if the close
+    /// code is not known to be fatal.
+    pub(crate) const fn from_close_code(
+        close_code: GatewayCloseCode,
+        config: &Config,
+    ) -> Option<Self> {
+        let kind = match close_code {
+            GatewayCloseCode::AuthenticationFailed => ShardFatalErrorType::AuthenticationFailed,
+            GatewayCloseCode::DisallowedIntents => ShardFatalErrorType::IntentsDisallowed {
+                intents: config.intents(),
+            },
+            GatewayCloseCode::InvalidApiVersion => ShardFatalErrorType::ApiVersionInvalid,
+            GatewayCloseCode::InvalidIntents => ShardFatalErrorType::IntentsInvalid {
+                intents: config.intents(),
+            },
+            GatewayCloseCode::InvalidShard => ShardFatalErrorType::ShardInvalid {
+                shard: config.shard(),
+            },
+            GatewayCloseCode::ShardingRequired => ShardFatalErrorType::ShardingRequired,
+            _ => return None,
+        };
+
+        Some(Self { close_code, kind })
+    }
+
+    /// Close code the gateway closed the connection with.
+    pub const fn close_code(&self) -> GatewayCloseCode {
+        self.close_code
+    }
+
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &ShardFatalErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[allow(clippy::unused_self)]
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        None
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(self) -> (ShardFatalErrorType, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, None)
+    }
+}
+
+impl Display for ShardFatalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            ShardFatalErrorType::ApiVersionInvalid => {
+                f.write_str("the gateway API version is invalid")
+            }
+            ShardFatalErrorType::AuthenticationFailed => {
+                f.write_str("the authorization token is invalid")
+            }
+            ShardFatalErrorType::IntentsDisallowed { intents } => {
+                f.write_str("at least one of the intents (")?;
+                Debug::fmt(intents, f)?;
+
+                f.write_str(") is disallowed")
+            }
+            ShardFatalErrorType::IntentsInvalid { intents } => {
+                f.write_str("at least one of the intents (")?;
+                Debug::fmt(intents, f)?;
+
+                f.write_str(") is invalid")
+            }
+            ShardFatalErrorType::ShardInvalid { shard } => {
+                f.write_str("the shard ID ")?;
+                Debug::fmt(shard, f)?;
+
+                f.write_str(" is invalid")
+            }
+            ShardFatalErrorType::ShardingRequired => {
+                f.write_str("the bot is in too many guilds for the number of shards")
+            }
+        }
+    }
+}
+
+impl Error for ShardFatalError {}
+
+/// Type of [`ShardFatalError`] that occurred.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum ShardFatalErrorType {
+    /// Gateway API version used by the shard is invalid.
+    ApiVersionInvalid,
+    /// Authorization token is invalid.
+    AuthenticationFailed,
+    /// Current user isn't allowed to use at least one of the configured
+    /// intents.
+    ///
+    /// Privileged intents need to be enabled in the application's settings.
+    IntentsDisallowed {
+        /// Configured intents of the shard.
+        intents: Intents,
+    },
+    /// At least one of the configured intents is invalid.
+    IntentsInvalid {
+        /// Configured intents of the shard.
+        intents: Intents,
+    },
+    /// Shard ID or total number of shards is invalid.
+    ShardInvalid {
+        /// ID of the shard and total number of shards.
+        shard: [u64; 2],
+    },
+    /// Bot is in too many guilds for the total number of shards.
+    ShardingRequired,
+}
+
 /// Starting a shard and connecting to the gateway failed.
 #[derive(Debug)]
 pub struct ShardStartError {
@@ -243,6 +379,8 @@ pub enum ShardStartErrorType {
 /// and connection stage.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Information {
+    #[serde(default)]
+    dropped_events: u64,
     id: u64,
     latency: Latency,
     session_id: Option<Box<str>>,
@@ -251,6 +389,16 @@ pub struct Information {
 }
 
 impl Information {
+    /// Number of events dropped because the shard's bounded event buffer was
+    /// full.
+    ///
+    /// This is always 0 if no [`EventBuffer`] is configured.
+    ///
+    /// [`EventBuffer`]: super::EventBuffer
+    pub const fn dropped_events(&self) -> u64 {
+        self.dropped_events
+    }
+
     /// Return the ID of the shard.
     pub const fn id(&self) -> u64 {
         self.id
@@ -293,6 +441,9 @@ impl Information {
 /// Details to resume a gateway session.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ResumeSession {
+    /// Gateway URL to resume the session with, if the gateway sent one.
+    #[serde(default)]
+    pub resume_gateway_url: Option<String>,
     /// ID of the session being resumed.
     pub session_id: String,
     /// Last received event sequence number.
@@ -303,6 +454,10 @@ pub struct ResumeSession {
 struct ShardRef {
     config: Arc<Config>,
     emitter: Emitter,
+    /// Error the shard stopped with, set by the processor.
+    fatal: Arc<OnceCell<ShardFatalError>>,
+    /// Presence to set when identifying, shared with the processor.
+    presence: Arc<Mutex<Option<UpdatePresencePayload>>>,
     processor_handle: OnceCell<JoinHandle<()>>,
     session: OnceCell<WatchReceiver<Arc<Session>>>,
 }
@@ -405,11 +560,14 @@ impl Shard {
         let config = Arc::new(config);
         let event_types = config.event_types();
 
-        let (emitter, rx) = Emitter::new(event_types);
+        let (emitter, rx) = Emitter::new(event_types, config.event_buffer());
+        let presence = Arc::new(Mutex::new(config.presence().cloned()));
 
         let this = Self(Arc::new(ShardRef {
             config,
             emitter,
+            fatal: Arc::new(OnceCell::new()),
+            presence,
             processor_handle: OnceCell::new(),
             session: OnceCell::new(),
         }));
@@ -461,24 +619,25 @@ impl Shard {
 
         let config = Arc::clone(&self.0.config);
         let emitter = self.0.emitter.clone();
-        let (processor, wrx) =
-            ShardProcessor::new(config, url, emitter)
-                .await
-                .map_err(|source| {
-                    let (kind, source) = source.into_parts();
-
-                    let new_kind = match kind {
-                        ConnectingErrorType::Establishing => ShardStartErrorType::Establishing,
-                        ConnectingErrorType::ParsingUrl { url } => {
-                            ShardStartErrorType::ParsingGatewayUrl { url }
-                        }
-                    };
-
-                    ShardStartError {
-                        source,
-                        kind: new_kind,
+        let fatal = Arc::clone(&self.0.fatal);
+        let presence = Arc::clone(&self.0.presence);
+        let (processor, wrx) = ShardProcessor::new(config, url, emitter, fatal, presence)
+            .await
+            .map_err(|source| {
+                let (kind, source) = source.into_parts();
+
+                let new_kind = match kind {
+                    ConnectingErrorType::Establishing => ShardStartErrorType::Establishing,
+                    ConnectingErrorType::ParsingUrl { url } => {
+                        ShardStartErrorType::ParsingGatewayUrl { url }
                     }
-                })?;
+                };
+
+                ShardStartError {
+                    source,
+                    kind: new_kind,
+                }
+            })?;
 
         let handle = tokio::spawn(async move {
             processor.run().await;
@@ -493,6 +652,20 @@ impl Shard {
         Ok(())
     }
 
+    /// Return the error the shard stopped with, if the gateway closed the
+    /// connection with a fatal close code.
+    ///
+    /// The error is set before the shard's event stream receives a final
+    /// [`Event::ShardDisconnected`] event whose [`kind`] is
+    /// [`CloseCodeKind::Fatal`], so it is available when handling the event.
+    ///
+    /// [`CloseCodeKind::Fatal`]: twilight_model::gateway::CloseCodeKind::Fatal
+    /// [`Event::ShardDisconnected`]: crate::Event::ShardDisconnected
+    /// [`kind`]: twilight_model::gateway::event::shard::Disconnected::kind
+    pub fn fatal_error(&self) -> Option<&ShardFatalError> {
+        self.0.fatal.get()
+    }
+
     /// Retrieve information about the running of the shard, such as the current
     /// connection stage.
     ///
@@ -503,6 +676,7 @@ impl Shard {
         let session = self.session()?;
 
         Ok(Information {
+            dropped_events: self.0.emitter.dropped(),
             id: self.config().shard()[0],
             latency: session.heartbeats.latency(),
             session_id: session.id(),
@@ -525,16 +699,171 @@ impl Shard {
     /// Returns a [`CommandErrorType::SessionInactive`] error type if the shard
     /// has not been started.
     pub async fn command(&self, value: &impl serde::Serialize) -> Result<(), CommandError> {
-        let json = json::to_vec(value).map_err(|source| CommandError {
+        let bytes = encoding::to_vec(value).map_err(|source| CommandError {
             source: Some(Box::new(source)),
             kind: CommandErrorType::Serializing,
         })?;
 
-        self.send(Message::Binary(json))
+        self.send(Message::Binary(bytes))
             .await
             .map_err(CommandError::from_send)
     }
 
+    /// Return the presence the shard sets when identifying a new session.
+    ///
+    /// This is the presence configured via [`ShardBuilder::presence`] until
+    /// it is replaced by [`update_presence`].
+    ///
+    /// [`update_presence`]: Self::update_presence
+    pub fn presence(&self) -> Option<UpdatePresencePayload> {
+        self.0.presence.lock().expect("presence poisoned").clone()
+    }
+
+    /// Update the presence of the shard.
+    ///
+    /// The presence replaces the presence the shard sets when identifying, so
+    /// it is kept when the shard reconnects with a new session. It is replaced
+    /// even if sending the update fails, such as when the shard has not been
+    /// started yet.
+    ///
+    /// # Examples
+    ///
+    /// Set the bot user's status to "Do Not Disturb":
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::env;
+    /// use twilight_gateway::{Intents, Shard};
+    /// use twilight_model::gateway::{
+    ///     payload::update_presence::UpdatePresencePayload,
+    ///     presence::{ActivityType, MinimalActivity, Status},
+    /// };
+    ///
+    /// let token = env::var("DISCORD_TOKEN")?;
+    /// let (shard, _) = Shard::new(token, Intents::empty());
+    /// shard.start().await?;
+    ///
+    /// let presence = UpdatePresencePayload::new(
+    ///     vec![MinimalActivity {
+    ///         kind: ActivityType::Playing,
+    ///         name: "in maintenance".to_owned(),
+    ///         url: None,
+    ///     }
+    ///     .into()],
+    ///     false,
+    ///     None,
+    ///     Status::DoNotDisturb,
+    /// )?;
+    ///
+    /// shard.update_presence(presence).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CommandErrorType::Sending`] error type if the update could
+    /// not be sent over the websocket. This indicates the shard is currently
+    /// restarting.
+    ///
+    /// Returns a [`CommandErrorType::SessionInactive`] error type if the shard
+    /// has not been started.
+    pub async fn update_presence(
+        &self,
+        presence: UpdatePresencePayload,
+    ) -> Result<(), CommandError> {
+        self.0
+            .presence
+            .lock()
+            .expect("presence poisoned")
+            .replace(presence.clone());
+
+        self.command(&UpdatePresence {
+            d: presence,
+            op: OpCode::PresenceUpdate,
+        })
+        .await
+    }
+
+    /// Request the members of a guild and collect all of the member chunks
+    /// sent in response.
+    ///
+    /// The request is sent with a generated nonce, replacing any nonce it
+    /// already has, which is used to match the member chunks to the request.
+    /// Matching member chunks are collected even if the shard isn't
+    /// configured to emit [`EventTypeFlags::MEMBER_CHUNK`] events. If it is,
+    /// they are still emitted to the shard's event stream.
+    ///
+    /// # Examples
+    ///
+    /// Collect all members of a guild:
+    ///
+    /// ```no_run
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::{env, time::Duration};
+    /// use twilight_gateway::{Intents, Shard};
+    /// use twilight_model::{gateway::payload::RequestGuildMembers, id::GuildId};
+    ///
+    /// let token = env::var("DISCORD_TOKEN")?;
+    /// let (shard, _) = Shard::new(token, Intents::GUILD_MEMBERS);
+    /// shard.start().await?;
+    ///
+    /// let request = RequestGuildMembers::builder(GuildId(1)).query("", None);
+    /// let members = shard
+    ///     .collect_guild_members(request, Duration::from_secs(30))
+    ///     .await?;
+    ///
+    /// println!("guild has {} members", members.members.len());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// When using a [`Cluster`], use [`Cluster::collect_guild_members`] to
+    /// send the request through the shard of the guild.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CollectGuildMembersErrorType::Sending`] error type if the
+    /// request could not be sent.
+    ///
+    /// Returns a [`CollectGuildMembersErrorType::TimedOut`] error type if not
+    /// every member chunk was received within the provided timeout.
+    ///
+    /// [`Cluster`]: crate::Cluster
+    /// [`Cluster::collect_guild_members`]: crate::Cluster::collect_guild_members
+    /// [`EventTypeFlags::MEMBER_CHUNK`]: crate::EventTypeFlags::MEMBER_CHUNK
+    pub async fn collect_guild_members(
+        &self,
+        mut request: RequestGuildMembers,
+        timeout: Duration,
+    ) -> Result<GuildMembers, CollectGuildMembersError> {
+        let (registration, mut rx) = self.0.emitter.member_chunks().register();
+        request.d.nonce = Some(registration.nonce().to_owned());
+
+        self.command(&request)
+            .await
+            .map_err(|source| CollectGuildMembersError {
+                kind: CollectGuildMembersErrorType::Sending,
+                source: Some(Box::new(source)),
+            })?;
+
+        let mut collector = MemberChunkCollector::new(request.d.guild_id);
+
+        let collect = async {
+            while let Some(chunk) = rx.recv().await {
+                if collector.push(chunk) {
+                    break;
+                }
+            }
+        };
+
+        let elapsed = tokio::time::timeout(timeout, collect).await.is_err();
+
+        if elapsed || !collector.is_complete() {
+            return Err(collector.timed_out());
+        }
+
+        Ok(collector.into_members())
+    }
+
     /// Send a raw websocket message.
     ///
     /// # Examples
@@ -686,6 +1015,7 @@ impl Shard {
         session.stop_heartbeater();
 
         let data = session_id.map(|id| ResumeSession {
+            resume_gateway_url: session.resume_url().map(str::into_string),
             session_id: id.into_string(),
             sequence,
         });
@@ -709,10 +1039,13 @@ impl Shard {
 mod tests {
     use super::{
         CommandError, CommandErrorType, Information, ResumeSession, SendError, SendErrorType,
-        SessionInactiveError, Shard, ShardStartError, ShardStartErrorType,
+        SessionInactiveError, Shard, ShardFatalError, ShardFatalErrorType, ShardStartError,
+        ShardStartErrorType,
     };
+    use crate::{shard::ShardBuilder, Intents};
     use static_assertions::{assert_fields, assert_impl_all};
     use std::{error::Error, fmt::Debug};
+    use twilight_model::gateway::CloseCode as GatewayCloseCode;
 
     assert_impl_all!(CommandErrorType: Debug, Send, Sync);
     assert_impl_all!(CommandError: Error, Send, Sync);
@@ -721,8 +1054,65 @@ mod tests {
     assert_impl_all!(SendErrorType: Debug, Send, Sync);
     assert_impl_all!(SendError: Error, Send, Sync);
     assert_impl_all!(SessionInactiveError: Error, Send, Sync);
+    assert_fields!(ShardFatalErrorType::IntentsDisallowed: intents);
+    assert_fields!(ShardFatalErrorType::IntentsInvalid: intents);
+    assert_fields!(ShardFatalErrorType::ShardInvalid: shard);
+    assert_impl_all!(ShardFatalErrorType: Clone, Debug, Send, Sync);
+    assert_impl_all!(ShardFatalError: Clone, Error, Send, Sync);
     assert_fields!(ShardStartErrorType::ParsingGatewayUrl: url);
     assert_impl_all!(ShardStartErrorType: Debug, Send, Sync);
     assert_impl_all!(ShardStartError: Error, Send, Sync);
     assert_impl_all!(Shard: Clone, Debug, Send, Sync);
+
+    #[tokio::test]
+    async fn test_fatal_error_from_close_code() {
+        let config = ShardBuilder::new("token", Intents::GUILD_MEMBERS).0;
+
+        let error =
+            ShardFatalError::from_close_code(GatewayCloseCode::DisallowedIntents, &config).unwrap();
+        assert_eq!(GatewayCloseCode::DisallowedIntents, error.close_code());
+        assert!(matches!(
+            error.kind(),
+            ShardFatalErrorType::IntentsDisallowed { intents }
+            if *intents == Intents::GUILD_MEMBERS
+        ));
+
+        let error =
+            ShardFatalError::from_close_code(GatewayCloseCode::InvalidShard, &config).unwrap();
+        assert!(matches!(
+            error.kind(),
+            ShardFatalErrorType::ShardInvalid { shard: [0, 1] }
+        ));
+
+        assert!(
+            ShardFatalError::from_close_code(GatewayCloseCode::UnknownError, &config).is_none()
+        );
+        assert!(
+            ShardFatalError::from_close_code(GatewayCloseCode::SessionTimedOut, &config).is_none()
+        );
+    }
+
+    /// Test that information serialized before the number of dropped events
+    /// was added can still be deserialized.
+    #[test]
+    fn test_information_dropped_events_default() {
+        let json = r#"{
+            "id": 2,
+            "latency": {
+                "average": null,
+                "heartbeats": 0,
+                "interval": null,
+                "jitter": null,
+                "recent": []
+            },
+            "session_id": "session",
+            "seq": 10,
+            "stage": "Connected"
+        }"#;
+
+        let info = serde_json::from_str::<Information>(json).unwrap();
+        assert_eq!(0, info.dropped_events());
+        assert_eq!(2, info.id());
+        assert_eq!(10, info.seq());
+    }
 }
@@ -0,0 +1,351 @@
+//! Collecting the member chunks sent in response to a request for the
+//! members of a guild.
+//!
+//! Refer to [`Shard::collect_guild_members`] for more information.
+//!
+//! [`Shard::collect_guild_members`]: super::Shard::collect_guild_members
+
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use twilight_model::{
+    gateway::{payload::MemberChunk, presence::Presence},
+    guild::Member,
+    id::{GuildId, UserId},
+};
+
+/// Collecting the members of a guild failed.
+#[derive(Debug)]
+pub struct CollectGuildMembersError {
+    pub(super) kind: CollectGuildMembersErrorType,
+    pub(super) source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl CollectGuildMembersError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &CollectGuildMembersErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(
+        self,
+    ) -> (
+        CollectGuildMembersErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for CollectGuildMembersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            CollectGuildMembersErrorType::Sending => {
+                f.write_str("sending the request for guild members failed")
+            }
+            CollectGuildMembersErrorType::TimedOut { received, total } => {
+                f.write_str("timed out after receiving ")?;
+                Display::fmt(received, f)?;
+                f.write_str(" of ")?;
+
+                if let Some(total) = total {
+                    Display::fmt(total, f)?;
+                } else {
+                    f.write_str("an unknown number of")?;
+                }
+
+                f.write_str(" member chunks")
+            }
+        }
+    }
+}
+
+impl Error for CollectGuildMembersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`CollectGuildMembersError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CollectGuildMembersErrorType {
+    /// Sending the request for guild members failed.
+    ///
+    /// The source error is a [`CommandError`].
+    ///
+    /// [`CommandError`]: super::CommandError
+    Sending,
+    /// Not every member chunk was received before the timeout elapsed.
+    TimedOut {
+        /// Number of member chunks received.
+        received: u32,
+        /// Total number of member chunks, if any chunk was received.
+        total: Option<u32>,
+    },
+}
+
+/// Members of a guild collected from all member chunks of a request.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuildMembers {
+    /// ID of the guild.
+    pub guild_id: GuildId,
+    /// Members of the guild matching the request.
+    pub members: Vec<Member>,
+    /// IDs of requested users that were not found in the guild.
+    pub not_found: Vec<UserId>,
+    /// Presences of the members, if presences were requested.
+    pub presences: Vec<Presence>,
+}
+
+/// Member chunks collected so far for a request.
+#[derive(Debug)]
+pub(super) struct MemberChunkCollector {
+    members: GuildMembers,
+    received: HashSet<u32>,
+    total: Option<u32>,
+}
+
+impl MemberChunkCollector {
+    pub fn new(guild_id: GuildId) -> Self {
+        Self {
+            members: GuildMembers {
+                guild_id,
+                members: Vec::new(),
+                not_found: Vec::new(),
+                presences: Vec::new(),
+            },
+            received: HashSet::new(),
+            total: None,
+        }
+    }
+
+    /// Add a chunk, returning whether every chunk has now been received.
+    ///
+    /// Chunks with an index that has already been received are ignored.
+    pub fn push(&mut self, chunk: MemberChunk) -> bool {
+        self.total = Some(chunk.chunk_count);
+
+        if self.received.insert(chunk.chunk_index) {
+            self.members.members.extend(chunk.members);
+            self.members.not_found.extend(chunk.not_found);
+            self.members.presences.extend(chunk.presences);
+        }
+
+        self.is_complete()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.total
+            .map_or(false, |total| self.received.len() >= total as usize)
+    }
+
+    pub fn into_members(self) -> GuildMembers {
+        self.members
+    }
+
+    pub fn timed_out(&self) -> CollectGuildMembersError {
+        #[allow(clippy::cast_possible_truncation)]
+        let received = self.received.len() as u32;
+
+        CollectGuildMembersError {
+            kind: CollectGuildMembersErrorType::TimedOut {
+                received,
+                total: self.total,
+            },
+            source: None,
+        }
+    }
+}
+
+/// Maximum length of the nonce of a guild member request, in bytes.
+///
+/// Discord ignores longer nonces.
+pub(super) const NONCE_LENGTH: usize = 32;
+
+/// Registry of in-flight guild member requests, keyed by their nonces.
+///
+/// The shard's emitter forwards member chunks with a registered nonce to the
+/// request, regardless of whether the shard's listener wants member chunks.
+/// Each shard has its own registry, so nonces only need to be unique per
+/// shard.
+#[derive(Debug, Default)]
+pub(super) struct MemberChunkRegistry {
+    next_id: AtomicU64,
+    senders: Mutex<HashMap<String, UnboundedSender<MemberChunk>>>,
+}
+
+impl MemberChunkRegistry {
+    /// Register a new request, returning its registration and a receiver of
+    /// its member chunks.
+    ///
+    /// The request is unregistered once the registration is dropped.
+    pub fn register(self: &Arc<Self>) -> (MemberChunkRegistration, UnboundedReceiver<MemberChunk>) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let nonce = format!("twilight-{:x}", id);
+        debug_assert!(nonce.len() <= NONCE_LENGTH);
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        self.senders
+            .lock()
+            .expect("member chunk senders poisoned")
+            .insert(nonce.clone(), tx);
+
+        let registration = MemberChunkRegistration {
+            nonce,
+            registry: Arc::clone(self),
+        };
+
+        (registration, rx)
+    }
+
+    /// Whether there are no in-flight requests.
+    pub fn is_empty(&self) -> bool {
+        self.senders
+            .lock()
+            .expect("member chunk senders poisoned")
+            .is_empty()
+    }
+
+    /// Forward a member chunk to the request with its nonce, if there is one.
+    pub fn forward(&self, chunk: &MemberChunk) {
+        let nonce = match chunk.nonce.as_deref() {
+            Some(nonce) => nonce,
+            None => return,
+        };
+
+        if let Some(tx) = self
+            .senders
+            .lock()
+            .expect("member chunk senders poisoned")
+            .get(nonce)
+        {
+            let _res = tx.send(chunk.clone());
+        }
+    }
+}
+
+/// Registration of an in-flight guild member request.
+#[derive(Debug)]
+pub(super) struct MemberChunkRegistration {
+    nonce: String,
+    registry: Arc<MemberChunkRegistry>,
+}
+
+impl MemberChunkRegistration {
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+}
+
+impl Drop for MemberChunkRegistration {
+    fn drop(&mut self) {
+        if let Ok(mut senders) = self.registry.senders.lock() {
+            senders.remove(&self.nonce);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CollectGuildMembersError, CollectGuildMembersErrorType, GuildMembers, MemberChunkCollector,
+        MemberChunkRegistry, NONCE_LENGTH,
+    };
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::Debug,
+        sync::{atomic::Ordering, Arc},
+    };
+    use twilight_model::{
+        gateway::payload::MemberChunk,
+        id::{GuildId, UserId},
+    };
+
+    assert_impl_all!(CollectGuildMembersErrorType: Debug, Send, Sync);
+    assert_fields!(CollectGuildMembersErrorType::TimedOut: received, total);
+    assert_impl_all!(CollectGuildMembersError: Error, Send, Sync);
+    assert_impl_all!(GuildMembers: Clone, Debug, Eq, PartialEq, Send, Sync);
+
+    fn chunk(index: u32, count: u32, nonce: Option<&str>) -> MemberChunk {
+        MemberChunk {
+            chunk_count: count,
+            chunk_index: index,
+            guild_id: GuildId(1),
+            members: Vec::new(),
+            nonce: nonce.map(ToOwned::to_owned),
+            not_found: vec![UserId(u64::from(index))],
+            presences: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_collector() {
+        let mut collector = MemberChunkCollector::new(GuildId(1));
+        assert!(!collector.is_complete());
+
+        assert!(!collector.push(chunk(1, 3, None)));
+        assert!(!collector.push(chunk(1, 3, None)));
+        assert!(!collector.push(chunk(0, 3, None)));
+        assert!(matches!(
+            collector.timed_out().kind(),
+            CollectGuildMembersErrorType::TimedOut {
+                received: 2,
+                total: Some(3),
+            }
+        ));
+        assert!(collector.push(chunk(2, 3, None)));
+
+        let members = collector.into_members();
+        assert_eq!(GuildId(1), members.guild_id);
+        assert_eq!(vec![UserId(1), UserId(0), UserId(2)], members.not_found);
+    }
+
+    #[tokio::test]
+    async fn test_registry() {
+        let registry = Arc::new(MemberChunkRegistry::default());
+        assert!(registry.is_empty());
+
+        let (registration, mut rx) = registry.register();
+        assert_eq!("twilight-0", registration.nonce());
+        assert!(!registry.is_empty());
+
+        registry.forward(&chunk(0, 1, None));
+        registry.forward(&chunk(0, 1, Some("other")));
+        registry.forward(&chunk(0, 1, Some(registration.nonce())));
+        assert_eq!(Some(0), rx.recv().await.map(|chunk| chunk.chunk_index));
+        assert!(rx.try_recv().is_err());
+
+        drop(registration);
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_registry_nonce_length() {
+        let registry = Arc::new(MemberChunkRegistry::default());
+        registry.next_id.store(u64::MAX, Ordering::Relaxed);
+
+        let (registration, _rx) = registry.register();
+        assert_eq!("twilight-ffffffffffffffff", registration.nonce());
+        assert!(registration.nonce().len() <= NONCE_LENGTH);
+    }
+}
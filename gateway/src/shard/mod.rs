@@ -19,27 +19,39 @@
 //! [information about itself]: Shard::info
 //! [new messages]: ::twilight_model::gateway::event::Event::MessageCreate
 
+#[cfg(feature = "etf")]
+pub mod etf;
 pub mod raw_message;
+pub mod replay;
 pub mod stage;
 
+pub(crate) mod buffer;
+
 mod builder;
 mod config;
 mod emitter;
+mod encoding;
 mod event;
 mod r#impl;
 mod json;
+mod members;
 mod processor;
+mod proxy;
 
 pub use self::{
+    buffer::{BackpressurePolicy, EventBuffer},
     builder::{
         LargeThresholdError, LargeThresholdErrorType, ShardBuilder, ShardIdError, ShardIdErrorType,
     },
     config::Config,
     event::Events,
-    processor::heartbeat::Latency,
+    members::{CollectGuildMembersError, CollectGuildMembersErrorType, GuildMembers},
+    processor::heartbeat::{HeartbeatPolicy, Latency},
+    proxy::{Proxy, ProxyError, ProxyErrorType, ProxyKind},
     r#impl::{
         CommandError, CommandErrorType, Information, ResumeSession, SendError, SendErrorType,
-        SessionInactiveError, Shard, ShardStartError, ShardStartErrorType,
+        SessionInactiveError, Shard, ShardFatalError, ShardFatalErrorType, ShardStartError,
+        ShardStartErrorType,
     },
     stage::Stage,
 };
@@ -100,7 +100,12 @@ impl Inflater {
             self.decompress.total_in() as f64 / self.decompress.total_out() as f64;
         let saved_percentage_readable = saved_percentage * 100.0;
 
-        let saved_kib = (self.decompress.total_out() - self.decompress.total_in()) / 1_024;
+        // Small payloads may be larger compressed than decompressed.
+        let saved_kib = self
+            .decompress
+            .total_out()
+            .saturating_sub(self.decompress.total_in())
+            / 1_024;
 
         tracing::trace!(
             saved_kib = saved_kib,
@@ -142,16 +147,19 @@ impl Inflater {
     #[allow(clippy::cast_precision_loss)]
     fn inflater_metrics(&self) {
         metrics::gauge!(
-            format!("Inflater-Capacity-{}", self.shard[0]),
-            self.buffer.capacity() as f64
+            crate::metrics::INFLATER_CAPACITY,
+            self.buffer.capacity() as f64,
+            "shard" => self.shard[0].to_string(),
         );
         metrics::gauge!(
-            format!("Inflater-In-{}", self.shard[0]),
-            self.decompress.total_in() as f64
+            crate::metrics::INFLATER_IN,
+            self.decompress.total_in() as f64,
+            "shard" => self.shard[0].to_string(),
         );
         metrics::gauge!(
-            format!("Inflater-Out-{}", self.shard[0]),
-            self.decompress.total_out() as f64
+            crate::metrics::INFLATER_OUT,
+            self.decompress.total_out() as f64,
+            "shard" => self.shard[0].to_string(),
         );
     }
 
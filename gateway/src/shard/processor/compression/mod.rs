@@ -41,6 +41,7 @@ impl Compression {
     ///
     /// When compression is disabled this will mutably reference the standard
     /// buffer.
+    #[cfg_attr(feature = "etf", allow(dead_code))]
     pub fn buffer_slice_mut(&mut self) -> &mut [u8] {
         #[cfg(feature = "compression")]
         {
@@ -74,6 +75,7 @@ impl Compression {
     ///
     /// Ensuring that the internal buffer slice is UTF-8 valid is left to the
     /// caller to determine.
+    #[cfg_attr(feature = "etf", allow(dead_code))]
     pub unsafe fn buffer_str_mut(&mut self) -> &mut str {
         // SAFETY: ensuring safety is left to the caller.
         str::from_utf8_unchecked_mut(self.buffer_slice_mut())
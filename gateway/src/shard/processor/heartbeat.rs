@@ -1,11 +1,12 @@
 use super::{
-    super::json,
+    super::encoding,
     session::{SessionSendError, SessionSendErrorType},
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::VecDeque,
+    collections::{hash_map::RandomState, VecDeque},
     convert::TryInto,
+    hash::{BuildHasher, Hasher},
     sync::{
         atomic::{AtomicU32, AtomicU64, Ordering},
         Arc, Mutex,
@@ -16,6 +17,60 @@ use tokio::sync::mpsc::UnboundedSender;
 use tokio_tungstenite::tungstenite::Message as TungsteniteMessage;
 use twilight_model::gateway::payload::Heartbeat;
 
+/// Policy of how a shard sends heartbeats and detects zombie connections.
+///
+/// A connection is a zombie when the gateway stops acknowledging heartbeats
+/// without the connection being closed. Once the number of consecutive
+/// heartbeats that were not acknowledged reaches the threshold, the shard
+/// emits a [`ShardZombied`] event and resumes the session on a new
+/// connection.
+///
+/// Refer to [`ShardBuilder::heartbeat_policy`] for more information.
+///
+/// [`ShardBuilder::heartbeat_policy`]: crate::shard::ShardBuilder::heartbeat_policy
+/// [`ShardZombied`]: twilight_model::gateway::event::Event::ShardZombied
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct HeartbeatPolicy {
+    jitter: bool,
+    missed_ack_threshold: u32,
+}
+
+impl HeartbeatPolicy {
+    /// Create a new heartbeat policy.
+    ///
+    /// If `jitter` is enabled the first heartbeat of a connection is sent
+    /// after a random fraction of the heartbeat interval, as recommended by
+    /// Discord, instead of after a full interval.
+    ///
+    /// A threshold of 0 is treated as a threshold of 1.
+    pub fn new(missed_ack_threshold: u32, jitter: bool) -> Self {
+        Self {
+            jitter,
+            missed_ack_threshold: missed_ack_threshold.max(1),
+        }
+    }
+
+    /// Whether the first heartbeat is sent after a random fraction of the
+    /// heartbeat interval.
+    pub const fn jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// Number of consecutive heartbeats that may go unacknowledged before the
+    /// connection is considered a zombie.
+    pub const fn missed_ack_threshold(&self) -> u32 {
+        self.missed_ack_threshold
+    }
+}
+
+impl Default for HeartbeatPolicy {
+    /// Create a policy with jitter and a threshold of 2 missed
+    /// acknowledgements.
+    fn default() -> Self {
+        Self::new(2, true)
+    }
+}
+
 /// Information about the latency of a [`Shard`]'s websocket connection.
 ///
 /// This is obtained through [`Shard::info`].
@@ -26,6 +81,10 @@ use twilight_model::gateway::payload::Heartbeat;
 pub struct Latency {
     average: Option<Duration>,
     heartbeats: u32,
+    interval: Option<Duration>,
+    jitter: Option<Duration>,
+    #[serde(default)]
+    missed_acks: u32,
     recent: VecDeque<Duration>,
     #[serde(skip)]
     received: Option<Instant>,
@@ -52,6 +111,28 @@ impl Latency {
         self.heartbeats
     }
 
+    /// Interval between heartbeats requested by the gateway.
+    ///
+    /// If this is None, the shard has not started sending heartbeats yet.
+    pub const fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// Delay before the first heartbeat of the connection was sent.
+    ///
+    /// This is a random fraction of the [`interval`] if jitter is enabled in
+    /// the shard's [`HeartbeatPolicy`], otherwise the full interval.
+    ///
+    /// [`interval`]: Self::interval
+    pub const fn jitter(&self) -> Option<Duration> {
+        self.jitter
+    }
+
+    /// Number of consecutive heartbeats that have not been acknowledged.
+    pub const fn missed_acks(&self) -> u32 {
+        self.missed_acks
+    }
+
     /// The 5 most recent latency times.
     ///
     /// Index 0 is the oldest, 4 is the most recent.
@@ -72,6 +153,9 @@ impl Latency {
 
 #[derive(Debug)]
 pub struct Heartbeats {
+    interval: Mutex<Option<Duration>>,
+    jitter: Mutex<Option<Duration>>,
+    missed_acks: AtomicU32,
     received: Mutex<Option<Instant>>,
     recent: Mutex<VecDeque<u64>>,
     sent: Mutex<Option<Instant>>,
@@ -94,6 +178,9 @@ impl Heartbeats {
         Latency {
             average: self.total_time().checked_div(iterations),
             heartbeats: iterations,
+            interval: *self.interval.lock().expect("interval poisoned"),
+            jitter: *self.jitter.lock().expect("jitter poisoned"),
+            missed_acks: self.missed_acks.load(Ordering::Relaxed),
             recent,
             received: self.received(),
             sent: self.sent(),
@@ -104,30 +191,40 @@ impl Heartbeats {
         self.received().is_some()
     }
 
-    pub fn receive(&self) {
+    /// Record that the previous heartbeat was not acknowledged, returning the
+    /// number of consecutive heartbeats that were not acknowledged.
+    pub fn miss(&self) -> u32 {
+        self.missed_acks.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Record that the previous heartbeat was acknowledged, returning its
+    /// latency if it was sent.
+    pub fn receive(&self) -> Option<Duration> {
         self.set_received(Instant::now());
+        self.missed_acks.store(0, Ordering::Relaxed);
 
         self.total_iterations.fetch_add(1, Ordering::SeqCst);
 
-        if let Some(dur) = self.sent().map(|s| s.elapsed()) {
-            let millis = if let Ok(millis) = dur.as_millis().try_into() {
-                millis
-            } else {
-                tracing::error!("duration millis is more than u64: {:?}", dur);
+        let dur = self.sent().map(|s| s.elapsed())?;
+        let millis = if let Ok(millis) = dur.as_millis().try_into() {
+            millis
+        } else {
+            tracing::error!("duration millis is more than u64: {:?}", dur);
 
-                return;
-            };
+            return None;
+        };
 
-            self.total_time.fetch_add(millis, Ordering::SeqCst);
+        self.total_time.fetch_add(millis, Ordering::SeqCst);
 
-            let mut recent = self.recent.lock().expect("recent poisoned");
+        let mut recent = self.recent.lock().expect("recent poisoned");
 
-            if recent.len() == 5 {
-                recent.pop_front();
-            }
-
-            recent.push_back(millis);
+        if recent.len() == 5 {
+            recent.pop_front();
         }
+
+        recent.push_back(millis);
+
+        Some(dur)
     }
 
     pub fn send(&self) {
@@ -138,6 +235,14 @@ impl Heartbeats {
             .replace(Instant::now());
     }
 
+    fn set_timing(&self, interval: Duration, jitter: Duration) {
+        self.interval
+            .lock()
+            .expect("interval poisoned")
+            .replace(interval);
+        self.jitter.lock().expect("jitter poisoned").replace(jitter);
+    }
+
     fn received(&self) -> Option<Instant> {
         *self.received.lock().expect("received poisoned")
     }
@@ -165,6 +270,9 @@ impl Heartbeats {
 impl Default for Heartbeats {
     fn default() -> Self {
         Self {
+            interval: Mutex::new(None),
+            jitter: Mutex::new(None),
+            missed_acks: AtomicU32::new(0),
             received: Mutex::new(None),
             recent: Mutex::new(VecDeque::with_capacity(5)),
             sent: Mutex::new(None),
@@ -177,22 +285,28 @@ impl Default for Heartbeats {
 pub struct Heartbeater {
     heartbeats: Arc<Heartbeats>,
     interval: u64,
+    policy: HeartbeatPolicy,
     seq: Arc<AtomicU64>,
     tx: UnboundedSender<TungsteniteMessage>,
+    zombie: UnboundedSender<u32>,
 }
 
 impl Heartbeater {
     pub fn new(
         heartbeats: Arc<Heartbeats>,
         interval: u64,
+        policy: HeartbeatPolicy,
         seq: Arc<AtomicU64>,
         tx: UnboundedSender<TungsteniteMessage>,
+        zombie: UnboundedSender<u32>,
     ) -> Self {
         Self {
             heartbeats,
             interval,
+            policy,
             seq,
             tx,
+            zombie,
         }
     }
 
@@ -209,29 +323,33 @@ impl Heartbeater {
     async fn try_run(self) -> Result<(), SessionSendError> {
         let duration = Duration::from_millis(self.interval);
 
-        let mut last = true;
+        let jitter = if self.policy.jitter() {
+            duration.mul_f64(random_fraction())
+        } else {
+            duration
+        };
 
-        loop {
-            tokio::time::sleep(duration).await;
+        self.heartbeats.set_timing(duration, jitter);
+        tokio::time::sleep(jitter).await;
 
-            // Check if a heartbeat acknowledgement was received.
-            //
-            // If so, then check if one was received last time.
-            //
-            // - if so, then mark that we didn't get one this time
-            // - if not, then end the heartbeater because something is off
-            // (connecting closed?)
-            if self.heartbeats.last_acked() {
-                last = true;
-            } else if last {
-                last = false;
-            } else {
-                return Ok(());
+        loop {
+            // Check whether the previous heartbeat was acknowledged. If too
+            // many heartbeats in a row were not, then the connection is a
+            // zombie and the processor needs to replace it.
+            if self.heartbeats.sent().is_some() && !self.heartbeats.last_acked() {
+                let missed_acks = self.heartbeats.miss();
+
+                if missed_acks >= self.policy.missed_ack_threshold() {
+                    tracing::warn!(missed_acks, "connection is a zombie");
+                    let _res = self.zombie.send(missed_acks);
+
+                    return Ok(());
+                }
             }
 
             let seq = self.seq.load(Ordering::Acquire);
             let heartbeat = Heartbeat::new(seq);
-            let bytes = json::to_vec(&heartbeat).map_err(|source| SessionSendError {
+            let bytes = encoding::to_vec(&heartbeat).map_err(|source| SessionSendError {
                 kind: SessionSendErrorType::Serializing,
                 source: Some(Box::new(source)),
             })?;
@@ -245,15 +363,66 @@ impl Heartbeater {
                 })?;
             tracing::debug!(seq, "sent heartbeat");
             self.heartbeats.send();
+
+            tokio::time::sleep(duration).await;
         }
     }
 }
 
+/// Random number in the range of `[0, 1)`.
+///
+/// Uses the randomly seeded keys of the standard library's hasher so that no
+/// dependency on a random number generator is needed.
+#[allow(clippy::cast_precision_loss)]
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+
+    // Use the upper 53 bits, the precision of an `f64`.
+    (hasher.finish() >> 11) as f64 / (1_u64 << 53) as f64
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Latency;
+    use super::{random_fraction, HeartbeatPolicy, Heartbeats, Latency};
     use static_assertions::assert_impl_all;
-    use std::fmt::Debug;
+    use std::{fmt::Debug, hash::Hash};
 
+    assert_impl_all!(HeartbeatPolicy: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
     assert_impl_all!(Latency: Clone, Debug, Send, Sync);
+
+    #[test]
+    fn test_policy_threshold() {
+        assert_eq!(1, HeartbeatPolicy::new(0, false).missed_ack_threshold());
+        assert_eq!(2, HeartbeatPolicy::default().missed_ack_threshold());
+        assert!(HeartbeatPolicy::default().jitter());
+    }
+
+    #[test]
+    fn test_missed_acks() {
+        let heartbeats = Heartbeats::default();
+        heartbeats.send();
+        assert_eq!(1, heartbeats.miss());
+        assert_eq!(2, heartbeats.miss());
+        assert_eq!(2, heartbeats.latency().missed_acks());
+
+        assert!(heartbeats.receive().is_some());
+        assert_eq!(0, heartbeats.latency().missed_acks());
+        assert_eq!(1, heartbeats.latency().heartbeats());
+    }
+
+    #[test]
+    fn test_receive_unsent() {
+        let heartbeats = Heartbeats::default();
+        assert!(heartbeats.receive().is_none());
+        assert!(heartbeats.latency().recent().is_empty());
+    }
+
+    #[test]
+    fn test_random_fraction() {
+        for _ in 0..100 {
+            let fraction = random_fraction();
+            assert!((0.0..1.0).contains(&fraction));
+        }
+    }
 }
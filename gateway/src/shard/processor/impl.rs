@@ -1,48 +1,63 @@
+#[cfg(feature = "etf")]
+use super::super::etf;
 use super::{
     super::{
         config::Config,
-        emitter::{EmitJsonErrorType, Emitter},
+        emitter::{EmitJsonError, EmitJsonErrorType, Emitter},
+        encoding,
         json::{self, GatewayEventParsingError, GatewayEventParsingErrorType},
         stage::Stage,
-        ShardStream,
+        ShardFatalError, ShardStream,
     },
     compression::{self, Compression},
     session::{Session, SessionSendError, SessionSendErrorType},
     socket_forwarder::SocketForwarder,
 };
 use crate::event::EventTypeFlags;
+use futures_util::future::{self, Either};
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "metrics")]
+use std::time::Instant;
 use std::{
     borrow::Cow,
-    env::consts::OS,
+    convert::TryFrom,
     error::Error,
     fmt::{Debug, Display, Formatter, Result as FmtResult},
     str,
-    sync::{atomic::Ordering, Arc},
+    sync::{atomic::Ordering, Arc, Mutex},
     time::Duration,
 };
 use tokio::sync::{
-    mpsc::UnboundedReceiver,
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
     watch::{channel as watch_channel, Receiver as WatchReceiver, Sender as WatchSender},
 };
 use tokio_tungstenite::tungstenite::{
     protocol::{frame::coding::CloseCode, CloseFrame, WebSocketConfig},
     Message,
 };
+use twilight_gateway_queue::QueueRequestErrorType;
 use twilight_model::gateway::{
     event::{
-        shard::{Connected, Connecting, Disconnected, Identifying, Reconnecting, Resuming},
-        DispatchEvent, Event, GatewayEvent, GatewayEventDeserializer,
+        shard::{
+            Connected, Connecting, Disconnected, Identifying, Reconnecting, Resuming,
+            SessionStartLimitExhausted, ZombieReason, Zombied,
+        },
+        DispatchEvent, Event, GatewayEvent,
     },
     payload::{
-        identify::{Identify, IdentifyInfo, IdentifyProperties},
+        identify::{Identify, IdentifyInfo},
         resume::Resume,
+        update_presence::UpdatePresencePayload,
         Ready,
     },
-    Intents, OpCode,
+    CloseCode as GatewayCloseCode, CloseCodeKind, OpCode,
 };
 use url::Url;
 
+#[cfg(not(feature = "etf"))]
+use twilight_model::gateway::event::GatewayEventDeserializer;
+
 /// Connecting to the gateway failed.
 #[derive(Debug)]
 pub struct ConnectingError {
@@ -148,6 +163,7 @@ enum ProcessErrorType {
     ParsingPayload,
     /// The binary payload received from Discord wasn't validly encoded as
     /// UTF-8.
+    #[cfg_attr(feature = "etf", allow(dead_code))]
     PayloadNotUtf8,
     /// A close message tried to be sent but the receiving half was dropped.
     /// This typically means that the shard is shutdown.
@@ -165,54 +181,37 @@ pub struct ReceivingEventError {
 }
 
 impl ReceivingEventError {
-    const fn fatal(&self) -> bool {
-        matches!(
-            self.kind,
-            ReceivingEventErrorType::AuthorizationInvalid { .. }
-                | ReceivingEventErrorType::IntentsDisallowed { .. }
-                | ReceivingEventErrorType::IntentsInvalid { .. }
-        )
-    }
-
     const fn reconnectable(&self) -> bool {
         matches!(self.kind, ReceivingEventErrorType::Decompressing)
     }
 
     const fn resumable(&self) -> bool {
-        matches!(self.kind, ReceivingEventErrorType::EventStreamEnded)
+        matches!(
+            self.kind,
+            ReceivingEventErrorType::EventStreamEnded | ReceivingEventErrorType::Zombied { .. }
+        )
     }
 }
 
 impl Display for ReceivingEventError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match &self.kind {
-            ReceivingEventErrorType::AuthorizationInvalid { shard_id, .. } => {
-                f.write_str("the authorization token for shard ")?;
-                Display::fmt(shard_id, f)?;
-
-                f.write_str(" is invalid")
-            }
             ReceivingEventErrorType::Decompressing => {
                 f.write_str("a frame could not be decompressed")
             }
-            ReceivingEventErrorType::IntentsDisallowed { intents, shard_id } => {
-                f.write_str("at least one of the intents (")?;
-                Debug::fmt(intents, f)?;
-                f.write_str(") for shard ")?;
-                Display::fmt(shard_id, f)?;
-
-                f.write_str(" are disallowed")
+            ReceivingEventErrorType::EventStreamEnded => {
+                f.write_str("event stream from gateway ended")
             }
-            ReceivingEventErrorType::IntentsInvalid { intents, shard_id } => {
-                f.write_str("at least one of the intents (")?;
-                Debug::fmt(intents, f)?;
-                f.write_str(") for shard ")?;
-                Display::fmt(shard_id, f)?;
+            ReceivingEventErrorType::Fatal { error } => {
+                f.write_str("the gateway closed the connection fatally: ")?;
 
-                f.write_str(" are invalid")
+                Display::fmt(error, f)
             }
-            ReceivingEventErrorType::EventStreamEnded => {
-                f.write_str("event stream from gateway ended")
+            ReceivingEventErrorType::Zombied { missed_acks } => {
+                f.write_str("connection is a zombie after ")?;
+                Display::fmt(missed_acks, f)?;
+
+                f.write_str(" heartbeats were not acknowledged")
             }
         }
     }
@@ -229,33 +228,74 @@ impl Error for ReceivingEventError {
 /// Type of [`ReceivingEventError`] that occurred.
 #[derive(Debug)]
 pub enum ReceivingEventErrorType {
-    /// Provided authorization token is invalid.
-    AuthorizationInvalid { shard_id: u64, token: String },
     /// Decompressing a frame from Discord failed.
     Decompressing,
     /// The event stream has ended, this is recoverable by resuming.
     EventStreamEnded,
-    /// Current user isn't allowed to use at least one of the configured
-    /// intents.
-    ///
-    /// The intents are provided.
-    IntentsDisallowed {
-        /// The configured intents for the shard.
-        intents: Intents,
-        /// The ID of the shard.
-        shard_id: u64,
+    /// Gateway closed the connection with a fatal close code, this is not
+    /// recoverable.
+    Fatal {
+        /// Error the shard stops with.
+        error: ShardFatalError,
     },
-    /// Configured intents aren't supported by Discord's gateway.
-    ///
-    /// The intents are provided.
-    IntentsInvalid {
-        /// Configured intents for the shard.
-        intents: Intents,
-        /// ID of the shard.
-        shard_id: u64,
+    /// Connection stopped acknowledging heartbeats, this is recoverable by
+    /// resuming.
+    Zombied {
+        /// Number of consecutive heartbeats that were not acknowledged.
+        missed_acks: u32,
     },
 }
 
+/// Cause of a shard reconnecting or resuming its session.
+#[derive(Clone, Copy, Debug)]
+enum Reason {
+    /// Gateway closed the connection.
+    Closed,
+    /// Message could not be decompressed.
+    Decompressing,
+    /// Connection was dropped.
+    EventStreamEnded,
+    /// Gateway invalidated the session.
+    InvalidSession,
+    /// Event could not be processed.
+    Processing,
+    /// Gateway requested a reconnect.
+    ReconnectRequested,
+    /// Resuming the session failed.
+    ResumeFailed,
+    /// Message could not be sent.
+    Sending,
+    /// Gateway skipped a sequence number.
+    SequenceSkipped,
+    /// There is no session to resume.
+    SessionUnavailable,
+    /// Shard was started with a session to resume.
+    Startup,
+    /// Connection was detected to be a zombie.
+    Zombied,
+}
+
+impl Reason {
+    /// Name of the reason used as the `reason` label of metrics.
+    #[cfg(feature = "metrics")]
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Decompressing => "decompressing",
+            Self::EventStreamEnded => "event_stream_ended",
+            Self::InvalidSession => "invalid_session",
+            Self::Processing => "processing",
+            Self::ReconnectRequested => "reconnect_requested",
+            Self::ResumeFailed => "resume_failed",
+            Self::Sending => "sending",
+            Self::SequenceSkipped => "sequence_skipped",
+            Self::SessionUnavailable => "session_unavailable",
+            Self::Startup => "startup",
+            Self::Zombied => "zombied",
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct ReadyMinimal {
     d: Ready,
@@ -267,13 +307,23 @@ struct ReadyMinimal {
 pub struct ShardProcessor {
     pub config: Arc<Config>,
     pub emitter: Emitter,
-    pub properties: IdentifyProperties,
     pub rx: UnboundedReceiver<Message>,
     pub session: Arc<Session>,
     compression: Compression,
+    /// Error the shard stopped with, shared with the shard.
+    fatal: Arc<OnceCell<ShardFatalError>>,
+    /// Gateway URL to use when resuming, as sent by the gateway.
+    resume_url: Option<Box<str>>,
+    /// Presence to set when identifying, shared with the shard.
+    presence: Arc<Mutex<Option<UpdatePresencePayload>>>,
     url: Box<str>,
     resume: Option<(u64, Box<str>)>,
+    /// Current stage and when the shard entered it.
+    #[cfg(feature = "metrics")]
+    stage_since: (Stage, Instant),
     wtx: WatchSender<Arc<Session>>,
+    zombie_rx: UnboundedReceiver<u32>,
+    zombie_tx: UnboundedSender<u32>,
 }
 
 impl ShardProcessor {
@@ -281,6 +331,8 @@ impl ShardProcessor {
         config: Arc<Config>,
         mut url: String,
         emitter: Emitter,
+        fatal: Arc<OnceCell<ShardFatalError>>,
+        presence: Arc<Mutex<Option<UpdatePresencePayload>>>,
     ) -> Result<(Self, WatchReceiver<Arc<Session>>), ConnectingError> {
         //if we got resume info we don't need to wait
         let shard_id = config.shard();
@@ -288,50 +340,61 @@ impl ShardProcessor {
         if !resumable {
             tracing::debug!("shard {:?} is not resumable", shard_id);
             tracing::debug!("shard {:?} queued", shard_id);
-            config.queue.request(shard_id).await;
+            Self::request_identify(&config, &emitter).await;
             tracing::debug!("shard {:?} finished queue", config.shard());
         }
 
-        let properties = IdentifyProperties::new("twilight.rs", "twilight.rs", OS, "", "");
-
-        url.push_str("?v=8");
-        compression::add_url_feature(&mut url);
+        add_url_query(&mut url);
 
-        emitter.event(Event::ShardConnecting(Connecting {
-            gateway: url.clone(),
-            shard_id: config.shard()[0],
-        }));
-        let stream = Self::connect(&url).await?;
+        emitter
+            .event(Event::ShardConnecting(Connecting {
+                gateway: url.clone(),
+                shard_id: config.shard()[0],
+            }))
+            .await;
+        let stream = Self::connect(&url, &config).await?;
         let (forwarder, rx, tx) = SocketForwarder::new(stream);
         tokio::spawn(async move {
             forwarder.run().await;
         });
 
         let session = Arc::new(Session::new(tx));
-        if resumable {
+        let resume_url = if resumable {
             session.set_id(config.session_id.clone().unwrap());
             session
                 .seq
-                .store(config.sequence.unwrap(), Ordering::Relaxed)
-        }
+                .store(config.sequence.unwrap(), Ordering::Relaxed);
+            session.set_resume_url(config.resume_gateway_url.clone());
+
+            config.resume_gateway_url.clone()
+        } else {
+            None
+        };
 
         let (wtx, wrx) = watch_channel(Arc::clone(&session));
+        let (zombie_tx, zombie_rx) = mpsc::unbounded_channel();
 
         let mut processor = Self {
             compression: Compression::new(shard_id),
             config,
+            fatal,
+            resume_url,
             emitter,
-            properties,
+            presence,
             rx,
             session,
             url: url.into_boxed_str(),
             resume: None,
+            #[cfg(feature = "metrics")]
+            stage_since: (Stage::default(), Instant::now()),
             wtx,
+            zombie_rx,
+            zombie_tx,
         };
 
         if resumable {
             tracing::debug!("resuming shard {:?}", shard_id);
-            processor.resume().await;
+            processor.resume(Reason::Startup).await;
         }
 
         Ok((processor, wrx))
@@ -344,18 +407,27 @@ impl ShardProcessor {
                 Err(source) => {
                     tracing::warn!("{}", source);
 
-                    self.emit_disconnected(None, None).await;
-
-                    if source.fatal() {
+                    // The shard has already been stopped and the disconnect
+                    // with the fatal close code emitted.
+                    if matches!(source.kind, ReceivingEventErrorType::Fatal { .. }) {
                         break;
                     }
 
+                    self.emit_disconnected(None, None).await;
+
                     if source.reconnectable() {
-                        self.reconnect().await;
+                        self.reconnect(Reason::Decompressing).await;
                     }
 
                     if source.resumable() {
-                        self.resume().await;
+                        let reason =
+                            if matches!(source.kind, ReceivingEventErrorType::Zombied { .. }) {
+                                Reason::Zombied
+                            } else {
+                                Reason::EventStreamEnded
+                            };
+
+                        self.resume(reason).await;
                     }
 
                     continue;
@@ -374,132 +446,82 @@ impl ShardProcessor {
                     tracing::debug!("error processing event; reconnecting");
                     self.emit_disconnected(None, None).await;
 
-                    self.reconnect().await;
+                    self.reconnect(Reason::Processing).await;
                 }
             }
         }
     }
 
-    #[allow(clippy::too_many_lines)]
     async fn process(&mut self) -> Result<(), ProcessError> {
-        let (op, seq, event_type) = {
-            let buffer = self.compression.buffer_slice_mut();
-            let json = str::from_utf8_mut(buffer).map_err(|source| ProcessError {
-                kind: ProcessErrorType::PayloadNotUtf8,
-                source: Some(Box::new(source)),
-            })?;
-
-            tracing::trace!(%json, "Received JSON");
-            let emitter = self.emitter.clone();
-
-            let (op, seq, event_type) =
-                if let Some(deserializer) = GatewayEventDeserializer::from_json(json) {
-                    let (op, seq, event_type) = deserializer.into_parts();
-
-                    // Unfortunately lifetimes and mutability requirements
-                    // conflict here if we return an immutable reference to the
-                    // event type, so we're going to have to take ownership of
-                    // this if we don't want to do anything too dangerous. It
-                    // should be a good trade-off either way.
-                    (op, seq, event_type.map(ToOwned::to_owned))
-                } else {
-                    tracing::warn!(
-                        json = ?self.compression.buffer_slice_ref(),
-                        shard_id = self.config.shard()[0],
-                        shard_total = self.config.shard()[1],
-                        seq = self.session.seq(),
-                        stage = ?self.session.stage(),
-                        "received payload without opcode",
-                    );
-
-                    return Err(ProcessError {
-                        kind: ProcessErrorType::ParsingPayload,
-                        source: Some(Box::new(GatewayEventParsingError {
-                            kind: GatewayEventParsingErrorType::PayloadInvalid,
-                            source: None,
-                        })),
-                    });
-                };
-
-            // We can do a few little optimisation tricks here. For the
-            // "heartbeat ack" and "reconnect" opcodes we can construct
-            // the gateway events without needing to go through a serde
-            // context.
-            //
-            // Additionally, the processor cares about the "resumed"
-            // dispatch event type, which has no payload and can be constructed.
-            //
-            // This might not be shaving off entire milliseconds for these few
-            // events each time, but it certainly adds up.
-            if matches!(op, 1 | 7 | 9 | 10 | 11) {
-                // Have to use an if statement here if we want to use the OpCode
-                // enum, since matching with repr values isn't allowed.
-                let gateway_event = if op == OpCode::HeartbeatAck as u8 {
-                    GatewayEvent::HeartbeatAck
-                } else if op == OpCode::Reconnect as u8 {
-                    GatewayEvent::Reconnect
-                } else {
-                    json::parse_gateway_event(op, seq, event_type.as_deref(), json).map_err(
-                        |source| ProcessError {
-                            kind: ProcessErrorType::ParsingPayload,
-                            source: Some(Box::new(source)),
-                        },
-                    )?
-                };
-
-                self.process_gateway_event(&gateway_event).await?;
-                emitter.event(Event::from(gateway_event));
+        let (op, seq, event_type) = self.payload_header()?;
+        self.record(op, seq, event_type.as_deref());
+        let emitter = self.emitter.clone();
+
+        // We can do a few little optimisation tricks here. For the
+        // "heartbeat ack" and "reconnect" opcodes we can construct
+        // the gateway events without needing to go through a serde
+        // context.
+        //
+        // Additionally, the processor cares about the "resumed"
+        // dispatch event type, which has no payload and can be constructed.
+        //
+        // This might not be shaving off entire milliseconds for these few
+        // events each time, but it certainly adds up.
+        if matches!(op, 1 | 7 | 9 | 10 | 11) {
+            // Have to use an if statement here if we want to use the OpCode
+            // enum, since matching with repr values isn't allowed.
+            let gateway_event = if op == OpCode::HeartbeatAck as u8 {
+                GatewayEvent::HeartbeatAck
+            } else if op == OpCode::Reconnect as u8 {
+                GatewayEvent::Reconnect
+            } else {
+                self.parse_gateway_event(op, seq, event_type.as_deref())?
+            };
 
-                if let Some(seq) = seq {
-                    self.session.set_seq(seq);
-                }
+            self.process_gateway_event(&gateway_event).await?;
+            emitter.event(Event::from(gateway_event)).await;
 
-                return Ok(());
+            if let Some(seq) = seq {
+                self.session.set_seq(seq);
             }
 
-            let seq = seq.ok_or(ProcessError {
-                kind: ProcessErrorType::SequenceMissing,
-                source: None,
-            })?;
+            return Ok(());
+        }
 
-            if event_type.as_deref() == Some("RESUMED") {
-                self.process_resumed(seq);
+        let seq = seq.ok_or(ProcessError {
+            kind: ProcessErrorType::SequenceMissing,
+            source: None,
+        })?;
 
-                if emitter.wants(EventTypeFlags::RESUMED) {
-                    let gateway_event =
-                        GatewayEvent::Dispatch(seq, Box::new(DispatchEvent::Resumed));
+        #[cfg(feature = "metrics")]
+        if let Some(event_type) = event_type.as_deref() {
+            crate::metrics::dispatch_event(self.config.shard()[0], event_type);
+        }
 
-                    emitter.event(Event::from(gateway_event));
-                }
+        if event_type.as_deref() == Some("RESUMED") {
+            self.process_resumed(seq).await;
 
-                return Ok(());
-            } else if event_type.as_deref() == Some("READY") {
-                let ready = json::from_slice::<ReadyMinimal>(self.compression.buffer_slice_mut())
-                    .map_err(|source| ProcessError {
-                    kind: ProcessErrorType::ParsingPayload,
-                    source: Some(Box::new(GatewayEventParsingError {
-                        kind: GatewayEventParsingErrorType::Deserializing,
-                        source: Some(Box::new(source)),
-                    })),
-                })?;
-
-                self.process_ready(&ready.d);
-                emitter.event(Event::Ready(Box::new(ready.d)));
+            if emitter.wants(EventTypeFlags::RESUMED) {
+                let gateway_event = GatewayEvent::Dispatch(seq, Box::new(DispatchEvent::Resumed));
 
-                return Ok(());
+                emitter.event(Event::from(gateway_event)).await;
             }
 
+            return Ok(());
+        } else if event_type.as_deref() == Some("READY") {
+            let ready = self.parse_ready()?;
+
             self.session.set_seq(seq);
+            self.process_ready(&ready).await;
+            emitter.event(Event::Ready(Box::new(ready))).await;
 
-            (op, seq, event_type)
-        };
+            return Ok(());
+        }
 
-        // We already know from earlier that the payload is valid UTF-8, so we
-        // can skip having to re-validate here since it hasn't been mutated.
-        let json = unsafe { self.compression.buffer_str_mut() };
+        self.session.set_seq(seq);
 
-        self.emitter
-            .json(op, Some(seq), event_type.as_deref(), json)
+        self.emit_payload(op, seq, event_type.as_deref())
+            .await
             .map_err(|source| {
                 let (kind, source) = source.into_parts();
 
@@ -517,30 +539,216 @@ impl ShardProcessor {
             })
     }
 
-    fn process_ready(&mut self, ready: &Ready) {
+    /// Retrieve the opcode, sequence, and event type of the JSON payload in
+    /// the buffer, validating that it is UTF-8.
+    #[cfg(not(feature = "etf"))]
+    fn payload_header(&mut self) -> Result<(u8, Option<u64>, Option<String>), ProcessError> {
+        let buffer = self.compression.buffer_slice_mut();
+        let json = str::from_utf8_mut(buffer).map_err(|source| ProcessError {
+            kind: ProcessErrorType::PayloadNotUtf8,
+            source: Some(Box::new(source)),
+        })?;
+
+        tracing::trace!(%json, "Received JSON");
+
+        GatewayEventDeserializer::from_json(json)
+            .map(|deserializer| {
+                let (op, seq, event_type) = deserializer.into_parts();
+
+                // Unfortunately lifetimes and mutability requirements
+                // conflict here if we return an immutable reference to the
+                // event type, so we're going to have to take ownership of
+                // this if we don't want to do anything too dangerous. It
+                // should be a good trade-off either way.
+                (op, seq, event_type.map(ToOwned::to_owned))
+            })
+            .ok_or_else(|| self.payload_invalid())
+    }
+
+    /// Retrieve the opcode, sequence, and event type of the ETF payload in the
+    /// buffer.
+    #[cfg(feature = "etf")]
+    fn payload_header(&mut self) -> Result<(u8, Option<u64>, Option<String>), ProcessError> {
+        let bytes = self.compression.buffer_slice_ref();
+
+        tracing::trace!(?bytes, "Received ETF");
+
+        etf::payload_header(bytes)
+            .map(|(op, seq, event_type)| (op, seq, event_type.map(ToOwned::to_owned)))
+            .ok_or_else(|| self.payload_invalid())
+    }
+
+    /// Error of a payload without an opcode.
+    fn payload_invalid(&self) -> ProcessError {
+        tracing::warn!(
+            payload = ?self.compression.buffer_slice_ref(),
+            shard_id = self.config.shard()[0],
+            shard_total = self.config.shard()[1],
+            seq = self.session.seq(),
+            stage = ?self.session.stage(),
+            "received payload without opcode",
+        );
+
+        ProcessError {
+            kind: ProcessErrorType::ParsingPayload,
+            source: Some(Box::new(GatewayEventParsingError {
+                kind: GatewayEventParsingErrorType::PayloadInvalid,
+                source: None,
+            })),
+        }
+    }
+
+    /// Record the JSON payload in the buffer if a recorder is configured.
+    #[cfg(not(feature = "etf"))]
+    fn record(&mut self, op: u8, seq: Option<u64>, event_type: Option<&str>) {
+        if let Some(recorder) = self.config.recorder() {
+            // We already know from earlier that the payload is valid UTF-8,
+            // so we can skip having to re-validate here since it hasn't been
+            // mutated.
+            let json = unsafe { self.compression.buffer_str_mut() };
+
+            recorder.record(self.config.shard()[0], op, seq, event_type, json);
+        }
+    }
+
+    /// Record the ETF payload in the buffer if a recorder is configured.
+    ///
+    /// Recordings are made of JSON payloads, so the payload is transcoded.
+    #[cfg(feature = "etf")]
+    fn record(&mut self, op: u8, seq: Option<u64>, event_type: Option<&str>) {
+        if let Some(recorder) = self.config.recorder() {
+            let json = etf::from_slice::<serde_json::Value>(self.compression.buffer_slice_ref())
+                .ok()
+                .and_then(|value| json::to_string(&value).ok());
+
+            if let Some(json) = json {
+                recorder.record(self.config.shard()[0], op, seq, event_type, &json);
+            } else {
+                tracing::warn!(op, seq, "failed to transcode payload for recording");
+            }
+        }
+    }
+
+    /// Parse the JSON payload in the buffer into a gateway event.
+    #[cfg(not(feature = "etf"))]
+    fn parse_gateway_event(
+        &mut self,
+        op: u8,
+        seq: Option<u64>,
+        event_type: Option<&str>,
+    ) -> Result<GatewayEvent, ProcessError> {
+        // We already know from earlier that the payload is valid UTF-8, so we
+        // can skip having to re-validate here since it hasn't been mutated.
+        let json = unsafe { self.compression.buffer_str_mut() };
+
+        json::parse_gateway_event(op, seq, event_type, json).map_err(|source| ProcessError {
+            kind: ProcessErrorType::ParsingPayload,
+            source: Some(Box::new(source)),
+        })
+    }
+
+    /// Parse the ETF payload in the buffer into a gateway event.
+    #[cfg(feature = "etf")]
+    fn parse_gateway_event(
+        &mut self,
+        op: u8,
+        seq: Option<u64>,
+        event_type: Option<&str>,
+    ) -> Result<GatewayEvent, ProcessError> {
+        etf::parse_gateway_event(op, seq, event_type, self.compression.buffer_slice_ref())
+            .map_err(Self::deserializing)
+    }
+
+    /// Parse the JSON payload in the buffer into a Ready event.
+    #[cfg(not(feature = "etf"))]
+    fn parse_ready(&mut self) -> Result<Ready, ProcessError> {
+        json::from_slice::<ReadyMinimal>(self.compression.buffer_slice_mut())
+            .map(|ready| ready.d)
+            .map_err(Self::deserializing)
+    }
+
+    /// Parse the ETF payload in the buffer into a Ready event.
+    #[cfg(feature = "etf")]
+    fn parse_ready(&mut self) -> Result<Ready, ProcessError> {
+        etf::from_slice::<ReadyMinimal>(self.compression.buffer_slice_ref())
+            .map(|ready| ready.d)
+            .map_err(Self::deserializing)
+    }
+
+    /// Error of a payload that failed to deserialize.
+    fn deserializing(source: impl Error + Send + Sync + 'static) -> ProcessError {
+        ProcessError {
+            kind: ProcessErrorType::ParsingPayload,
+            source: Some(Box::new(GatewayEventParsingError {
+                kind: GatewayEventParsingErrorType::Deserializing,
+                source: Some(Box::new(source)),
+            })),
+        }
+    }
+
+    /// Emit the JSON payload in the buffer if the event type is wanted.
+    #[cfg(not(feature = "etf"))]
+    async fn emit_payload(
+        &mut self,
+        op: u8,
+        seq: u64,
+        event_type: Option<&str>,
+    ) -> Result<(), EmitJsonError> {
+        // We already know from earlier that the payload is valid UTF-8, so we
+        // can skip having to re-validate here since it hasn't been mutated.
+        let json = unsafe { self.compression.buffer_str_mut() };
+
+        self.emitter.json(op, Some(seq), event_type, json).await
+    }
+
+    /// Emit the ETF payload in the buffer if the event type is wanted.
+    #[cfg(feature = "etf")]
+    async fn emit_payload(
+        &mut self,
+        op: u8,
+        seq: u64,
+        event_type: Option<&str>,
+    ) -> Result<(), EmitJsonError> {
+        self.emitter
+            .etf(
+                op,
+                Some(seq),
+                event_type,
+                self.compression.buffer_slice_ref(),
+            )
+            .await
+    }
+
+    async fn process_ready(&mut self, ready: &Ready) {
         #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "Dispatch");
+        crate::metrics::gateway_event(self.config.shard()[0], "Dispatch");
 
-        self.session.set_stage(Stage::Connected);
+        self.set_stage(Stage::Connected);
         self.session
             .set_id(ready.session_id.clone().into_boxed_str());
+        self.resume_url = ready.resume_gateway_url.clone().map(String::into_boxed_str);
+        self.session.set_resume_url(self.resume_url.clone());
 
-        self.emitter.event(Event::ShardConnected(Connected {
-            heartbeat_interval: self.session.heartbeat_interval(),
-            shard_id: self.config.shard()[0],
-        }));
+        self.emitter
+            .event(Event::ShardConnected(Connected {
+                heartbeat_interval: self.session.heartbeat_interval(),
+                shard_id: self.config.shard()[0],
+            }))
+            .await;
     }
 
-    fn process_resumed(&self, seq: u64) {
+    async fn process_resumed(&mut self, seq: u64) {
         #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "Dispatch");
+        crate::metrics::gateway_event(self.config.shard()[0], "Dispatch");
 
         self.session.set_seq(seq);
-        self.session.set_stage(Stage::Connected);
-        self.emitter.event(Event::ShardConnected(Connected {
-            heartbeat_interval: self.session.heartbeat_interval(),
-            shard_id: self.config.shard()[0],
-        }));
+        self.set_stage(Stage::Connected);
+        self.emitter
+            .event(Event::ShardConnected(Connected {
+                heartbeat_interval: self.session.heartbeat_interval(),
+                shard_id: self.config.shard()[0],
+            }))
+            .await;
         self.session.heartbeats.receive();
     }
 
@@ -561,30 +769,34 @@ impl ShardProcessor {
 
     fn process_heartbeat_ack(&self) {
         #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "HeartbeatAck");
+        crate::metrics::gateway_event(self.config.shard()[0], "HeartbeatAck");
 
-        self.session.heartbeats.receive();
+        #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+        if let Some(latency) = self.session.heartbeats.receive() {
+            #[cfg(feature = "metrics")]
+            crate::metrics::heartbeat_latency(self.config.shard()[0], latency);
+        }
     }
 
     async fn process_heartbeat(&mut self, seq: u64) {
         #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "Heartbeat");
+        crate::metrics::gateway_event(self.config.shard()[0], "Heartbeat");
 
         if seq > self.session.seq() + 1 {
-            self.resume().await;
+            self.resume(Reason::SequenceSkipped).await;
         }
 
         if let Err(err) = self.session.heartbeat() {
             tracing::warn!("error sending heartbeat; reconnecting: {}", err);
             self.emit_disconnected(None, None).await;
 
-            self.reconnect().await;
+            self.reconnect(Reason::Sending).await;
         }
     }
 
     async fn process_hello(&mut self, interval: u64) -> Result<(), ProcessError> {
         #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "Hello");
+        crate::metrics::gateway_event(self.config.shard()[0], "Hello");
 
         tracing::debug!("got hello with interval {}", interval);
 
@@ -600,7 +812,7 @@ impl ShardProcessor {
 
             if interval > 0 {
                 self.session.set_heartbeat_interval(interval);
-                self.session.start_heartbeater();
+                self.start_heartbeater();
             }
 
             self.send(payload).await.map_err(|source| ProcessError {
@@ -608,11 +820,11 @@ impl ShardProcessor {
                 source: Some(Box::new(source)),
             })?;
         } else {
-            self.session.set_stage(Stage::Identifying);
+            self.set_stage(Stage::Identifying);
 
             if interval > 0 {
                 self.session.set_heartbeat_interval(interval);
-                self.session.start_heartbeater();
+                self.start_heartbeater();
             }
 
             self.identify().await.map_err(|source| ProcessError {
@@ -625,26 +837,29 @@ impl ShardProcessor {
     }
 
     async fn process_invalidate_session(&mut self, resumable: bool) {
+        #[cfg(feature = "metrics")]
+        crate::metrics::invalid_session(self.config.shard()[0], resumable);
+
         self.emit_disconnected(None, None).await;
 
         if resumable {
             #[cfg(feature = "metrics")]
-            metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "InvalidateSessionTrue");
+            crate::metrics::gateway_event(self.config.shard()[0], "InvalidateSessionTrue");
 
             tracing::debug!("got request to resume the session");
-            self.resume().await;
+            self.resume(Reason::InvalidSession).await;
         } else {
             #[cfg(feature = "metrics")]
-            metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "InvalidateSessionFalse");
+            crate::metrics::gateway_event(self.config.shard()[0], "InvalidateSessionFalse");
 
             tracing::debug!("got request to invalidate the session and reconnect");
-            self.reconnect().await;
+            self.reconnect(Reason::InvalidSession).await;
         }
     }
 
     async fn process_reconnect(&mut self) -> Result<(), ProcessError> {
         #[cfg(feature = "metrics")]
-        metrics::counter!("GatewayEvent", 1, "GatewayEvent" => "Reconnect");
+        crate::metrics::gateway_event(self.config.shard()[0], "Reconnect");
 
         tracing::debug!("got request to reconnect");
         let frame = CloseFrame {
@@ -659,7 +874,7 @@ impl ShardProcessor {
             })?;
         self.emit_disconnected(Some(frame.code.into()), Some(frame.reason.to_string()))
             .await;
-        self.resume().await;
+        self.resume(Reason::ReconnectRequested).await;
 
         Ok(())
     }
@@ -671,7 +886,7 @@ impl ShardProcessor {
             if matches!(source.kind(), SessionSendErrorType::Sending { .. }) {
                 self.emit_disconnected(None, None).await;
 
-                self.reconnect().await;
+                self.reconnect(Reason::Sending).await;
             }
 
             return Err(source);
@@ -693,9 +908,32 @@ impl ShardProcessor {
         self.compression.clear();
 
         loop {
+            let received =
+                match future::select(Box::pin(self.rx.recv()), Box::pin(self.zombie_rx.recv()))
+                    .await
+                {
+                    Either::Left((maybe_msg, _)) => Either::Left(maybe_msg),
+                    Either::Right((maybe_missed_acks, _)) => Either::Right(maybe_missed_acks),
+                };
+
+            let maybe_msg = match received {
+                Either::Left(maybe_msg) => maybe_msg,
+                Either::Right(maybe_missed_acks) => {
+                    // The processor always holds a sender, so the channel
+                    // can't be closed.
+                    let missed_acks = maybe_missed_acks.unwrap_or_default();
+                    self.emit_zombied(missed_acks).await;
+
+                    return Err(ReceivingEventError {
+                        kind: ReceivingEventErrorType::Zombied { missed_acks },
+                        source: None,
+                    });
+                }
+            };
+
             // Returns None when the socket forwarder has ended, meaning the
             // connection was dropped.
-            let mut msg = self.rx.recv().await.ok_or(ReceivingEventError {
+            let mut msg = maybe_msg.ok_or(ReceivingEventError {
                 kind: ReceivingEventErrorType::EventStreamEnded,
                 source: None,
             })?;
@@ -726,11 +964,23 @@ impl ShardProcessor {
     ) -> Result<bool, ReceivingEventError> {
         match msg {
             Message::Binary(json) => {
+                #[cfg(feature = "metrics")]
+                crate::metrics::bytes_received(self.config.shard()[0], "compressed", json.len());
+
                 let extended = self.compression.extend_binary(json.as_slice());
 
                 if extended {
                     match self.compression.message_mut() {
-                        Ok(Some(bytes)) => self.emitter.bytes(bytes),
+                        Ok(Some(bytes)) => {
+                            #[cfg(feature = "metrics")]
+                            crate::metrics::bytes_received(
+                                self.config.shard()[0],
+                                "decompressed",
+                                bytes.len(),
+                            );
+
+                            self.emitter.bytes(bytes).await
+                        }
                         Ok(None) => return Ok(false),
                         Err(source) => {
                             return Err(ReceivingEventError {
@@ -749,10 +999,13 @@ impl ShardProcessor {
                 Ok(false)
             }
             Message::Text(json) => {
+                #[cfg(feature = "metrics")]
+                crate::metrics::bytes_received(self.config.shard()[0], "decompressed", json.len());
+
                 let extended = self.compression.extend_text(json.as_bytes());
 
                 if extended {
-                    self.emitter.bytes(json.as_bytes());
+                    self.emitter.bytes(json.as_bytes()).await;
                 }
 
                 Ok(extended)
@@ -769,51 +1022,105 @@ impl ShardProcessor {
     ) -> Result<(), ReceivingEventError> {
         tracing::info!("got close code: {:?}", close_frame);
 
+        #[cfg(feature = "metrics")]
+        crate::metrics::close_code(
+            self.config.shard()[0],
+            close_frame.map(|frame| frame.code.into()),
+        );
+
+        let close_code =
+            close_frame.and_then(|frame| GatewayCloseCode::try_from(u16::from(frame.code)).ok());
+        // Fatal close codes unknown to the shard are resumed like other
+        // unknown close codes.
+        let fatal =
+            close_code.and_then(|code| ShardFatalError::from_close_code(code, &self.config));
+
+        // Stop the shard before emitting the disconnect, so that the error is
+        // available to handlers of the event.
+        if let Some(error) = &fatal {
+            self.set_stage(Stage::Disconnected);
+            self.session.stop_heartbeater();
+            let _res = self.fatal.set(error.clone());
+        }
+
         self.emit_disconnected(
             close_frame.map(|c| c.code.into()),
             close_frame.map(|c| c.reason.to_string()),
         )
         .await;
 
-        if let Some(close_frame) = close_frame {
-            match close_frame.code {
-                CloseCode::Library(4004) => {
-                    return Err(ReceivingEventError {
-                        kind: ReceivingEventErrorType::AuthorizationInvalid {
-                            shard_id: self.config.shard()[0],
-                            token: self.config.token().to_owned(),
-                        },
-                        source: None,
-                    });
-                }
-                CloseCode::Library(4013) => {
-                    return Err(ReceivingEventError {
-                        kind: ReceivingEventErrorType::IntentsInvalid {
-                            intents: self.config.intents(),
-                            shard_id: self.config.shard()[0],
-                        },
-                        source: None,
-                    });
-                }
-                CloseCode::Library(4014) => {
-                    return Err(ReceivingEventError {
-                        kind: ReceivingEventErrorType::IntentsDisallowed {
-                            intents: self.config.intents(),
-                            shard_id: self.config.shard()[0],
-                        },
-                        source: None,
-                    });
-                }
-                _ => {}
-            }
+        if let Some(error) = fatal {
+            return Err(ReceivingEventError {
+                kind: ReceivingEventErrorType::Fatal { error },
+                source: None,
+            });
+        }
+
+        if close_code.map(GatewayCloseCode::kind) == Some(CloseCodeKind::Reconnect) {
+            self.reconnect(Reason::Closed).await;
+
+            return Ok(());
         }
 
-        self.resume().await;
+        self.resume(Reason::Closed).await;
 
         Ok(())
     }
 
-    async fn connect(url: &str) -> Result<ShardStream, ConnectingError> {
+    /// Wait for the queue to allow the shard to identify.
+    ///
+    /// Whenever the session start limit is exhausted a
+    /// [`Event::ShardSessionStartLimitExhausted`] event is emitted and the
+    /// shard waits for the limit to be reset before requesting again.
+    async fn request_identify(config: &Config, emitter: &Emitter) {
+        /// Minimum duration to wait before requesting again, in case the limit
+        /// was reported to be reset already.
+        const MIN_WAIT: Duration = Duration::from_secs(1);
+
+        loop {
+            let source = match config
+                .queue
+                .try_request_with_priority(config.shard(), config.queue_priority())
+                .await
+            {
+                Ok(()) => return,
+                Err(source) => source,
+            };
+
+            let (remaining, reset_after) = match source.kind() {
+                QueueRequestErrorType::SessionStartLimitExhausted {
+                    remaining,
+                    reset_after,
+                } => (*remaining, *reset_after),
+                _ => (0, MIN_WAIT),
+            };
+
+            tracing::warn!(
+                shard_id = config.shard()[0],
+                shard_total = config.shard()[1],
+                "waiting for session start limit reset: {}",
+                source,
+            );
+
+            // The limit is reset at least once a day.
+            #[allow(clippy::cast_possible_truncation)]
+            let reset_after_millis = reset_after.as_millis() as u64;
+
+            emitter
+                .event(Event::ShardSessionStartLimitExhausted(
+                    SessionStartLimitExhausted {
+                        remaining,
+                        reset_after: reset_after_millis,
+                        shard_id: config.shard()[0],
+                    },
+                ))
+                .await;
+
+            tokio::time::sleep(reset_after.max(MIN_WAIT)).await;
+        }
+    }
+
+    async fn connect(url: &str, config: &Config) -> Result<ShardStream, ConnectingError> {
         #[allow(disjoint_capture_migration)]
         let url = Url::parse(url).map_err(|source| ConnectingError {
             kind: ConnectingErrorType::ParsingUrl {
@@ -822,24 +1129,39 @@ impl ShardProcessor {
             source: Some(Box::new(source)),
         })?;
 
-        // `max_frame_size` and `max_message_queue` limits are disabled because
-        // Discord is not a malicious actor.
+        // `max_frame_size` and `max_message_size` limits are disabled unless
+        // configured because Discord is not a malicious actor.
         //
         // `accept_unmasked_frames` and `max_send_queue` are set to their
         // defaults.
-        let config = WebSocketConfig {
+        let ws_config = WebSocketConfig {
             accept_unmasked_frames: false,
-            max_frame_size: None,
-            max_message_size: None,
+            max_frame_size: config.max_frame_size(),
+            max_message_size: config.max_message_size(),
             max_send_queue: None,
         };
 
-        let (stream, _) = tokio_tungstenite::connect_async_with_config(url, Some(config))
-            .await
-            .map_err(|source| ConnectingError {
-                kind: ConnectingErrorType::Establishing,
-                source: Some(Box::new(source)),
-            })?;
+        let establishing = |source| ConnectingError {
+            kind: ConnectingErrorType::Establishing,
+            source: Some(source),
+        };
+
+        let (stream, _) = if let Some(proxy) = config.proxy() {
+            let host = url.host_str().unwrap_or_default();
+            let port = url.port_or_known_default().unwrap_or(443);
+            let socket = proxy
+                .connect(host, port)
+                .await
+                .map_err(|source| establishing(Box::new(source)))?;
+
+            tokio_tungstenite::client_async_tls_with_config(url, socket, Some(ws_config), None)
+                .await
+                .map_err(|source| establishing(Box::new(source)))?
+        } else {
+            tokio_tungstenite::connect_async_with_config(url, Some(ws_config))
+                .await
+                .map_err(|source| establishing(Box::new(source)))?
+        };
 
         tracing::debug!("Shook hands with remote");
 
@@ -848,28 +1170,36 @@ impl ShardProcessor {
 
     /// Identifies with the gateway to create a new session.
     async fn identify(&mut self) -> Result<(), SessionSendError> {
-        self.session.set_stage(Stage::Identifying);
+        self.set_stage(Stage::Identifying);
 
         let identify = Identify::new(IdentifyInfo {
             compress: false,
             large_threshold: self.config.large_threshold(),
             intents: self.config.intents(),
-            properties: self.properties.clone(),
+            properties: self.config.identify_properties().clone(),
             shard: Some(self.config.shard()),
-            presence: self.config.presence().cloned(),
+            presence: self.presence.lock().expect("presence poisoned").clone(),
             token: self.config.token().to_owned(),
         });
-        self.emitter.event(Event::ShardIdentifying(Identifying {
-            shard_id: self.config.shard()[0],
-            shard_total: self.config.shard()[1],
-        }));
+        self.emitter
+            .event(Event::ShardIdentifying(Identifying {
+                shard_id: self.config.shard()[0],
+                shard_total: self.config.shard()[1],
+            }))
+            .await;
 
         self.send(identify).await
     }
 
     /// Perform a full reconnect to the gateway, instantiating a new session.
-    async fn reconnect(&mut self) {
-        tracing::info!("reconnection started");
+    async fn reconnect(&mut self, reason: Reason) {
+        tracing::info!("reconnection started: {:?}", reason);
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::reconnect(self.config.shard()[0], reason.name());
+
+        // The resume URL belongs to the session being replaced.
+        self.resume_url = None;
 
         let mut wait = Duration::from_secs(1);
 
@@ -883,13 +1213,15 @@ impl ShardProcessor {
             tokio::time::sleep(wait).await;
 
             // Await allowance when doing a full reconnect.
-            self.config.queue.request(self.config.shard()).await;
+            Self::request_identify(&self.config, &self.emitter).await;
 
-            self.emitter.event(Event::ShardReconnecting(Reconnecting {
-                shard_id: self.config.shard()[0],
-            }));
+            self.emitter
+                .event(Event::ShardReconnecting(Reconnecting {
+                    shard_id: self.config.shard()[0],
+                }))
+                .await;
 
-            let stream = match Self::connect(&self.url).await {
+            let stream = match Self::connect(&self.url, &self.config).await {
                 Ok(s) => s,
                 Err(why) => {
                     tracing::warn!("reconnecting failed: {:?}", why);
@@ -907,17 +1239,23 @@ impl ShardProcessor {
             break;
         }
 
-        self.emitter.event(Event::ShardConnecting(Connecting {
-            gateway: self.url.clone().into_string(),
-            shard_id: self.config.shard()[0],
-        }));
+        self.emitter
+            .event(Event::ShardConnecting(Connecting {
+                gateway: self.url.clone().into_string(),
+                shard_id: self.config.shard()[0],
+            }))
+            .await;
     }
 
     /// Resume a session if possible, defaulting to instantiating a new
     /// connection.
-    async fn resume(&mut self) {
-        tracing::info!("resuming shard {:?}", self.config.shard());
-        self.session.set_stage(Stage::Resuming);
+    async fn resume(&mut self, reason: Reason) {
+        tracing::info!("resuming shard {:?}: {:?}", self.config.shard(), reason);
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::resume(self.config.shard()[0], reason.name());
+
+        self.set_stage(Stage::Resuming);
         self.session.stop_heartbeater();
 
         let seq = self.session.seq();
@@ -926,7 +1264,7 @@ impl ShardProcessor {
             id
         } else {
             tracing::info!("session id unavailable, reconnecting");
-            self.reconnect().await;
+            self.reconnect(Reason::SessionUnavailable).await;
             return;
         };
 
@@ -941,24 +1279,57 @@ impl ShardProcessor {
                 why,
             );
 
-            self.reconnect().await;
+            self.reconnect(Reason::ResumeFailed).await;
         }
     }
 
     /// Attempt to resume a session.
     async fn try_resume(&mut self) -> Result<(), ConnectingError> {
-        self.emitter.event(Event::ShardResuming(Resuming {
-            seq: self.session.seq(),
-            shard_id: self.config.shard()[0],
-        }));
+        self.emitter
+            .event(Event::ShardResuming(Resuming {
+                seq: self.session.seq(),
+                shard_id: self.config.shard()[0],
+            }))
+            .await;
 
-        let stream = Self::connect(&self.url).await?;
+        // Resume with the URL sent by the gateway for the session, if any.
+        let url = self.resume_url.as_ref().map_or_else(
+            || self.url.clone(),
+            |resume_url| {
+                let mut url = resume_url.clone().into_string();
+                add_url_query(&mut url);
+
+                url.into_boxed_str()
+            },
+        );
+        let stream = Self::connect(&url, &self.config).await?;
 
         self.set_session(stream, Stage::Resuming);
 
         Ok(())
     }
 
+    /// Set the stage of the session, recording how long the shard was in the
+    /// previous stage.
+    fn set_stage(&mut self, stage: Stage) {
+        #[cfg(feature = "metrics")]
+        if self.stage_since.0 != stage {
+            crate::metrics::stage_duration(
+                self.config.shard()[0],
+                self.stage_since.0,
+                self.stage_since.1.elapsed(),
+            );
+            self.stage_since = (stage, Instant::now());
+        }
+
+        self.session.set_stage(stage);
+    }
+
+    fn start_heartbeater(&self) {
+        self.session
+            .start_heartbeater(self.config.heartbeat_policy(), self.zombie_tx.clone());
+    }
+
     /// Set the session with a new connection.
     ///
     /// Set the session details and create and run a new socket forwarder for a
@@ -970,20 +1341,52 @@ impl ShardProcessor {
 
         self.rx = rx;
         self.session = Arc::new(Session::new(tx));
+        self.session.set_resume_url(self.resume_url.clone());
 
         if let Err(why) = self.wtx.send(Arc::clone(&self.session)) {
             tracing::error!("failed to broadcast new session: {:?}", why);
         }
 
-        self.session.set_stage(stage);
+        self.set_stage(stage);
         self.compression.reset();
+
+        // Heartbeaters of previous sessions may have detected a zombie
+        // connection that has already been replaced.
+        let (zombie_tx, zombie_rx) = mpsc::unbounded_channel();
+        self.zombie_rx = zombie_rx;
+        self.zombie_tx = zombie_tx;
+    }
+
+    async fn emit_zombied(&self, missed_acks: u32) {
+        self.emitter
+            .event(Event::ShardZombied(Zombied {
+                missed_acks,
+                reason: ZombieReason::HeartbeatAckMissed,
+                shard_id: self.config.shard()[0],
+            }))
+            .await;
     }
 
     async fn emit_disconnected(&self, code: Option<u16>, reason: Option<String>) {
-        self.emitter.event(Event::ShardDisconnected(Disconnected {
-            code,
-            reason,
-            shard_id: self.config.shard()[0],
-        }));
+        let kind = code
+            .and_then(|code| GatewayCloseCode::try_from(code).ok())
+            .map(GatewayCloseCode::kind);
+
+        self.emitter
+            .event(Event::ShardDisconnected(Disconnected {
+                code,
+                kind,
+                reason,
+                shard_id: self.config.shard()[0],
+            }))
+            .await;
     }
 }
+
+/// Add the query parameters selecting the API version, encoding, and
+/// compression to a gateway URL.
+fn add_url_query(url: &mut String) {
+    url.push_str("?v=8");
+    encoding::add_url_feature(url);
+    compression::add_url_feature(url);
+}
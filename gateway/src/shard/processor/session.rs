@@ -1,6 +1,6 @@
 use super::{
-    super::{json, stage::Stage},
-    heartbeat::{Heartbeater, Heartbeats},
+    super::{encoding, stage::Stage},
+    heartbeat::{HeartbeatPolicy, Heartbeater, Heartbeats},
     throttle::Throttle,
 };
 use serde::ser::Serialize;
@@ -40,7 +40,7 @@ impl SessionSendError {
 impl Display for SessionSendError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match &self.kind {
-            SessionSendErrorType::Serializing => f.write_str("failed to serialize payload as json"),
+            SessionSendErrorType::Serializing => f.write_str("failed to serialize payload"),
             SessionSendErrorType::Sending => f.write_str("failed to send message over websocket"),
         }
     }
@@ -69,6 +69,8 @@ pub struct Session {
     pub heartbeats: Arc<Heartbeats>,
     pub heartbeat_interval: AtomicU64,
     pub id: MutexSync<Option<Box<str>>>,
+    /// Gateway URL to use when resuming the session, as sent by the gateway.
+    pub resume_url: MutexSync<Option<Box<str>>>,
     pub seq: Arc<AtomicU64>,
     pub stage: AtomicU8,
     pub tx: UnboundedSender<TungsteniteMessage>,
@@ -82,6 +84,7 @@ impl Session {
             heartbeats: Arc::new(Heartbeats::default()),
             heartbeat_interval: AtomicU64::new(0),
             id: MutexSync::new(None),
+            resume_url: MutexSync::new(None),
             seq: Arc::new(AtomicU64::new(0)),
             stage: AtomicU8::new(Stage::default() as u8),
             tx,
@@ -101,7 +104,7 @@ impl Session {
     /// receiving channel has hung up. This will only happen when the shard has
     /// either not started or has already shutdown.
     pub fn send(&self, payload: impl Serialize) -> Result<(), SessionSendError> {
-        let bytes = json::to_vec(&payload).map_err(|source| SessionSendError {
+        let bytes = encoding::to_vec(&payload).map_err(|source| SessionSendError {
             kind: SessionSendErrorType::Serializing,
             source: Some(Box::new(source)),
         })?;
@@ -164,6 +167,14 @@ impl Session {
         self.id.lock().expect("id poisoned").replace(new_id);
     }
 
+    pub fn resume_url(&self) -> Option<Box<str>> {
+        self.resume_url.lock().expect("resume url poisoned").clone()
+    }
+
+    pub fn set_resume_url(&self, new_resume_url: Option<Box<str>>) {
+        *self.resume_url.lock().expect("resume url poisoned") = new_resume_url;
+    }
+
     pub fn stop_heartbeater(&self) {
         if let Some(handle) = self
             .heartbeater_handle
@@ -175,12 +186,15 @@ impl Session {
         }
     }
 
-    pub fn start_heartbeater(&self) {
+    /// Start sending heartbeats, notifying the zombie channel with the number
+    /// of missed acknowledgements if the connection turns out to be a zombie.
+    pub fn start_heartbeater(&self, policy: HeartbeatPolicy, zombie: UnboundedSender<u32>) {
         let interval = self.heartbeat_interval();
         let seq = Arc::clone(&self.seq);
         let heartbeats = Arc::clone(&self.heartbeats);
 
-        let heartbeater = Heartbeater::new(heartbeats, interval, seq, self.tx.clone()).run();
+        let heartbeater =
+            Heartbeater::new(heartbeats, interval, policy, seq, self.tx.clone(), zombie).run();
         let handle = tokio::spawn(heartbeater);
 
         if let Some(old) = self
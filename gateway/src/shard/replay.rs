@@ -0,0 +1,610 @@
+//! Recording raw gateway traffic and replaying it without a connection.
+//!
+//! A [`Recorder`] configured via [`ShardBuilder::recorder`] writes every
+//! inbound payload of a shard as a line of JSON. A [`Replayer`] reads such a
+//! recording and feeds the payloads through the same deserialization path as a
+//! shard, producing an [`Events`] stream. This is useful for reproducing bugs
+//! that depend on an exact sequence of gateway events.
+//!
+//! Payloads received in the External Term Format when the `etf` feature is
+//! enabled are transcoded to JSON before being recorded.
+//!
+//! # Examples
+//!
+//! Record the traffic of a shard to a file:
+//!
+//! ```no_run
+//! # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::env;
+//! use twilight_gateway::{shard::replay::Recorder, Intents, Shard};
+//!
+//! let token = env::var("DISCORD_TOKEN")?;
+//! let recorder = Recorder::create("gateway.jsonl")?;
+//!
+//! let (shard, events) = Shard::builder(token, Intents::GUILDS)
+//!     .recorder(Some(recorder))
+//!     .build();
+//! shard.start().await?;
+//! # Ok(()) }
+//! ```
+//!
+//! Replay the recording later, preserving the original timing between
+//! payloads:
+//!
+//! ```no_run
+//! # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use futures::StreamExt;
+//! use twilight_gateway::shard::replay::Replayer;
+//!
+//! let mut events = Replayer::open("gateway.jsonl")?
+//!     .preserve_timing(true)
+//!     .start();
+//!
+//! while let Some(event) = events.next().await {
+//!     println!("replayed {:?}", event.kind());
+//! }
+//! # Ok(()) }
+//! ```
+//!
+//! [`Events`]: super::Events
+//! [`ShardBuilder::recorder`]: super::ShardBuilder::recorder
+
+use super::{emitter::Emitter, event::Events, json};
+use crate::EventTypeFlags;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    fs::File,
+    io::{BufRead, BufReader, LineWriter, Write},
+    path::Path,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use twilight_model::gateway::event::{
+    shard::Connected, Event, GatewayEvent, GatewayEventDeserializer,
+};
+
+/// Reading a recording failed.
+#[derive(Debug)]
+pub struct ReplayError {
+    kind: ReplayErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl ReplayError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &ReplayErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(self) -> (ReplayErrorType, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            ReplayErrorType::Parsing { line } => {
+                f.write_str("line ")?;
+                Display::fmt(line, f)?;
+
+                f.write_str(" of the recording is not a recorded payload")
+            }
+            ReplayErrorType::Reading => f.write_str("reading the recording failed"),
+        }
+    }
+}
+
+impl Error for ReplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`ReplayError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReplayErrorType {
+    /// A line of the recording could not be parsed as a [`RecordedPayload`].
+    Parsing {
+        /// Line number, starting at 1.
+        line: usize,
+    },
+    /// Reading the recording failed.
+    Reading,
+}
+
+/// Raw inbound payload received by a shard.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RecordedPayload {
+    /// Dispatch event type of the payload, if any.
+    #[serde(rename = "t")]
+    pub event_type: Option<String>,
+    /// Opcode of the payload.
+    pub op: u8,
+    /// Full JSON of the payload.
+    pub payload: String,
+    /// Sequence number of the payload, if any.
+    #[serde(rename = "s")]
+    pub seq: Option<u64>,
+    /// ID of the shard that received the payload.
+    pub shard_id: u64,
+    /// Time the payload was received, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+/// Recorder of the raw inbound payloads of shards.
+///
+/// Each payload is written as a [`RecordedPayload`] on its own line of JSON.
+/// The recorder can be cloned and shared between multiple shards, such as the
+/// shards of a cluster, in which case their payloads are interleaved.
+///
+/// Payloads are written by a dedicated thread so that slow writes don't hold
+/// up the processing of events by the shards. Payloads that have not been
+/// written yet are buffered in memory. The thread ends once the recorder and
+/// all of its clones are dropped.
+///
+/// Failing to write a payload is logged and otherwise ignored.
+#[derive(Clone)]
+pub struct Recorder(UnboundedSender<Vec<u8>>);
+
+impl Recorder {
+    /// Create a recorder writing to the provided writer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thread writing the payloads could not be spawned.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        thread::Builder::new()
+            .name("twilight-gateway-recorder".to_owned())
+            .spawn(move || Self::write(writer, rx))
+            .expect("failed to spawn recorder thread");
+
+        Self(tx)
+    }
+
+    /// Create a recorder writing to a file, truncating it if it exists.
+    ///
+    /// The file is flushed after every payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an IO error if the file could not be created.
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = File::create(path)?;
+
+        Ok(Self::new(LineWriter::new(file)))
+    }
+
+    /// Record an inbound payload.
+    pub(super) fn record(
+        &self,
+        shard_id: u64,
+        op: u8,
+        seq: Option<u64>,
+        event_type: Option<&str>,
+        json: &str,
+    ) {
+        #[allow(clippy::cast_possible_truncation)]
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+
+        let payload = RecordedPayload {
+            event_type: event_type.map(ToOwned::to_owned),
+            op,
+            payload: json.to_owned(),
+            seq,
+            shard_id,
+            timestamp,
+        };
+
+        let mut bytes = match json::to_vec(&payload) {
+            Ok(bytes) => bytes,
+            Err(source) => {
+                tracing::warn!(shard_id, "failed to serialize recorded payload: {}", source);
+
+                return;
+            }
+        };
+        bytes.push(b'\n');
+
+        // The writing thread only ends once every sender is dropped.
+        let _res = self.0.send(bytes);
+    }
+
+    /// Write the recorded payloads until every sender is dropped.
+    fn write(mut writer: impl Write, mut rx: UnboundedReceiver<Vec<u8>>) {
+        while let Some(bytes) = rx.blocking_recv() {
+            if let Err(source) = writer.write_all(&bytes) {
+                tracing::warn!("failed to record payload: {}", source);
+            }
+        }
+
+        if let Err(source) = writer.flush() {
+            tracing::warn!("failed to flush recorded payloads: {}", source);
+        }
+    }
+}
+
+impl Debug for Recorder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Recorder").finish()
+    }
+}
+
+/// Replayer of recorded payloads, producing a stream of events.
+///
+/// Refer to the [module-level documentation] for an example.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Debug)]
+pub struct Replayer {
+    event_types: EventTypeFlags,
+    payloads: Vec<RecordedPayload>,
+    preserve_timing: bool,
+    shard_id: Option<u64>,
+}
+
+impl Replayer {
+    /// Create a replayer of the provided payloads.
+    pub fn new(payloads: impl IntoIterator<Item = RecordedPayload>) -> Self {
+        Self {
+            event_types: EventTypeFlags::default(),
+            payloads: payloads.into_iter().collect(),
+            preserve_timing: false,
+            shard_id: None,
+        }
+    }
+
+    /// Create a replayer of a recording written by a [`Recorder`].
+    ///
+    /// Empty lines are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ReplayErrorType::Parsing`] error type if a line could not
+    /// be parsed as a recorded payload.
+    ///
+    /// Returns a [`ReplayErrorType::Reading`] error type if reading from the
+    /// reader failed.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ReplayError> {
+        let mut payloads = Vec::new();
+
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line.map_err(|source| ReplayError {
+                kind: ReplayErrorType::Reading,
+                source: Some(Box::new(source)),
+            })?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut bytes = line.into_bytes();
+            let payload = json::from_slice(bytes.as_mut_slice()).map_err(|source| ReplayError {
+                kind: ReplayErrorType::Parsing { line: idx + 1 },
+                source: Some(Box::new(source)),
+            })?;
+
+            payloads.push(payload);
+        }
+
+        Ok(Self::new(payloads))
+    }
+
+    /// Create a replayer of a recording file written by a [`Recorder`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ReplayErrorType::Parsing`] error type if a line could not
+    /// be parsed as a recorded payload.
+    ///
+    /// Returns a [`ReplayErrorType::Reading`] error type if the file could not
+    /// be opened or read.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let file = File::open(path).map_err(|source| ReplayError {
+            kind: ReplayErrorType::Reading,
+            source: Some(Box::new(source)),
+        })?;
+
+        Self::from_reader(BufReader::new(file))
+    }
+
+    /// Set the event types to emit.
+    ///
+    /// Refer to [`ShardBuilder::event_types`] for more information.
+    ///
+    /// [`ShardBuilder::event_types`]: super::ShardBuilder::event_types
+    pub const fn event_types(mut self, event_types: EventTypeFlags) -> Self {
+        self.event_types = event_types;
+
+        self
+    }
+
+    /// Set whether to wait between payloads for as long as passed between
+    /// them when they were recorded.
+    ///
+    /// Defaults to `false`, replaying all payloads as fast as possible.
+    pub const fn preserve_timing(mut self, preserve_timing: bool) -> Self {
+        self.preserve_timing = preserve_timing;
+
+        self
+    }
+
+    /// Only replay the payloads received by a single shard.
+    ///
+    /// Defaults to replaying the payloads of all shards.
+    pub const fn shard(mut self, shard_id: u64) -> Self {
+        self.shard_id = Some(shard_id);
+
+        self
+    }
+
+    /// Start replaying the payloads in a background task, returning the
+    /// stream of events.
+    ///
+    /// The stream ends once all payloads have been replayed. Payloads that
+    /// fail to be deserialized are logged and skipped. Like a live shard,
+    /// [`Event::ShardConnected`] is emitted when a shard is ready or resumed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn start(self) -> Events {
+        let event_types = self.event_types;
+        let (emitter, rx) = Emitter::new(event_types, None);
+
+        tokio::spawn(self.run(emitter));
+
+        Events::new(event_types, rx)
+    }
+
+    async fn run(self, emitter: Emitter) {
+        // Heartbeat intervals of the shards, from their last `Hello` payload.
+        let mut intervals = HashMap::new();
+        let mut previous = None;
+        let shard_id = self.shard_id;
+
+        let payloads = self
+            .payloads
+            .into_iter()
+            .filter(|payload| shard_id.map_or(true, |shard_id| payload.shard_id == shard_id));
+
+        for payload in payloads {
+            if self.preserve_timing {
+                if let Some(previous) = previous {
+                    let wait = payload.timestamp.saturating_sub(previous);
+                    tokio::time::sleep(Duration::from_millis(wait)).await;
+                }
+
+                previous = Some(payload.timestamp);
+            }
+
+            Self::replay(&emitter, &mut intervals, payload).await;
+        }
+    }
+
+    async fn replay(
+        emitter: &Emitter,
+        intervals: &mut HashMap<u64, u64>,
+        payload: RecordedPayload,
+    ) {
+        let shard_id = payload.shard_id;
+        let mut json = payload.payload;
+
+        let (op, seq, event_type) = match GatewayEventDeserializer::from_json(&json) {
+            Some(deserializer) => {
+                let (op, seq, event_type) = deserializer.into_parts();
+
+                (op, seq, event_type.map(ToOwned::to_owned))
+            }
+            None => {
+                tracing::warn!(shard_id, "replayed payload is invalid");
+
+                return;
+            }
+        };
+
+        emitter.bytes(json.as_bytes()).await;
+
+        // Shards are connected once they are ready or resumed, which they
+        // emit before the event itself.
+        let connected =
+            event_type.as_deref() == Some("READY") || event_type.as_deref() == Some("RESUMED");
+
+        if op == 10 {
+            let mut hello = json.clone();
+
+            if let Ok(GatewayEvent::Hello(interval)) =
+                json::parse_gateway_event(op, seq, None, hello.as_mut_str())
+            {
+                intervals.insert(shard_id, interval);
+            }
+        } else if connected {
+            emitter
+                .event(Event::ShardConnected(Connected {
+                    heartbeat_interval: intervals.get(&shard_id).copied().unwrap_or_default(),
+                    shard_id,
+                }))
+                .await;
+        }
+
+        if let Err(source) = emitter
+            .json(op, seq, event_type.as_deref(), json.as_mut_str())
+            .await
+        {
+            tracing::warn!(shard_id, "failed to replay payload: {}", source);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RecordedPayload, Recorder, ReplayError, ReplayErrorType, Replayer};
+    use crate::{Event, EventTypeFlags};
+    use futures_util::StreamExt;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::Debug,
+        io::{Result as IoResult, Write},
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+    use twilight_model::gateway::event::shard::Connected;
+
+    assert_impl_all!(Recorder: Clone, Debug, Send, Sync);
+    assert_impl_all!(Replayer: Clone, Debug, Send, Sync);
+    assert_impl_all!(ReplayErrorType: Debug, Send, Sync);
+    assert_fields!(ReplayErrorType::Parsing: line);
+    assert_impl_all!(ReplayError: Error, Send, Sync);
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> IoResult<()> {
+            Ok(())
+        }
+    }
+
+    /// Wait for the provided number of lines to be written to the buffer.
+    async fn written(buffer: &SharedBuffer, lines: usize) -> Vec<u8> {
+        let wait = async {
+            loop {
+                let bytes = buffer.0.lock().unwrap().clone();
+
+                if bytes.iter().filter(|byte| **byte == b'\n').count() >= lines {
+                    return bytes;
+                }
+
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        };
+
+        tokio::time::timeout(Duration::from_secs(5), wait)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_record_replay() {
+        let buffer = SharedBuffer::default();
+        let recorder = Recorder::new(buffer.clone());
+
+        recorder.record(0, 11, None, None, r#"{"op":11,"d":null}"#);
+        recorder.record(1, 7, None, None, r#"{"op":7,"d":null}"#);
+        recorder.record(
+            0,
+            0,
+            Some(2),
+            Some("GUILD_DELETE"),
+            r#"{"op":0,"s":2,"t":"GUILD_DELETE","d":{"id":"1","unavailable":true}}"#,
+        );
+
+        let bytes = written(&buffer, 3).await;
+        let replayer = Replayer::from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(3, replayer.payloads.len());
+
+        let payload: &RecordedPayload = &replayer.payloads[2];
+        assert_eq!(Some("GUILD_DELETE"), payload.event_type.as_deref());
+        assert_eq!(Some(2), payload.seq);
+
+        let events = replayer
+            .event_types(EventTypeFlags::GATEWAY_HEARTBEAT_ACK | EventTypeFlags::GUILD_DELETE)
+            .shard(0)
+            .start()
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(2, events.len());
+        assert!(matches!(events[0], Event::GatewayHeartbeatAck));
+        assert!(matches!(&events[1], Event::GuildDelete(guild) if guild.id.0 == 1));
+    }
+
+    /// Test that shards are connected once they are ready or resumed, like
+    /// live shards.
+    #[tokio::test]
+    async fn test_replay_connected() {
+        let payload =
+            |shard_id, op, seq, event_type: Option<&str>, payload: &str| RecordedPayload {
+                event_type: event_type.map(ToOwned::to_owned),
+                op,
+                payload: payload.to_owned(),
+                seq,
+                shard_id,
+                timestamp: 0,
+            };
+
+        let ready = r#"{"op":0,"s":1,"t":"READY","d":{"application":{"flags":0,"id":"1"},"guilds":[],"session_id":"session","shard":[0,1],"user":{"avatar":null,"bot":true,"discriminator":"0001","id":"2","mfa_enabled":true,"username":"test","verified":true},"v":8}}"#;
+
+        let events = Replayer::new(vec![
+            payload(
+                0,
+                10,
+                None,
+                None,
+                r#"{"op":10,"d":{"heartbeat_interval":41250}}"#,
+            ),
+            payload(0, 0, Some(1), Some("READY"), ready),
+            payload(
+                1,
+                0,
+                Some(2),
+                Some("RESUMED"),
+                r#"{"op":0,"s":2,"t":"RESUMED","d":null}"#,
+            ),
+        ])
+        .event_types(EventTypeFlags::SHARD_CONNECTED | EventTypeFlags::READY)
+        .start()
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(3, events.len());
+        assert_eq!(
+            Event::ShardConnected(Connected {
+                heartbeat_interval: 41_250,
+                shard_id: 0,
+            }),
+            events[0]
+        );
+        assert!(matches!(events[1], Event::Ready(_)));
+        assert_eq!(
+            Event::ShardConnected(Connected {
+                heartbeat_interval: 0,
+                shard_id: 1,
+            }),
+            events[2]
+        );
+    }
+
+    #[test]
+    fn test_parsing_error() {
+        let recording = b"\n{}\n";
+        let error = Replayer::from_reader(&recording[..]).unwrap_err();
+
+        assert!(matches!(error.kind(), ReplayErrorType::Parsing { line: 2 }));
+    }
+}
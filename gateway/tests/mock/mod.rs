@@ -0,0 +1,412 @@
+//! Scriptable local stand-in for Discord's gateway.
+//!
+//! A [`MockGateway`] listens on a local port that a shard can connect to via
+//! [`ShardBuilder::gateway_url`]. Each connection the shard makes is accepted
+//! as a [`MockConnection`], which the test drives step by step: sending Hello,
+//! validating the Identify or Resume the shard sends, injecting dispatch
+//! events, and closing the connection with a close code.
+//!
+//! If the shard requests zlib-stream compression, payloads are compressed
+//! with a single zlib context per connection like Discord does. If it requests
+//! the ETF encoding, payloads are encoded as ETF instead of JSON.
+//!
+//! [`ShardBuilder::gateway_url`]: twilight_gateway::shard::ShardBuilder::gateway_url
+
+#![allow(dead_code)]
+
+use flate2::{write::ZlibEncoder, Compression};
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::{
+    borrow::Cow,
+    future::Future,
+    io::Write,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{
+    tungstenite::{
+        handshake::server::{Request, Response},
+        protocol::{frame::coding::CloseCode, CloseFrame},
+        Message,
+    },
+    WebSocketStream,
+};
+use twilight_gateway::{
+    queue::Queue,
+    shard::{Events, Shard, ShardBuilder},
+    Event, Intents,
+};
+
+/// Token the test shards identify with.
+pub const TOKEN: &str = "Bot mock-token";
+
+/// Maximum time to wait for the shard to do something before failing.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Queue allowing shards to identify immediately.
+#[derive(Debug)]
+pub struct InstantQueue;
+
+impl Queue for InstantQueue {
+    fn request(&'_ self, _: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async {})
+    }
+}
+
+/// Local gateway accepting shard connections.
+pub struct MockGateway {
+    listener: TcpListener,
+    url: String,
+}
+
+impl MockGateway {
+    /// Bind to a free local port.
+    pub async fn bind() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        Self { listener, url }
+    }
+
+    /// Create a shard builder pointed at the gateway.
+    pub fn builder(&self) -> ShardBuilder {
+        Shard::builder(TOKEN, Intents::GUILDS)
+            .gateway_url(Some(self.url.clone()))
+            .queue(Arc::new(Box::new(InstantQueue)))
+    }
+
+    /// Create a shard pointed at the gateway, without starting it.
+    pub fn shard(&self) -> (Shard, Events) {
+        self.builder().build()
+    }
+
+    /// Start a shard pointed at the gateway, accepting its connection.
+    ///
+    /// Starting a shard only completes once the gateway has accepted the
+    /// connection, so both have to be done concurrently.
+    pub async fn start(&self, shard: &Shard) -> MockConnection {
+        let (started, conn) = tokio::join!(shard.start(), self.accept());
+        started.expect("shard failed to start");
+
+        conn
+    }
+
+    /// URL to point a shard at.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Accept the next connection of a shard.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no shard connects within the timeout.
+    pub async fn accept(&self) -> MockConnection {
+        tokio::time::timeout(TIMEOUT, self.try_accept())
+            .await
+            .expect("shard did not connect")
+    }
+
+    /// Wait for a shard to connect for up to the provided duration.
+    pub async fn accept_within(&self, duration: Duration) -> Option<MockConnection> {
+        tokio::time::timeout(duration, self.try_accept()).await.ok()
+    }
+
+    async fn try_accept(&self) -> MockConnection {
+        let (stream, _) = self.listener.accept().await.unwrap();
+        let query = Arc::new(Mutex::new(None));
+        let callback_query = Arc::clone(&query);
+
+        let stream =
+            tokio_tungstenite::accept_hdr_async(stream, move |request: &Request, response| {
+                callback_query
+                    .lock()
+                    .unwrap()
+                    .replace(request.uri().query().unwrap_or_default().to_owned());
+
+                Ok::<Response, _>(response)
+            })
+            .await
+            .unwrap();
+
+        let query = query.lock().unwrap().take().unwrap_or_default();
+        let compressor = if query.split('&').any(|pair| pair == "compress=zlib-stream") {
+            Some(ZlibEncoder::new(Vec::new(), Compression::default()))
+        } else {
+            None
+        };
+        let etf = query.split('&').any(|pair| pair == "encoding=etf");
+
+        MockConnection {
+            ack_heartbeats: true,
+            compressor,
+            etf,
+            query,
+            seq: 0,
+            stream,
+        }
+    }
+}
+
+/// Connection of a shard to the mock gateway.
+pub struct MockConnection {
+    ack_heartbeats: bool,
+    compressor: Option<ZlibEncoder<Vec<u8>>>,
+    etf: bool,
+    query: String,
+    seq: u64,
+    stream: WebSocketStream<TcpStream>,
+}
+
+impl MockConnection {
+    /// Query string of the URL the shard connected with.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Whether payloads are zlib-stream compressed.
+    pub const fn compressed(&self) -> bool {
+        self.compressor.is_some()
+    }
+
+    /// Whether payloads are encoded as ETF.
+    pub const fn etf(&self) -> bool {
+        self.etf
+    }
+
+    /// Set whether heartbeats skipped while waiting for other payloads are
+    /// acknowledged.
+    ///
+    /// Defaults to `true`.
+    pub fn ack_heartbeats(&mut self, ack_heartbeats: bool) {
+        self.ack_heartbeats = ack_heartbeats;
+    }
+
+    /// Send a raw payload.
+    pub async fn send(&mut self, payload: Value) {
+        let bytes = encode(self.etf, &payload);
+
+        let message = if let Some(compressor) = self.compressor.as_mut() {
+            compressor.write_all(&bytes).unwrap();
+            compressor.flush().unwrap();
+
+            Message::Binary(compressor.get_mut().drain(..).collect())
+        } else if self.etf {
+            Message::Binary(bytes)
+        } else {
+            Message::Text(String::from_utf8(bytes).unwrap())
+        };
+
+        // The shard may have already closed the connection.
+        let _res = self.stream.send(message).await;
+    }
+
+    /// Send a Hello with the provided heartbeat interval in milliseconds.
+    pub async fn hello(&mut self, heartbeat_interval: u64) {
+        self.send(json!({
+            "op": 10,
+            "d": { "heartbeat_interval": heartbeat_interval },
+        }))
+        .await;
+    }
+
+    /// Send a heartbeat acknowledgement.
+    pub async fn heartbeat_ack(&mut self) {
+        self.send(json!({ "op": 11, "d": null })).await;
+    }
+
+    /// Send an Invalid Session.
+    pub async fn invalid_session(&mut self, resumable: bool) {
+        self.send(json!({ "op": 9, "d": resumable })).await;
+    }
+
+    /// Send a request to reconnect.
+    pub async fn reconnect(&mut self) {
+        self.send(json!({ "op": 7, "d": null })).await;
+    }
+
+    /// Send a dispatch event with the next sequence number, returning the
+    /// sequence number.
+    pub async fn dispatch(&mut self, event_type: &str, data: Value) -> u64 {
+        self.seq += 1;
+        let seq = self.seq;
+
+        self.send(json!({ "op": 0, "s": seq, "t": event_type, "d": data }))
+            .await;
+
+        seq
+    }
+
+    /// Dispatch a Ready event for the session.
+    pub async fn ready(&mut self, session_id: &str) -> u64 {
+        self.dispatch(
+            "READY",
+            json!({
+                "application": { "flags": 0, "id": "1" },
+                "guilds": [],
+                "session_id": session_id,
+                "shard": [0, 1],
+                "user": {
+                    "avatar": null,
+                    "bot": true,
+                    "discriminator": "0001",
+                    "id": "2",
+                    "mfa_enabled": true,
+                    "username": "mock",
+                    "verified": true,
+                },
+                "v": 8,
+            }),
+        )
+        .await
+    }
+
+    /// Dispatch a Resumed event.
+    pub async fn resumed(&mut self) -> u64 {
+        self.dispatch("RESUMED", Value::Null).await
+    }
+
+    /// Close the connection with a close code.
+    pub async fn close(&mut self, code: u16, reason: &str) {
+        let frame = CloseFrame {
+            code: CloseCode::from(code),
+            reason: Cow::Owned(reason.to_owned()),
+        };
+
+        let _res = self.stream.close(Some(frame)).await;
+    }
+
+    /// Receive the next payload sent by the shard.
+    ///
+    /// Returns `None` if the connection was closed.
+    pub async fn recv(&mut self) -> Option<Value> {
+        loop {
+            let message = tokio::time::timeout(TIMEOUT, self.stream.next())
+                .await
+                .expect("shard did not send a payload")?
+                .ok()?;
+
+            match message {
+                Message::Binary(bytes) => return Some(decode(self.etf, &bytes)),
+                Message::Text(text) => return Some(serde_json::from_str(&text).unwrap()),
+                Message::Close(_) => return None,
+                Message::Ping(_) | Message::Pong(_) => {}
+            }
+        }
+    }
+
+    /// Receive the next payload with the provided opcode, skipping and
+    /// possibly acknowledging heartbeats.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a payload with another opcode is received or the connection
+    /// is closed.
+    pub async fn expect(&mut self, op: u64) -> Value {
+        loop {
+            let payload = self.recv().await.expect("connection closed");
+            let received = payload["op"].as_u64().unwrap();
+
+            if received == op {
+                return payload;
+            }
+
+            assert_eq!(1, received, "unexpected payload: {}", payload);
+
+            if self.ack_heartbeats {
+                self.heartbeat_ack().await;
+            }
+        }
+    }
+
+    /// Receive a heartbeat, returning its sequence number.
+    pub async fn expect_heartbeat(&mut self) -> Option<u64> {
+        self.expect(1).await["d"].as_u64()
+    }
+
+    /// Receive an Identify, validating its token and shard information.
+    pub async fn expect_identify(&mut self) -> Value {
+        let identify = self.expect(2).await;
+        let data = &identify["d"];
+
+        assert_eq!(TOKEN, data["token"]);
+        assert_eq!(json!([0, 1]), data["shard"]);
+        assert!(data["intents"].is_u64());
+        assert!(data["properties"].is_object());
+
+        identify
+    }
+
+    /// Receive a Resume, validating its token, session ID and sequence
+    /// number.
+    ///
+    /// Following dispatch events continue from the resumed sequence number.
+    pub async fn expect_resume(&mut self, session_id: &str, seq: u64) {
+        let resume = self.expect(6).await;
+        let data = &resume["d"];
+
+        assert_eq!(TOKEN, data["token"]);
+        assert_eq!(session_id, data["session_id"]);
+        assert_eq!(seq, data["seq"]);
+
+        self.seq = seq;
+    }
+
+    /// Assert that the shard closes the connection.
+    pub async fn expect_closed(&mut self) {
+        while let Some(payload) = self.recv().await {
+            assert_eq!(1, payload["op"], "unexpected payload: {}", payload);
+        }
+    }
+}
+
+/// Encode a payload as ETF or JSON.
+fn encode(etf: bool, payload: &Value) -> Vec<u8> {
+    #[cfg(feature = "etf")]
+    if etf {
+        return twilight_gateway::shard::etf::to_vec(payload).unwrap();
+    }
+
+    assert!(!etf, "ETF requires the `etf` feature");
+
+    serde_json::to_vec(payload).unwrap()
+}
+
+/// Decode a payload from ETF or JSON.
+fn decode(etf: bool, bytes: &[u8]) -> Value {
+    #[cfg(feature = "etf")]
+    if etf {
+        return twilight_gateway::shard::etf::from_slice(bytes).unwrap();
+    }
+
+    assert!(!etf, "ETF requires the `etf` feature");
+
+    serde_json::from_slice(bytes).unwrap()
+}
+
+/// Wait for the next event of the shard.
+///
+/// # Panics
+///
+/// Panics if the event stream ends or no event is received within the
+/// timeout.
+pub async fn next_event(events: &mut Events) -> Event {
+    tokio::time::timeout(TIMEOUT, events.next())
+        .await
+        .expect("no event received")
+        .expect("event stream ended")
+}
+
+/// Wait for an event matching the predicate, skipping others.
+pub async fn wait_for(events: &mut Events, predicate: impl Fn(&Event) -> bool) -> Event {
+    loop {
+        let event = next_event(events).await;
+
+        if predicate(&event) {
+            return event;
+        }
+    }
+}
@@ -0,0 +1,309 @@
+mod mock;
+
+use mock::{next_event, wait_for, MockConnection, MockGateway, TOKEN};
+use serde_json::json;
+use std::{error::Error, sync::Arc, time::Duration};
+use tokio::{
+    io::{self, AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use twilight_gateway::{
+    queue::LocalQueue,
+    shard::{Events, Proxy, Shard, ShardFatalError, ShardFatalErrorType, Stage},
+    Event,
+};
+use twilight_http::Client as HttpClient;
+use twilight_model::gateway::{
+    event::shard::ZombieReason, payload::identify::IdentifyProperties, CloseCodeKind,
+};
+
+const SESSION_ID: &str = "mock-session";
+
+/// Connect a shard to the gateway and identify a new session.
+async fn connected(gateway: &MockGateway) -> (Shard, Events, MockConnection) {
+    let (shard, mut events) = gateway.shard();
+    let mut conn = gateway.start(&shard).await;
+    conn.hello(45_000).await;
+    conn.expect_identify().await;
+    conn.ready(SESSION_ID).await;
+    wait_for(&mut events, |event| matches!(event, Event::Ready(_))).await;
+
+    (shard, events, conn)
+}
+
+#[tokio::test]
+async fn test_identify() -> Result<(), Box<dyn Error>> {
+    let gateway = MockGateway::bind().await;
+    let (shard, mut events) = gateway.shard();
+    let mut conn = gateway.start(&shard).await;
+    assert!(conn.query().contains("v=8"));
+    assert_eq!(cfg!(feature = "compression"), conn.compressed());
+    assert_eq!(cfg!(feature = "etf"), conn.etf());
+
+    assert!(matches!(next_event(&mut events).await, Event::ShardConnecting(c) if c.shard_id == 0));
+
+    conn.hello(45_000).await;
+    conn.expect_identify().await;
+    assert!(matches!(next_event(&mut events).await, Event::ShardIdentifying(c) if c.shard_id == 0));
+    assert!(matches!(
+        next_event(&mut events).await,
+        Event::GatewayHello(45_000)
+    ));
+
+    conn.ready(SESSION_ID).await;
+    assert!(matches!(
+        next_event(&mut events).await,
+        Event::ShardConnected(_)
+    ));
+    assert!(matches!(next_event(&mut events).await, Event::Ready(r) if r.session_id == SESSION_ID));
+
+    conn.dispatch("GUILD_DELETE", json!({ "id": "1", "unavailable": true }))
+        .await;
+    assert!(matches!(next_event(&mut events).await, Event::GuildDelete(g) if g.id.0 == 1));
+
+    let info = shard.info()?;
+    assert_eq!(Stage::Connected, info.stage());
+    assert_eq!(Some(SESSION_ID), info.session_id());
+    assert_eq!(2, info.seq());
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_identify_properties_through_proxy() -> Result<(), Box<dyn Error>> {
+    let gateway = MockGateway::bind().await;
+    let proxy = TcpListener::bind("127.0.0.1:0").await?;
+    let proxy_address = proxy.local_addr()?.to_string();
+
+    // Tunnel a single connection to the host requested via CONNECT.
+    tokio::spawn(async move {
+        let (mut client, _) = proxy.accept().await.unwrap();
+        let mut request = Vec::new();
+
+        while !request.ends_with(b"\r\n\r\n") {
+            request.push(client.read_u8().await.unwrap());
+        }
+
+        let request = String::from_utf8(request).unwrap();
+        let target = request.split(' ').nth(1).unwrap();
+        let mut server = TcpStream::connect(target).await.unwrap();
+        client
+            .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+            .await
+            .unwrap();
+        let _ = io::copy_bidirectional(&mut client, &mut server).await;
+    });
+
+    let (shard, _events) = gateway
+        .builder()
+        .identify_properties(IdentifyProperties::new(
+            "Discord iOS",
+            "twilight.rs",
+            "iOS",
+            "",
+            "",
+        ))
+        .proxy(Some(Proxy::http(proxy_address)))
+        .build();
+    let mut conn = gateway.start(&shard).await;
+
+    conn.hello(45_000).await;
+    let identify = conn.expect_identify().await;
+    assert_eq!("Discord iOS", identify["d"]["properties"]["$browser"]);
+    assert_eq!("iOS", identify["d"]["properties"]["$os"]);
+
+    shard.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resume_after_close() -> Result<(), Box<dyn Error>> {
+    let gateway = MockGateway::bind().await;
+    let (_shard, mut events, mut conn) = connected(&gateway).await;
+    let seq = conn
+        .dispatch("GUILD_DELETE", json!({ "id": "1", "unavailable": true }))
+        .await;
+
+    conn.close(4000, "unknown error").await;
+    assert!(matches!(
+        wait_for(&mut events, |e| matches!(e, Event::ShardDisconnected(_))).await,
+        Event::ShardDisconnected(d) if d.code == Some(4000)
+    ));
+    assert!(matches!(next_event(&mut events).await, Event::ShardResuming(r) if r.seq == seq));
+
+    let mut conn = gateway.accept().await;
+    conn.hello(45_000).await;
+    conn.expect_resume(SESSION_ID, seq).await;
+    conn.resumed().await;
+    wait_for(&mut events, |e| matches!(e, Event::ShardConnected(_))).await;
+
+    conn.dispatch("GUILD_DELETE", json!({ "id": "2", "unavailable": true }))
+        .await;
+    assert!(matches!(
+        wait_for(&mut events, |e| matches!(e, Event::GuildDelete(_))).await,
+        Event::GuildDelete(g) if g.id.0 == 2
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reconnect_request() -> Result<(), Box<dyn Error>> {
+    let gateway = MockGateway::bind().await;
+    let (_shard, mut events, mut conn) = connected(&gateway).await;
+
+    conn.reconnect().await;
+    assert!(matches!(
+        next_event(&mut events).await,
+        Event::ShardDisconnected(d) if d.code == Some(1012)
+    ));
+    assert!(matches!(next_event(&mut events).await, Event::ShardResuming(r) if r.seq == 1));
+    conn.expect_closed().await;
+
+    let mut conn = gateway.accept().await;
+    conn.hello(45_000).await;
+    conn.expect_resume(SESSION_ID, 1).await;
+    conn.resumed().await;
+    wait_for(&mut events, |e| matches!(e, Event::ShardConnected(_))).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_session_resumable() -> Result<(), Box<dyn Error>> {
+    let gateway = MockGateway::bind().await;
+    let (_shard, mut events, mut conn) = connected(&gateway).await;
+
+    conn.invalid_session(true).await;
+    wait_for(&mut events, |e| matches!(e, Event::ShardResuming(_))).await;
+
+    let mut conn = gateway.accept().await;
+    conn.hello(45_000).await;
+    conn.expect_resume(SESSION_ID, 1).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_session_reidentifies() -> Result<(), Box<dyn Error>> {
+    let gateway = MockGateway::bind().await;
+    let (_shard, mut events, mut conn) = connected(&gateway).await;
+
+    conn.invalid_session(false).await;
+    assert!(matches!(
+        next_event(&mut events).await,
+        Event::ShardDisconnected(d) if d.code.is_none()
+    ));
+
+    let mut conn = gateway.accept().await;
+    wait_for(&mut events, |e| matches!(e, Event::ShardReconnecting(_))).await;
+
+    conn.hello(45_000).await;
+    conn.expect_identify().await;
+    conn.ready("new-session").await;
+    assert!(matches!(
+        wait_for(&mut events, |e| matches!(e, Event::Ready(_))).await,
+        Event::Ready(r) if r.session_id == "new-session"
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_start_limit_exhausted() {
+    let gateway = MockGateway::bind().await;
+    let queue = LocalQueue::with_session_start_limit(
+        &HttpClient::new(TOKEN.to_owned()),
+        1_000,
+        0,
+        Duration::from_secs(60),
+    );
+    let (shard, mut events) = gateway.builder().queue(Arc::new(Box::new(queue))).build();
+
+    // Starting only completes once the limit is reset.
+    let start = tokio::spawn(async move { shard.start().await });
+
+    assert!(matches!(
+        next_event(&mut events).await,
+        Event::ShardSessionStartLimitExhausted(e)
+            if e.remaining == 0 && e.reset_after <= 60_000 && e.shard_id == 0
+    ));
+
+    start.abort();
+}
+
+#[tokio::test]
+async fn test_authentication_failed_is_fatal() -> Result<(), Box<dyn Error>> {
+    let gateway = MockGateway::bind().await;
+    let (shard, mut events) = gateway.shard();
+    let mut conn = gateway.start(&shard).await;
+    conn.hello(45_000).await;
+    conn.expect_identify().await;
+    conn.close(4004, "authentication failed").await;
+
+    assert!(matches!(
+        wait_for(&mut events, |e| matches!(e, Event::ShardDisconnected(_))).await,
+        Event::ShardDisconnected(d)
+            if d.code == Some(4004) && d.kind == Some(CloseCodeKind::Fatal)
+    ));
+
+    // The error is set before the disconnect is emitted.
+    assert!(matches!(
+        shard.fatal_error().map(ShardFatalError::kind),
+        Some(ShardFatalErrorType::AuthenticationFailed)
+    ));
+    assert!(gateway
+        .accept_within(Duration::from_secs(2))
+        .await
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_heartbeats() -> Result<(), Box<dyn Error>> {
+    let gateway = MockGateway::bind().await;
+    let (shard, mut events) = gateway.shard();
+    let mut conn = gateway.start(&shard).await;
+    conn.hello(200).await;
+    conn.expect_identify().await;
+    let seq = conn.ready(SESSION_ID).await;
+
+    // The first heartbeat may be sent before the Ready event is processed.
+    while conn.expect_heartbeat().await != Some(seq) {}
+    conn.heartbeat_ack().await;
+    wait_for(&mut events, |e| matches!(e, Event::GatewayHeartbeatAck)).await;
+
+    let latency = shard.info()?.latency().clone();
+    assert!(latency.heartbeats() >= 1);
+    assert_eq!(0, latency.missed_acks());
+    assert_eq!(Some(Duration::from_millis(200)), latency.interval());
+    assert!(latency.jitter() < latency.interval());
+
+    // Without acknowledgements the connection is a zombie after the default
+    // threshold of two missed acknowledgements and the session is resumed.
+    conn.ack_heartbeats(false);
+    conn.expect_heartbeat().await;
+    conn.expect_heartbeat().await;
+    assert!(matches!(
+        wait_for(&mut events, |e| matches!(e, Event::ShardZombied(_))).await,
+        Event::ShardZombied(z) if z.missed_acks == 2 && z.reason == ZombieReason::HeartbeatAckMissed
+    ));
+    assert!(matches!(
+        next_event(&mut events).await,
+        Event::ShardDisconnected(_)
+    ));
+    assert!(matches!(next_event(&mut events).await, Event::ShardResuming(r) if r.seq == seq));
+
+    // The zombie connection is dropped once the new connection is
+    // established.
+    let mut new_conn = gateway.accept().await;
+    conn.expect_closed().await;
+    new_conn.hello(45_000).await;
+    new_conn.expect_resume(SESSION_ID, seq).await;
+
+    Ok(())
+}
@@ -49,10 +49,13 @@ use twilight_model::{
         command::{permissions::CommandPermissions, Command},
     },
     channel::message::allowed_mentions::AllowedMentions,
-    guild::Permissions,
+    guild::{
+        auto_moderation::{AutoModerationEventType, AutoModerationTriggerType},
+        Permissions,
+    },
     id::{
-        ApplicationId, ChannelId, CommandId, EmojiId, GuildId, IntegrationId, InteractionId,
-        MessageId, RoleId, UserId, WebhookId,
+        ApplicationId, AutoModerationRuleId, ChannelId, CommandId, EmojiId, GuildId, IntegrationId,
+        InteractionId, MessageId, RoleId, UserId, WebhookId,
     },
 };
 
@@ -241,6 +244,53 @@ impl Client {
         GetAuditLog::new(self, guild_id)
     }
 
+    /// Get the auto moderation rules of a guild.
+    pub fn auto_moderation_rules(&self, guild_id: GuildId) -> GetAutoModerationRules<'_> {
+        GetAutoModerationRules::new(self, guild_id)
+    }
+
+    /// Get an auto moderation rule of a guild.
+    pub fn auto_moderation_rule(
+        &self,
+        guild_id: GuildId,
+        auto_moderation_rule_id: AutoModerationRuleId,
+    ) -> GetAutoModerationRule<'_> {
+        GetAutoModerationRule::new(self, guild_id, auto_moderation_rule_id)
+    }
+
+    /// Create an auto moderation rule in a guild.
+    ///
+    /// The rule's actions, exempt channels and roles, and trigger metadata can
+    /// be set on the returned builder. Refer to [`CreateAutoModerationRule`]
+    /// for an example.
+    pub fn create_auto_moderation_rule(
+        &self,
+        guild_id: GuildId,
+        name: impl Into<String>,
+        event_type: AutoModerationEventType,
+        trigger_type: AutoModerationTriggerType,
+    ) -> CreateAutoModerationRule<'_> {
+        CreateAutoModerationRule::new(self, guild_id, name, event_type, trigger_type)
+    }
+
+    /// Update an auto moderation rule of a guild.
+    pub fn update_auto_moderation_rule(
+        &self,
+        guild_id: GuildId,
+        auto_moderation_rule_id: AutoModerationRuleId,
+    ) -> UpdateAutoModerationRule<'_> {
+        UpdateAutoModerationRule::new(self, guild_id, auto_moderation_rule_id)
+    }
+
+    /// Delete an auto moderation rule of a guild.
+    pub fn delete_auto_moderation_rule(
+        &self,
+        guild_id: GuildId,
+        auto_moderation_rule_id: AutoModerationRuleId,
+    ) -> DeleteAutoModerationRule<'_> {
+        DeleteAutoModerationRule::new(self, guild_id, auto_moderation_rule_id)
+    }
+
     /// Retrieve the bans for a guild.
     ///
     /// # Examples
@@ -1654,12 +1704,20 @@ impl Client {
     }
 
     /// Respond to an interaction, by ID and token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InteractionErrorType::ModalValidationFailed`] error type
+    /// if the response is a modal exceeding one of the limits listed in
+    /// [`ModalValidationError`].
+    ///
+    /// [`ModalValidationError`]: crate::request::application::ModalValidationError
     pub fn interaction_callback(
         &self,
         interaction_id: InteractionId,
         interaction_token: impl Into<String>,
         response: InteractionResponse,
-    ) -> InteractionCallback<'_> {
+    ) -> Result<InteractionCallback<'_>, InteractionError> {
         InteractionCallback::new(self, interaction_id, interaction_token, response)
     }
 
@@ -1676,6 +1734,7 @@ impl Client {
     ) -> Result<UpdateOriginalResponse<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(UpdateOriginalResponse::new(
@@ -1698,6 +1757,7 @@ impl Client {
     ) -> Result<DeleteOriginalResponse<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(DeleteOriginalResponse::new(
@@ -1720,6 +1780,7 @@ impl Client {
     ) -> Result<CreateFollowupMessage<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(CreateFollowupMessage::new(
@@ -1743,6 +1804,7 @@ impl Client {
     ) -> Result<UpdateFollowupMessage<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(UpdateFollowupMessage::new(
@@ -1767,6 +1829,7 @@ impl Client {
     ) -> Result<DeleteFollowupMessage<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(DeleteFollowupMessage::new(
@@ -1807,6 +1870,7 @@ impl Client {
     ) -> Result<CreateGuildCommand<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         CreateGuildCommand::new(&self, application_id, guild_id, name, description)
@@ -1825,6 +1889,7 @@ impl Client {
     ) -> Result<GetGuildCommands<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(GetGuildCommands::new(self, application_id, guild_id))
@@ -1849,6 +1914,7 @@ impl Client {
     ) -> Result<UpdateGuildCommand<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(UpdateGuildCommand::new(
@@ -1873,6 +1939,7 @@ impl Client {
     ) -> Result<DeleteGuildCommand<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(DeleteGuildCommand::new(
@@ -1900,6 +1967,7 @@ impl Client {
     ) -> Result<SetGuildCommands<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(SetGuildCommands::new(
@@ -1938,6 +2006,7 @@ impl Client {
     ) -> Result<CreateGlobalCommand<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         CreateGlobalCommand::new(self, application_id, name, description)
@@ -1953,6 +2022,7 @@ impl Client {
     pub fn get_global_commands(&self) -> Result<GetGlobalCommands<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(GetGlobalCommands::new(self, application_id))
@@ -1976,6 +2046,7 @@ impl Client {
     ) -> Result<UpdateGlobalCommand<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(UpdateGlobalCommand::new(self, application_id, command_id))
@@ -1994,6 +2065,7 @@ impl Client {
     ) -> Result<DeleteGlobalCommand<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(DeleteGlobalCommand::new(self, application_id, command_id))
@@ -2015,6 +2087,7 @@ impl Client {
     ) -> Result<SetGlobalCommands<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(SetGlobalCommands::new(self, application_id, commands))
@@ -2035,6 +2108,7 @@ impl Client {
     ) -> Result<GetCommandPermissions<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(GetCommandPermissions::new(
@@ -2059,6 +2133,7 @@ impl Client {
     ) -> Result<GetGuildCommandPermissions<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         Ok(GetGuildCommandPermissions::new(
@@ -2086,6 +2161,7 @@ impl Client {
     ) -> Result<UpdateCommandPermissions<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         UpdateCommandPermissions::new(self, application_id, guild_id, command_id, permissions)
@@ -2108,6 +2184,7 @@ impl Client {
     ) -> Result<SetCommandPermissions<'_>, InteractionError> {
         let application_id = self.application_id().ok_or(InteractionError {
             kind: InteractionErrorType::ApplicationIdNotPresent,
+            source: None,
         })?;
 
         SetCommandPermissions::new(self, application_id, guild_id, permissions)
@@ -1,7 +1,7 @@
 use crate::{
     client::Client,
     error::Error,
-    request::{Form, Pending, Request},
+    request::{attachment::AttachmentList, Form, MessageAttachment, Pending, Request},
     routing::Route,
 };
 use serde::Serialize;
@@ -16,6 +16,8 @@ use twilight_model::{
 
 #[derive(Default, Serialize)]
 pub(crate) struct CreateFollowupMessageFields {
+    #[serde(skip_serializing_if = "AttachmentList::is_empty")]
+    attachments: AttachmentList,
     #[serde(skip_serializing_if = "Option::is_none")]
     avatar_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -60,7 +62,6 @@ pub(crate) struct CreateFollowupMessageFields {
 /// [`file`]: Self::file
 pub struct CreateFollowupMessage<'a> {
     pub(crate) fields: CreateFollowupMessageFields,
-    files: Vec<(String, Vec<u8>)>,
     fut: Option<Pending<'a, Option<Message>>>,
     http: &'a Client,
     token: String,
@@ -75,7 +76,6 @@ impl<'a> CreateFollowupMessage<'a> {
     ) -> Self {
         Self {
             fields: CreateFollowupMessageFields::default(),
-            files: Vec::new(),
             fut: None,
             http,
             token: token.into(),
@@ -124,15 +124,33 @@ impl<'a> CreateFollowupMessage<'a> {
         self
     }
 
-    /// Attach a file to the webhook.
+    /// Attach a file to the followup message, described by a [`MessageAttachment`].
     ///
-    /// This method is repeatable.
-    pub fn file(mut self, name: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
-        self.files.push((name.into(), file.into()));
+    /// Use this to set a description for the file or to mark it as a
+    /// spoiler.
+    pub fn attachment(mut self, attachment: MessageAttachment) -> Self {
+        self.fields.attachments.push(attachment);
+
+        self
+    }
+
+    /// Attach multiple files to the followup message, described by
+    /// [`MessageAttachment`]s.
+    pub fn attachments(mut self, attachments: impl IntoIterator<Item = MessageAttachment>) -> Self {
+        for attachment in attachments {
+            self.fields.attachments.push(attachment);
+        }
 
         self
     }
 
+    /// Attach a file to the webhook.
+    ///
+    /// This method is repeatable.
+    pub fn file(self, name: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
+        self.attachment(MessageAttachment::from_bytes(name, file))
+    }
+
     /// Attach multiple files to the webhook.
     pub fn files<N: Into<String>, F: Into<Vec<u8>>>(
         mut self,
@@ -226,12 +244,10 @@ impl<'a> CreateFollowupMessage<'a> {
             webhook_id: self.application_id.0,
         });
 
-        if !self.files.is_empty() || self.fields.payload_json.is_some() {
+        if self.fields.attachments.has_files() || self.fields.payload_json.is_some() {
             let mut form = Form::new();
 
-            for (index, (name, file)) in self.files.drain(..).enumerate() {
-                form.file(format!("{}", index).as_bytes(), name.as_bytes(), &file);
-            }
+            self.fields.attachments.write_files(&mut form);
 
             if let Some(payload_json) = &self.fields.payload_json {
                 form.payload_json(&payload_json);
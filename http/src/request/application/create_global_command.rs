@@ -41,11 +41,13 @@ impl<'a> CreateGlobalCommand<'a> {
         if !validate::command_name(&name) {
             return Err(InteractionError {
                 kind: InteractionErrorType::CommandNameValidationFailed { name },
+                source: None,
             });
         }
         if !validate::command_description(&description) {
             return Err(InteractionError {
                 kind: InteractionErrorType::CommandDescriptionValidationFailed { description },
+                source: None,
             });
         }
 
@@ -82,6 +84,7 @@ impl<'a> CreateGlobalCommand<'a> {
         if option.is_required() && self.optional_option_added {
             return Err(InteractionError {
                 kind: InteractionErrorType::CommandOptionsRequiredFirst { option },
+                source: None,
             });
         }
 
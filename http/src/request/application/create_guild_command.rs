@@ -43,12 +43,14 @@ impl<'a> CreateGuildCommand<'a> {
         if !validate::command_name(&name) {
             return Err(InteractionError {
                 kind: InteractionErrorType::CommandNameValidationFailed { name },
+                source: None,
             });
         }
 
         if !validate::command_description(&description) {
             return Err(InteractionError {
                 kind: InteractionErrorType::CommandDescriptionValidationFailed { description },
+                source: None,
             });
         }
 
@@ -94,6 +96,7 @@ impl<'a> CreateGuildCommand<'a> {
         if option.is_required() && self.optional_option_added {
             return Err(InteractionError {
                 kind: InteractionErrorType::CommandOptionsRequiredFirst { option },
+                source: None,
             });
         }
 
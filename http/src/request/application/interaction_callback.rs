@@ -1,12 +1,20 @@
 use crate::{
     client::Client,
     error::Error,
-    request::{Pending, Request},
+    request::{
+        application::{InteractionError, InteractionErrorType},
+        validate, Pending, Request,
+    },
     routing::Route,
 };
 use twilight_model::{application::callback::InteractionResponse, id::InteractionId};
 
 /// Respond to an interaction, by ID and token.
+///
+/// Modal responses are validated against the limits listed in
+/// [`ModalValidationError`].
+///
+/// [`ModalValidationError`]: super::ModalValidationError
 pub struct InteractionCallback<'a> {
     interaction_id: InteractionId,
     interaction_token: String,
@@ -21,14 +29,28 @@ impl<'a> InteractionCallback<'a> {
         interaction_id: InteractionId,
         interaction_token: impl Into<String>,
         response: InteractionResponse,
-    ) -> Self {
-        Self {
+    ) -> Result<Self, InteractionError> {
+        let invalid = match &response {
+            InteractionResponse::Modal(modal) => validate::modal(modal).err(),
+            _ => None,
+        };
+
+        if let Some(source) = invalid {
+            return Err(InteractionError {
+                kind: InteractionErrorType::ModalValidationFailed {
+                    response: Box::new(response),
+                },
+                source: Some(Box::new(source)),
+            });
+        }
+
+        Ok(Self {
             interaction_id,
             interaction_token: interaction_token.into(),
             response,
             fut: None,
             http,
-        }
+        })
     }
 
     fn start(&mut self) -> Result<(), Error> {
@@ -45,17 +45,19 @@ pub use self::{
         UpdateOriginalResponse, UpdateOriginalResponseError, UpdateOriginalResponseErrorType,
     },
 };
+pub use super::validate::{ModalValidationError, ModalValidationErrorType};
 
 use std::{
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
 };
-use twilight_model::application::command::CommandOption;
+use twilight_model::application::{callback::InteractionResponse, command::CommandOption};
 
 /// The error created if the creation of interaction fails.
 #[derive(Debug)]
 pub struct InteractionError {
     pub(crate) kind: InteractionErrorType,
+    pub(crate) source: Option<Box<dyn Error + Send + Sync>>,
 }
 
 #[derive(Debug)]
@@ -69,6 +71,11 @@ pub enum InteractionErrorType {
     CommandDescriptionValidationFailed { description: String },
     /// Required command options have to be passed before optional ones.
     CommandOptionsRequiredFirst { option: CommandOption },
+    /// Modal response is invalid.
+    ///
+    /// The source error is a [`ModalValidationError`] detailing which limit
+    /// was exceeded.
+    ModalValidationFailed { response: Box<InteractionResponse> },
     /// More than 10 permission overwrites were set.
     TooManyCommandPermissions,
 }
@@ -81,16 +88,15 @@ impl InteractionError {
     }
 
     /// Consume the error, returning the source error if there is any.
-    #[allow(clippy::unused_self)]
     #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
     pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
-        None
+        self.source
     }
 
     /// Consume the error, returning the owned error type and the source error.
     #[must_use = "consuming the error into its parts has no effect if left unused"]
     pub fn into_parts(self) -> (InteractionErrorType, Option<Box<dyn Error + Send + Sync>>) {
-        (self.kind, None)
+        (self.kind, self.source)
     }
 }
 
@@ -109,6 +115,9 @@ impl Display for InteractionError {
             InteractionErrorType::CommandOptionsRequiredFirst { .. } => {
                 f.write_str("optional command options must be added after required")
             }
+            InteractionErrorType::ModalValidationFailed { .. } => {
+                f.write_str("modal response is invalid")
+            }
             InteractionErrorType::TooManyCommandPermissions { .. } => {
                 f.write_str("more than 10 permission overwrites were set")
             }
@@ -116,4 +125,10 @@ impl Display for InteractionError {
     }
 }
 
-impl Error for InteractionError {}
+impl Error for InteractionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
@@ -62,6 +62,7 @@ impl<'a> SetCommandPermissions<'a> {
         {
             return Err(InteractionError {
                 kind: InteractionErrorType::TooManyCommandPermissions,
+                source: None,
             });
         }
 
@@ -44,6 +44,7 @@ impl<'a> UpdateCommandPermissions<'a> {
         if !validate::command_permissions(permissions.len()) {
             return Err(InteractionError {
                 kind: InteractionErrorType::TooManyCommandPermissions,
+                source: None,
             });
         }
 
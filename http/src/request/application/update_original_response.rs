@@ -3,7 +3,10 @@
 use crate::{
     client::Client,
     error::Error as HttpError,
-    request::{validate, Form, NullableField, Pending, Request},
+    request::{
+        attachment::AttachmentList, validate, Form, MessageAttachment, NullableField, Pending,
+        Request,
+    },
     routing::Route,
 };
 use serde::Serialize;
@@ -12,7 +15,7 @@ use std::{
     fmt::{Display, Formatter, Result as FmtResult},
 };
 use twilight_model::{
-    channel::{embed::Embed, message::AllowedMentions, Attachment},
+    channel::{embed::Embed, message::AllowedMentions},
     id::ApplicationId,
 };
 
@@ -107,8 +110,8 @@ pub enum UpdateOriginalResponseErrorType {
 struct UpdateOriginalResponseFields {
     #[serde(skip_serializing_if = "Option::is_none")]
     allowed_mentions: Option<AllowedMentions>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    attachments: Vec<Attachment>,
+    #[serde(skip_serializing_if = "AttachmentList::is_implicit")]
+    attachments: AttachmentList,
     #[serde(skip_serializing_if = "Option::is_none")]
     content: Option<NullableField<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -155,7 +158,6 @@ struct UpdateOriginalResponseFields {
 pub struct UpdateOriginalResponse<'a> {
     application_id: ApplicationId,
     fields: UpdateOriginalResponseFields,
-    files: Vec<(String, Vec<u8>)>,
     fut: Option<Pending<'a, ()>>,
     http: &'a Client,
     token: String,
@@ -176,7 +178,6 @@ impl<'a> UpdateOriginalResponse<'a> {
                 allowed_mentions: http.default_allowed_mentions(),
                 ..UpdateOriginalResponseFields::default()
             },
-            files: Vec::new(),
             fut: None,
             http,
             token: interaction_token.into(),
@@ -190,24 +191,51 @@ impl<'a> UpdateOriginalResponse<'a> {
         self
     }
 
-    /// Specify an attachment already present in the target message to keep.
+    /// Add an attachment to the original response.
+    ///
+    /// The attachment is a new file to upload, which may carry a description.
+    /// Attachments already present in the original response are kept, unless
+    /// [`replace_attachments`] is called.
     ///
-    /// If called, all unspecified attachments will be removed from the message.
-    /// If not called, all attachments will be kept.
-    pub fn attachment(mut self, attachment: Attachment) -> Self {
+    /// [`replace_attachments`]: Self::replace_attachments
+    pub fn attachment(mut self, attachment: MessageAttachment) -> Self {
         self.fields.attachments.push(attachment);
 
         self
     }
 
-    /// Specify multiple attachments already present in the target message to keep.
+    /// Add multiple attachments to the original response.
     ///
-    /// If called, all unspecified attachments will be removed from the message.
-    /// If not called, all attachments will be kept.
-    pub fn attachments(mut self, attachments: impl IntoIterator<Item = Attachment>) -> Self {
-        self.fields
-            .attachments
-            .extend(attachments.into_iter().collect::<Vec<Attachment>>());
+    /// The attachments are new files to upload, which may carry descriptions.
+    /// Attachments already present in the original response are kept, unless
+    /// [`replace_attachments`] is called.
+    ///
+    /// [`replace_attachments`]: Self::replace_attachments
+    pub fn attachments(mut self, attachments: impl IntoIterator<Item = MessageAttachment>) -> Self {
+        self.fields.attachments.extend(attachments);
+
+        self
+    }
+
+    /// Specify the full set of attachments the original response will have after the
+    /// update.
+    ///
+    /// The attachments may either be ones already present in the target
+    /// original response to keep, or new files to upload. Attachments added via
+    /// [`attachment`], [`attachments`], or [`file`] are part of the set. Pass
+    /// an empty list to remove all attachments.
+    ///
+    /// If called, all unspecified attachments will be removed from the
+    /// original response. If not called, all attachments will be kept.
+    ///
+    /// [`attachment`]: Self::attachment
+    /// [`attachments`]: Self::attachments
+    /// [`file`]: Self::file
+    pub fn replace_attachments(
+        mut self,
+        attachments: impl IntoIterator<Item = MessageAttachment>,
+    ) -> Self {
+        self.fields.attachments.replace(attachments);
 
         self
     }
@@ -332,7 +360,9 @@ impl<'a> UpdateOriginalResponse<'a> {
     ///
     /// This method is repeatable.
     pub fn file(mut self, name: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
-        self.files.push((name.into(), file.into()));
+        self.fields
+            .attachments
+            .push(MessageAttachment::from_bytes(name, file));
 
         self
     }
@@ -370,12 +400,10 @@ impl<'a> UpdateOriginalResponse<'a> {
             interaction_token: self.token.clone(),
         });
 
-        if !self.files.is_empty() || self.fields.payload_json.is_some() {
+        if self.fields.attachments.has_files() || self.fields.payload_json.is_some() {
             let mut form = Form::new();
 
-            for (index, (name, file)) in self.files.drain(..).enumerate() {
-                form.file(format!("{}", index).as_bytes(), name.as_bytes(), &file);
-            }
+            self.fields.attachments.write_files(&mut form);
 
             if let Some(payload_json) = &self.fields.payload_json {
                 form.payload_json(&payload_json);
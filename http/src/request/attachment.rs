@@ -0,0 +1,311 @@
+use super::Form;
+use serde::{
+    ser::{SerializeSeq, SerializeStruct},
+    Serialize, Serializer,
+};
+use twilight_model::{channel::Attachment, id::AttachmentId};
+
+/// Prefix Discord uses to mark an attachment as a spoiler.
+const SPOILER_PREFIX: &str = "SPOILER_";
+
+/// Attachment to upload or to keep when creating or updating a message.
+///
+/// Used by the message, webhook, and interaction followup request builders.
+/// New files are uploaded alongside the request body, while existing
+/// attachments are referenced by their ID.
+///
+/// # Examples
+///
+/// Upload a new file with a description and mark it as a spoiler:
+///
+/// ```
+/// use twilight_http::request::MessageAttachment;
+///
+/// let attachment = MessageAttachment::from_bytes("cat.png", vec![1, 2, 3])
+///     .description("A sleepy cat")
+///     .spoiler();
+///
+/// assert_eq!(Some("SPOILER_cat.png"), attachment.filename.as_deref());
+/// ```
+///
+/// Keep an attachment that is already present on a message:
+///
+/// ```
+/// use twilight_http::request::MessageAttachment;
+/// use twilight_model::id::AttachmentId;
+///
+/// let attachment = MessageAttachment::from_id(AttachmentId(1));
+///
+/// assert_eq!(Some(AttachmentId(1)), attachment.id());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MessageAttachment {
+    /// Description of the attachment, used as alt-text.
+    pub description: Option<String>,
+    /// Name of the file.
+    ///
+    /// Required when uploading a new file. May be omitted when keeping an
+    /// existing attachment.
+    pub filename: Option<String>,
+    /// Contents of a new file, or the ID of an existing attachment.
+    pub source: MessageAttachmentSource,
+}
+
+impl MessageAttachment {
+    /// Create a descriptor for a new file to upload.
+    ///
+    /// The file is raw binary data. It can be an image, or any other kind of
+    /// file.
+    pub fn from_bytes(filename: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
+        Self {
+            description: None,
+            filename: Some(filename.into()),
+            source: MessageAttachmentSource::File(file.into()),
+        }
+    }
+
+    /// Create a descriptor for an attachment already present on a message.
+    pub const fn from_id(id: AttachmentId) -> Self {
+        Self {
+            description: None,
+            filename: None,
+            source: MessageAttachmentSource::Existing(id),
+        }
+    }
+
+    /// Set the description of the attachment, used as alt-text.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description.replace(description.into());
+
+        self
+    }
+
+    /// Mark the attachment as a spoiler by prefixing its filename.
+    ///
+    /// Has no effect if no filename is set.
+    pub fn spoiler(mut self) -> Self {
+        if let Some(filename) = self.filename.as_mut() {
+            if !filename.starts_with(SPOILER_PREFIX) {
+                filename.insert_str(0, SPOILER_PREFIX);
+            }
+        }
+
+        self
+    }
+
+    /// Contents of the file, if this is a new file to upload.
+    pub fn file(&self) -> Option<&[u8]> {
+        match &self.source {
+            MessageAttachmentSource::File(file) => Some(file),
+            MessageAttachmentSource::Existing(_) => None,
+        }
+    }
+
+    /// ID of the attachment, if this is an existing attachment.
+    pub const fn id(&self) -> Option<AttachmentId> {
+        match self.source {
+            MessageAttachmentSource::File(_) => None,
+            MessageAttachmentSource::Existing(id) => Some(id),
+        }
+    }
+}
+
+impl From<&Attachment> for MessageAttachment {
+    fn from(attachment: &Attachment) -> Self {
+        Self {
+            description: None,
+            filename: Some(attachment.filename.clone()),
+            source: MessageAttachmentSource::Existing(attachment.id),
+        }
+    }
+}
+
+impl From<Attachment> for MessageAttachment {
+    fn from(attachment: Attachment) -> Self {
+        Self {
+            description: None,
+            filename: Some(attachment.filename),
+            source: MessageAttachmentSource::Existing(attachment.id),
+        }
+    }
+}
+
+/// Source of a [`MessageAttachment`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MessageAttachmentSource {
+    /// Attachment is already present on the message.
+    Existing(AttachmentId),
+    /// Raw contents of a new file to upload.
+    File(Vec<u8>),
+}
+
+/// List of attachments of a request.
+///
+/// Serializes into the `attachments` array of the request body. New files
+/// are assigned IDs in the order they were added, matching the names of the
+/// parts written by [`write_files`].
+///
+/// [`write_files`]: Self::write_files
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct AttachmentList {
+    attachments: Vec<MessageAttachment>,
+    /// Whether the list replaces the attachments of the message being
+    /// updated, rather than only adding new files to it.
+    replace: bool,
+}
+
+impl AttachmentList {
+    /// Add an attachment to the list.
+    pub fn push(&mut self, attachment: MessageAttachment) {
+        self.attachments.push(attachment);
+    }
+
+    /// Add multiple attachments to the list.
+    pub fn extend(&mut self, attachments: impl IntoIterator<Item = MessageAttachment>) {
+        self.attachments.extend(attachments);
+    }
+
+    /// Add attachments to the list, marking the list as the full set of
+    /// attachments the updated message will have.
+    pub fn replace(&mut self, attachments: impl IntoIterator<Item = MessageAttachment>) {
+        self.attachments.extend(attachments);
+        self.replace = true;
+    }
+
+    /// Whether any of the attachments are new files to upload.
+    pub fn has_files(&self) -> bool {
+        self.attachments
+            .iter()
+            .any(|attachment| attachment.file().is_some())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attachments.is_empty()
+    }
+
+    /// Whether the list may be left out of the body of an update.
+    ///
+    /// This is the case when the attachments of the message being updated
+    /// are left as is and no new file carries a description, so the files
+    /// are described by their parts alone.
+    pub fn is_implicit(&self) -> bool {
+        !self.replace
+            && self
+                .attachments
+                .iter()
+                .all(|attachment| attachment.description.is_none())
+    }
+
+    /// Write each new file into the form as a `files[n]` part.
+    pub fn write_files(&self, form: &mut Form) {
+        let files = self
+            .attachments
+            .iter()
+            .filter_map(|attachment| Some((attachment.filename.as_deref(), attachment.file()?)));
+
+        for (index, (filename, file)) in files.enumerate() {
+            form.file(
+                format!("files[{}]", index).as_bytes(),
+                filename.unwrap_or_default().as_bytes(),
+                file,
+            );
+        }
+    }
+}
+
+impl Serialize for AttachmentList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.attachments.len()))?;
+        let mut file_index = 0;
+
+        for attachment in &self.attachments {
+            let id = match attachment.source {
+                MessageAttachmentSource::Existing(id) => id.0,
+                MessageAttachmentSource::File(_) => {
+                    file_index += 1;
+
+                    file_index - 1
+                }
+            };
+
+            seq.serialize_element(&AttachmentPayload {
+                description: attachment.description.as_deref(),
+                filename: attachment.filename.as_deref(),
+                id,
+            })?;
+        }
+
+        seq.end()
+    }
+}
+
+struct AttachmentPayload<'a> {
+    description: Option<&'a str>,
+    filename: Option<&'a str>,
+    id: u64,
+}
+
+impl Serialize for AttachmentPayload<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len =
+            1 + usize::from(self.description.is_some()) + usize::from(self.filename.is_some());
+        let mut state = serializer.serialize_struct("Attachment", len)?;
+
+        if let Some(description) = self.description {
+            state.serialize_field("description", description)?;
+        }
+
+        if let Some(filename) = self.filename {
+            state.serialize_field("filename", filename)?;
+        }
+
+        state.serialize_field("id", &self.id)?;
+
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttachmentList, MessageAttachment};
+    use twilight_model::id::AttachmentId;
+
+    #[test]
+    fn test_spoiler() {
+        let attachment = MessageAttachment::from_bytes("a.png", Vec::new())
+            .spoiler()
+            .spoiler();
+        assert_eq!(Some("SPOILER_a.png"), attachment.filename.as_deref());
+
+        let existing = MessageAttachment::from_id(AttachmentId(1)).spoiler();
+        assert!(existing.filename.is_none());
+    }
+
+    #[test]
+    fn test_serialize_list() {
+        let mut list = AttachmentList::default();
+        list.push(MessageAttachment::from_bytes("a.png", vec![1]));
+        assert!(list.is_implicit());
+
+        list.extend(Some(
+            MessageAttachment::from_bytes("b.png", vec![2]).description("second"),
+        ));
+        assert!(!list.is_implicit());
+        assert_eq!(
+            r#"[{"filename":"a.png","id":0},{"description":"second","filename":"b.png","id":1}]"#,
+            serde_json::to_string(&list).unwrap(),
+        );
+
+        list.replace(vec![
+            MessageAttachment::from_id(AttachmentId(500)),
+            MessageAttachment::from_bytes("c.txt", vec![3]),
+        ]);
+
+        assert!(list.has_files());
+        assert!(!list.is_implicit());
+        assert_eq!(
+            r#"[{"filename":"a.png","id":0},{"description":"second","filename":"b.png","id":1},{"id":500},{"filename":"c.txt","id":2}]"#,
+            serde_json::to_string(&list).unwrap(),
+        );
+    }
+}
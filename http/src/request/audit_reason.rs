@@ -22,6 +22,9 @@ mod private {
             UpdateChannelPermissionConfigured,
         },
         guild::{
+            auto_moderation::{
+                CreateAutoModerationRule, DeleteAutoModerationRule, UpdateAutoModerationRule,
+            },
             ban::{CreateBan, DeleteBan},
             emoji::{CreateEmoji, DeleteEmoji, UpdateEmoji},
             integration::DeleteGuildIntegration,
@@ -63,6 +66,9 @@ mod private {
     impl<'a> Sealed for DeleteRole<'a> {}
     impl<'a> Sealed for UpdateRole<'a> {}
     impl<'a> Sealed for UpdateGuild<'a> {}
+    impl Sealed for CreateAutoModerationRule<'_> {}
+    impl Sealed for DeleteAutoModerationRule<'_> {}
+    impl Sealed for UpdateAutoModerationRule<'_> {}
     impl Sealed for UpdateWebhookMessage<'_> {}
 }
 
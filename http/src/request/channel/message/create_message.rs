@@ -2,9 +2,10 @@ use crate::{
     client::Client,
     error::Error as HttpError,
     request::{
+        attachment::AttachmentList,
         multipart::Form,
         validate::{self, EmbedValidationError},
-        Pending, Request,
+        MessageAttachment, Pending, Request,
     },
     routing::Route,
 };
@@ -107,6 +108,8 @@ pub enum CreateMessageErrorType {
 
 #[derive(Default, Serialize)]
 pub(crate) struct CreateMessageFields {
+    #[serde(skip_serializing_if = "AttachmentList::is_empty")]
+    attachments: AttachmentList,
     #[serde(skip_serializing_if = "Option::is_none")]
     content: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -146,7 +149,6 @@ pub(crate) struct CreateMessageFields {
 pub struct CreateMessage<'a> {
     channel_id: ChannelId,
     pub(crate) fields: CreateMessageFields,
-    files: Vec<(String, Vec<u8>)>,
     fut: Option<Pending<'a, Message>>,
     http: &'a Client,
 }
@@ -159,7 +161,6 @@ impl<'a> CreateMessage<'a> {
                 allowed_mentions: http.default_allowed_mentions(),
                 ..CreateMessageFields::default()
             },
-            files: Vec::new(),
             fut: None,
             http,
         }
@@ -267,15 +268,33 @@ impl<'a> CreateMessage<'a> {
         self
     }
 
-    /// Attach a file to the message.
+    /// Attach a file to the message, described by a [`MessageAttachment`].
     ///
-    /// The file is raw binary data. It can be an image, or any other kind of file.
-    pub fn file(mut self, name: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
-        self.files.push((name.into(), file.into()));
+    /// Use this to set a description for the file or to mark it as a
+    /// spoiler.
+    pub fn attachment(mut self, attachment: MessageAttachment) -> Self {
+        self.fields.attachments.push(attachment);
+
+        self
+    }
+
+    /// Attach multiple files to the message, described by
+    /// [`MessageAttachment`]s.
+    pub fn attachments(mut self, attachments: impl IntoIterator<Item = MessageAttachment>) -> Self {
+        for attachment in attachments {
+            self.fields.attachments.push(attachment);
+        }
 
         self
     }
 
+    /// Attach a file to the message.
+    ///
+    /// The file is raw binary data. It can be an image, or any other kind of file.
+    pub fn file(self, name: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
+        self.attachment(MessageAttachment::from_bytes(name, file))
+    }
+
     /// Attach multiple files to the message.
     pub fn files<N: Into<String>, F: Into<Vec<u8>>>(
         mut self,
@@ -341,12 +360,10 @@ impl<'a> CreateMessage<'a> {
             channel_id: self.channel_id.0,
         });
 
-        if !self.files.is_empty() || self.fields.payload_json.is_some() {
+        if self.fields.attachments.has_files() || self.fields.payload_json.is_some() {
             let mut form = Form::new();
 
-            for (index, (name, file)) in self.files.drain(..).enumerate() {
-                form.file(format!("{}", index).as_bytes(), name.as_bytes(), &file);
-            }
+            self.fields.attachments.write_files(&mut form);
 
             if let Some(payload_json) = &self.fields.payload_json {
                 form.payload_json(&payload_json);
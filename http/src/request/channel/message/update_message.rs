@@ -2,8 +2,9 @@ use crate::{
     client::Client,
     error::Error as HttpError,
     request::{
+        attachment::AttachmentList,
         validate::{self, EmbedValidationError},
-        NullableField, Pending, Request,
+        Form, MessageAttachment, NullableField, Pending, Request,
     },
     routing::Route,
 };
@@ -16,7 +17,7 @@ use twilight_model::{
     channel::{
         embed::Embed,
         message::{AllowedMentions, MessageFlags},
-        Attachment, Message,
+        Message,
     },
     id::{ChannelId, MessageId},
 };
@@ -108,8 +109,8 @@ pub enum UpdateMessageErrorType {
 struct UpdateMessageFields {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) allowed_mentions: Option<AllowedMentions>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub attachments: Vec<Attachment>,
+    #[serde(skip_serializing_if = "AttachmentList::is_implicit")]
+    attachments: AttachmentList,
     // We don't serialize if this is Option::None, to avoid overwriting the
     // field without meaning to.
     //
@@ -183,24 +184,51 @@ impl<'a> UpdateMessage<'a> {
         }
     }
 
-    /// Specify an attachment already present in the target message to keep.
+    /// Add an attachment to the message.
     ///
-    /// If called, all unspecified attachments will be removed from the message.
-    /// If not called, all attachments will be kept.
-    pub fn attachment(mut self, attachment: Attachment) -> Self {
+    /// The attachment is a new file to upload, which may carry a description.
+    /// Attachments already present in the message are kept, unless
+    /// [`replace_attachments`] is called.
+    ///
+    /// [`replace_attachments`]: Self::replace_attachments
+    pub fn attachment(mut self, attachment: MessageAttachment) -> Self {
         self.fields.attachments.push(attachment);
 
         self
     }
 
-    /// Specify multiple attachments already present in the target message to keep.
+    /// Add multiple attachments to the message.
     ///
-    /// If called, all unspecified attachments will be removed from the message.
-    /// If not called, all attachments will be kept.
-    pub fn attachments(mut self, attachments: impl IntoIterator<Item = Attachment>) -> Self {
-        self.fields
-            .attachments
-            .extend(attachments.into_iter().collect::<Vec<Attachment>>());
+    /// The attachments are new files to upload, which may carry descriptions.
+    /// Attachments already present in the message are kept, unless
+    /// [`replace_attachments`] is called.
+    ///
+    /// [`replace_attachments`]: Self::replace_attachments
+    pub fn attachments(mut self, attachments: impl IntoIterator<Item = MessageAttachment>) -> Self {
+        self.fields.attachments.extend(attachments);
+
+        self
+    }
+
+    /// Specify the full set of attachments the message will have after the
+    /// update.
+    ///
+    /// The attachments may either be ones already present in the target
+    /// message to keep, or new files to upload. Attachments added via
+    /// [`attachment`], [`attachments`], or [`file`] are part of the set. Pass
+    /// an empty list to remove all attachments.
+    ///
+    /// If called, all unspecified attachments will be removed from the
+    /// message. If not called, all attachments will be kept.
+    ///
+    /// [`attachment`]: Self::attachment
+    /// [`attachments`]: Self::attachments
+    /// [`file`]: Self::file
+    pub fn replace_attachments(
+        mut self,
+        attachments: impl IntoIterator<Item = MessageAttachment>,
+    ) -> Self {
+        self.fields.attachments.replace(attachments);
 
         self
     }
@@ -330,6 +358,29 @@ impl<'a> UpdateMessage<'a> {
         self
     }
 
+    /// Attach a new file to the message.
+    ///
+    /// The file is raw binary data. It can be an image, or any other kind of file.
+    pub fn file(mut self, name: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
+        self.fields
+            .attachments
+            .push(MessageAttachment::from_bytes(name, file));
+
+        self
+    }
+
+    /// Attach multiple new files to the message.
+    pub fn files<N: Into<String>, F: Into<Vec<u8>>>(
+        mut self,
+        attachments: impl IntoIterator<Item = (N, F)>,
+    ) -> Self {
+        for (name, file) in attachments {
+            self = self.file(name, file);
+        }
+
+        self
+    }
+
     /// Set the [`AllowedMentions`] in the message.
     pub fn allowed_mentions(mut self, allowed: AllowedMentions) -> Self {
         self.fields.allowed_mentions.replace(allowed);
@@ -338,14 +389,26 @@ impl<'a> UpdateMessage<'a> {
     }
 
     fn start(&mut self) -> Result<(), HttpError> {
-        let request = Request::builder(Route::UpdateMessage {
+        let mut request = Request::builder(Route::UpdateMessage {
             channel_id: self.channel_id.0,
             message_id: self.message_id.0,
-        })
-        .json(&self.fields)?
-        .build();
+        });
+
+        if self.fields.attachments.has_files() {
+            let mut form = Form::new();
+
+            self.fields.attachments.write_files(&mut form);
+
+            let body = crate::json::to_vec(&self.fields).map_err(HttpError::json)?;
+            form.payload_json(&body);
+
+            request = request.form(form);
+        } else {
+            request = request.json(&self.fields)?;
+        }
 
-        self.fut.replace(Box::pin(self.http.request(request)));
+        self.fut
+            .replace(Box::pin(self.http.request(request.build())));
 
         Ok(())
     }
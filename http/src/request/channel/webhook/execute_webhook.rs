@@ -1,7 +1,7 @@
 use crate::{
     client::Client,
     error::{Error, ErrorType},
-    request::{Form, PendingOption, Request},
+    request::{attachment::AttachmentList, Form, MessageAttachment, PendingOption, Request},
     routing::Route,
 };
 use hyper::StatusCode;
@@ -18,6 +18,8 @@ use twilight_model::{
 
 #[derive(Default, Serialize)]
 pub(crate) struct ExecuteWebhookFields {
+    #[serde(skip_serializing_if = "AttachmentList::is_empty")]
+    attachments: AttachmentList,
     #[serde(skip_serializing_if = "Option::is_none")]
     avatar_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -63,7 +65,6 @@ pub(crate) struct ExecuteWebhookFields {
 /// [`file`]: Self::file
 pub struct ExecuteWebhook<'a> {
     pub(crate) fields: ExecuteWebhookFields,
-    files: Vec<(String, Vec<u8>)>,
     fut: Option<PendingOption<'a>>,
     http: &'a Client,
     token: String,
@@ -74,7 +75,6 @@ impl<'a> ExecuteWebhook<'a> {
     pub(crate) fn new(http: &'a Client, webhook_id: WebhookId, token: impl Into<String>) -> Self {
         Self {
             fields: ExecuteWebhookFields::default(),
-            files: Vec::new(),
             fut: None,
             http,
             token: token.into(),
@@ -112,15 +112,33 @@ impl<'a> ExecuteWebhook<'a> {
         self
     }
 
-    /// Attach a file to the webhook.
+    /// Attach a file to the webhook message, described by a [`MessageAttachment`].
     ///
-    /// This method is repeatable.
-    pub fn file(mut self, name: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
-        self.files.push((name.into(), file.into()));
+    /// Use this to set a description for the file or to mark it as a
+    /// spoiler.
+    pub fn attachment(mut self, attachment: MessageAttachment) -> Self {
+        self.fields.attachments.push(attachment);
+
+        self
+    }
+
+    /// Attach multiple files to the webhook message, described by
+    /// [`MessageAttachment`]s.
+    pub fn attachments(mut self, attachments: impl IntoIterator<Item = MessageAttachment>) -> Self {
+        for attachment in attachments {
+            self.fields.attachments.push(attachment);
+        }
 
         self
     }
 
+    /// Attach a file to the webhook.
+    ///
+    /// This method is repeatable.
+    pub fn file(self, name: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
+        self.attachment(MessageAttachment::from_bytes(name, file))
+    }
+
     /// Attach multiple files to the webhook.
     pub fn files<N: Into<String>, F: Into<Vec<u8>>>(
         mut self,
@@ -224,12 +242,10 @@ impl<'a> ExecuteWebhook<'a> {
         // webhook token.
         request = request.use_authorization_token(false);
 
-        if !self.files.is_empty() || self.fields.payload_json.is_some() {
+        if self.fields.attachments.has_files() || self.fields.payload_json.is_some() {
             let mut form = Form::new();
 
-            for (index, (name, file)) in self.files.drain(..).enumerate() {
-                form.file(format!("{}", index).as_bytes(), name.as_bytes(), &file);
-            }
+            self.fields.attachments.write_files(&mut form);
 
             if let Some(payload_json) = &self.fields.payload_json {
                 form.payload_json(&payload_json);
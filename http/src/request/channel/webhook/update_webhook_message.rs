@@ -4,7 +4,8 @@ use crate::{
     client::Client,
     error::Error as HttpError,
     request::{
-        self, validate, AuditLogReason, AuditLogReasonError, Form, NullableField, Pending, Request,
+        self, attachment::AttachmentList, validate, AuditLogReason, AuditLogReasonError, Form,
+        MessageAttachment, NullableField, Pending, Request,
     },
     routing::Route,
 };
@@ -14,7 +15,7 @@ use std::{
     fmt::{Display, Formatter, Result as FmtResult},
 };
 use twilight_model::{
-    channel::{embed::Embed, message::AllowedMentions, Attachment},
+    channel::{embed::Embed, message::AllowedMentions},
     id::{MessageId, WebhookId},
 };
 
@@ -109,8 +110,8 @@ pub enum UpdateWebhookMessageErrorType {
 struct UpdateWebhookMessageFields {
     #[serde(skip_serializing_if = "Option::is_none")]
     allowed_mentions: Option<AllowedMentions>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    attachments: Vec<Attachment>,
+    #[serde(skip_serializing_if = "AttachmentList::is_implicit")]
+    attachments: AttachmentList,
     #[serde(skip_serializing_if = "Option::is_none")]
     content: Option<NullableField<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -153,7 +154,6 @@ struct UpdateWebhookMessageFields {
 /// [`DeleteWebhookMessage`]: super::DeleteWebhookMessage
 pub struct UpdateWebhookMessage<'a> {
     fields: UpdateWebhookMessageFields,
-    files: Vec<(String, Vec<u8>)>,
     fut: Option<Pending<'a, ()>>,
     http: &'a Client,
     message_id: MessageId,
@@ -177,7 +177,6 @@ impl<'a> UpdateWebhookMessage<'a> {
                 allowed_mentions: http.default_allowed_mentions(),
                 ..UpdateWebhookMessageFields::default()
             },
-            files: Vec::new(),
             fut: None,
             http,
             message_id,
@@ -194,24 +193,51 @@ impl<'a> UpdateWebhookMessage<'a> {
         self
     }
 
-    /// Specify an attachment already present in the target message to keep.
+    /// Add an attachment to the webhook message.
     ///
-    /// If called, all unspecified attachments will be removed from the message.
-    /// If not called, all attachments will be kept.
-    pub fn attachment(mut self, attachment: Attachment) -> Self {
+    /// The attachment is a new file to upload, which may carry a description.
+    /// Attachments already present in the webhook message are kept, unless
+    /// [`replace_attachments`] is called.
+    ///
+    /// [`replace_attachments`]: Self::replace_attachments
+    pub fn attachment(mut self, attachment: MessageAttachment) -> Self {
         self.fields.attachments.push(attachment);
 
         self
     }
 
-    /// Specify multiple attachments already present in the target message to keep.
+    /// Add multiple attachments to the webhook message.
     ///
-    /// If called, all unspecified attachments will be removed from the message.
-    /// If not called, all attachments will be kept.
-    pub fn attachments(mut self, attachments: impl IntoIterator<Item = Attachment>) -> Self {
-        self.fields
-            .attachments
-            .extend(attachments.into_iter().collect::<Vec<Attachment>>());
+    /// The attachments are new files to upload, which may carry descriptions.
+    /// Attachments already present in the webhook message are kept, unless
+    /// [`replace_attachments`] is called.
+    ///
+    /// [`replace_attachments`]: Self::replace_attachments
+    pub fn attachments(mut self, attachments: impl IntoIterator<Item = MessageAttachment>) -> Self {
+        self.fields.attachments.extend(attachments);
+
+        self
+    }
+
+    /// Specify the full set of attachments the webhook message will have after the
+    /// update.
+    ///
+    /// The attachments may either be ones already present in the target
+    /// webhook message to keep, or new files to upload. Attachments added via
+    /// [`attachment`], [`attachments`], or [`file`] are part of the set. Pass
+    /// an empty list to remove all attachments.
+    ///
+    /// If called, all unspecified attachments will be removed from the
+    /// webhook message. If not called, all attachments will be kept.
+    ///
+    /// [`attachment`]: Self::attachment
+    /// [`attachments`]: Self::attachments
+    /// [`file`]: Self::file
+    pub fn replace_attachments(
+        mut self,
+        attachments: impl IntoIterator<Item = MessageAttachment>,
+    ) -> Self {
+        self.fields.attachments.replace(attachments);
 
         self
     }
@@ -329,7 +355,9 @@ impl<'a> UpdateWebhookMessage<'a> {
     ///
     /// This method is repeatable.
     pub fn file(mut self, name: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
-        self.files.push((name.into(), file.into()));
+        self.fields
+            .attachments
+            .push(MessageAttachment::from_bytes(name, file));
 
         self
     }
@@ -369,12 +397,10 @@ impl<'a> UpdateWebhookMessage<'a> {
         })
         .use_authorization_token(false);
 
-        if !self.files.is_empty() || self.fields.payload_json.is_some() {
+        if self.fields.attachments.has_files() || self.fields.payload_json.is_some() {
             let mut form = Form::new();
 
-            for (index, (name, file)) in self.files.drain(..).enumerate() {
-                form.file(format!("{}", index).as_bytes(), name.as_bytes(), &file);
-            }
+            self.fields.attachments.write_files(&mut form);
 
             if let Some(payload_json) = &self.fields.payload_json {
                 form.payload_json(&payload_json);
@@ -419,7 +445,7 @@ mod tests {
     use super::{UpdateWebhookMessage, UpdateWebhookMessageFields};
     use crate::{
         client::Client,
-        request::{AuditLogReason, NullableField, Request},
+        request::{attachment::AttachmentList, AuditLogReason, NullableField, Request},
         routing::Route,
     };
     use twilight_model::id::{MessageId, WebhookId};
@@ -436,7 +462,7 @@ mod tests {
 
         let body = UpdateWebhookMessageFields {
             allowed_mentions: None,
-            attachments: Vec::new(),
+            attachments: AttachmentList::default(),
             content: Some(NullableField::Value("test".to_owned())),
             embeds: None,
             payload_json: None,
@@ -0,0 +1,50 @@
+use crate::{
+    client::Client,
+    error::Error,
+    request::{PendingOption, Request},
+    routing::Route,
+};
+use twilight_model::{
+    guild::auto_moderation::AutoModerationRule,
+    id::{AutoModerationRuleId, GuildId},
+};
+
+/// Get an auto moderation rule of a guild by its ID.
+///
+/// Requires the [`MANAGE_GUILD`] permission.
+///
+/// [`MANAGE_GUILD`]: twilight_model::guild::Permissions::MANAGE_GUILD
+pub struct GetAutoModerationRule<'a> {
+    auto_moderation_rule_id: AutoModerationRuleId,
+    fut: Option<PendingOption<'a>>,
+    guild_id: GuildId,
+    http: &'a Client,
+}
+
+impl<'a> GetAutoModerationRule<'a> {
+    pub(crate) fn new(
+        http: &'a Client,
+        guild_id: GuildId,
+        auto_moderation_rule_id: AutoModerationRuleId,
+    ) -> Self {
+        Self {
+            auto_moderation_rule_id,
+            fut: None,
+            guild_id,
+            http,
+        }
+    }
+
+    fn start(&mut self) -> Result<(), Error> {
+        let request = Request::from_route(Route::GetAutoModerationRule {
+            auto_moderation_rule_id: self.auto_moderation_rule_id.0,
+            guild_id: self.guild_id.0,
+        });
+
+        self.fut.replace(Box::pin(self.http.request_bytes(request)));
+
+        Ok(())
+    }
+}
+
+poll_req!(opt, GetAutoModerationRule<'_>, AutoModerationRule);
@@ -0,0 +1,54 @@
+use crate::{
+    client::Client,
+    error::Error,
+    request::{Pending, Request},
+    routing::Route,
+};
+use twilight_model::{guild::auto_moderation::AutoModerationRule, id::GuildId};
+
+/// Get the auto moderation rules of a guild.
+///
+/// Requires the [`MANAGE_GUILD`] permission.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use twilight_http::Client;
+/// use twilight_model::id::GuildId;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("my token");
+///
+/// let rules = client.auto_moderation_rules(GuildId(100)).await?;
+/// # Ok(()) }
+/// ```
+///
+/// [`MANAGE_GUILD`]: twilight_model::guild::Permissions::MANAGE_GUILD
+pub struct GetAutoModerationRules<'a> {
+    fut: Option<Pending<'a, Vec<AutoModerationRule>>>,
+    guild_id: GuildId,
+    http: &'a Client,
+}
+
+impl<'a> GetAutoModerationRules<'a> {
+    pub(crate) fn new(http: &'a Client, guild_id: GuildId) -> Self {
+        Self {
+            fut: None,
+            guild_id,
+            http,
+        }
+    }
+
+    fn start(&mut self) -> Result<(), Error> {
+        let request = Request::from_route(Route::GetAutoModerationRules {
+            guild_id: self.guild_id.0,
+        });
+
+        self.fut.replace(Box::pin(self.http.request(request)));
+
+        Ok(())
+    }
+}
+
+poll_req!(GetAutoModerationRules<'_>, Vec<AutoModerationRule>);
@@ -0,0 +1,423 @@
+use crate::{
+    client::Client,
+    error::Error as HttpError,
+    request::{self, validate, AuditLogReason, AuditLogReasonError, Pending, Request},
+    routing::Route,
+};
+use serde::Serialize;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    guild::auto_moderation::{
+        AutoModerationAction, AutoModerationActionMetadata, AutoModerationActionType,
+        AutoModerationEventType, AutoModerationKeywordPresetType, AutoModerationRule,
+        AutoModerationTriggerMetadata,
+    },
+    id::{AutoModerationRuleId, ChannelId, GuildId, RoleId},
+};
+
+/// The request can not be created as configured.
+#[derive(Debug)]
+pub struct UpdateAutoModerationRuleError {
+    kind: UpdateAutoModerationRuleErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl UpdateAutoModerationRuleError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &UpdateAutoModerationRuleErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(
+        self,
+    ) -> (
+        UpdateAutoModerationRuleErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for UpdateAutoModerationRuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            UpdateAutoModerationRuleErrorType::ActionTimeoutDurationInvalid { .. } => {
+                f.write_str("timeout duration is more than 4 weeks")
+            }
+            UpdateAutoModerationRuleErrorType::AllowListInvalid { .. } => {
+                f.write_str("allow list has more than 100 entries or an invalid keyword")
+            }
+            UpdateAutoModerationRuleErrorType::ExemptChannelsInvalid { .. } => {
+                f.write_str("more than 50 channels are exempt")
+            }
+            UpdateAutoModerationRuleErrorType::ExemptRolesInvalid { .. } => {
+                f.write_str("more than 20 roles are exempt")
+            }
+            UpdateAutoModerationRuleErrorType::KeywordFilterInvalid { .. } => {
+                f.write_str("keyword filter has more than 1000 entries or an invalid keyword")
+            }
+            UpdateAutoModerationRuleErrorType::MentionTotalLimitInvalid { .. } => {
+                f.write_str("mention total limit is more than 50")
+            }
+        }
+    }
+}
+
+impl Error for UpdateAutoModerationRuleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`UpdateAutoModerationRuleError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum UpdateAutoModerationRuleErrorType {
+    /// Timeout duration is more than 2419200 seconds, or 4 weeks.
+    ActionTimeoutDurationInvalid {
+        /// Provided duration in seconds.
+        duration_seconds: u32,
+    },
+    /// Allow list has more than 100 entries, or one of its keywords is not
+    /// between 1 and 60 characters in length.
+    AllowListInvalid {
+        /// Provided allow list.
+        allow_list: Vec<String>,
+    },
+    /// More than 50 channels are exempt.
+    ExemptChannelsInvalid {
+        /// Number of provided channels.
+        len: usize,
+    },
+    /// More than 20 roles are exempt.
+    ExemptRolesInvalid {
+        /// Number of provided roles.
+        len: usize,
+    },
+    /// Keyword filter has more than 1000 entries, or one of its keywords is
+    /// not between 1 and 60 characters in length.
+    KeywordFilterInvalid {
+        /// Provided keyword filter.
+        keyword_filter: Vec<String>,
+    },
+    /// Mention total limit is more than 50.
+    MentionTotalLimitInvalid {
+        /// Provided limit.
+        limit: u8,
+    },
+}
+
+#[derive(Default, Serialize)]
+struct UpdateAutoModerationRuleFields {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actions: Option<Vec<AutoModerationAction>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_type: Option<AutoModerationEventType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exempt_channels: Option<Vec<ChannelId>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exempt_roles: Option<Vec<RoleId>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trigger_metadata: Option<AutoModerationTriggerMetadata>,
+}
+
+/// Update an auto moderation rule in a guild.
+///
+/// Setting any of the actions replaces all of the rule's existing actions, and
+/// setting any of the trigger metadata replaces the rule's existing trigger
+/// metadata.
+///
+/// Requires the [`MANAGE_GUILD`] permission.
+///
+/// [`MANAGE_GUILD`]: twilight_model::guild::Permissions::MANAGE_GUILD
+pub struct UpdateAutoModerationRule<'a> {
+    auto_moderation_rule_id: AutoModerationRuleId,
+    fields: UpdateAutoModerationRuleFields,
+    fut: Option<Pending<'a, AutoModerationRule>>,
+    guild_id: GuildId,
+    http: &'a Client,
+    reason: Option<String>,
+}
+
+impl<'a> UpdateAutoModerationRule<'a> {
+    pub(crate) fn new(
+        http: &'a Client,
+        guild_id: GuildId,
+        auto_moderation_rule_id: AutoModerationRuleId,
+    ) -> Self {
+        Self {
+            auto_moderation_rule_id,
+            fields: UpdateAutoModerationRuleFields::default(),
+            fut: None,
+            guild_id,
+            http,
+            reason: None,
+        }
+    }
+
+    /// Append an action which blocks the content of the message.
+    pub fn action_block_message(mut self) -> Self {
+        self.push_action(AutoModerationActionType::BlockMessage, None);
+
+        self
+    }
+
+    /// Append an action which logs the user content to a channel.
+    pub fn action_send_alert_message(mut self, channel_id: ChannelId) -> Self {
+        self.push_action(
+            AutoModerationActionType::SendAlertMessage,
+            Some(AutoModerationActionMetadata {
+                channel_id: Some(channel_id),
+                duration_seconds: None,
+            }),
+        );
+
+        self
+    }
+
+    /// Append an action which times out the user for a duration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`UpdateAutoModerationRuleErrorType::ActionTimeoutDurationInvalid`]
+    /// error type if the duration is more than 2419200 seconds, or 4 weeks.
+    pub fn action_timeout(
+        mut self,
+        duration_seconds: u32,
+    ) -> Result<Self, UpdateAutoModerationRuleError> {
+        if !validate::auto_moderation_action_timeout(duration_seconds) {
+            return Err(UpdateAutoModerationRuleError {
+                kind: UpdateAutoModerationRuleErrorType::ActionTimeoutDurationInvalid {
+                    duration_seconds,
+                },
+                source: None,
+            });
+        }
+
+        self.push_action(
+            AutoModerationActionType::Timeout,
+            Some(AutoModerationActionMetadata {
+                channel_id: None,
+                duration_seconds: Some(duration_seconds),
+            }),
+        );
+
+        Ok(self)
+    }
+
+    /// Set the substrings which are exempt from triggering a
+    /// [`KeywordPreset`] rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`UpdateAutoModerationRuleErrorType::AllowListInvalid`] error
+    /// type if there are more than 100 entries or one of them is not between 1
+    /// and 60 characters in length.
+    ///
+    /// [`KeywordPreset`]: twilight_model::guild::auto_moderation::AutoModerationTriggerType::KeywordPreset
+    pub fn allow_list(
+        mut self,
+        allow_list: Vec<String>,
+    ) -> Result<Self, UpdateAutoModerationRuleError> {
+        if !validate::auto_moderation_allow_list(&allow_list) {
+            return Err(UpdateAutoModerationRuleError {
+                kind: UpdateAutoModerationRuleErrorType::AllowListInvalid { allow_list },
+                source: None,
+            });
+        }
+
+        self.trigger_metadata().allow_list.replace(allow_list);
+
+        Ok(self)
+    }
+
+    /// Set whether the rule is enabled.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.fields.enabled.replace(enabled);
+
+        self
+    }
+
+    /// Set the type of event in which the rule is checked.
+    pub fn event_type(mut self, event_type: AutoModerationEventType) -> Self {
+        self.fields.event_type.replace(event_type);
+
+        self
+    }
+
+    /// Set the channels which are exempt from the rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`UpdateAutoModerationRuleErrorType::ExemptChannelsInvalid`]
+    /// error type if more than 50 channels are provided.
+    pub fn exempt_channels(
+        mut self,
+        exempt_channels: Vec<ChannelId>,
+    ) -> Result<Self, UpdateAutoModerationRuleError> {
+        if !validate::auto_moderation_exempt_channels(exempt_channels.len()) {
+            return Err(UpdateAutoModerationRuleError {
+                kind: UpdateAutoModerationRuleErrorType::ExemptChannelsInvalid {
+                    len: exempt_channels.len(),
+                },
+                source: None,
+            });
+        }
+
+        self.fields.exempt_channels.replace(exempt_channels);
+
+        Ok(self)
+    }
+
+    /// Set the roles which are exempt from the rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`UpdateAutoModerationRuleErrorType::ExemptRolesInvalid`]
+    /// error type if more than 20 roles are provided.
+    pub fn exempt_roles(
+        mut self,
+        exempt_roles: Vec<RoleId>,
+    ) -> Result<Self, UpdateAutoModerationRuleError> {
+        if !validate::auto_moderation_exempt_roles(exempt_roles.len()) {
+            return Err(UpdateAutoModerationRuleError {
+                kind: UpdateAutoModerationRuleErrorType::ExemptRolesInvalid {
+                    len: exempt_roles.len(),
+                },
+                source: None,
+            });
+        }
+
+        self.fields.exempt_roles.replace(exempt_roles);
+
+        Ok(self)
+    }
+
+    /// Set the substrings which trigger a [`Keyword`] rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`UpdateAutoModerationRuleErrorType::KeywordFilterInvalid`]
+    /// error type if there are more than 1000 entries or one of them is not
+    /// between 1 and 60 characters in length.
+    ///
+    /// [`Keyword`]: twilight_model::guild::auto_moderation::AutoModerationTriggerType::Keyword
+    pub fn keyword_filter(
+        mut self,
+        keyword_filter: Vec<String>,
+    ) -> Result<Self, UpdateAutoModerationRuleError> {
+        if !validate::auto_moderation_keyword_filter(&keyword_filter) {
+            return Err(UpdateAutoModerationRuleError {
+                kind: UpdateAutoModerationRuleErrorType::KeywordFilterInvalid { keyword_filter },
+                source: None,
+            });
+        }
+
+        self.trigger_metadata()
+            .keyword_filter
+            .replace(keyword_filter);
+
+        Ok(self)
+    }
+
+    /// Set the name of the rule.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.fields.name.replace(name.into());
+
+        self
+    }
+
+    /// Set the total number of unique role and user mentions allowed per
+    /// message in a [`MentionSpam`] rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`UpdateAutoModerationRuleErrorType::MentionTotalLimitInvalid`]
+    /// error type if the limit is more than 50.
+    ///
+    /// [`MentionSpam`]: twilight_model::guild::auto_moderation::AutoModerationTriggerType::MentionSpam
+    pub fn mention_total_limit(mut self, limit: u8) -> Result<Self, UpdateAutoModerationRuleError> {
+        if !validate::auto_moderation_mention_total_limit(limit) {
+            return Err(UpdateAutoModerationRuleError {
+                kind: UpdateAutoModerationRuleErrorType::MentionTotalLimitInvalid { limit },
+                source: None,
+            });
+        }
+
+        self.trigger_metadata().mention_total_limit.replace(limit);
+
+        Ok(self)
+    }
+
+    /// Set the pre-defined wordsets which trigger a [`KeywordPreset`] rule.
+    ///
+    /// [`KeywordPreset`]: twilight_model::guild::auto_moderation::AutoModerationTriggerType::KeywordPreset
+    pub fn presets(mut self, presets: Vec<AutoModerationKeywordPresetType>) -> Self {
+        self.trigger_metadata().presets.replace(presets);
+
+        self
+    }
+
+    fn push_action(
+        &mut self,
+        kind: AutoModerationActionType,
+        metadata: Option<AutoModerationActionMetadata>,
+    ) {
+        self.fields
+            .actions
+            .get_or_insert_with(Vec::new)
+            .push(AutoModerationAction { kind, metadata });
+    }
+
+    fn trigger_metadata(&mut self) -> &mut AutoModerationTriggerMetadata {
+        self.fields
+            .trigger_metadata
+            .get_or_insert_with(AutoModerationTriggerMetadata::default)
+    }
+
+    fn start(&mut self) -> Result<(), HttpError> {
+        let mut request = Request::builder(Route::UpdateAutoModerationRule {
+            auto_moderation_rule_id: self.auto_moderation_rule_id.0,
+            guild_id: self.guild_id.0,
+        })
+        .json(&self.fields)?;
+
+        if let Some(reason) = &self.reason {
+            request = request.headers(request::audit_header(reason)?);
+        }
+
+        self.fut
+            .replace(Box::pin(self.http.request(request.build())));
+
+        Ok(())
+    }
+}
+
+impl<'a> AuditLogReason for UpdateAutoModerationRule<'a> {
+    fn reason(mut self, reason: impl Into<String>) -> Result<Self, AuditLogReasonError> {
+        self.reason
+            .replace(AuditLogReasonError::validate(reason.into())?);
+
+        Ok(self)
+    }
+}
+
+poll_req!(UpdateAutoModerationRule<'_>, AutoModerationRule);
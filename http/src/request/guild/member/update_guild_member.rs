@@ -60,6 +60,9 @@ impl UpdateGuildMemberError {
 impl Display for UpdateGuildMemberError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match &self.kind {
+            UpdateGuildMemberErrorType::CommunicationDisabledUntilInvalid { .. } => {
+                f.write_str("the timeout is more than 28 days in the future")
+            }
             UpdateGuildMemberErrorType::NicknameInvalid { .. } => {
                 f.write_str("the nickname length is invalid")
             }
@@ -73,6 +76,11 @@ impl Error for UpdateGuildMemberError {}
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum UpdateGuildMemberErrorType {
+    /// The timeout ends more than 28 days in the future.
+    CommunicationDisabledUntilInvalid {
+        /// Provided Unix timestamp in seconds.
+        timestamp: u64,
+    },
     /// The nickname is either empty or the length is more than 32 UTF-16 characters.
     NicknameInvalid { nickname: String },
 }
@@ -82,6 +90,8 @@ struct UpdateGuildMemberFields {
     #[serde(skip_serializing_if = "Option::is_none")]
     channel_id: Option<NullableField<ChannelId>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    communication_disabled_until: Option<NullableField<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     deaf: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     mute: Option<bool>,
@@ -127,6 +137,41 @@ impl<'a> UpdateGuildMember<'a> {
         self
     }
 
+    /// Set when the member's timeout ends, as a Unix timestamp in seconds.
+    ///
+    /// While timed out, the member can only view channels and read their
+    /// message history. Requires the [`MODERATE_MEMBERS`] permission. Pass
+    /// [`None`] to remove an active timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`UpdateGuildMemberErrorType::CommunicationDisabledUntilInvalid`]
+    /// error type if the timestamp is more than 28 days in the future.
+    ///
+    /// [`MODERATE_MEMBERS`]: twilight_model::guild::Permissions::MODERATE_MEMBERS
+    pub fn communication_disabled_until(
+        mut self,
+        communication_disabled_until: Option<u64>,
+    ) -> Result<Self, UpdateGuildMemberError> {
+        if let Some(timestamp) = communication_disabled_until {
+            if !validate::communication_disabled_until(timestamp) {
+                return Err(UpdateGuildMemberError {
+                    kind: UpdateGuildMemberErrorType::CommunicationDisabledUntilInvalid {
+                        timestamp,
+                    },
+                });
+            }
+        }
+
+        self.fields
+            .communication_disabled_until
+            .replace(NullableField::from_option(
+                communication_disabled_until.map(iso8601),
+            ));
+
+        Ok(self)
+    }
+
     /// If true, restrict the member's ability to hear sound from a voice channel.
     pub fn deaf(mut self, deaf: bool) -> Self {
         self.fields.deaf.replace(deaf);
@@ -198,6 +243,39 @@ impl<'a> UpdateGuildMember<'a> {
     }
 }
 
+/// Format a Unix timestamp in seconds as an ISO 8601 timestamp.
+fn iso8601(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+
+    // Convert days since the Unix epoch into a civil date, see
+    // <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}+00:00",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+    )
+}
+
 impl<'a> AuditLogReason for UpdateGuildMember<'a> {
     fn reason(mut self, reason: impl Into<String>) -> Result<Self, AuditLogReasonError> {
         self.reason
@@ -238,7 +316,7 @@ impl Future for UpdateGuildMember<'_> {
 
 #[cfg(test)]
 mod tests {
-    use super::{UpdateGuildMember, UpdateGuildMemberFields};
+    use super::{UpdateGuildMember, UpdateGuildMemberErrorType, UpdateGuildMemberFields};
     use crate::{
         request::{NullableField, Request},
         routing::Route,
@@ -275,6 +353,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_iso8601() {
+        assert_eq!("1970-01-01T00:00:00+00:00", super::iso8601(0));
+        assert_eq!("2000-02-29T12:34:56+00:00", super::iso8601(951_827_696));
+        assert_eq!("2021-12-31T23:59:59+00:00", super::iso8601(1_640_995_199));
+    }
+
+    #[test]
+    fn test_communication_disabled_until() -> Result<(), Box<dyn Error>> {
+        let client = Client::new("foo");
+        let builder = UpdateGuildMember::new(&client, GUILD_ID, USER_ID)
+            .communication_disabled_until(Some(0))?;
+        let actual = builder.request()?;
+
+        let body = UpdateGuildMemberFields {
+            communication_disabled_until: Some(NullableField::Value(
+                "1970-01-01T00:00:00+00:00".to_owned(),
+            )),
+            ..UpdateGuildMemberFields::default()
+        };
+        let route = Route::UpdateMember {
+            guild_id: GUILD_ID.0,
+            user_id: USER_ID.0,
+        };
+        let expected = Request::builder(route).json(&body)?.build();
+
+        assert_eq!(actual.body, expected.body);
+
+        let result = UpdateGuildMember::new(&client, GUILD_ID, USER_ID)
+            .communication_disabled_until(Some(u64::MAX));
+        assert!(matches!(
+            result.map(|_| ()).unwrap_err().kind(),
+            UpdateGuildMemberErrorType::CommunicationDisabledUntilInvalid { .. }
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_nick_set_null() -> Result<(), Box<dyn Error>> {
         let client = Client::new("foo");
@@ -62,6 +62,7 @@ pub mod prelude;
 pub mod template;
 pub mod user;
 
+mod attachment;
 mod audit_reason;
 mod base;
 mod get_gateway;
@@ -72,6 +73,7 @@ mod multipart;
 mod validate;
 
 pub use self::{
+    attachment::{MessageAttachment, MessageAttachmentSource},
     audit_reason::{AuditLogReason, AuditLogReasonError},
     base::{Request, RequestBuilder},
     get_gateway::GetGateway,
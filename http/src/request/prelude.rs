@@ -4,7 +4,7 @@ pub use super::{
     get_gateway::GetGateway,
     get_gateway_authed::GetGatewayAuthed,
     get_voice_regions::GetVoiceRegions,
-    guild::{ban::*, emoji::*, integration::*, member::*, role::*, user::*, *},
+    guild::{auto_moderation::*, ban::*, emoji::*, integration::*, member::*, role::*, user::*, *},
     template::{
         create_guild_from_template::CreateGuildFromTemplateError,
         create_template::CreateTemplateError, *,
@@ -6,8 +6,15 @@
 use std::{
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use twilight_model::{
+    application::{
+        callback::ModalCallbackData,
+        component::{Component, TextInput},
+    },
+    channel::embed::Embed,
 };
-use twilight_model::channel::embed::Embed;
 
 /// An embed is not valid.
 ///
@@ -201,6 +208,258 @@ pub enum EmbedValidationErrorType {
     },
 }
 
+/// A modal is not valid.
+///
+/// Referenced values are used from [the Discord docs][docs].
+///
+/// [docs]: https://discord.com/developers/docs/interactions/message-components#text-inputs
+#[derive(Debug)]
+pub struct ModalValidationError {
+    kind: ModalValidationErrorType,
+}
+
+impl ModalValidationError {
+    /// The maximum number of action rows in a modal.
+    pub const COMPONENT_COUNT: usize = 5;
+
+    /// The maximum length of a custom ID in codepoints.
+    pub const CUSTOM_ID_LENGTH: usize = 100;
+
+    /// The maximum length of a text input label in codepoints.
+    pub const LABEL_LENGTH: usize = 45;
+
+    /// The maximum length of a text input placeholder in codepoints.
+    pub const PLACEHOLDER_LENGTH: usize = 100;
+
+    /// The maximum value of a text input's minimum or maximum length, and the
+    /// maximum length of its pre-filled value in codepoints.
+    pub const TEXT_INPUT_LENGTH: u16 = 4000;
+
+    /// The maximum modal title length in codepoints.
+    pub const TITLE_LENGTH: usize = 45;
+
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &ModalValidationErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[allow(clippy::unused_self)]
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        None
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(
+        self,
+    ) -> (
+        ModalValidationErrorType,
+        Option<Box<dyn Error + Send + Sync>>,
+    ) {
+        (self.kind, None)
+    }
+}
+
+impl Display for ModalValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            ModalValidationErrorType::ComponentCount { amount } => {
+                f.write_str("there are ")?;
+                Display::fmt(amount, f)?;
+                f.write_str(" action rows, but there must be between 1 and ")?;
+
+                Display::fmt(&Self::COMPONENT_COUNT, f)
+            }
+            ModalValidationErrorType::CustomIdInvalid { chars } => {
+                f.write_str("a custom id is ")?;
+                Display::fmt(chars, f)?;
+                f.write_str(" characters long, but the max is ")?;
+
+                Display::fmt(&Self::CUSTOM_ID_LENGTH, f)
+            }
+            ModalValidationErrorType::InvalidActionRow { idx } => {
+                f.write_str("the action row at index ")?;
+                Display::fmt(idx, f)?;
+
+                f.write_str(" must contain exactly one text input")
+            }
+            ModalValidationErrorType::LabelInvalid { chars } => {
+                f.write_str("a text input label is ")?;
+                Display::fmt(chars, f)?;
+                f.write_str(" characters long, but it must be between 1 and ")?;
+
+                Display::fmt(&Self::LABEL_LENGTH, f)
+            }
+            ModalValidationErrorType::MaxLengthInvalid { len } => {
+                f.write_str("a text input's maximum length is ")?;
+                Display::fmt(len, f)?;
+                f.write_str(", but it must be between 1 and ")?;
+
+                Display::fmt(&Self::TEXT_INPUT_LENGTH, f)
+            }
+            ModalValidationErrorType::MinLengthInvalid { len } => {
+                f.write_str("a text input's minimum length is ")?;
+                Display::fmt(len, f)?;
+                f.write_str(", but the max is ")?;
+
+                Display::fmt(&Self::TEXT_INPUT_LENGTH, f)
+            }
+            ModalValidationErrorType::MinLengthLargerThanMaxLength { max, min } => {
+                f.write_str("a text input's minimum length is ")?;
+                Display::fmt(min, f)?;
+                f.write_str(", but it must not be larger than its maximum length of ")?;
+
+                Display::fmt(max, f)
+            }
+            ModalValidationErrorType::PlaceholderTooLarge { chars } => {
+                f.write_str("a text input placeholder is ")?;
+                Display::fmt(chars, f)?;
+                f.write_str(" characters long, but the max is ")?;
+
+                Display::fmt(&Self::PLACEHOLDER_LENGTH, f)
+            }
+            ModalValidationErrorType::TitleTooLarge { chars } => {
+                f.write_str("the title's length is ")?;
+                Display::fmt(chars, f)?;
+                f.write_str(" characters long, but the max is ")?;
+
+                Display::fmt(&Self::TITLE_LENGTH, f)
+            }
+            ModalValidationErrorType::ValueTooLarge { chars } => {
+                f.write_str("a text input value is ")?;
+                Display::fmt(chars, f)?;
+                f.write_str(" characters long, but the max is ")?;
+
+                Display::fmt(&Self::TEXT_INPUT_LENGTH, f)
+            }
+        }
+    }
+}
+
+impl Error for ModalValidationError {}
+
+/// Type of [`ModalValidationError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ModalValidationErrorType {
+    /// The modal has no action rows or more than
+    /// [the maximum][`COMPONENT_COUNT`].
+    ///
+    /// [`COMPONENT_COUNT`]: ModalValidationError::COMPONENT_COUNT
+    ComponentCount {
+        /// The number of action rows that were provided.
+        amount: usize,
+    },
+    /// The custom ID of the modal or one of its text inputs is empty or
+    /// larger than [the maximum][`CUSTOM_ID_LENGTH`].
+    ///
+    /// [`CUSTOM_ID_LENGTH`]: ModalValidationError::CUSTOM_ID_LENGTH
+    CustomIdInvalid {
+        /// The number of codepoints that were provided.
+        chars: usize,
+    },
+    /// A top-level component is not an action row holding exactly one text
+    /// input.
+    InvalidActionRow {
+        /// Index of the invalid component.
+        idx: usize,
+    },
+    /// A text input's label is empty or larger than
+    /// [the maximum][`LABEL_LENGTH`].
+    ///
+    /// [`LABEL_LENGTH`]: ModalValidationError::LABEL_LENGTH
+    LabelInvalid {
+        /// The number of codepoints that were provided.
+        chars: usize,
+    },
+    /// A text input's maximum length is 0 or larger than
+    /// [the maximum][`TEXT_INPUT_LENGTH`].
+    ///
+    /// [`TEXT_INPUT_LENGTH`]: ModalValidationError::TEXT_INPUT_LENGTH
+    MaxLengthInvalid {
+        /// The provided maximum length.
+        len: u16,
+    },
+    /// A text input's minimum length is larger than
+    /// [the maximum][`TEXT_INPUT_LENGTH`].
+    ///
+    /// [`TEXT_INPUT_LENGTH`]: ModalValidationError::TEXT_INPUT_LENGTH
+    MinLengthInvalid {
+        /// The provided minimum length.
+        len: u16,
+    },
+    /// A text input's minimum length is larger than its maximum length.
+    MinLengthLargerThanMaxLength {
+        /// The provided maximum length.
+        max: u16,
+        /// The provided minimum length.
+        min: u16,
+    },
+    /// A text input's placeholder is larger than
+    /// [the maximum][`PLACEHOLDER_LENGTH`].
+    ///
+    /// [`PLACEHOLDER_LENGTH`]: ModalValidationError::PLACEHOLDER_LENGTH
+    PlaceholderTooLarge {
+        /// The number of codepoints that were provided.
+        chars: usize,
+    },
+    /// The modal title is larger than [the maximum][`TITLE_LENGTH`].
+    ///
+    /// [`TITLE_LENGTH`]: ModalValidationError::TITLE_LENGTH
+    TitleTooLarge {
+        /// The number of codepoints that were provided.
+        chars: usize,
+    },
+    /// A text input's pre-filled value is larger than
+    /// [the maximum][`TEXT_INPUT_LENGTH`].
+    ///
+    /// [`TEXT_INPUT_LENGTH`]: ModalValidationError::TEXT_INPUT_LENGTH
+    ValueTooLarge {
+        /// The number of codepoints that were provided.
+        chars: usize,
+    },
+}
+
+pub const fn auto_moderation_action_timeout(duration_seconds: u32) -> bool {
+    // <https://discord.com/developers/docs/resources/auto-moderation#auto-moderation-action-object-action-metadata>
+    duration_seconds <= 2_419_200
+}
+
+pub fn auto_moderation_allow_list(values: &[String]) -> bool {
+    // <https://discord.com/developers/docs/resources/auto-moderation#auto-moderation-rule-object-trigger-metadata-field-limits>
+    values.len() <= 100 && values.iter().all(|value| auto_moderation_keyword(value))
+}
+
+pub const fn auto_moderation_exempt_channels(len: usize) -> bool {
+    // <https://discord.com/developers/docs/resources/auto-moderation#auto-moderation-rule-object-auto-moderation-rule-structure>
+    len <= 50
+}
+
+pub const fn auto_moderation_exempt_roles(len: usize) -> bool {
+    // <https://discord.com/developers/docs/resources/auto-moderation#auto-moderation-rule-object-auto-moderation-rule-structure>
+    len <= 20
+}
+
+fn auto_moderation_keyword(value: &str) -> bool {
+    let len = value.chars().count();
+
+    // <https://discord.com/developers/docs/resources/auto-moderation#auto-moderation-rule-object-trigger-metadata-field-limits>
+    (1..=60).contains(&len)
+}
+
+pub fn auto_moderation_keyword_filter(values: &[String]) -> bool {
+    // <https://discord.com/developers/docs/resources/auto-moderation#auto-moderation-rule-object-trigger-metadata-field-limits>
+    values.len() <= 1000 && values.iter().all(|value| auto_moderation_keyword(value))
+}
+
+pub const fn auto_moderation_mention_total_limit(value: u8) -> bool {
+    // <https://discord.com/developers/docs/resources/auto-moderation#auto-moderation-rule-object-trigger-metadata-field-limits>
+    value <= 50
+}
+
 pub const fn ban_delete_message_days(value: u64) -> bool {
     // <https://discordapp.com/developers/docs/resources/guild#create-guild-ban-query-string-params>
     value <= 7
@@ -217,6 +476,17 @@ fn _channel_name(value: &str) -> bool {
     (1..=100).contains(&len)
 }
 
+pub fn communication_disabled_until(timestamp: u64) -> bool {
+    // <https://discord.com/developers/docs/resources/guild#modify-guild-member>
+    const MAX_DURATION: u64 = 28 * 24 * 60 * 60;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    timestamp <= now + MAX_DURATION
+}
+
 pub fn content_limit(value: impl AsRef<str>) -> bool {
     _content_limit(value.as_ref())
 }
@@ -318,6 +588,119 @@ pub fn embed(embed: &Embed) -> Result<(), EmbedValidationError> {
     Ok(())
 }
 
+pub fn modal(modal: &ModalCallbackData) -> Result<(), ModalValidationError> {
+    let chars = modal.title.chars().count();
+
+    if chars > ModalValidationError::TITLE_LENGTH {
+        return Err(ModalValidationError {
+            kind: ModalValidationErrorType::TitleTooLarge { chars },
+        });
+    }
+
+    modal_custom_id(&modal.custom_id)?;
+
+    let amount = modal.components.len();
+
+    if !(1..=ModalValidationError::COMPONENT_COUNT).contains(&amount) {
+        return Err(ModalValidationError {
+            kind: ModalValidationErrorType::ComponentCount { amount },
+        });
+    }
+
+    for (idx, component) in modal.components.iter().enumerate() {
+        let text_input = match component {
+            Component::ActionRow(action_row) => match action_row.components.as_slice() {
+                [Component::TextInput(text_input)] => text_input,
+                _ => {
+                    return Err(ModalValidationError {
+                        kind: ModalValidationErrorType::InvalidActionRow { idx },
+                    })
+                }
+            },
+            _ => {
+                return Err(ModalValidationError {
+                    kind: ModalValidationErrorType::InvalidActionRow { idx },
+                })
+            }
+        };
+
+        modal_text_input(text_input)?;
+    }
+
+    Ok(())
+}
+
+fn modal_custom_id(value: &str) -> Result<(), ModalValidationError> {
+    let chars = value.chars().count();
+
+    // <https://discord.com/developers/docs/interactions/message-components#text-inputs-text-input-structure>
+    if !(1..=ModalValidationError::CUSTOM_ID_LENGTH).contains(&chars) {
+        return Err(ModalValidationError {
+            kind: ModalValidationErrorType::CustomIdInvalid { chars },
+        });
+    }
+
+    Ok(())
+}
+
+fn modal_text_input(text_input: &TextInput) -> Result<(), ModalValidationError> {
+    modal_custom_id(&text_input.custom_id)?;
+
+    let chars = text_input.label.chars().count();
+
+    if !(1..=ModalValidationError::LABEL_LENGTH).contains(&chars) {
+        return Err(ModalValidationError {
+            kind: ModalValidationErrorType::LabelInvalid { chars },
+        });
+    }
+
+    if let Some(len) = text_input.max_length {
+        if !(1..=ModalValidationError::TEXT_INPUT_LENGTH).contains(&len) {
+            return Err(ModalValidationError {
+                kind: ModalValidationErrorType::MaxLengthInvalid { len },
+            });
+        }
+    }
+
+    if let Some(len) = text_input.min_length {
+        if len > ModalValidationError::TEXT_INPUT_LENGTH {
+            return Err(ModalValidationError {
+                kind: ModalValidationErrorType::MinLengthInvalid { len },
+            });
+        }
+    }
+
+    if let (Some(max), Some(min)) = (text_input.max_length, text_input.min_length) {
+        if min > max {
+            return Err(ModalValidationError {
+                kind: ModalValidationErrorType::MinLengthLargerThanMaxLength { max, min },
+            });
+        }
+    }
+
+    if let Some(placeholder) = text_input.placeholder.as_ref() {
+        let chars = placeholder.chars().count();
+
+        if chars > ModalValidationError::PLACEHOLDER_LENGTH {
+            return Err(ModalValidationError {
+                kind: ModalValidationErrorType::PlaceholderTooLarge { chars },
+            });
+        }
+    }
+
+    if let Some(value) = text_input.value.as_ref() {
+        let chars = value.chars().count();
+
+        if chars > usize::from(ModalValidationError::TEXT_INPUT_LENGTH) {
+            return Err(ModalValidationError {
+                kind: ModalValidationErrorType::ValueTooLarge { chars },
+            });
+        }
+    }
+
+    Ok(())
+}
+
 pub const fn get_audit_log_limit(value: u64) -> bool {
     // <https://discordapp.com/developers/docs/resources/audit-log#get-guild-audit-log-query-string-parameters>
     value >= 1 && value <= 100
@@ -459,7 +842,10 @@ pub fn command_permissions(len: usize) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use twilight_model::channel::embed::{EmbedAuthor, EmbedField, EmbedFooter};
+    use twilight_model::{
+        application::component::{ActionRow, TextInputStyle},
+        channel::embed::{EmbedAuthor, EmbedField, EmbedFooter},
+    };
 
     fn base_embed() -> Embed {
         Embed {
@@ -479,6 +865,87 @@ mod tests {
         }
     }
 
+    fn base_modal() -> ModalCallbackData {
+        ModalCallbackData {
+            components: vec![Component::ActionRow(ActionRow {
+                components: vec![Component::TextInput(TextInput {
+                    custom_id: "input".to_owned(),
+                    label: "label".to_owned(),
+                    max_length: None,
+                    min_length: None,
+                    placeholder: None,
+                    required: None,
+                    style: TextInputStyle::Short,
+                    value: None,
+                })],
+            })],
+            custom_id: "modal".to_owned(),
+            title: "title".to_owned(),
+        }
+    }
+
+    fn base_text_input(modal: &mut ModalCallbackData) -> &mut TextInput {
+        match &mut modal.components[0] {
+            Component::ActionRow(action_row) => match &mut action_row.components[0] {
+                Component::TextInput(text_input) => text_input,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_auto_moderation_action_timeout() {
+        assert!(auto_moderation_action_timeout(0));
+        assert!(auto_moderation_action_timeout(2_419_200));
+
+        assert!(!auto_moderation_action_timeout(2_419_201));
+    }
+
+    #[test]
+    fn test_auto_moderation_allow_list() {
+        assert!(auto_moderation_allow_list(&[]));
+        assert!(auto_moderation_allow_list(&vec!["a".repeat(60); 100]));
+
+        assert!(!auto_moderation_allow_list(&[String::new()]));
+        assert!(!auto_moderation_allow_list(&["a".repeat(61)]));
+        assert!(!auto_moderation_allow_list(&vec!["a".to_owned(); 101]));
+    }
+
+    #[test]
+    fn test_auto_moderation_exempt_channels() {
+        assert!(auto_moderation_exempt_channels(0));
+        assert!(auto_moderation_exempt_channels(50));
+
+        assert!(!auto_moderation_exempt_channels(51));
+    }
+
+    #[test]
+    fn test_auto_moderation_exempt_roles() {
+        assert!(auto_moderation_exempt_roles(0));
+        assert!(auto_moderation_exempt_roles(20));
+
+        assert!(!auto_moderation_exempt_roles(21));
+    }
+
+    #[test]
+    fn test_auto_moderation_keyword_filter() {
+        assert!(auto_moderation_keyword_filter(&[]));
+        assert!(auto_moderation_keyword_filter(&vec!["a".repeat(60); 1000]));
+
+        assert!(!auto_moderation_keyword_filter(&[String::new()]));
+        assert!(!auto_moderation_keyword_filter(&["a".repeat(61)]));
+        assert!(!auto_moderation_keyword_filter(&vec!["a".to_owned(); 1001]));
+    }
+
+    #[test]
+    fn test_auto_moderation_mention_total_limit() {
+        assert!(auto_moderation_mention_total_limit(0));
+        assert!(auto_moderation_mention_total_limit(50));
+
+        assert!(!auto_moderation_mention_total_limit(51));
+    }
+
     #[test]
     fn test_ban_delete_message_days() {
         assert!(ban_delete_message_days(0));
@@ -497,6 +964,20 @@ mod tests {
         assert!(!channel_name("a".repeat(101)));
     }
 
+    #[test]
+    fn test_communication_disabled_until() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        assert!(communication_disabled_until(0));
+        assert!(communication_disabled_until(now));
+        assert!(communication_disabled_until(now + 27 * 24 * 60 * 60));
+
+        assert!(!communication_disabled_until(now + 29 * 24 * 60 * 60));
+    }
+
     #[test]
     fn test_content_limit() {
         assert!(content_limit(""));
@@ -692,6 +1173,125 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_modal() {
+        let mut modal = base_modal();
+        assert!(super::modal(&modal).is_ok());
+
+        modal.title = "a".repeat(46);
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::TitleTooLarge { chars: 46 }
+        ));
+
+        let mut modal = base_modal();
+        modal.custom_id = String::new();
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::CustomIdInvalid { chars: 0 }
+        ));
+
+        let mut modal = base_modal();
+        let row = modal.components[0].clone();
+        modal.components = vec![row; 6];
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::ComponentCount { amount: 6 }
+        ));
+
+        modal.components.clear();
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::ComponentCount { amount: 0 }
+        ));
+    }
+
+    #[test]
+    fn test_modal_action_row() {
+        let mut modal = base_modal();
+        let text_input = base_text_input(&mut modal).clone();
+        modal.components = vec![Component::TextInput(text_input.clone())];
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::InvalidActionRow { idx: 0 }
+        ));
+
+        modal.components = vec![Component::ActionRow(ActionRow {
+            components: vec![
+                Component::TextInput(text_input.clone()),
+                Component::TextInput(text_input),
+            ],
+        })];
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::InvalidActionRow { idx: 0 }
+        ));
+    }
+
+    #[test]
+    fn test_modal_text_input() {
+        let mut modal = base_modal();
+        let text_input = base_text_input(&mut modal);
+        text_input.label = "a".repeat(45);
+        text_input.max_length = Some(4000);
+        text_input.min_length = Some(0);
+        text_input.placeholder = Some("a".repeat(100));
+        text_input.value = Some("a".repeat(4000));
+        assert!(super::modal(&modal).is_ok());
+
+        base_text_input(&mut modal).label = "a".repeat(46);
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::LabelInvalid { chars: 46 }
+        ));
+
+        base_text_input(&mut modal).label = String::new();
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::LabelInvalid { chars: 0 }
+        ));
+
+        let mut modal = base_modal();
+        base_text_input(&mut modal).max_length = Some(0);
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::MaxLengthInvalid { len: 0 }
+        ));
+
+        let mut modal = base_modal();
+        base_text_input(&mut modal).min_length = Some(4001);
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::MinLengthInvalid { len: 4001 }
+        ));
+
+        let mut modal = base_modal();
+        let text_input = base_text_input(&mut modal);
+        text_input.max_length = Some(10);
+        text_input.min_length = Some(10);
+        assert!(super::modal(&modal).is_ok());
+
+        base_text_input(&mut modal).min_length = Some(11);
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::MinLengthLargerThanMaxLength { max: 10, min: 11 }
+        ));
+
+        let mut modal = base_modal();
+        base_text_input(&mut modal).placeholder = Some("a".repeat(101));
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::PlaceholderTooLarge { chars: 101 }
+        ));
+
+        let mut modal = base_modal();
+        base_text_input(&mut modal).value = Some("a".repeat(4001));
+        assert!(matches!(
+            super::modal(&modal).unwrap_err().kind(),
+            ModalValidationErrorType::ValueTooLarge { chars: 4001 }
+        ));
+    }
+
     #[test]
     fn test_get_audit_log_limit() {
         assert!(get_audit_log_limit(1));
@@ -124,6 +124,10 @@ pub enum Path {
     GuildsIdBans(u64),
     GuildsIdBansId(u64),
     GuildsIdAuditLogs(u64),
+    /// Operating on a guild's auto moderation rules.
+    GuildsIdAutoModerationRules(u64),
+    /// Operating on an auto moderation rule of a guild.
+    GuildsIdAutoModerationRulesId(u64),
     GuildsIdBansUserId(u64),
     GuildsIdChannels(u64),
     GuildsIdWidget(u64),
@@ -258,6 +262,12 @@ impl FromStr for Path {
             ["guilds"] => Guilds,
             ["guilds", id] => GuildsId(parse_id(id)?),
             ["guilds", id, "audit-logs"] => GuildsIdAuditLogs(parse_id(id)?),
+            ["guilds", id, "auto-moderation", "rules"] => {
+                GuildsIdAutoModerationRules(parse_id(id)?)
+            }
+            ["guilds", id, "auto-moderation", "rules", _] => {
+                GuildsIdAutoModerationRulesId(parse_id(id)?)
+            }
             ["guilds", id, "bans"] => GuildsIdBans(parse_id(id)?),
             ["guilds", id, "bans", _] => GuildsIdBansUserId(parse_id(id)?),
             ["guilds", id, "channels"] => GuildsIdChannels(parse_id(id)?),
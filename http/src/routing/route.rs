@@ -16,6 +16,11 @@ pub enum Route {
         /// The ID of the user.
         user_id: u64,
     },
+    /// Route information to create an auto moderation rule.
+    CreateAutoModerationRule {
+        /// The ID of the guild.
+        guild_id: u64,
+    },
     /// Route information to create a ban on a user in a guild.
     CreateBan {
         /// The number of days' worth of the user's messages to delete in the
@@ -127,6 +132,13 @@ pub enum Route {
         /// The ID of the message.
         message_id: u64,
     },
+    /// Route information to delete an auto moderation rule for a guild.
+    DeleteAutoModerationRule {
+        /// The ID of the auto moderation rule.
+        auto_moderation_rule_id: u64,
+        /// The ID of the guild.
+        guild_id: u64,
+    },
     /// Route information to delete a ban on a user in a guild.
     DeleteBan {
         /// The ID of the guild.
@@ -293,6 +305,18 @@ pub enum Route {
         /// The ID of the user, if specified.
         user_id: Option<u64>,
     },
+    /// Route information to get an auto moderation rule for a guild.
+    GetAutoModerationRule {
+        /// The ID of the auto moderation rule.
+        auto_moderation_rule_id: u64,
+        /// The ID of the guild.
+        guild_id: u64,
+    },
+    /// Route information to get the auto moderation rules in a guild.
+    GetAutoModerationRules {
+        /// The ID of the guild.
+        guild_id: u64,
+    },
     /// Route information to get information about a single ban in a guild.
     GetBan {
         /// The ID of the guild.
@@ -648,6 +672,13 @@ pub enum Route {
         /// The ID of the message.
         message_id: u64,
     },
+    /// Route information to update an auto moderation rule for a guild.
+    UpdateAutoModerationRule {
+        /// The ID of the auto moderation rule.
+        auto_moderation_rule_id: u64,
+        /// The ID of the guild.
+        guild_id: u64,
+    },
     /// Route information to update a channel, such as a guild channel or group.
     UpdateChannel {
         /// The ID of the channel.
@@ -877,7 +908,8 @@ impl Route {
     #[allow(clippy::too_many_lines)]
     pub const fn method(&self) -> Method {
         match self {
-            Self::DeleteBan { .. }
+            Self::DeleteAutoModerationRule { .. }
+            | Self::DeleteBan { .. }
             | Self::DeleteChannel { .. }
             | Self::DeleteEmoji { .. }
             | Self::DeleteGlobalCommand { .. }
@@ -901,6 +933,8 @@ impl Route {
             | Self::RemoveMemberRole { .. }
             | Self::UnpinMessage { .. } => Method::Delete,
             Self::GetAuditLogs { .. }
+            | Self::GetAutoModerationRule { .. }
+            | Self::GetAutoModerationRules { .. }
             | Self::GetBan { .. }
             | Self::GetBans { .. }
             | Self::GetGatewayBot
@@ -946,7 +980,8 @@ impl Route {
             | Self::GetWebhook { .. }
             | Self::GetWebhookMessage { .. }
             | Self::SearchGuildMembers { .. } => Method::Get,
-            Self::UpdateChannel { .. }
+            Self::UpdateAutoModerationRule { .. }
+            | Self::UpdateChannel { .. }
             | Self::UpdateCurrentUser
             | Self::UpdateCurrentUserVoiceState { .. }
             | Self::UpdateEmoji { .. }
@@ -968,7 +1003,8 @@ impl Route {
             | Self::UpdateUserVoiceState { .. }
             | Self::UpdateWebhookMessage { .. }
             | Self::UpdateWebhook { .. } => Method::Patch,
-            Self::CreateChannel { .. }
+            Self::CreateAutoModerationRule { .. }
+            | Self::CreateChannel { .. }
             | Self::CreateGlobalCommand { .. }
             | Self::CreateGuildCommand { .. }
             | Self::CreateEmoji { .. }
@@ -1043,6 +1079,15 @@ impl Route {
             Self::AddMemberRole { guild_id, .. } | Self::RemoveMemberRole { guild_id, .. } => {
                 Path::GuildsIdMembersIdRolesId(*guild_id)
             }
+            Self::CreateAutoModerationRule { guild_id }
+            | Self::GetAutoModerationRules { guild_id } => {
+                Path::GuildsIdAutoModerationRules(*guild_id)
+            }
+            Self::DeleteAutoModerationRule { guild_id, .. }
+            | Self::GetAutoModerationRule { guild_id, .. }
+            | Self::UpdateAutoModerationRule { guild_id, .. } => {
+                Path::GuildsIdAutoModerationRulesId(*guild_id)
+            }
             Self::CreateBan { guild_id, .. } | Self::DeleteBan { guild_id, .. } => {
                 Path::GuildsIdBansUserId(*guild_id)
             }
@@ -69,6 +69,13 @@ impl Display for RouteDisplay<'_> {
 
                 Display::fmt(role_id, f)
             }
+            Route::CreateAutoModerationRule { guild_id }
+            | Route::GetAutoModerationRules { guild_id } => {
+                f.write_str("guilds/")?;
+                Display::fmt(guild_id, f)?;
+
+                f.write_str("/auto-moderation/rules")
+            }
             Route::CreateBan {
                 guild_id,
                 delete_message_days,
@@ -257,6 +264,24 @@ impl Display for RouteDisplay<'_> {
 
                 f.write_str("/crosspost")
             }
+            Route::DeleteAutoModerationRule {
+                auto_moderation_rule_id,
+                guild_id,
+            }
+            | Route::GetAutoModerationRule {
+                auto_moderation_rule_id,
+                guild_id,
+            }
+            | Route::UpdateAutoModerationRule {
+                auto_moderation_rule_id,
+                guild_id,
+            } => {
+                f.write_str("guilds/")?;
+                Display::fmt(guild_id, f)?;
+                f.write_str("/auto-moderation/rules/")?;
+
+                Display::fmt(auto_moderation_rule_id, f)
+            }
             Route::DeleteBan { guild_id, user_id } | Route::GetBan { guild_id, user_id } => {
                 f.write_str("guilds/")?;
                 Display::fmt(guild_id, f)?;
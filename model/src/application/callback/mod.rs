@@ -1,15 +1,20 @@
 //! Used when responding to interactions.
 
 mod callback_data;
+mod modal_callback_data;
 mod response_type;
 
-pub use self::{callback_data::CallbackData, response_type::ResponseType};
+pub use self::{
+    callback_data::CallbackData, modal_callback_data::ModalCallbackData,
+    response_type::ResponseType,
+};
 
 use serde::{
     de::{Deserializer, Error as DeError, IgnoredAny, MapAccess, Visitor},
     ser::{SerializeStruct, Serializer},
     Deserialize, Serialize,
 };
+use serde_value::{Value, ValueDeserializer};
 use std::fmt::{Formatter, Result as FmtResult};
 
 /// Payload used for responding to an interaction.
@@ -25,6 +30,8 @@ pub enum InteractionResponse {
     ChannelMessageWithSource(CallbackData),
     /// Acknowledges an interaction, showing a loading state.
     DeferredChannelMessageWithSource(CallbackData),
+    /// Responds to an interaction with a popup modal.
+    Modal(ModalCallbackData),
 }
 
 impl InteractionResponse {
@@ -35,6 +42,7 @@ impl InteractionResponse {
             Self::DeferredChannelMessageWithSource(_) => {
                 ResponseType::DeferredChannelMessageWithSource
             }
+            Self::Modal(_) => ResponseType::Modal,
         }
     }
 }
@@ -62,7 +70,7 @@ impl<'de> Visitor<'de> for ResponseVisitor {
     }
 
     fn visit_map<V: MapAccess<'de>>(self, mut map: V) -> Result<Self::Value, V::Error> {
-        let mut data: Option<CallbackData> = None;
+        let mut data: Option<Value> = None;
         let mut kind: Option<ResponseType> = None;
 
         let span = tracing::trace_span!("deserializing interaction response");
@@ -108,18 +116,29 @@ impl<'de> Visitor<'de> for ResponseVisitor {
 
         let kind = kind.ok_or_else(|| DeError::missing_field("type"))?;
 
+        // The data is buffered as the type of the data depends on the response
+        // type, which may come after it.
+        let data = data
+            .map(ValueDeserializer::<V::Error>::new)
+            .ok_or_else(|| DeError::missing_field("data"));
+
         Ok(match kind {
             ResponseType::Pong => Self::Value::Pong,
             ResponseType::ChannelMessageWithSource => {
-                let data = data.ok_or_else(|| DeError::missing_field("data"))?;
+                let data = CallbackData::deserialize(data?)?;
 
                 Self::Value::ChannelMessageWithSource(data)
             }
             ResponseType::DeferredChannelMessageWithSource => {
-                let data = data.ok_or_else(|| DeError::missing_field("data"))?;
+                let data = CallbackData::deserialize(data?)?;
 
                 Self::Value::DeferredChannelMessageWithSource(data)
             }
+            ResponseType::Modal => {
+                let data = ModalCallbackData::deserialize(data?)?;
+
+                Self::Value::Modal(data)
+            }
         })
     }
 }
@@ -140,6 +159,14 @@ impl Serialize for InteractionResponse {
                 state.serialize_field("type", &self.kind())?;
                 state.serialize_field("data", &data)?;
 
+                state.end()
+            }
+            Self::Modal(data) => {
+                let mut state = serializer.serialize_struct("InteractionResponse", 2)?;
+
+                state.serialize_field("type", &self.kind())?;
+                state.serialize_field("data", &data)?;
+
                 state.end()
             }
         }
@@ -148,8 +175,11 @@ impl Serialize for InteractionResponse {
 
 #[cfg(test)]
 mod tests {
-    use super::{CallbackData, InteractionResponse};
-    use crate::channel::message::MessageFlags;
+    use super::{CallbackData, InteractionResponse, ModalCallbackData};
+    use crate::{
+        application::component::{ActionRow, Component, TextInput, TextInputStyle},
+        channel::message::MessageFlags,
+    };
     use serde_test::Token;
 
     #[test]
@@ -187,4 +217,80 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_modal_response() {
+        let value = InteractionResponse::Modal(ModalCallbackData {
+            components: vec![Component::ActionRow(ActionRow {
+                components: vec![Component::TextInput(TextInput {
+                    custom_id: "feedback".into(),
+                    label: "Feedback".into(),
+                    max_length: None,
+                    min_length: Some(10),
+                    placeholder: None,
+                    required: None,
+                    style: TextInputStyle::Paragraph,
+                    value: None,
+                })],
+            })],
+            custom_id: "modal".into(),
+            title: "Send feedback".into(),
+        });
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "InteractionResponse",
+                    len: 2,
+                },
+                Token::Str("type"),
+                Token::U8(9),
+                Token::Str("data"),
+                Token::Struct {
+                    name: "ModalCallbackData",
+                    len: 3,
+                },
+                Token::Str("components"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "Component",
+                    len: 2,
+                },
+                Token::Str("components"),
+                Token::Some,
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "Component",
+                    len: 5,
+                },
+                Token::Str("custom_id"),
+                Token::Some,
+                Token::Str("feedback"),
+                Token::Str("label"),
+                Token::Some,
+                Token::Str("Feedback"),
+                Token::Str("min_length"),
+                Token::Some,
+                Token::U16(10),
+                Token::Str("style"),
+                Token::Some,
+                Token::U8(2),
+                Token::Str("type"),
+                Token::U8(4),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("type"),
+                Token::U8(1),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("custom_id"),
+                Token::Str("modal"),
+                Token::Str("title"),
+                Token::Str("Send feedback"),
+                Token::StructEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
 }
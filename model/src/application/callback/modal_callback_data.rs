@@ -0,0 +1,20 @@
+use crate::application::component::Component;
+use serde::{Deserialize, Serialize};
+
+/// Data sent when responding to an [`Interaction`] with a modal.
+///
+/// Modals may contain up to 5 [`ActionRow`]s, each holding a single
+/// [`TextInput`].
+///
+/// [`Interaction`]: crate::application::interaction::Interaction
+/// [`ActionRow`]: crate::application::component::ActionRow
+/// [`TextInput`]: crate::application::component::TextInput
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ModalCallbackData {
+    /// Components of the modal.
+    pub components: Vec<Component>,
+    /// Developer defined identifier of the modal.
+    pub custom_id: String,
+    /// Title shown at the top of the modal.
+    pub title: String,
+}
@@ -9,6 +9,7 @@ pub enum ResponseType {
     Pong = 1,
     ChannelMessageWithSource = 4,
     DeferredChannelMessageWithSource = 5,
+    Modal = 9,
 }
 
 impl ResponseType {
@@ -17,6 +18,7 @@ impl ResponseType {
             Self::Pong => "Pong",
             Self::ChannelMessageWithSource => "ChannelMessageWithSource",
             Self::DeferredChannelMessageWithSource => "DeferredChannelMessageWithSource",
+            Self::Modal => "Modal",
         }
     }
 }
@@ -0,0 +1,12 @@
+use super::Component;
+
+/// Container for other components.
+///
+/// Refer to [the discord docs] for more information.
+///
+/// [the discord docs]: https://discord.com/developers/docs/interactions/message-components#action-rows
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ActionRow {
+    /// Nested components.
+    pub components: Vec<Component>,
+}
@@ -0,0 +1,45 @@
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Type of a [`Component`].
+///
+/// Refer to [the discord docs] for more information.
+///
+/// [`Component`]: super::Component
+/// [the discord docs]: https://discord.com/developers/docs/interactions/message-components#component-object-component-types
+#[derive(
+    Clone, Copy, Debug, Deserialize_repr, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize_repr,
+)]
+#[repr(u8)]
+pub enum ComponentType {
+    /// Container for other components.
+    ActionRow = 1,
+    /// Text input field, only usable in modals.
+    TextInput = 4,
+}
+
+impl ComponentType {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ActionRow => "ActionRow",
+            Self::TextInput => "TextInput",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ComponentType;
+    use serde_test::Token;
+
+    #[test]
+    fn test_variants() {
+        serde_test::assert_tokens(&ComponentType::ActionRow, &[Token::U8(1)]);
+        serde_test::assert_tokens(&ComponentType::TextInput, &[Token::U8(4)]);
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!("ActionRow", ComponentType::ActionRow.name());
+        assert_eq!("TextInput", ComponentType::TextInput.name());
+    }
+}
@@ -0,0 +1,354 @@
+//! Interactive elements that are rendered in modals.
+
+mod action_row;
+mod component_type;
+mod text_input;
+
+pub use self::{
+    action_row::ActionRow,
+    component_type::ComponentType,
+    text_input::{TextInput, TextInputStyle},
+};
+
+use serde::{
+    de::{Deserializer, Error as DeError, IgnoredAny, MapAccess, Visitor},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
+use std::fmt::{Formatter, Result as FmtResult};
+
+/// Interactive element.
+///
+/// Each variant corresponds to a [`ComponentType`]. Refer to
+/// [the discord docs] for more information.
+///
+/// [the discord docs]: https://discord.com/developers/docs/interactions/message-components#what-is-a-component
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Component {
+    /// Container for other components.
+    ActionRow(ActionRow),
+    /// Text input field.
+    TextInput(TextInput),
+}
+
+impl Component {
+    pub const fn kind(&self) -> ComponentType {
+        match self {
+            Self::ActionRow(_) => ComponentType::ActionRow,
+            Self::TextInput(_) => ComponentType::TextInput,
+        }
+    }
+}
+
+impl From<ActionRow> for Component {
+    fn from(action_row: ActionRow) -> Self {
+        Self::ActionRow(action_row)
+    }
+}
+
+impl From<TextInput> for Component {
+    fn from(text_input: TextInput) -> Self {
+        Self::TextInput(text_input)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Component")]
+struct ComponentEnvelope<'ser> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    components: Option<&'ser [Component]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    custom_id: Option<&'ser str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<&'ser str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_length: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_length: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    placeholder: Option<&'ser str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    required: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style: Option<TextInputStyle>,
+    #[serde(rename = "type")]
+    kind: ComponentType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<&'ser str>,
+}
+
+impl Serialize for Component {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let envelope = match self {
+            Self::ActionRow(action_row) => ComponentEnvelope {
+                components: Some(action_row.components.as_ref()),
+                custom_id: None,
+                label: None,
+                max_length: None,
+                min_length: None,
+                placeholder: None,
+                required: None,
+                style: None,
+                kind: self.kind(),
+                value: None,
+            },
+            Self::TextInput(text_input) => ComponentEnvelope {
+                components: None,
+                custom_id: Some(text_input.custom_id.as_ref()),
+                label: Some(text_input.label.as_ref()),
+                max_length: text_input.max_length,
+                min_length: text_input.min_length,
+                placeholder: text_input.placeholder.as_deref(),
+                required: text_input.required,
+                style: Some(text_input.style),
+                kind: self.kind(),
+                value: text_input.value.as_deref(),
+            },
+        };
+
+        envelope.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Component {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(ComponentVisitor)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum ComponentField {
+    Components,
+    CustomId,
+    Label,
+    MaxLength,
+    MinLength,
+    Placeholder,
+    Required,
+    Style,
+    Type,
+    Value,
+}
+
+struct ComponentVisitor;
+
+impl<'de> Visitor<'de> for ComponentVisitor {
+    type Value = Component;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("struct Component")
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn visit_map<V: MapAccess<'de>>(self, mut map: V) -> Result<Self::Value, V::Error> {
+        let mut components: Option<Option<Vec<Component>>> = None;
+        let mut custom_id: Option<Option<String>> = None;
+        let mut kind: Option<ComponentType> = None;
+        let mut label: Option<Option<String>> = None;
+        let mut max_length: Option<Option<u16>> = None;
+        let mut min_length: Option<Option<u16>> = None;
+        let mut placeholder: Option<Option<String>> = None;
+        let mut required: Option<Option<bool>> = None;
+        let mut style: Option<Option<TextInputStyle>> = None;
+        let mut value: Option<Option<String>> = None;
+
+        let span = tracing::trace_span!("deserializing component");
+        let _span_enter = span.enter();
+
+        loop {
+            let span_child = tracing::trace_span!("iterating over component");
+            let _span_child_enter = span_child.enter();
+
+            let key = match map.next_key() {
+                Ok(Some(key)) => {
+                    tracing::trace!(?key, "found key");
+
+                    key
+                }
+                Ok(None) => break,
+                Err(why) => {
+                    map.next_value::<IgnoredAny>()?;
+
+                    tracing::trace!("ran into an unknown key: {:?}", why);
+
+                    continue;
+                }
+            };
+
+            match key {
+                ComponentField::Components => {
+                    if components.is_some() {
+                        return Err(DeError::duplicate_field("components"));
+                    }
+
+                    components = Some(map.next_value()?);
+                }
+                ComponentField::CustomId => {
+                    if custom_id.is_some() {
+                        return Err(DeError::duplicate_field("custom_id"));
+                    }
+
+                    custom_id = Some(map.next_value()?);
+                }
+                ComponentField::Label => {
+                    if label.is_some() {
+                        return Err(DeError::duplicate_field("label"));
+                    }
+
+                    label = Some(map.next_value()?);
+                }
+                ComponentField::MaxLength => {
+                    if max_length.is_some() {
+                        return Err(DeError::duplicate_field("max_length"));
+                    }
+
+                    max_length = Some(map.next_value()?);
+                }
+                ComponentField::MinLength => {
+                    if min_length.is_some() {
+                        return Err(DeError::duplicate_field("min_length"));
+                    }
+
+                    min_length = Some(map.next_value()?);
+                }
+                ComponentField::Placeholder => {
+                    if placeholder.is_some() {
+                        return Err(DeError::duplicate_field("placeholder"));
+                    }
+
+                    placeholder = Some(map.next_value()?);
+                }
+                ComponentField::Required => {
+                    if required.is_some() {
+                        return Err(DeError::duplicate_field("required"));
+                    }
+
+                    required = Some(map.next_value()?);
+                }
+                ComponentField::Style => {
+                    if style.is_some() {
+                        return Err(DeError::duplicate_field("style"));
+                    }
+
+                    style = Some(map.next_value()?);
+                }
+                ComponentField::Type => {
+                    if kind.is_some() {
+                        return Err(DeError::duplicate_field("type"));
+                    }
+
+                    kind = Some(map.next_value()?);
+                }
+                ComponentField::Value => {
+                    if value.is_some() {
+                        return Err(DeError::duplicate_field("value"));
+                    }
+
+                    value = Some(map.next_value()?);
+                }
+            }
+        }
+
+        let kind = kind.ok_or_else(|| DeError::missing_field("type"))?;
+
+        tracing::trace!(?kind, "common fields of all variants exist");
+
+        Ok(match kind {
+            ComponentType::ActionRow => {
+                let components = components
+                    .flatten()
+                    .ok_or_else(|| DeError::missing_field("components"))?;
+
+                Component::ActionRow(ActionRow { components })
+            }
+            ComponentType::TextInput => {
+                let custom_id = custom_id
+                    .flatten()
+                    .ok_or_else(|| DeError::missing_field("custom_id"))?;
+                let label = label
+                    .flatten()
+                    .ok_or_else(|| DeError::missing_field("label"))?;
+                let style = style
+                    .flatten()
+                    .ok_or_else(|| DeError::missing_field("style"))?;
+
+                Component::TextInput(TextInput {
+                    custom_id,
+                    label,
+                    max_length: max_length.flatten(),
+                    min_length: min_length.flatten(),
+                    placeholder: placeholder.flatten(),
+                    required: required.flatten(),
+                    style,
+                    value: value.flatten(),
+                })
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ActionRow, Component, TextInput, TextInputStyle};
+    use serde_test::Token;
+
+    #[test]
+    fn test_action_row_text_input() {
+        let value = Component::ActionRow(ActionRow {
+            components: vec![Component::TextInput(TextInput {
+                custom_id: "input".to_owned(),
+                label: "Reason".to_owned(),
+                max_length: Some(100),
+                min_length: None,
+                placeholder: Some("Why?".to_owned()),
+                required: Some(true),
+                style: TextInputStyle::Short,
+                value: None,
+            })],
+        });
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Component",
+                    len: 2,
+                },
+                Token::Str("components"),
+                Token::Some,
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "Component",
+                    len: 7,
+                },
+                Token::Str("custom_id"),
+                Token::Some,
+                Token::Str("input"),
+                Token::Str("label"),
+                Token::Some,
+                Token::Str("Reason"),
+                Token::Str("max_length"),
+                Token::Some,
+                Token::U16(100),
+                Token::Str("placeholder"),
+                Token::Some,
+                Token::Str("Why?"),
+                Token::Str("required"),
+                Token::Some,
+                Token::Bool(true),
+                Token::Str("style"),
+                Token::Some,
+                Token::U8(1),
+                Token::Str("type"),
+                Token::U8(4),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("type"),
+                Token::U8(1),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Pop-up item that renders on modals.
+///
+/// Refer to [the discord docs] for more information.
+///
+/// [the discord docs]: https://discord.com/developers/docs/interactions/message-components#text-inputs
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TextInput {
+    /// Developer defined identifier.
+    pub custom_id: String,
+    /// Text appearing over the input field.
+    pub label: String,
+    /// Maximum length of the text.
+    pub max_length: Option<u16>,
+    /// Minimum length of the text.
+    pub min_length: Option<u16>,
+    /// Placeholder for the text input.
+    pub placeholder: Option<String>,
+    /// Whether the user is required to input a text.
+    ///
+    /// Defaults to `true` if not provided.
+    pub required: Option<bool>,
+    /// Style variant of the input field.
+    pub style: TextInputStyle,
+    /// Pre-filled value for the input field.
+    pub value: Option<String>,
+}
+
+/// Style of a [`TextInput`].
+///
+/// Refer to [the discord docs] for more information.
+///
+/// [the discord docs]: https://discord.com/developers/docs/interactions/message-components#text-inputs-text-input-styles
+#[derive(
+    Clone, Copy, Debug, Deserialize_repr, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize_repr,
+)]
+#[repr(u8)]
+pub enum TextInputStyle {
+    /// Single-line input.
+    Short = 1,
+    /// Multi-line input.
+    Paragraph = 2,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextInputStyle;
+    use serde_test::Token;
+
+    #[test]
+    fn test_text_input_style() {
+        serde_test::assert_tokens(&TextInputStyle::Short, &[Token::U8(1)]);
+        serde_test::assert_tokens(&TextInputStyle::Paragraph, &[Token::U8(2)]);
+    }
+}
@@ -144,6 +144,7 @@ impl<'de> Visitor<'de> for ResolvedVisitor {
                         mapped_members
                             .into_iter()
                             .map(|(k, v)| InteractionMember {
+                                communication_disabled_until: v.communication_disabled_until,
                                 hoisted_role: v.hoisted_role,
                                 id: k,
                                 joined_at: v.joined_at,
@@ -196,6 +197,12 @@ pub struct InteractionChannel {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(rename = "InteractionMemberEnvelope")]
 pub struct InteractionMember {
+    /// ISO 8601 timestamp until which the member is timed out.
+    ///
+    /// While timed out, the member can only view channels and read their
+    /// message history.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub communication_disabled_until: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hoisted_role: Option<RoleId>,
     #[serde(skip_serializing)]
@@ -212,6 +219,8 @@ pub struct InteractionMember {
 
 #[derive(Deserialize)]
 struct InteractionMemberEnvelope {
+    #[serde(default)]
+    pub communication_disabled_until: Option<String>,
     pub hoisted_role: Option<RoleId>,
     pub joined_at: Option<String>,
     pub nick: Option<String>,
@@ -242,6 +251,7 @@ mod tests {
                 permissions: Permissions::empty(),
             }],
             members: vec![InteractionMember {
+                communication_disabled_until: None,
                 hoisted_role: None,
                 id: UserId(300),
                 joined_at: Some("joined at".into()),
@@ -15,6 +15,7 @@ use std::fmt::{Display, Formatter, Result as FmtResult};
 pub enum InteractionType {
     Ping = 1,
     ApplicationCommand = 2,
+    ModalSubmit = 5,
 }
 
 impl InteractionType {
@@ -22,6 +23,7 @@ impl InteractionType {
         match self {
             Self::Ping => "Ping",
             Self::ApplicationCommand => "ApplicationCommand",
+            Self::ModalSubmit => "ModalSubmit",
         }
     }
 }
@@ -46,6 +48,7 @@ impl TryFrom<u8> for InteractionType {
         match i {
             1 => Ok(Self::Ping),
             2 => Ok(Self::ApplicationCommand),
+            5 => Ok(Self::ModalSubmit),
             other => Err(UnknownInteractionTypeError { value: other }),
         }
     }
@@ -1,15 +1,18 @@
 //! Used when recieving interactions through gateway or webhooks.
 
 pub mod application_command;
+pub mod modal_submit;
+
 mod interaction_type;
 mod ping;
 
 pub use self::{
-    application_command::ApplicationCommand, interaction_type::InteractionType, ping::Ping,
+    application_command::ApplicationCommand, interaction_type::InteractionType,
+    modal_submit::ModalSubmit, ping::Ping,
 };
 
 use crate::{
-    application::interaction::application_command::CommandData,
+    application::interaction::{application_command::CommandData, modal_submit::ModalSubmitData},
     guild::PartialMember,
     id::{ApplicationId, ChannelId, GuildId, InteractionId},
     user::User,
@@ -18,6 +21,7 @@ use serde::{
     de::{Deserializer, Error as DeError, IgnoredAny, MapAccess, Visitor},
     Deserialize, Serialize,
 };
+use serde_value::{Value, ValueDeserializer};
 use std::fmt::{Formatter, Result as FmtResult};
 
 /// Payload received when a user executes an interaction.
@@ -34,6 +38,8 @@ pub enum Interaction {
     Ping(Box<Ping>),
     /// Application command variant.
     ApplicationCommand(Box<ApplicationCommand>),
+    /// Modal submit variant.
+    ModalSubmit(Box<ModalSubmit>),
 }
 
 impl Interaction {
@@ -41,6 +47,7 @@ impl Interaction {
         match self {
             Self::Ping(_) => None,
             Self::ApplicationCommand(inner) => inner.guild_id,
+            Self::ModalSubmit(inner) => inner.guild_id,
         }
     }
 }
@@ -78,7 +85,7 @@ impl<'de> Visitor<'de> for InteractionVisitor {
     fn visit_map<V: MapAccess<'de>>(self, mut map: V) -> Result<Self::Value, V::Error> {
         let mut application_id: Option<ApplicationId> = None;
         let mut channel_id: Option<ChannelId> = None;
-        let mut data: Option<CommandData> = None;
+        let mut data: Option<Value> = None;
         let mut guild_id: Option<Option<GuildId>> = None;
         let mut id: Option<InteractionId> = None;
         let mut member: Option<Option<PartialMember>> = None;
@@ -191,6 +198,12 @@ impl<'de> Visitor<'de> for InteractionVisitor {
             "common fields of all variants exist"
         );
 
+        // The data is buffered as the type of the data depends on the
+        // interaction type, which may come after it.
+        let data = data
+            .map(ValueDeserializer::<V::Error>::new)
+            .ok_or_else(|| DeError::missing_field("data"));
+
         Ok(match kind {
             InteractionType::Ping => {
                 tracing::trace!("handling ping");
@@ -204,7 +217,7 @@ impl<'de> Visitor<'de> for InteractionVisitor {
             }
             InteractionType::ApplicationCommand => {
                 let channel_id = channel_id.ok_or_else(|| DeError::missing_field("channel_id"))?;
-                let data = data.ok_or_else(|| DeError::missing_field("data"))?;
+                let data = CommandData::deserialize(data?)?;
 
                 let guild_id = guild_id.unwrap_or_default();
                 let member = member.unwrap_or_default();
@@ -224,6 +237,28 @@ impl<'de> Visitor<'de> for InteractionVisitor {
                     user,
                 }))
             }
+            InteractionType::ModalSubmit => {
+                let channel_id = channel_id.ok_or_else(|| DeError::missing_field("channel_id"))?;
+                let data = ModalSubmitData::deserialize(data?)?;
+
+                let guild_id = guild_id.unwrap_or_default();
+                let member = member.unwrap_or_default();
+                let user = user.unwrap_or_default();
+
+                tracing::trace!(%channel_id, "handling modal submit");
+
+                Self::Value::ModalSubmit(Box::new(ModalSubmit {
+                    application_id,
+                    channel_id,
+                    data,
+                    guild_id,
+                    id,
+                    kind,
+                    member,
+                    token,
+                    user,
+                }))
+            }
         })
     }
 }
@@ -231,10 +266,14 @@ impl<'de> Visitor<'de> for InteractionVisitor {
 #[cfg(test)]
 mod test {
     use crate::{
+        application::component::ComponentType,
         application::interaction::{
             application_command::{
                 ApplicationCommand, CommandData, CommandDataOption, CommandInteractionDataResolved,
             },
+            modal_submit::{
+                ModalSubmit, ModalSubmitActionRow, ModalSubmitComponent, ModalSubmitData,
+            },
             Interaction, InteractionType,
         },
         guild::{PartialMember, Permissions},
@@ -281,6 +320,7 @@ mod test {
             id: InteractionId(500),
             kind: InteractionType::ApplicationCommand,
             member: Some(PartialMember {
+                communication_disabled_until: None,
                 deaf: false,
                 joined_at: Some("joined at".into()),
                 mute: false,
@@ -434,4 +474,88 @@ mod test {
             ],
         );
     }
+
+    #[test]
+    fn test_interaction_modal_submit() {
+        let value = Interaction::ModalSubmit(Box::new(ModalSubmit {
+            application_id: ApplicationId(100),
+            channel_id: ChannelId(200),
+            data: ModalSubmitData {
+                components: vec![ModalSubmitActionRow {
+                    components: vec![ModalSubmitComponent {
+                        custom_id: "reason".into(),
+                        kind: ComponentType::TextInput,
+                        value: "spam".into(),
+                    }],
+                }],
+                custom_id: "report".into(),
+            },
+            guild_id: None,
+            id: InteractionId(500),
+            kind: InteractionType::ModalSubmit,
+            member: None,
+            token: "interaction token".into(),
+            user: None,
+        }));
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Interaction",
+                    len: 7,
+                },
+                Token::Str("application_id"),
+                Token::NewtypeStruct {
+                    name: "ApplicationId",
+                },
+                Token::Str("100"),
+                Token::Str("channel_id"),
+                Token::NewtypeStruct { name: "ChannelId" },
+                Token::Str("200"),
+                Token::Str("data"),
+                Token::Struct {
+                    name: "ModalSubmitData",
+                    len: 2,
+                },
+                Token::Str("components"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "ModalSubmitActionRow",
+                    len: 1,
+                },
+                Token::Str("components"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "ModalSubmitComponent",
+                    len: 3,
+                },
+                Token::Str("custom_id"),
+                Token::Str("reason"),
+                Token::Str("type"),
+                Token::U8(4),
+                Token::Str("value"),
+                Token::Str("spam"),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("custom_id"),
+                Token::Str("report"),
+                Token::StructEnd,
+                Token::Str("guild_id"),
+                Token::None,
+                Token::Str("id"),
+                Token::NewtypeStruct {
+                    name: "InteractionId",
+                },
+                Token::Str("500"),
+                Token::Str("type"),
+                Token::U8(5),
+                Token::Str("token"),
+                Token::Str("interaction token"),
+                Token::StructEnd,
+            ],
+        );
+    }
 }
@@ -0,0 +1,35 @@
+use crate::application::component::ComponentType;
+use serde::{Deserialize, Serialize};
+
+/// Data received when a [`ModalSubmit`] interaction is executed.
+///
+/// Refer to [the discord docs] for more information.
+///
+/// [`ModalSubmit`]: crate::application::interaction::Interaction::ModalSubmit
+/// [the discord docs]: https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object-modal-submit-data-structure
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ModalSubmitData {
+    /// Action rows containing the submitted values.
+    pub components: Vec<ModalSubmitActionRow>,
+    /// Developer defined identifier of the modal.
+    pub custom_id: String,
+}
+
+/// Action row of a submitted modal.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ModalSubmitActionRow {
+    /// Submitted components of the row.
+    pub components: Vec<ModalSubmitComponent>,
+}
+
+/// Value a user submitted for a component of a modal.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ModalSubmitComponent {
+    /// Developer defined identifier of the component.
+    pub custom_id: String,
+    /// Type of the component.
+    #[serde(rename = "type")]
+    pub kind: ComponentType,
+    /// Value submitted by the user.
+    pub value: String,
+}
@@ -0,0 +1,45 @@
+mod data;
+
+pub use self::data::{ModalSubmitActionRow, ModalSubmitComponent, ModalSubmitData};
+
+use super::InteractionType;
+use crate::{
+    guild::PartialMember,
+    id::{ApplicationId, ChannelId, GuildId, InteractionId},
+    user::User,
+};
+use serde::Serialize;
+
+/// Data present in an [`Interaction`] of type [`ModalSubmit`].
+///
+/// [`Interaction`]: super::Interaction
+/// [`ModalSubmit`]: super::Interaction::ModalSubmit
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename(serialize = "Interaction"))]
+pub struct ModalSubmit {
+    /// ID of the associated application.
+    pub application_id: ApplicationId,
+    /// The channel the interaction was triggered from.
+    pub channel_id: ChannelId,
+    /// Values submitted through the modal.
+    pub data: ModalSubmitData,
+    /// ID of the guild the interaction was triggered from.
+    pub guild_id: Option<GuildId>,
+    /// ID of the interaction.
+    pub id: InteractionId,
+    /// Kind of the interaction.
+    #[serde(rename = "type")]
+    pub kind: InteractionType,
+    /// Member that submitted the modal.
+    ///
+    /// Present when the modal is submitted in a guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member: Option<PartialMember>,
+    /// Token of the interaction.
+    pub token: String,
+    /// User that submitted the modal.
+    ///
+    /// Present when the modal is submitted in a direct message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+}
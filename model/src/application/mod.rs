@@ -1,3 +1,4 @@
 pub mod callback;
 pub mod command;
+pub mod component;
 pub mod interaction;
@@ -85,6 +85,7 @@ mod tests {
             discriminator: "0001".to_owned(),
             id: UserId(1),
             member: Some(PartialMember {
+                communication_disabled_until: None,
                 deaf: false,
                 joined_at: None,
                 mute: true,
@@ -127,6 +127,7 @@ mod tests {
             interaction: None,
             kind: MessageType::Regular,
             member: Some(PartialMember {
+                communication_disabled_until: None,
                 deaf: false,
                 joined_at: Some("2020-01-01T00:00:00.000000+00:00".to_owned()),
                 mute: false,
@@ -320,6 +321,7 @@ mod tests {
             interaction: None,
             kind: MessageType::Regular,
             member: Some(PartialMember {
+                communication_disabled_until: None,
                 deaf: false,
                 joined_at: Some("2020-01-01T00:00:00.000000+00:00".to_owned()),
                 mute: false,
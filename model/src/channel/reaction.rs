@@ -178,6 +178,7 @@ mod tests {
             },
             guild_id: Some(GuildId(1)),
             member: Some(Member {
+                communication_disabled_until: None,
                 deaf: false,
                 guild_id: GuildId(1),
                 hoisted_role: Some(RoleId(5)),
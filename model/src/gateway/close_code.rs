@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::convert::TryFrom;
 use std::{
@@ -42,6 +43,42 @@ pub enum CloseCode {
     DisallowedIntents = 4014,
 }
 
+impl CloseCode {
+    /// Classify how a shard can recover from the close code.
+    pub const fn kind(self) -> CloseCodeKind {
+        match self {
+            Self::UnknownError
+            | Self::UnknownOpcode
+            | Self::DecodeError
+            | Self::AlreadyAuthenticated
+            | Self::RateLimited => CloseCodeKind::Resume,
+            Self::NotAuthenticated | Self::InvalidSequence | Self::SessionTimedOut => {
+                CloseCodeKind::Reconnect
+            }
+            Self::AuthenticationFailed
+            | Self::InvalidShard
+            | Self::ShardingRequired
+            | Self::InvalidApiVersion
+            | Self::InvalidIntents
+            | Self::DisallowedIntents => CloseCodeKind::Fatal,
+        }
+    }
+}
+
+/// Classification of a [`CloseCode`] by how a shard can recover from it.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+#[serde(rename_all = "snake_case")]
+pub enum CloseCodeKind {
+    /// Shard can not recover by connecting again with the same configuration,
+    /// such as when the token is invalid.
+    Fatal,
+    /// Shard can recover by connecting again with a new session.
+    Reconnect,
+    /// Shard can recover by resuming the session.
+    Resume,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct CloseCodeConversionError {
     code: u16,
@@ -95,7 +132,7 @@ impl TryFrom<u16> for CloseCode {
 
 #[cfg(test)]
 mod tests {
-    use super::CloseCode;
+    use super::{CloseCode, CloseCodeKind};
     use serde_test::Token;
     use std::convert::TryFrom;
 
@@ -117,6 +154,29 @@ mod tests {
         serde_test::assert_tokens(&CloseCode::DisallowedIntents, &[Token::U16(4014)]);
     }
 
+    #[test]
+    fn test_kind() {
+        assert_eq!(CloseCodeKind::Resume, CloseCode::UnknownError.kind());
+        assert_eq!(CloseCodeKind::Resume, CloseCode::RateLimited.kind());
+        assert_eq!(CloseCodeKind::Reconnect, CloseCode::NotAuthenticated.kind());
+        assert_eq!(CloseCodeKind::Reconnect, CloseCode::InvalidSequence.kind());
+        assert_eq!(CloseCodeKind::Reconnect, CloseCode::SessionTimedOut.kind());
+        assert_eq!(CloseCodeKind::Fatal, CloseCode::AuthenticationFailed.kind());
+        assert_eq!(CloseCodeKind::Fatal, CloseCode::InvalidShard.kind());
+        assert_eq!(CloseCodeKind::Fatal, CloseCode::ShardingRequired.kind());
+        assert_eq!(CloseCodeKind::Fatal, CloseCode::InvalidApiVersion.kind());
+        assert_eq!(CloseCodeKind::Fatal, CloseCode::InvalidIntents.kind());
+        assert_eq!(CloseCodeKind::Fatal, CloseCode::DisallowedIntents.kind());
+
+        serde_test::assert_tokens(
+            &CloseCodeKind::Fatal,
+            &[Token::UnitVariant {
+                name: "CloseCodeKind",
+                variant: "fatal",
+            }],
+        );
+    }
+
     #[test]
     fn test_conversion() {
         assert_eq!(CloseCode::try_from(4000).unwrap(), CloseCode::UnknownError);
@@ -15,6 +15,10 @@ use std::convert::TryFrom;
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum DispatchEvent {
+    AutoModerationActionExecution(AutoModerationActionExecution),
+    AutoModerationRuleCreate(Box<AutoModerationRuleCreate>),
+    AutoModerationRuleDelete(Box<AutoModerationRuleDelete>),
+    AutoModerationRuleUpdate(Box<AutoModerationRuleUpdate>),
     BanAdd(BanAdd),
     BanRemove(BanRemove),
     ChannelCreate(ChannelCreate),
@@ -67,6 +71,10 @@ impl DispatchEvent {
     /// Returns the type of event that this event is.
     pub const fn kind(&self) -> EventType {
         match self {
+            Self::AutoModerationActionExecution(_) => EventType::AutoModerationActionExecution,
+            Self::AutoModerationRuleCreate(_) => EventType::AutoModerationRuleCreate,
+            Self::AutoModerationRuleDelete(_) => EventType::AutoModerationRuleDelete,
+            Self::AutoModerationRuleUpdate(_) => EventType::AutoModerationRuleUpdate,
             Self::BanAdd(_) => EventType::BanAdd,
             Self::BanRemove(_) => EventType::BanRemove,
             Self::ChannelCreate(_) => EventType::ChannelCreate,
@@ -122,6 +130,10 @@ impl TryFrom<Event> for DispatchEvent {
 
     fn try_from(event: Event) -> Result<Self, Self::Error> {
         Ok(match event {
+            Event::AutoModerationActionExecution(v) => Self::AutoModerationActionExecution(v),
+            Event::AutoModerationRuleCreate(v) => Self::AutoModerationRuleCreate(v),
+            Event::AutoModerationRuleDelete(v) => Self::AutoModerationRuleDelete(v),
+            Event::AutoModerationRuleUpdate(v) => Self::AutoModerationRuleUpdate(v),
             Event::BanAdd(v) => Self::BanAdd(v),
             Event::BanRemove(v) => Self::BanRemove(v),
             Event::ChannelCreate(v) => Self::ChannelCreate(v),
@@ -193,6 +205,18 @@ impl<'de, 'a> DeserializeSeed<'de> for DispatchEventWithTypeDeserializer<'a> {
     #[allow(clippy::too_many_lines)]
     fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
         Ok(match self.0 {
+            "AUTO_MODERATION_ACTION_EXECUTION" => DispatchEvent::AutoModerationActionExecution(
+                AutoModerationActionExecution::deserialize(deserializer)?,
+            ),
+            "AUTO_MODERATION_RULE_CREATE" => DispatchEvent::AutoModerationRuleCreate(Box::new(
+                AutoModerationRuleCreate::deserialize(deserializer)?,
+            )),
+            "AUTO_MODERATION_RULE_DELETE" => DispatchEvent::AutoModerationRuleDelete(Box::new(
+                AutoModerationRuleDelete::deserialize(deserializer)?,
+            )),
+            "AUTO_MODERATION_RULE_UPDATE" => DispatchEvent::AutoModerationRuleUpdate(Box::new(
+                AutoModerationRuleUpdate::deserialize(deserializer)?,
+            )),
             "CHANNEL_CREATE" => {
                 DispatchEvent::ChannelCreate(ChannelCreate::deserialize(deserializer)?)
             }
@@ -5,6 +5,10 @@ use std::convert::TryFrom;
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum EventType {
+    AutoModerationActionExecution,
+    AutoModerationRuleCreate,
+    AutoModerationRuleDelete,
+    AutoModerationRuleUpdate,
     #[serde(rename = "GUILD_BAN_ADD")]
     BanAdd,
     #[serde(rename = "GUILD_BAN_REMOVE")]
@@ -67,6 +71,8 @@ pub enum EventType {
     ShardReconnecting,
     ShardPayload,
     ShardResuming,
+    ShardSessionStartLimitExhausted,
+    ShardZombied,
     StageInstanceCreate,
     StageInstanceDelete,
     StageInstanceUpdate,
@@ -81,6 +87,10 @@ pub enum EventType {
 impl EventType {
     pub const fn name(self) -> Option<&'static str> {
         match self {
+            Self::AutoModerationActionExecution => Some("AUTO_MODERATION_ACTION_EXECUTION"),
+            Self::AutoModerationRuleCreate => Some("AUTO_MODERATION_RULE_CREATE"),
+            Self::AutoModerationRuleDelete => Some("AUTO_MODERATION_RULE_DELETE"),
+            Self::AutoModerationRuleUpdate => Some("AUTO_MODERATION_RULE_UPDATE"),
             Self::BanAdd => Some("GUILD_BAN_ADD"),
             Self::BanRemove => Some("GUILD_BAN_REMOVE"),
             Self::ChannelCreate => Some("CHANNEL_CREATE"),
@@ -138,7 +148,9 @@ impl EventType {
             | Self::ShardIdentifying
             | Self::ShardReconnecting
             | Self::ShardPayload
-            | Self::ShardResuming => None,
+            | Self::ShardResuming
+            | Self::ShardSessionStartLimitExhausted
+            | Self::ShardZombied => None,
         }
     }
 }
@@ -148,6 +160,10 @@ impl<'a> TryFrom<&'a str> for EventType {
 
     fn try_from(event_type: &'a str) -> Result<Self, Self::Error> {
         match event_type {
+            "AUTO_MODERATION_ACTION_EXECUTION" => Ok(Self::AutoModerationActionExecution),
+            "AUTO_MODERATION_RULE_CREATE" => Ok(Self::AutoModerationRuleCreate),
+            "AUTO_MODERATION_RULE_DELETE" => Ok(Self::AutoModerationRuleDelete),
+            "AUTO_MODERATION_RULE_UPDATE" => Ok(Self::AutoModerationRuleUpdate),
             "GUILD_BAN_ADD" => Ok(Self::BanAdd),
             "GUILD_BAN_REMOVE" => Ok(Self::BanRemove),
             "CHANNEL_CREATE" => Ok(Self::ChannelCreate),
@@ -216,6 +232,22 @@ mod tests {
 
     #[test]
     fn test_variants() {
+        assert_variant(
+            EventType::AutoModerationActionExecution,
+            "AUTO_MODERATION_ACTION_EXECUTION",
+        );
+        assert_variant(
+            EventType::AutoModerationRuleCreate,
+            "AUTO_MODERATION_RULE_CREATE",
+        );
+        assert_variant(
+            EventType::AutoModerationRuleDelete,
+            "AUTO_MODERATION_RULE_DELETE",
+        );
+        assert_variant(
+            EventType::AutoModerationRuleUpdate,
+            "AUTO_MODERATION_RULE_UPDATE",
+        );
         assert_variant(EventType::BanAdd, "GUILD_BAN_ADD");
         assert_variant(EventType::BanRemove, "GUILD_BAN_REMOVE");
         assert_variant(EventType::ChannelCreate, "CHANNEL_CREATE");
@@ -274,6 +306,11 @@ mod tests {
         assert_variant(EventType::ShardPayload, "SHARD_PAYLOAD");
         assert_variant(EventType::ShardReconnecting, "SHARD_RECONNECTING");
         assert_variant(EventType::ShardResuming, "SHARD_RESUMING");
+        assert_variant(
+            EventType::ShardSessionStartLimitExhausted,
+            "SHARD_SESSION_START_LIMIT_EXHAUSTED",
+        );
+        assert_variant(EventType::ShardZombied, "SHARD_ZOMBIED");
         assert_variant(EventType::StageInstanceCreate, "STAGE_INSTANCE_CREATE");
         assert_variant(EventType::StageInstanceDelete, "STAGE_INSTANCE_DELETE");
         assert_variant(EventType::StageInstanceUpdate, "STAGE_INSTANCE_UPDATE");
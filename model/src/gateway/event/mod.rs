@@ -23,6 +23,14 @@ use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 /// [`GatewayEvent`]s, and [`ShardEvent`]s.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Event {
+    /// An auto moderation rule was triggered and an action was executed.
+    AutoModerationActionExecution(AutoModerationActionExecution),
+    /// An auto moderation rule was created.
+    AutoModerationRuleCreate(Box<AutoModerationRuleCreate>),
+    /// An auto moderation rule was deleted.
+    AutoModerationRuleDelete(Box<AutoModerationRuleDelete>),
+    /// An auto moderation rule was updated.
+    AutoModerationRuleUpdate(Box<AutoModerationRuleUpdate>),
     /// A user was banned from a guild.
     BanAdd(BanAdd),
     /// A user's ban from a guild was removed.
@@ -129,6 +137,11 @@ pub enum Event {
     ShardPayload(Payload),
     /// A shard is now in a Resuming stage after a disconnect.
     ShardResuming(Resuming),
+    /// A shard is waiting for the session start limit to be reset before
+    /// identifying.
+    ShardSessionStartLimitExhausted(SessionStartLimitExhausted),
+    /// A shard's connection stopped responding and is being replaced.
+    ShardZombied(Zombied),
     /// A stage instance was created in a stage channel.
     StageInstanceCreate(StageInstanceCreate),
     /// A stage instance was deleted in a stage channel.
@@ -152,6 +165,10 @@ pub enum Event {
 impl Event {
     pub const fn kind(&self) -> EventType {
         match self {
+            Self::AutoModerationActionExecution(_) => EventType::AutoModerationActionExecution,
+            Self::AutoModerationRuleCreate(_) => EventType::AutoModerationRuleCreate,
+            Self::AutoModerationRuleDelete(_) => EventType::AutoModerationRuleDelete,
+            Self::AutoModerationRuleUpdate(_) => EventType::AutoModerationRuleUpdate,
             Self::BanAdd(_) => EventType::BanAdd,
             Self::BanRemove(_) => EventType::BanRemove,
             Self::ChannelCreate(_) => EventType::ChannelCreate,
@@ -201,6 +218,8 @@ impl Event {
             Self::ShardReconnecting(_) => EventType::ShardReconnecting,
             Self::ShardPayload(_) => EventType::ShardPayload,
             Self::ShardResuming(_) => EventType::ShardResuming,
+            Self::ShardSessionStartLimitExhausted(_) => EventType::ShardSessionStartLimitExhausted,
+            Self::ShardZombied(_) => EventType::ShardZombied,
             Self::StageInstanceCreate(_) => EventType::StageInstanceCreate,
             Self::StageInstanceDelete(_) => EventType::StageInstanceDelete,
             Self::StageInstanceUpdate(_) => EventType::StageInstanceUpdate,
@@ -217,6 +236,12 @@ impl Event {
 impl From<Box<DispatchEvent>> for Event {
     fn from(event: Box<DispatchEvent>) -> Self {
         match *event {
+            DispatchEvent::AutoModerationActionExecution(v) => {
+                Self::AutoModerationActionExecution(v)
+            }
+            DispatchEvent::AutoModerationRuleCreate(v) => Self::AutoModerationRuleCreate(v),
+            DispatchEvent::AutoModerationRuleDelete(v) => Self::AutoModerationRuleDelete(v),
+            DispatchEvent::AutoModerationRuleUpdate(v) => Self::AutoModerationRuleUpdate(v),
             DispatchEvent::BanAdd(v) => Self::BanAdd(v),
             DispatchEvent::BanRemove(v) => Self::BanRemove(v),
             DispatchEvent::ChannelCreate(v) => Self::ChannelCreate(v),
@@ -290,6 +315,8 @@ impl From<ShardEvent> for Event {
             ShardEvent::Payload(v) => Self::ShardPayload(v),
             ShardEvent::Reconnecting(v) => Self::ShardReconnecting(v),
             ShardEvent::Resuming(v) => Self::ShardResuming(v),
+            ShardEvent::SessionStartLimitExhausted(v) => Self::ShardSessionStartLimitExhausted(v),
+            ShardEvent::Zombied(v) => Self::ShardZombied(v),
         }
     }
 }
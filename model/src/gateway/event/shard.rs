@@ -1,4 +1,7 @@
-use super::{Event, EventConversionError};
+use super::{
+    super::{CloseCode, CloseCodeKind},
+    Event, EventConversionError,
+};
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 
@@ -26,12 +29,29 @@ pub struct Connecting {
 pub struct Disconnected {
     /// The code for the disconnect if not initiated by the host, if any.
     pub code: Option<u16>,
+    /// Classification of the gateway close code of the disconnect, if the
+    /// code is one.
+    ///
+    /// A shard disconnected with a [`CloseCodeKind::Fatal`] code is stopped
+    /// and does not reconnect. Its error is set before the event is emitted.
+    #[serde(default)]
+    pub kind: Option<CloseCodeKind>,
     /// The reason for the disconnect if not initiated by the host, if any.
     pub reason: Option<String>,
     /// The ID of the shard that's now disconnected.
     pub shard_id: u64,
 }
 
+impl Disconnected {
+    /// Gateway close code of the disconnect, if the code is one.
+    ///
+    /// Other codes, such as those of websocket close frames sent by the shard
+    /// itself, return `None`.
+    pub fn close_code(&self) -> Option<CloseCode> {
+        self.code.and_then(|code| CloseCode::try_from(code).ok())
+    }
+}
+
 /// Indicator that a shard is now identifying with the gateway to create a new
 /// session.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -65,6 +85,39 @@ pub struct Resuming {
     pub shard_id: u64,
 }
 
+/// Indicator that a shard is waiting for the session start limit to be reset
+/// before identifying with the gateway.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SessionStartLimitExhausted {
+    /// Number of sessions remaining, all of which are reserved.
+    pub remaining: u64,
+    /// Milliseconds until the session start limit is reset.
+    pub reset_after: u64,
+    /// The ID of the shard that is waiting.
+    pub shard_id: u64,
+}
+
+/// Reason a shard's connection was deemed to be a zombie.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+#[serde(rename_all = "snake_case")]
+pub enum ZombieReason {
+    /// Heartbeats sent to the gateway were not acknowledged.
+    HeartbeatAckMissed,
+}
+
+/// Indicator that a shard's connection stopped responding and is being
+/// replaced by resuming or reconnecting.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Zombied {
+    /// Number of consecutive heartbeats that were not acknowledged.
+    pub missed_acks: u32,
+    /// Reason the connection was deemed to be a zombie.
+    pub reason: ZombieReason,
+    /// The ID of the shard whose connection was a zombie.
+    pub shard_id: u64,
+}
+
 /// "Meta" events about a shard's status, not from the gateway.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
@@ -86,6 +139,10 @@ pub enum ShardEvent {
     Reconnecting(Reconnecting),
     /// A shard is now in a Resuming stage after a disconnect.
     Resuming(Resuming),
+    /// A shard is waiting for the session start limit to be reset.
+    SessionStartLimitExhausted(SessionStartLimitExhausted),
+    /// A shard's connection stopped responding.
+    Zombied(Zombied),
 }
 
 impl TryFrom<Event> for ShardEvent {
@@ -100,6 +157,8 @@ impl TryFrom<Event> for ShardEvent {
             Event::ShardPayload(v) => Self::Payload(v),
             Event::ShardReconnecting(v) => Self::Reconnecting(v),
             Event::ShardResuming(v) => Self::Resuming(v),
+            Event::ShardSessionStartLimitExhausted(v) => Self::SessionStartLimitExhausted(v),
+            Event::ShardZombied(v) => Self::Zombied(v),
 
             _ => return Err(EventConversionError::new(event)),
         })
@@ -109,8 +168,8 @@ impl TryFrom<Event> for ShardEvent {
 #[cfg(test)]
 mod tests {
     use super::{
-        Connected, Connecting, Disconnected, Event, Identifying, Payload, Reconnecting, Resuming,
-        ShardEvent,
+        CloseCode, CloseCodeKind, Connected, Connecting, Disconnected, Event, Identifying, Payload,
+        Reconnecting, Resuming, SessionStartLimitExhausted, ShardEvent, ZombieReason, Zombied,
     };
     use serde_test::Token;
     use std::convert::TryInto;
@@ -161,10 +220,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_disconnected_close_code() {
+        let mut value = Disconnected {
+            code: Some(4_004),
+            kind: Some(CloseCodeKind::Fatal),
+            reason: None,
+            shard_id: 4,
+        };
+        assert_eq!(Some(CloseCode::AuthenticationFailed), value.close_code());
+
+        value.code = Some(1_000);
+        assert!(value.close_code().is_none());
+
+        value.code = None;
+        assert!(value.close_code().is_none());
+    }
+
     #[test]
     fn test_disconnected() {
         let value = Disconnected {
             code: Some(4_000),
+            kind: Some(CloseCodeKind::Resume),
             reason: Some("the reason".to_owned()),
             shard_id: 4,
         };
@@ -174,11 +251,17 @@ mod tests {
             &[
                 Token::Struct {
                     name: "Disconnected",
-                    len: 3,
+                    len: 4,
                 },
                 Token::Str("code"),
                 Token::Some,
                 Token::U16(4_000),
+                Token::Str("kind"),
+                Token::Some,
+                Token::UnitVariant {
+                    name: "CloseCodeKind",
+                    variant: "resume",
+                },
                 Token::Str("reason"),
                 Token::Some,
                 Token::Str("the reason"),
@@ -274,6 +357,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_session_start_limit_exhausted() {
+        let value = SessionStartLimitExhausted {
+            remaining: 2,
+            reset_after: 60_000,
+            shard_id: 4,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "SessionStartLimitExhausted",
+                    len: 3,
+                },
+                Token::Str("remaining"),
+                Token::U64(2),
+                Token::Str("reset_after"),
+                Token::U64(60_000),
+                Token::Str("shard_id"),
+                Token::U64(4),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_zombied() {
+        let value = Zombied {
+            missed_acks: 2,
+            reason: ZombieReason::HeartbeatAckMissed,
+            shard_id: 4,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "Zombied",
+                    len: 3,
+                },
+                Token::Str("missed_acks"),
+                Token::U32(2),
+                Token::Str("reason"),
+                Token::UnitVariant {
+                    name: "ZombieReason",
+                    variant: "heartbeat_ack_missed",
+                },
+                Token::Str("shard_id"),
+                Token::U64(4),
+                Token::StructEnd,
+            ],
+        );
+    }
+
     #[test]
     fn test_shard_event_try_from_event() {
         let connected = Event::ShardConnected(Connected {
@@ -296,6 +434,7 @@ mod tests {
 
         let disconnected = Event::ShardDisconnected(Disconnected {
             code: Some(4_000),
+            kind: Some(CloseCodeKind::Resume),
             reason: None,
             shard_id: 4,
         });
@@ -333,5 +472,25 @@ mod tests {
             resuming.try_into().unwrap(),
             ShardEvent::Resuming(_)
         ));
+
+        let exhausted = Event::ShardSessionStartLimitExhausted(SessionStartLimitExhausted {
+            remaining: 2,
+            reset_after: 60_000,
+            shard_id: 4,
+        });
+        assert!(matches!(
+            exhausted.try_into().unwrap(),
+            ShardEvent::SessionStartLimitExhausted(_)
+        ));
+
+        let zombied = Event::ShardZombied(Zombied {
+            missed_acks: 2,
+            reason: ZombieReason::HeartbeatAckMissed,
+            shard_id: 4,
+        });
+        assert!(matches!(
+            zombied.try_into().unwrap(),
+            ShardEvent::Zombied(_)
+        ));
     }
 }
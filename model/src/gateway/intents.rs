@@ -189,6 +189,24 @@ bitflags! {
         /// [`TYPING_START`]: super::event::Event::TypingStart
         /// [`GUILD_MESSAGE_TYPING`]: Self::GUILD_MESSAGE_TYPING
         const DIRECT_MESSAGE_TYPING = 1 << 14;
+        /// Auto moderation configuration intent.
+        ///
+        /// Event(s) received:
+        ///  - [`AUTO_MODERATION_RULE_CREATE`]
+        ///  - [`AUTO_MODERATION_RULE_UPDATE`]
+        ///  - [`AUTO_MODERATION_RULE_DELETE`]
+        ///
+        /// [`AUTO_MODERATION_RULE_CREATE`]: super::event::Event::AutoModerationRuleCreate
+        /// [`AUTO_MODERATION_RULE_UPDATE`]: super::event::Event::AutoModerationRuleUpdate
+        /// [`AUTO_MODERATION_RULE_DELETE`]: super::event::Event::AutoModerationRuleDelete
+        const AUTO_MODERATION_CONFIGURATION = 1 << 20;
+        /// Auto moderation execution intent.
+        ///
+        /// Event(s) received:
+        ///  - [`AUTO_MODERATION_ACTION_EXECUTION`]
+        ///
+        /// [`AUTO_MODERATION_ACTION_EXECUTION`]: super::event::Event::AutoModerationActionExecution
+        const AUTO_MODERATION_EXECUTION = 1 << 21;
     }
 }
 
@@ -229,5 +247,10 @@ mod tests {
         serde_test::assert_tokens(&Intents::DIRECT_MESSAGES, &[Token::U64(1 << 12)]);
         serde_test::assert_tokens(&Intents::DIRECT_MESSAGE_REACTIONS, &[Token::U64(1 << 13)]);
         serde_test::assert_tokens(&Intents::DIRECT_MESSAGE_TYPING, &[Token::U64(1 << 14)]);
+        serde_test::assert_tokens(
+            &Intents::AUTO_MODERATION_CONFIGURATION,
+            &[Token::U64(1 << 20)],
+        );
+        serde_test::assert_tokens(&Intents::AUTO_MODERATION_EXECUTION, &[Token::U64(1 << 21)]);
     }
 }
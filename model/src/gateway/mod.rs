@@ -9,7 +9,7 @@ mod opcode;
 mod session_start_limit;
 
 pub use self::{
-    close_code::{CloseCode, CloseCodeConversionError},
+    close_code::{CloseCode, CloseCodeConversionError, CloseCodeKind},
     intents::Intents,
     opcode::OpCode,
     session_start_limit::SessionStartLimit,
@@ -0,0 +1,124 @@
+use crate::{
+    guild::auto_moderation::{AutoModerationAction, AutoModerationTriggerType},
+    id::{AutoModerationRuleId, ChannelId, GuildId, MessageId, UserId},
+};
+use serde::{Deserialize, Serialize};
+
+/// An [`AutoModerationRule`] has been triggered and an action was executed.
+///
+/// [`AutoModerationRule`]: crate::guild::auto_moderation::AutoModerationRule
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct AutoModerationActionExecution {
+    /// Action which was executed.
+    pub action: AutoModerationAction,
+    /// ID of any system auto moderation messages posted as a result of this
+    /// action.
+    ///
+    /// Not present if the message was blocked or the alert could not be sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alert_system_message_id: Option<MessageId>,
+    /// ID of the channel in which user content was posted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    /// User generated text content.
+    pub content: String,
+    /// ID of the guild in which the action was executed.
+    pub guild_id: GuildId,
+    /// Substring in content that triggered the rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_content: Option<String>,
+    /// Word or phrase configured in the rule that triggered the rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_keyword: Option<String>,
+    /// ID of any user message which content belongs to.
+    ///
+    /// Not present if the message was blocked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<MessageId>,
+    /// ID of the rule which action belongs to.
+    pub rule_id: AutoModerationRuleId,
+    /// Type of rule which was triggered.
+    pub rule_trigger_type: AutoModerationTriggerType,
+    /// ID of the user which generated the content which triggered the rule.
+    pub user_id: UserId,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AutoModerationActionExecution;
+    use crate::{
+        guild::auto_moderation::{
+            AutoModerationAction, AutoModerationActionType, AutoModerationTriggerType,
+        },
+        id::{AutoModerationRuleId, ChannelId, GuildId, MessageId, UserId},
+    };
+    use serde_test::Token;
+
+    #[test]
+    fn test_action_execution() {
+        let value = AutoModerationActionExecution {
+            action: AutoModerationAction {
+                kind: AutoModerationActionType::BlockMessage,
+                metadata: None,
+            },
+            alert_system_message_id: None,
+            channel_id: Some(ChannelId(1)),
+            content: "darn".to_owned(),
+            guild_id: GuildId(2),
+            matched_content: Some("darn".to_owned()),
+            matched_keyword: Some("darn".to_owned()),
+            message_id: Some(MessageId(3)),
+            rule_id: AutoModerationRuleId(4),
+            rule_trigger_type: AutoModerationTriggerType::Keyword,
+            user_id: UserId(5),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "AutoModerationActionExecution",
+                    len: 10,
+                },
+                Token::Str("action"),
+                Token::Struct {
+                    name: "AutoModerationAction",
+                    len: 1,
+                },
+                Token::Str("type"),
+                Token::U8(1),
+                Token::StructEnd,
+                Token::Str("channel_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "ChannelId" },
+                Token::Str("1"),
+                Token::Str("content"),
+                Token::Str("darn"),
+                Token::Str("guild_id"),
+                Token::NewtypeStruct { name: "GuildId" },
+                Token::Str("2"),
+                Token::Str("matched_content"),
+                Token::Some,
+                Token::Str("darn"),
+                Token::Str("matched_keyword"),
+                Token::Some,
+                Token::Str("darn"),
+                Token::Str("message_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "MessageId" },
+                Token::Str("3"),
+                Token::Str("rule_id"),
+                Token::NewtypeStruct {
+                    name: "AutoModerationRuleId",
+                },
+                Token::Str("4"),
+                Token::Str("rule_trigger_type"),
+                Token::U8(1),
+                Token::Str("user_id"),
+                Token::NewtypeStruct { name: "UserId" },
+                Token::Str("5"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
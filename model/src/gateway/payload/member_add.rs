@@ -31,6 +31,7 @@ mod tests {
     #[test]
     fn test_member_add() {
         let value = MemberAdd(Member {
+            communication_disabled_until: None,
             deaf: false,
             guild_id: GuildId(1),
             hoisted_role: None,
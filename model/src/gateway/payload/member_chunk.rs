@@ -305,6 +305,7 @@ mod tests {
             guild_id: GuildId(1),
             members: Vec::from([
                 Member {
+                    communication_disabled_until: None,
                     deaf: false,
                     guild_id: GuildId(1),
                     hoisted_role: Some(RoleId(6)),
@@ -331,6 +332,7 @@ mod tests {
                     },
                 },
                 Member {
+                    communication_disabled_until: None,
                     deaf: false,
                     guild_id: GuildId(1),
                     hoisted_role: Some(RoleId(6)),
@@ -357,6 +359,7 @@ mod tests {
                     },
                 },
                 Member {
+                    communication_disabled_until: None,
                     deaf: false,
                     guild_id: GuildId(1),
                     hoisted_role: Some(RoleId(6)),
@@ -383,6 +386,7 @@ mod tests {
                     },
                 },
                 Member {
+                    communication_disabled_until: None,
                     deaf: false,
                     guild_id: GuildId(1),
                     hoisted_role: Some(RoleId(6)),
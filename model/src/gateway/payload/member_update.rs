@@ -6,6 +6,12 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct MemberUpdate {
+    /// ISO 8601 timestamp until which the member is timed out.
+    ///
+    /// While timed out, the member can only view channels and read their
+    /// message history.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub communication_disabled_until: Option<String>,
     pub guild_id: GuildId,
     pub deaf: Option<bool>,
     pub joined_at: String,
@@ -36,6 +42,7 @@ mod tests {
     #[test]
     fn test_member_update() {
         let value = MemberUpdate {
+            communication_disabled_until: None,
             user: User {
                 name: "Twilight Sparkle".to_string(),
                 public_flags: None,
@@ -4,6 +4,10 @@ pub mod request_guild_members;
 pub mod resume;
 pub mod update_presence;
 
+mod auto_moderation_action_execution;
+mod auto_moderation_rule_create;
+mod auto_moderation_rule_delete;
+mod auto_moderation_rule_update;
 mod ban_add;
 mod ban_remove;
 mod channel_create;
@@ -50,11 +54,15 @@ mod voice_state_update;
 mod webhooks_update;
 
 pub use self::{
-    ban_add::BanAdd, ban_remove::BanRemove, channel_create::ChannelCreate,
-    channel_delete::ChannelDelete, channel_pins_update::ChannelPinsUpdate,
-    channel_update::ChannelUpdate, guild_create::GuildCreate, guild_delete::GuildDelete,
-    guild_emojis_update::GuildEmojisUpdate, guild_integrations_update::GuildIntegrationsUpdate,
-    guild_update::GuildUpdate, heartbeat::Heartbeat, integration_create::IntegrationCreate,
+    auto_moderation_action_execution::AutoModerationActionExecution,
+    auto_moderation_rule_create::AutoModerationRuleCreate,
+    auto_moderation_rule_delete::AutoModerationRuleDelete,
+    auto_moderation_rule_update::AutoModerationRuleUpdate, ban_add::BanAdd, ban_remove::BanRemove,
+    channel_create::ChannelCreate, channel_delete::ChannelDelete,
+    channel_pins_update::ChannelPinsUpdate, channel_update::ChannelUpdate,
+    guild_create::GuildCreate, guild_delete::GuildDelete, guild_emojis_update::GuildEmojisUpdate,
+    guild_integrations_update::GuildIntegrationsUpdate, guild_update::GuildUpdate,
+    heartbeat::Heartbeat, integration_create::IntegrationCreate,
     integration_delete::IntegrationDelete, integration_update::IntegrationUpdate,
     interaction_create::InteractionCreate, invite_create::InviteCreate,
     invite_delete::InviteDelete, member_add::MemberAdd, member_chunk::MemberChunk,
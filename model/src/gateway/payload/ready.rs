@@ -5,6 +5,9 @@ use serde::{Deserialize, Serialize};
 pub struct Ready {
     pub application: PartialApplication,
     pub guilds: Vec<UnavailableGuild>,
+    /// Gateway URL to use when resuming the session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_gateway_url: Option<String>,
     pub session_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shard: Option<[u64; 2]>,
@@ -44,6 +47,7 @@ mod tests {
                 id: ApplicationId(100),
             },
             guilds,
+            resume_gateway_url: Some("wss://resume.discord.gg".to_owned()),
             session_id: "foo".to_owned(),
             shard: Some([4, 7]),
             user: CurrentUser {
@@ -68,7 +72,7 @@ mod tests {
             &[
                 Token::Struct {
                     name: "Ready",
-                    len: 6,
+                    len: 7,
                 },
                 Token::Str("application"),
                 Token::Struct {
@@ -106,6 +110,9 @@ mod tests {
                 Token::Bool(true),
                 Token::StructEnd,
                 Token::SeqEnd,
+                Token::Str("resume_gateway_url"),
+                Token::Some,
+                Token::Str("wss://resume.discord.gg"),
                 Token::Str("session_id"),
                 Token::Str("foo"),
                 Token::Str("shard"),
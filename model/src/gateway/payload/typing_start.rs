@@ -163,6 +163,7 @@ mod tests {
             channel_id: ChannelId(2),
             guild_id: Some(GuildId(1)),
             member: Some(Member {
+                communication_disabled_until: None,
                 deaf: false,
                 guild_id: GuildId(1),
                 hoisted_role: Some(RoleId(4)),
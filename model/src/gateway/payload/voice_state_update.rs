@@ -22,6 +22,7 @@ mod tests {
             deaf: false,
             guild_id: Some(GuildId(1)),
             member: Some(Member {
+                communication_disabled_until: None,
                 deaf: false,
                 guild_id: GuildId(1),
                 hoisted_role: Some(RoleId(4)),
@@ -152,6 +153,7 @@ mod tests {
             deaf: false,
             guild_id: Some(GuildId(999_999)),
             member: Some(Member {
+                communication_disabled_until: None,
                 deaf: false,
                 guild_id: GuildId(999_999),
                 hoisted_role: Some(RoleId(123)),
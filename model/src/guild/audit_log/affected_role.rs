@@ -0,0 +1,44 @@
+use crate::id::RoleId;
+use serde::{Deserialize, Serialize};
+
+/// Role added to or removed from a member in an [`AuditLogChange`].
+///
+/// [`AuditLogChange`]: super::AuditLogChange
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct AffectedRole {
+    /// ID of the role.
+    pub id: RoleId,
+    /// Name of the role.
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AffectedRole;
+    use crate::id::RoleId;
+    use serde_test::Token;
+
+    #[test]
+    fn test_affected_role() {
+        let value = AffectedRole {
+            id: RoleId(1),
+            name: "role".to_owned(),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "AffectedRole",
+                    len: 2,
+                },
+                Token::Str("id"),
+                Token::NewtypeStruct { name: "RoleId" },
+                Token::Str("1"),
+                Token::Str("name"),
+                Token::Str("role"),
+                Token::StructEnd,
+            ],
+        );
+    }
+}
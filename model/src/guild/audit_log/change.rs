@@ -1,12 +1,1406 @@
-use crate::guild::audit_log::AuditLogChangeKey;
-use serde::{Deserialize, Serialize};
-use serde_value::Value;
-
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct AuditLogChange {
-    pub key: AuditLogChangeKey,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub new_value: Option<Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub old_value: Option<Value>,
+use super::{AffectedRole, AuditLogChangeKey, AuditLogChangeTypeValue};
+use crate::{
+    channel::{permission_overwrite::PermissionOverwrite, stage_instance::PrivacyLevel},
+    guild::{
+        DefaultMessageNotificationLevel, ExplicitContentFilter, IntegrationExpireBehavior,
+        MfaLevel, NSFWLevel, Permissions, VerificationLevel,
+    },
+    id::{ApplicationId, ChannelId, GenericId, UserId},
+};
+use serde::{
+    de::{
+        DeserializeOwned, Deserializer, Error as DeError, IgnoredAny, IntoDeserializer, MapAccess,
+        Visitor,
+    },
+    ser::{SerializeStruct, Serializer},
+    Deserialize, Serialize,
+};
+use serde_value::{Value, ValueDeserializer};
+use std::fmt::{Formatter, Result as FmtResult};
+
+/// Change made to an entity in an [`AuditLogEntry`].
+///
+/// Each variant corresponds to an [`AuditLogChangeKey`] and contains the typed
+/// old and new values of the change. Either value may be absent, such as when
+/// an entity was created or deleted.
+///
+/// Changes with a key that isn't known, or with values of an unexpected type,
+/// are deserialized as [`Other`] with their raw values.
+///
+/// [`Other`]: Self::Other
+///
+/// [`AuditLogEntry`]: super::AuditLogEntry
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AuditLogChange {
+    /// AFK channel of a guild.
+    AfkChannelId {
+        /// New value.
+        new: Option<ChannelId>,
+        /// Old value.
+        old: Option<ChannelId>,
+    },
+    /// AFK timeout of a guild, in seconds.
+    AfkTimeout {
+        /// New value.
+        new: Option<u64>,
+        /// Old value.
+        old: Option<u64>,
+    },
+    /// Permissions allowed by a permission overwrite.
+    Allow {
+        /// New value.
+        new: Option<Permissions>,
+        /// Old value.
+        old: Option<Permissions>,
+    },
+    /// ID of the application of a webhook or bot.
+    ApplicationId {
+        /// New value.
+        new: Option<ApplicationId>,
+        /// Old value.
+        old: Option<ApplicationId>,
+    },
+    /// Hash of a user's or webhook's avatar.
+    AvatarHash {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Hash of a guild's banner.
+    BannerHash {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Bitrate of a voice channel.
+    Bitrate {
+        /// New value.
+        new: Option<u64>,
+        /// Old value.
+        old: Option<u64>,
+    },
+    /// Channel of an invite or webhook.
+    ChannelId {
+        /// New value.
+        new: Option<ChannelId>,
+        /// Old value.
+        old: Option<ChannelId>,
+    },
+    /// Code of an invite.
+    Code {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Color of a role.
+    Color {
+        /// New value.
+        new: Option<u32>,
+        /// Old value.
+        old: Option<u32>,
+    },
+    /// ISO 8601 timestamp until which a member is timed out.
+    CommunicationDisabledUntil {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Whether a member is server deafened.
+    Deaf {
+        /// New value.
+        new: Option<bool>,
+        /// Old value.
+        old: Option<bool>,
+    },
+    /// Default message notification level of a guild.
+    DefaultMessageNotifications {
+        /// New value.
+        new: Option<DefaultMessageNotificationLevel>,
+        /// Old value.
+        old: Option<DefaultMessageNotificationLevel>,
+    },
+    /// Permissions denied by a permission overwrite.
+    Deny {
+        /// New value.
+        new: Option<Permissions>,
+        /// Old value.
+        old: Option<Permissions>,
+    },
+    /// Description of a guild.
+    Description {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Hash of a guild's discovery splash.
+    DiscoverySplashHash {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Whether emoticons of an integration are enabled.
+    EnableEmoticons {
+        /// New value.
+        new: Option<bool>,
+        /// Old value.
+        old: Option<bool>,
+    },
+    /// Behavior of an integration when its subscription expires.
+    ExpireBehavior {
+        /// New value.
+        new: Option<IntegrationExpireBehavior>,
+        /// Old value.
+        old: Option<IntegrationExpireBehavior>,
+    },
+    /// Grace period of an integration's expired subscription, in days.
+    ExpireGracePeriod {
+        /// New value.
+        new: Option<u64>,
+        /// Old value.
+        old: Option<u64>,
+    },
+    /// Explicit content filter level of a guild.
+    ExplicitContentFilter {
+        /// New value.
+        new: Option<ExplicitContentFilter>,
+        /// Old value.
+        old: Option<ExplicitContentFilter>,
+    },
+    /// Whether a role is displayed separately in the sidebar.
+    Hoist {
+        /// New value.
+        new: Option<bool>,
+        /// Old value.
+        old: Option<bool>,
+    },
+    /// Hash of a guild's icon.
+    IconHash {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// ID of the changed entity.
+    Id {
+        /// New value.
+        new: Option<GenericId>,
+        /// Old value.
+        old: Option<GenericId>,
+    },
+    /// Creator of an invite.
+    InviterId {
+        /// New value.
+        new: Option<UserId>,
+        /// Old value.
+        old: Option<UserId>,
+    },
+    /// Maximum age of an invite, in seconds.
+    MaxAge {
+        /// New value.
+        new: Option<u64>,
+        /// Old value.
+        old: Option<u64>,
+    },
+    /// Maximum number of uses of an invite.
+    MaxUses {
+        /// New value.
+        new: Option<u64>,
+        /// Old value.
+        old: Option<u64>,
+    },
+    /// Whether a role is mentionable.
+    Mentionable {
+        /// New value.
+        new: Option<bool>,
+        /// Old value.
+        old: Option<bool>,
+    },
+    /// Multi-Factor Authentication level required for a guild's moderators.
+    MfaLevel {
+        /// New value.
+        new: Option<MfaLevel>,
+        /// Old value.
+        old: Option<MfaLevel>,
+    },
+    /// Whether a member is server muted.
+    Mute {
+        /// New value.
+        new: Option<bool>,
+        /// Old value.
+        old: Option<bool>,
+    },
+    /// Name of the changed entity.
+    Name {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Nickname of a member.
+    Nick {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// NSFW level of a guild.
+    NsfwLevel {
+        /// New value.
+        new: Option<NSFWLevel>,
+        /// Old value.
+        old: Option<NSFWLevel>,
+    },
+    /// Change with a key that isn't known, or with values of an unexpected
+    /// type.
+    Other {
+        /// Key of the change.
+        key: String,
+        /// Raw new value.
+        new: Option<Value>,
+        /// Raw old value.
+        old: Option<Value>,
+    },
+    /// Owner of a guild.
+    OwnerId {
+        /// New value.
+        new: Option<UserId>,
+        /// Old value.
+        old: Option<UserId>,
+    },
+    /// Permission overwrites of a channel.
+    PermissionOverwrites {
+        /// New value.
+        new: Option<Vec<PermissionOverwrite>>,
+        /// Old value.
+        old: Option<Vec<PermissionOverwrite>>,
+    },
+    /// Permissions of a role.
+    Permissions {
+        /// New value.
+        new: Option<Permissions>,
+        /// Old value.
+        old: Option<Permissions>,
+    },
+    /// Position of a channel or role.
+    Position {
+        /// New value.
+        new: Option<i64>,
+        /// Old value.
+        old: Option<i64>,
+    },
+    /// Preferred locale of a guild.
+    PreferredLocale {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Privacy level of a stage instance.
+    PrivacyLevel {
+        /// New value.
+        new: Option<PrivacyLevel>,
+        /// Old value.
+        old: Option<PrivacyLevel>,
+    },
+    /// Number of days of inactivity after which members were pruned.
+    PruneDeleteDays {
+        /// New value.
+        new: Option<u64>,
+        /// Old value.
+        old: Option<u64>,
+    },
+    /// Channel where community guilds receive notices from Discord.
+    PublicUpdatesChannelId {
+        /// New value.
+        new: Option<ChannelId>,
+        /// Old value.
+        old: Option<ChannelId>,
+    },
+    /// Ratelimit per user of a text channel, in seconds.
+    RateLimitPerUser {
+        /// New value.
+        new: Option<u64>,
+        /// Old value.
+        old: Option<u64>,
+    },
+    /// Voice region of a guild.
+    Region {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Roles added to a member.
+    RoleAdded {
+        /// New value.
+        new: Option<Vec<AffectedRole>>,
+        /// Old value.
+        old: Option<Vec<AffectedRole>>,
+    },
+    /// Roles removed from a member.
+    RoleRemoved {
+        /// New value.
+        new: Option<Vec<AffectedRole>>,
+        /// Old value.
+        old: Option<Vec<AffectedRole>>,
+    },
+    /// Channel where community guilds display rules.
+    RulesChannelId {
+        /// New value.
+        new: Option<ChannelId>,
+        /// Old value.
+        old: Option<ChannelId>,
+    },
+    /// Hash of a guild's invite splash.
+    SplashHash {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Channel where system messages of a guild are sent.
+    SystemChannelId {
+        /// New value.
+        new: Option<ChannelId>,
+        /// Old value.
+        old: Option<ChannelId>,
+    },
+    /// Whether an invite grants temporary membership.
+    Temporary {
+        /// New value.
+        new: Option<bool>,
+        /// Old value.
+        old: Option<bool>,
+    },
+    /// Topic of a text channel or stage instance.
+    Topic {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Type of a channel or integration.
+    Type {
+        /// New value.
+        new: Option<AuditLogChangeTypeValue>,
+        /// Old value.
+        old: Option<AuditLogChangeTypeValue>,
+    },
+    /// Maximum number of users in a voice channel.
+    UserLimit {
+        /// New value.
+        new: Option<u64>,
+        /// Old value.
+        old: Option<u64>,
+    },
+    /// Number of times an invite has been used.
+    Uses {
+        /// New value.
+        new: Option<u64>,
+        /// Old value.
+        old: Option<u64>,
+    },
+    /// Vanity URL code of a guild.
+    VanityUrlCode {
+        /// New value.
+        new: Option<String>,
+        /// Old value.
+        old: Option<String>,
+    },
+    /// Verification level of a guild.
+    VerificationLevel {
+        /// New value.
+        new: Option<VerificationLevel>,
+        /// Old value.
+        old: Option<VerificationLevel>,
+    },
+    /// Channel a guild's widget generates invites to.
+    WidgetChannelId {
+        /// New value.
+        new: Option<ChannelId>,
+        /// Old value.
+        old: Option<ChannelId>,
+    },
+    /// Whether a guild's widget is enabled.
+    WidgetEnabled {
+        /// New value.
+        new: Option<bool>,
+        /// Old value.
+        old: Option<bool>,
+    },
+}
+
+impl AuditLogChange {
+    /// Key of the change.
+    ///
+    /// Returns `None` for [`Other`] changes, whose key isn't known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twilight_model::guild::audit_log::{AuditLogChange, AuditLogChangeKey};
+    ///
+    /// let change = AuditLogChange::Name {
+    ///     new: Some("new name".to_owned()),
+    ///     old: Some("old name".to_owned()),
+    /// };
+    ///
+    /// assert_eq!(Some(AuditLogChangeKey::Name), change.key());
+    /// ```
+    ///
+    /// [`Other`]: Self::Other
+    pub const fn key(&self) -> Option<AuditLogChangeKey> {
+        Some(match self {
+            Self::AfkChannelId { .. } => AuditLogChangeKey::AfkChannelId,
+            Self::AfkTimeout { .. } => AuditLogChangeKey::AfkTimeout,
+            Self::Allow { .. } => AuditLogChangeKey::Allow,
+            Self::ApplicationId { .. } => AuditLogChangeKey::ApplicationId,
+            Self::AvatarHash { .. } => AuditLogChangeKey::AvatarHash,
+            Self::BannerHash { .. } => AuditLogChangeKey::BannerHash,
+            Self::Bitrate { .. } => AuditLogChangeKey::Bitrate,
+            Self::ChannelId { .. } => AuditLogChangeKey::ChannelId,
+            Self::Code { .. } => AuditLogChangeKey::Code,
+            Self::Color { .. } => AuditLogChangeKey::Color,
+            Self::CommunicationDisabledUntil { .. } => {
+                AuditLogChangeKey::CommunicationDisabledUntil
+            }
+            Self::Deaf { .. } => AuditLogChangeKey::Deaf,
+            Self::DefaultMessageNotifications { .. } => {
+                AuditLogChangeKey::DefaultMessageNotifications
+            }
+            Self::Deny { .. } => AuditLogChangeKey::Deny,
+            Self::Description { .. } => AuditLogChangeKey::Description,
+            Self::DiscoverySplashHash { .. } => AuditLogChangeKey::DiscoverySplashHash,
+            Self::EnableEmoticons { .. } => AuditLogChangeKey::EnableEmoticons,
+            Self::ExpireBehavior { .. } => AuditLogChangeKey::ExpireBehavior,
+            Self::ExpireGracePeriod { .. } => AuditLogChangeKey::ExpireGracePeriod,
+            Self::ExplicitContentFilter { .. } => AuditLogChangeKey::ExplicitContentFilter,
+            Self::Hoist { .. } => AuditLogChangeKey::Hoist,
+            Self::IconHash { .. } => AuditLogChangeKey::IconHash,
+            Self::Id { .. } => AuditLogChangeKey::Id,
+            Self::InviterId { .. } => AuditLogChangeKey::InviterId,
+            Self::MaxAge { .. } => AuditLogChangeKey::MaxAge,
+            Self::MaxUses { .. } => AuditLogChangeKey::MaxUses,
+            Self::Mentionable { .. } => AuditLogChangeKey::Mentionable,
+            Self::MfaLevel { .. } => AuditLogChangeKey::MfaLevel,
+            Self::Mute { .. } => AuditLogChangeKey::Mute,
+            Self::Name { .. } => AuditLogChangeKey::Name,
+            Self::Nick { .. } => AuditLogChangeKey::Nick,
+            Self::NsfwLevel { .. } => AuditLogChangeKey::NsfwLevel,
+            Self::OwnerId { .. } => AuditLogChangeKey::OwnerId,
+            Self::PermissionOverwrites { .. } => AuditLogChangeKey::PermissionOverwrites,
+            Self::Permissions { .. } => AuditLogChangeKey::Permissions,
+            Self::Position { .. } => AuditLogChangeKey::Position,
+            Self::PreferredLocale { .. } => AuditLogChangeKey::PreferredLocale,
+            Self::PrivacyLevel { .. } => AuditLogChangeKey::PrivacyLevel,
+            Self::PruneDeleteDays { .. } => AuditLogChangeKey::PruneDeleteDays,
+            Self::PublicUpdatesChannelId { .. } => AuditLogChangeKey::PublicUpdatesChannelId,
+            Self::RateLimitPerUser { .. } => AuditLogChangeKey::RateLimitPerUser,
+            Self::Region { .. } => AuditLogChangeKey::Region,
+            Self::RoleAdded { .. } => AuditLogChangeKey::RoleAdded,
+            Self::RoleRemoved { .. } => AuditLogChangeKey::RoleRemoved,
+            Self::RulesChannelId { .. } => AuditLogChangeKey::RulesChannelId,
+            Self::SplashHash { .. } => AuditLogChangeKey::SplashHash,
+            Self::SystemChannelId { .. } => AuditLogChangeKey::SystemChannelId,
+            Self::Temporary { .. } => AuditLogChangeKey::Temporary,
+            Self::Topic { .. } => AuditLogChangeKey::Topic,
+            Self::Type { .. } => AuditLogChangeKey::Type,
+            Self::UserLimit { .. } => AuditLogChangeKey::UserLimit,
+            Self::Uses { .. } => AuditLogChangeKey::Uses,
+            Self::VanityUrlCode { .. } => AuditLogChangeKey::VanityUrlCode,
+            Self::VerificationLevel { .. } => AuditLogChangeKey::VerificationLevel,
+            Self::WidgetChannelId { .. } => AuditLogChangeKey::WidgetChannelId,
+            Self::WidgetEnabled { .. } => AuditLogChangeKey::WidgetEnabled,
+            Self::Other { .. } => return None,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum Field {
+    Key,
+    NewValue,
+    OldValue,
+}
+
+struct AuditLogChangeVisitor;
+
+impl<'de> Visitor<'de> for AuditLogChangeVisitor {
+    type Value = AuditLogChange;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("struct AuditLogChange")
+    }
+
+    fn visit_map<V: MapAccess<'de>>(self, mut map: V) -> Result<Self::Value, V::Error> {
+        let mut key = None::<String>;
+        let mut new = None::<Value>;
+        let mut old = None::<Value>;
+
+        let span = tracing::trace_span!("deserializing audit log change");
+        let _span_enter = span.enter();
+
+        loop {
+            let span_child = tracing::trace_span!("iterating over element");
+            let _span_child_enter = span_child.enter();
+
+            let field = match map.next_key() {
+                Ok(Some(field)) => field,
+                Ok(None) => break,
+                Err(why) => {
+                    // Encountered when we run into an unknown key.
+                    map.next_value::<IgnoredAny>()?;
+
+                    tracing::trace!("ran into an unknown key: {:?}", why);
+
+                    continue;
+                }
+            };
+
+            tracing::trace!("found key: {:?}", field);
+
+            match field {
+                Field::Key => {
+                    if key.is_some() {
+                        return Err(DeError::duplicate_field("key"));
+                    }
+
+                    key = Some(map.next_value()?);
+                }
+                Field::NewValue => {
+                    if new.is_some() {
+                        return Err(DeError::duplicate_field("new_value"));
+                    }
+
+                    new = Some(map.next_value()?);
+                }
+                Field::OldValue => {
+                    if old.is_some() {
+                        return Err(DeError::duplicate_field("old_value"));
+                    }
+
+                    old = Some(map.next_value()?);
+                }
+            }
+        }
+
+        let key = key.ok_or_else(|| DeError::missing_field("key"))?;
+
+        // Unknown keys and values of unexpected types are kept as they are,
+        // so that they don't fail the entire audit log.
+        let known = AuditLogChangeKey::deserialize(
+            IntoDeserializer::<'_, V::Error>::into_deserializer(key.as_str()),
+        );
+
+        if let Ok(known) = known {
+            tracing::trace!(key = ?known, "deserializing change values");
+
+            match typed::<V::Error>(&known, new.clone(), old.clone()) {
+                Ok(change) => return Ok(change),
+                Err(source) => {
+                    tracing::debug!(%key, %source, "change has values of an unexpected type");
+                }
+            }
+        }
+
+        Ok(AuditLogChange::Other {
+            key,
+            new: new.filter(|value| !is_null(value)),
+            old: old.filter(|value| !is_null(value)),
+        })
+    }
+}
+
+/// Deserialize the values of a change with a known key into their types.
+#[allow(clippy::too_many_lines)]
+fn typed<E: DeError>(
+    key: &AuditLogChangeKey,
+    new: Option<Value>,
+    old: Option<Value>,
+) -> Result<AuditLogChange, E> {
+    Ok(match key {
+        AuditLogChangeKey::AfkChannelId => AuditLogChange::AfkChannelId {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::AfkTimeout => AuditLogChange::AfkTimeout {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Allow => AuditLogChange::Allow {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::ApplicationId => AuditLogChange::ApplicationId {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::AvatarHash => AuditLogChange::AvatarHash {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::BannerHash => AuditLogChange::BannerHash {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Bitrate => AuditLogChange::Bitrate {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::ChannelId => AuditLogChange::ChannelId {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Code => AuditLogChange::Code {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Color => AuditLogChange::Color {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::CommunicationDisabledUntil => {
+            AuditLogChange::CommunicationDisabledUntil {
+                new: value(new)?,
+                old: value(old)?,
+            }
+        }
+        AuditLogChangeKey::Deaf => AuditLogChange::Deaf {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::DefaultMessageNotifications => {
+            AuditLogChange::DefaultMessageNotifications {
+                new: value(new)?,
+                old: value(old)?,
+            }
+        }
+        AuditLogChangeKey::Deny => AuditLogChange::Deny {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Description => AuditLogChange::Description {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::DiscoverySplashHash => AuditLogChange::DiscoverySplashHash {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::EnableEmoticons => AuditLogChange::EnableEmoticons {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::ExpireBehavior => AuditLogChange::ExpireBehavior {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::ExpireGracePeriod => AuditLogChange::ExpireGracePeriod {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::ExplicitContentFilter => AuditLogChange::ExplicitContentFilter {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Hoist => AuditLogChange::Hoist {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::IconHash => AuditLogChange::IconHash {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Id => AuditLogChange::Id {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::InviterId => AuditLogChange::InviterId {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::MaxAge => AuditLogChange::MaxAge {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::MaxUses => AuditLogChange::MaxUses {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Mentionable => AuditLogChange::Mentionable {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::MfaLevel => AuditLogChange::MfaLevel {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Mute => AuditLogChange::Mute {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Name => AuditLogChange::Name {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Nick => AuditLogChange::Nick {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::NsfwLevel => AuditLogChange::NsfwLevel {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::OwnerId => AuditLogChange::OwnerId {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::PermissionOverwrites => AuditLogChange::PermissionOverwrites {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Permissions => AuditLogChange::Permissions {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Position => AuditLogChange::Position {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::PreferredLocale => AuditLogChange::PreferredLocale {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::PrivacyLevel => AuditLogChange::PrivacyLevel {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::PruneDeleteDays => AuditLogChange::PruneDeleteDays {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::PublicUpdatesChannelId => AuditLogChange::PublicUpdatesChannelId {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::RateLimitPerUser => AuditLogChange::RateLimitPerUser {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Region => AuditLogChange::Region {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::RoleAdded => AuditLogChange::RoleAdded {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::RoleRemoved => AuditLogChange::RoleRemoved {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::RulesChannelId => AuditLogChange::RulesChannelId {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::SplashHash => AuditLogChange::SplashHash {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::SystemChannelId => AuditLogChange::SystemChannelId {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Temporary => AuditLogChange::Temporary {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Topic => AuditLogChange::Topic {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Type => AuditLogChange::Type {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::UserLimit => AuditLogChange::UserLimit {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::Uses => AuditLogChange::Uses {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::VanityUrlCode => AuditLogChange::VanityUrlCode {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::VerificationLevel => AuditLogChange::VerificationLevel {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::WidgetChannelId => AuditLogChange::WidgetChannelId {
+            new: value(new)?,
+            old: value(old)?,
+        },
+        AuditLogChangeKey::WidgetEnabled => AuditLogChange::WidgetEnabled {
+            new: value(new)?,
+            old: value(old)?,
+        },
+    })
+}
+
+const fn is_null(value: &Value) -> bool {
+    matches!(value, Value::Option(None) | Value::Unit)
+}
+
+fn value<T: DeserializeOwned, E: DeError>(value: Option<Value>) -> Result<Option<T>, E> {
+    // Values may be `null`, which is the same as them being absent.
+    value
+        .map(|value| Option::<T>::deserialize(ValueDeserializer::<E>::new(value)))
+        .transpose()
+        .map(Option::flatten)
+}
+
+impl<'de> Deserialize<'de> for AuditLogChange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const FIELDS: &[&str] = &["key", "new_value", "old_value"];
+
+        deserializer.deserialize_struct("AuditLogChange", FIELDS, AuditLogChangeVisitor)
+    }
+}
+
+impl Serialize for AuditLogChange {
+    #[allow(clippy::match_same_arms, clippy::too_many_lines)]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fn serialize_change<S: Serializer, K: Serialize + ?Sized, T: Serialize>(
+            serializer: S,
+            key: &K,
+            new: Option<&T>,
+            old: Option<&T>,
+        ) -> Result<S::Ok, S::Error> {
+            let len = 1 + usize::from(new.is_some()) + usize::from(old.is_some());
+            let mut state = serializer.serialize_struct("AuditLogChange", len)?;
+
+            state.serialize_field("key", key)?;
+
+            if let Some(new) = new {
+                state.serialize_field("new_value", new)?;
+            }
+
+            if let Some(old) = old {
+                state.serialize_field("old_value", old)?;
+            }
+
+            state.end()
+        }
+
+        match self {
+            Self::AfkChannelId { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::AfkChannelId,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::AfkTimeout { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::AfkTimeout,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Allow { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Allow,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::ApplicationId { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::ApplicationId,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::AvatarHash { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::AvatarHash,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::BannerHash { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::BannerHash,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Bitrate { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Bitrate,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::ChannelId { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::ChannelId,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Code { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Code,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Color { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Color,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::CommunicationDisabledUntil { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::CommunicationDisabledUntil,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Deaf { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Deaf,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::DefaultMessageNotifications { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::DefaultMessageNotifications,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Deny { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Deny,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Description { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Description,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::DiscoverySplashHash { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::DiscoverySplashHash,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::EnableEmoticons { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::EnableEmoticons,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::ExpireBehavior { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::ExpireBehavior,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::ExpireGracePeriod { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::ExpireGracePeriod,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::ExplicitContentFilter { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::ExplicitContentFilter,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Hoist { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Hoist,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::IconHash { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::IconHash,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Id { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Id,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::InviterId { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::InviterId,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::MaxAge { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::MaxAge,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::MaxUses { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::MaxUses,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Mentionable { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Mentionable,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::MfaLevel { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::MfaLevel,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Mute { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Mute,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Name { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Name,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Nick { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Nick,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::NsfwLevel { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::NsfwLevel,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Other { key, new, old } => {
+                serialize_change(serializer, key, new.as_ref(), old.as_ref())
+            }
+            Self::OwnerId { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::OwnerId,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::PermissionOverwrites { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::PermissionOverwrites,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Permissions { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Permissions,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Position { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Position,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::PreferredLocale { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::PreferredLocale,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::PrivacyLevel { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::PrivacyLevel,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::PruneDeleteDays { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::PruneDeleteDays,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::PublicUpdatesChannelId { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::PublicUpdatesChannelId,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::RateLimitPerUser { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::RateLimitPerUser,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Region { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Region,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::RoleAdded { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::RoleAdded,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::RoleRemoved { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::RoleRemoved,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::RulesChannelId { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::RulesChannelId,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::SplashHash { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::SplashHash,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::SystemChannelId { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::SystemChannelId,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Temporary { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Temporary,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Topic { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Topic,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Type { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Type,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::UserLimit { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::UserLimit,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::Uses { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::Uses,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::VanityUrlCode { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::VanityUrlCode,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::VerificationLevel { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::VerificationLevel,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::WidgetChannelId { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::WidgetChannelId,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+            Self::WidgetEnabled { new, old } => serialize_change(
+                serializer,
+                &AuditLogChangeKey::WidgetEnabled,
+                new.as_ref(),
+                old.as_ref(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AffectedRole, AuditLogChange, AuditLogChangeKey};
+    use crate::{guild::Permissions, id::RoleId};
+    use serde_test::Token;
+    use serde_value::Value;
+
+    #[test]
+    fn test_name() {
+        let value = AuditLogChange::Name {
+            new: Some("new".to_owned()),
+            old: Some("old".to_owned()),
+        };
+        assert_eq!(Some(AuditLogChangeKey::Name), value.key());
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "AuditLogChange",
+                    len: 3,
+                },
+                Token::Str("key"),
+                Token::UnitVariant {
+                    name: "AuditLogChangeKey",
+                    variant: "name",
+                },
+                Token::Str("new_value"),
+                Token::Str("new"),
+                Token::Str("old_value"),
+                Token::Str("old"),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_permissions_created() {
+        let value = AuditLogChange::Permissions {
+            new: Some(Permissions::SEND_MESSAGES),
+            old: None,
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "AuditLogChange",
+                    len: 2,
+                },
+                Token::Str("key"),
+                Token::UnitVariant {
+                    name: "AuditLogChangeKey",
+                    variant: "permissions",
+                },
+                Token::Str("new_value"),
+                Token::Str("2048"),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    /// Test that the values are deserialized correctly when they are received
+    /// before the key.
+    #[test]
+    fn test_role_added_key_last() {
+        let value = AuditLogChange::RoleAdded {
+            new: Some(vec![AffectedRole {
+                id: RoleId(1),
+                name: "role".to_owned(),
+            }]),
+            old: None,
+        };
+
+        serde_test::assert_de_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "AuditLogChange",
+                    len: 2,
+                },
+                Token::Str("new_value"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "AffectedRole",
+                    len: 2,
+                },
+                Token::Str("id"),
+                Token::Str("1"),
+                Token::Str("name"),
+                Token::Str("role"),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::Str("key"),
+                Token::UnitVariant {
+                    name: "AuditLogChangeKey",
+                    variant: "$add",
+                },
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_other_unknown_key() {
+        let value = AuditLogChange::Other {
+            key: "unknown_key".to_owned(),
+            new: Some(Value::U64(1)),
+            old: None,
+        };
+        assert!(value.key().is_none());
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "AuditLogChange",
+                    len: 2,
+                },
+                Token::Str("key"),
+                Token::Str("unknown_key"),
+                Token::Str("new_value"),
+                Token::U64(1),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    /// Test that values of a known key with an unexpected type are kept
+    /// instead of failing deserialization, and that `null` values are
+    /// absent.
+    #[test]
+    fn test_other_unexpected_value() {
+        let value = AuditLogChange::Other {
+            key: "name".to_owned(),
+            new: Some(Value::Bool(true)),
+            old: None,
+        };
+
+        serde_test::assert_de_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "AuditLogChange",
+                    len: 3,
+                },
+                Token::Str("key"),
+                Token::Str("name"),
+                Token::Str("new_value"),
+                Token::Bool(true),
+                Token::Str("old_value"),
+                Token::Unit,
+                Token::StructEnd,
+            ],
+        );
+    }
 }
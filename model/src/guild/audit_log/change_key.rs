@@ -14,6 +14,7 @@ pub enum AuditLogChangeKey {
     ChannelId,
     Code,
     Color,
+    CommunicationDisabledUntil,
     Deaf,
     DefaultMessageNotifications,
     Deny,
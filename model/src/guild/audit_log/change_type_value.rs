@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Value of an [`AuditLogChange::Type`].
+///
+/// Channels have an integer type while integrations have a string type.
+///
+/// [`AuditLogChange::Type`]: super::AuditLogChange::Type
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum AuditLogChangeTypeValue {
+    /// Type of a channel.
+    Unsigned(u64),
+    /// Type of an integration.
+    String(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditLogChangeTypeValue;
+    use serde_test::Token;
+
+    #[test]
+    fn test_variants() {
+        serde_test::assert_tokens(&AuditLogChangeTypeValue::Unsigned(2), &[Token::U64(2)]);
+        serde_test::assert_tokens(
+            &AuditLogChangeTypeValue::String("twitch".to_owned()),
+            &[Token::Str("twitch")],
+        );
+    }
+}
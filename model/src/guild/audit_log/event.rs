@@ -43,4 +43,8 @@ pub enum AuditLogEvent {
     StageInstanceCreate = 83,
     StageInstanceUpdate = 84,
     StageInstanceDelete = 85,
+    AutoModerationRuleCreate = 140,
+    AutoModerationRuleUpdate = 141,
+    AutoModerationRuleDelete = 142,
+    AutoModerationBlockMessage = 143,
 }
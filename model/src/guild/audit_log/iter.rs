@@ -0,0 +1,239 @@
+use super::{AuditLog, AuditLogEntry, PartialGuildIntegration};
+use crate::{channel::Webhook, user::User};
+use std::{iter::FusedIterator, slice::Iter};
+
+/// Audit log entry joined with the users, webhooks, and integrations of the
+/// [`AuditLog`] it references.
+///
+/// Returned by [`AuditLogIter`].
+#[derive(Clone, Copy, Debug)]
+pub struct JoinedAuditLogEntry<'a> {
+    /// Audit log entry.
+    pub entry: &'a AuditLogEntry,
+    /// Integration targeted by the entry.
+    pub target_integration: Option<&'a PartialGuildIntegration>,
+    /// User targeted by the entry.
+    pub target_user: Option<&'a User>,
+    /// Webhook targeted by the entry.
+    pub target_webhook: Option<&'a Webhook>,
+    /// User who made the changes of the entry.
+    pub user: Option<&'a User>,
+}
+
+/// Iterator over the entries of an [`AuditLog`], joining each entry with the
+/// users, webhooks, and integrations it references.
+///
+/// Created via [`AuditLog::iter`].
+///
+/// # Examples
+///
+/// Print who banned whom:
+///
+/// ```
+/// use twilight_model::guild::audit_log::{AuditLog, AuditLogEvent};
+///
+/// # fn print(audit_log: &AuditLog) {
+/// for joined in audit_log {
+///     if joined.entry.action_type != AuditLogEvent::MemberBanAdd {
+///         continue;
+///     }
+///
+///     if let (Some(user), Some(target)) = (joined.user, joined.target_user) {
+///         println!("{} banned {}", user.name, target.name);
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AuditLogIter<'a> {
+    audit_log: &'a AuditLog,
+    entries: Iter<'a, AuditLogEntry>,
+}
+
+impl<'a> AuditLogIter<'a> {
+    pub(super) fn new(audit_log: &'a AuditLog) -> Self {
+        Self {
+            audit_log,
+            entries: audit_log.audit_log_entries.iter(),
+        }
+    }
+
+    fn join(&self, entry: &'a AuditLogEntry) -> JoinedAuditLogEntry<'a> {
+        let user = entry
+            .user_id
+            .and_then(|id| self.audit_log.users.iter().find(|user| user.id == id));
+
+        let target_id = entry
+            .target_id
+            .as_deref()
+            .and_then(|id| id.parse::<u64>().ok());
+
+        // IDs are unique snowflakes, so a target ID can only ever match one
+        // kind of entity.
+        let (target_integration, target_user, target_webhook) = match target_id {
+            Some(id) => (
+                self.audit_log
+                    .integrations
+                    .iter()
+                    .find(|integration| integration.id.map(|id| id.0) == Some(id)),
+                self.audit_log.users.iter().find(|user| user.id.0 == id),
+                self.audit_log
+                    .webhooks
+                    .iter()
+                    .find(|webhook| webhook.id.0 == id),
+            ),
+            None => (None, None, None),
+        };
+
+        JoinedAuditLogEntry {
+            entry,
+            target_integration,
+            target_user,
+            target_webhook,
+            user,
+        }
+    }
+}
+
+impl<'a> Iterator for AuditLogIter<'a> {
+    type Item = JoinedAuditLogEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.next()?;
+
+        Some(self.join(entry))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for AuditLogIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.next_back()?;
+
+        Some(self.join(entry))
+    }
+}
+
+impl ExactSizeIterator for AuditLogIter<'_> {}
+
+impl FusedIterator for AuditLogIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        AuditLog, AuditLogEntry, AuditLogEvent, AuditLogIter, PartialGuildIntegration,
+    };
+    use crate::{
+        channel::{Webhook, WebhookType},
+        id::{AuditLogEntryId, ChannelId, IntegrationId, UserId, WebhookId},
+        user::User,
+    };
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+
+    assert_impl_all!(
+        AuditLogIter<'_>: Clone,
+        Debug,
+        DoubleEndedIterator,
+        ExactSizeIterator,
+        Send,
+        Sync
+    );
+
+    fn entry(action_type: AuditLogEvent, target_id: u64) -> AuditLogEntry {
+        AuditLogEntry {
+            action_type,
+            changes: None,
+            id: AuditLogEntryId(target_id),
+            options: None,
+            reason: None,
+            target_id: Some(target_id.to_string()),
+            user_id: Some(UserId(1)),
+        }
+    }
+
+    fn user(id: u64) -> User {
+        User {
+            avatar: None,
+            bot: false,
+            discriminator: "0001".to_owned(),
+            email: None,
+            flags: None,
+            id: UserId(id),
+            locale: None,
+            mfa_enabled: None,
+            name: "user".to_owned(),
+            premium_type: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        }
+    }
+
+    #[test]
+    fn test_join() {
+        let audit_log = AuditLog {
+            audit_log_entries: vec![
+                entry(AuditLogEvent::MemberKick, 2),
+                entry(AuditLogEvent::WebhookCreate, 3),
+                entry(AuditLogEvent::IntegrationCreate, 4),
+            ],
+            integrations: vec![PartialGuildIntegration {
+                account: None,
+                enabled: None,
+                expire_behavior: None,
+                expire_grace_period: None,
+                id: Some(IntegrationId(4)),
+                kind: None,
+                name: None,
+                role_id: None,
+                synced_at: None,
+                syncing: None,
+                user: None,
+            }],
+            users: vec![user(1), user(2)],
+            webhooks: vec![Webhook {
+                application_id: None,
+                avatar: None,
+                channel_id: ChannelId(5),
+                guild_id: None,
+                id: WebhookId(3),
+                kind: WebhookType::Incoming,
+                name: None,
+                source_channel: None,
+                source_guild: None,
+                token: None,
+                url: None,
+                user: None,
+            }],
+        };
+
+        let mut iter = audit_log.iter();
+        assert_eq!(3, iter.len());
+
+        let kick = iter.next().unwrap();
+        assert_eq!(Some(UserId(1)), kick.user.map(|user| user.id));
+        assert_eq!(Some(UserId(2)), kick.target_user.map(|user| user.id));
+        assert!(kick.target_webhook.is_none());
+
+        let webhook = iter.next().unwrap();
+        assert_eq!(
+            Some(WebhookId(3)),
+            webhook.target_webhook.map(|webhook| webhook.id)
+        );
+        assert!(webhook.target_user.is_none());
+
+        let integration = iter.next_back().unwrap();
+        assert_eq!(
+            Some(IntegrationId(4)),
+            integration
+                .target_integration
+                .and_then(|integration| integration.id)
+        );
+
+        assert!(iter.next().is_none());
+    }
+}
@@ -1,13 +1,22 @@
+mod affected_role;
 mod change;
 mod change_key;
+mod change_type_value;
 mod entry;
 mod event;
+mod iter;
 mod optional_entry_info;
 mod partial_integration;
 
 pub use self::{
-    change::AuditLogChange, change_key::AuditLogChangeKey, entry::AuditLogEntry,
-    event::AuditLogEvent, optional_entry_info::AuditLogOptionalEntryInfo,
+    affected_role::AffectedRole,
+    change::AuditLogChange,
+    change_key::AuditLogChangeKey,
+    change_type_value::AuditLogChangeTypeValue,
+    entry::AuditLogEntry,
+    event::AuditLogEvent,
+    iter::{AuditLogIter, JoinedAuditLogEntry},
+    optional_entry_info::AuditLogOptionalEntryInfo,
     partial_integration::PartialGuildIntegration,
 };
 
@@ -21,3 +30,20 @@ pub struct AuditLog {
     pub users: Vec<User>,
     pub webhooks: Vec<Webhook>,
 }
+
+impl AuditLog {
+    /// Iterate over the entries of the audit log, joined with the users,
+    /// webhooks, and integrations they reference.
+    pub fn iter(&self) -> AuditLogIter<'_> {
+        AuditLogIter::new(self)
+    }
+}
+
+impl<'a> IntoIterator for &'a AuditLog {
+    type IntoIter = AuditLogIter<'a>;
+    type Item = JoinedAuditLogEntry<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,103 @@
+use crate::id::ChannelId;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Action which will execute whenever a rule is triggered.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct AutoModerationAction {
+    /// Type of action.
+    #[serde(rename = "type")]
+    pub kind: AutoModerationActionType,
+    /// Additional metadata needed during execution for this specific action
+    /// type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<AutoModerationActionMetadata>,
+}
+
+/// Additional metadata needed during execution for a specific
+/// [`AutoModerationActionType`].
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct AutoModerationActionMetadata {
+    /// Channel to which user content should be logged.
+    ///
+    /// Only used by [`AutoModerationActionType::SendAlertMessage`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    /// Timeout duration in seconds.
+    ///
+    /// Maximum value is 2419200 seconds, or 4 weeks. Only used by
+    /// [`AutoModerationActionType::Timeout`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u32>,
+}
+
+/// Type of [`AutoModerationAction`].
+#[derive(
+    Clone, Copy, Debug, Deserialize_repr, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize_repr,
+)]
+#[repr(u8)]
+pub enum AutoModerationActionType {
+    /// Blocks the content of a message according to the rule.
+    BlockMessage = 1,
+    /// Logs user content to a specified channel.
+    SendAlertMessage = 2,
+    /// Timeout user for a specified duration.
+    ///
+    /// Can only be set up for [`AutoModerationTriggerType::Keyword`] and
+    /// [`AutoModerationTriggerType::MentionSpam`] rules. The [Moderate
+    /// Members] permission is required to use this action type.
+    ///
+    /// [`AutoModerationTriggerType::Keyword`]: super::AutoModerationTriggerType::Keyword
+    /// [`AutoModerationTriggerType::MentionSpam`]: super::AutoModerationTriggerType::MentionSpam
+    /// [Moderate Members]: crate::guild::Permissions::MODERATE_MEMBERS
+    Timeout = 3,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AutoModerationAction, AutoModerationActionMetadata, AutoModerationActionType};
+    use crate::id::ChannelId;
+    use serde_test::Token;
+
+    #[test]
+    fn test_action() {
+        let value = AutoModerationAction {
+            kind: AutoModerationActionType::SendAlertMessage,
+            metadata: Some(AutoModerationActionMetadata {
+                channel_id: Some(ChannelId(1)),
+                duration_seconds: None,
+            }),
+        };
+
+        serde_test::assert_tokens(
+            &value,
+            &[
+                Token::Struct {
+                    name: "AutoModerationAction",
+                    len: 2,
+                },
+                Token::Str("type"),
+                Token::U8(2),
+                Token::Str("metadata"),
+                Token::Some,
+                Token::Struct {
+                    name: "AutoModerationActionMetadata",
+                    len: 1,
+                },
+                Token::Str("channel_id"),
+                Token::Some,
+                Token::NewtypeStruct { name: "ChannelId" },
+                Token::Str("1"),
+                Token::StructEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_action_type_variants() {
+        serde_test::assert_tokens(&AutoModerationActionType::BlockMessage, &[Token::U8(1)]);
+        serde_test::assert_tokens(&AutoModerationActionType::SendAlertMessage, &[Token::U8(2)]);
+        serde_test::assert_tokens(&AutoModerationActionType::Timeout, &[Token::U8(3)]);
+    }
+}
@@ -0,0 +1,41 @@
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Type of content which can trigger an [`AutoModerationRule`].
+///
+/// [`AutoModerationRule`]: super::AutoModerationRule
+#[derive(
+    Clone, Copy, Debug, Deserialize_repr, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize_repr,
+)]
+#[repr(u8)]
+pub enum AutoModerationTriggerType {
+    /// Check if content contains words from a user defined list of keywords.
+    ///
+    /// Maximum of 3 per guild.
+    Keyword = 1,
+    /// Check if content represents generic spam.
+    ///
+    /// Maximum of 1 per guild.
+    Spam = 3,
+    /// Check if content contains words from internal pre-defined wordsets.
+    ///
+    /// Maximum of 1 per guild.
+    KeywordPreset = 4,
+    /// Check if content contains more unique mentions than allowed.
+    ///
+    /// Maximum of 1 per guild.
+    MentionSpam = 5,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AutoModerationTriggerType;
+    use serde_test::Token;
+
+    #[test]
+    fn test_variants() {
+        serde_test::assert_tokens(&AutoModerationTriggerType::Keyword, &[Token::U8(1)]);
+        serde_test::assert_tokens(&AutoModerationTriggerType::Spam, &[Token::U8(3)]);
+        serde_test::assert_tokens(&AutoModerationTriggerType::KeywordPreset, &[Token::U8(4)]);
+        serde_test::assert_tokens(&AutoModerationTriggerType::MentionSpam, &[Token::U8(5)]);
+    }
+}
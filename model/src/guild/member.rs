@@ -14,6 +14,12 @@ use std::fmt::{Formatter, Result as FmtResult};
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct Member {
+    /// ISO 8601 timestamp until which the member is timed out.
+    ///
+    /// While timed out, the member can only view channels and read their
+    /// message history.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub communication_disabled_until: Option<String>,
     pub deaf: bool,
     pub guild_id: GuildId,
     pub hoisted_role: Option<RoleId>,
@@ -32,6 +38,8 @@ pub struct Member {
 // Used in the guild deserializer.
 #[derive(Deserialize, Serialize)]
 pub(crate) struct MemberIntermediary {
+    #[serde(default)]
+    pub communication_disabled_until: Option<String>,
     pub deaf: bool,
     pub hoisted_role: Option<RoleId>,
     pub joined_at: Option<String>,
@@ -82,6 +90,7 @@ impl<'de> Visitor<'de> for MemberVisitor {
         let member = MemberIntermediary::deserialize(deser)?;
 
         Ok(Member {
+            communication_disabled_until: member.communication_disabled_until,
             deaf: member.deaf,
             guild_id: self.0,
             hoisted_role: member.hoisted_role,
@@ -184,6 +193,7 @@ mod tests {
     #[test]
     fn test_member_deserializer() {
         let value = Member {
+            communication_disabled_until: None,
             deaf: false,
             guild_id: GuildId(1),
             hoisted_role: Some(RoleId(2)),
@@ -3,6 +3,12 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct PartialMember {
+    /// ISO 8601 timestamp until which the member is timed out.
+    ///
+    /// While timed out, the member can only view channels and read their
+    /// message history.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub communication_disabled_until: Option<String>,
     pub deaf: bool,
     pub joined_at: Option<String>,
     pub mute: bool,
@@ -27,6 +33,7 @@ mod tests {
     #[test]
     fn test_partial_member() {
         let value = PartialMember {
+            communication_disabled_until: None,
             deaf: false,
             joined_at: Some("timestamp".to_owned()),
             mute: true,
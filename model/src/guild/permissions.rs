@@ -40,6 +40,10 @@ bitflags! {
         const MANAGE_EMOJIS = 0x4000_0000;
         const USE_SLASH_COMMANDS = 0x8000_0000;
         const REQUEST_TO_SPEAK = 0x10000_0000;
+        /// Allows for timing out users to prevent them from sending or
+        /// reacting to messages in chat and threads, and from speaking in
+        /// voice and stage channels.
+        const MODERATE_MEMBERS = 0x100_0000_0000;
     }
 }
 
@@ -102,6 +102,24 @@ impl From<u64> for AuditLogEntryId {
     }
 }
 
+/// Unique ID of an auto moderation rule.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+pub struct AutoModerationRuleId(#[serde(with = "string")] pub u64);
+
+impl Display for AutoModerationRuleId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl From<u64> for AutoModerationRuleId {
+    fn from(id: u64) -> Self {
+        AutoModerationRuleId(id)
+    }
+}
+
 #[derive(
     Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
 )]
@@ -311,8 +329,9 @@ impl From<u64> for WebhookId {
 #[cfg(test)]
 mod tests {
     use super::{
-        ApplicationId, AttachmentId, AuditLogEntryId, ChannelId, CommandId, EmojiId, GenericId,
-        GuildId, IntegrationId, InteractionId, MessageId, RoleId, StageId, UserId, WebhookId,
+        ApplicationId, AttachmentId, AuditLogEntryId, AutoModerationRuleId, ChannelId, CommandId,
+        EmojiId, GenericId, GuildId, IntegrationId, InteractionId, MessageId, RoleId, StageId,
+        UserId, WebhookId,
     };
     use serde_test::Token;
 
@@ -373,6 +392,24 @@ mod tests {
                 Token::U64(114_941_315_417_899_012),
             ],
         );
+        serde_test::assert_tokens(
+            &AutoModerationRuleId(114_941_315_417_899_012),
+            &[
+                Token::NewtypeStruct {
+                    name: "AutoModerationRuleId",
+                },
+                Token::Str("114941315417899012"),
+            ],
+        );
+        serde_test::assert_de_tokens(
+            &AutoModerationRuleId(114_941_315_417_899_012),
+            &[
+                Token::NewtypeStruct {
+                    name: "AutoModerationRuleId",
+                },
+                Token::U64(114_941_315_417_899_012),
+            ],
+        );
         serde_test::assert_tokens(
             &ChannelId(114_941_315_417_899_012),
             &[
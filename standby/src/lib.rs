@@ -798,6 +798,10 @@ impl Standby {
 
 const fn event_guild_id(event: &Event) -> Option<GuildId> {
     match event {
+        Event::AutoModerationActionExecution(e) => Some(e.guild_id),
+        Event::AutoModerationRuleCreate(e) => Some(e.0.guild_id),
+        Event::AutoModerationRuleDelete(e) => Some(e.0.guild_id),
+        Event::AutoModerationRuleUpdate(e) => Some(e.0.guild_id),
         Event::BanAdd(e) => Some(e.guild_id),
         Event::BanRemove(e) => Some(e.guild_id),
         Event::ChannelCreate(e) => channel_guild_id(&e.0),
@@ -847,6 +851,8 @@ const fn event_guild_id(event: &Event) -> Option<GuildId> {
         Event::ShardPayload(_) => None,
         Event::ShardReconnecting(_) => None,
         Event::ShardResuming(_) => None,
+        Event::ShardSessionStartLimitExhausted(_) => None,
+        Event::ShardZombied(_) => None,
         Event::StageInstanceCreate(e) => Some(e.0.guild_id),
         Event::StageInstanceDelete(e) => Some(e.0.guild_id),
         Event::StageInstanceUpdate(e) => Some(e.0.guild_id),
@@ -1016,6 +1022,7 @@ mod tests {
                 id: ApplicationId(0),
             },
             guilds: Vec::new(),
+            resume_gateway_url: None,
             session_id: String::new(),
             shard: Some([5, 7]),
             user: CurrentUser {
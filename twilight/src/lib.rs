@@ -115,6 +115,15 @@
 //! - A calculator to calculate the permissions of a member in a guild or
 //! channel.
 //!
+//! ### [`twilight-voice`]
+//!
+//! Implementation of Discord's voice connections, sending Opus audio to voice
+//! channels without an external audio server.
+//!
+//! It combines the voice events received over the gateway into the information
+//! needed to connect, performs the voice gateway handshake, and sends encrypted
+//! audio over UDP.
+//!
 //! ### [`twilight-gateway-queue`]
 //!
 //! A trait and some implementations that are used by the gateway to ratelimit
@@ -233,3 +242,4 @@
 //! [`twilight-model`]: https://twilight.rs/chapter_1_crates/section_1_model.html
 //! [`twilight-standby`]: https://twilight.rs/chapter_1_crates/section_6_standby.html
 //! [`twilight-util`]: https://twilight.rs/chapter_1_crates/section_7_first_party/section_4_util.html
+//! [`twilight-voice`]: https://docs.rs/twilight-voice
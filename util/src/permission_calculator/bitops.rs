@@ -9,6 +9,11 @@ pub const fn insert(permissions: Permissions, other: Permissions) -> Permissions
     Permissions::from_bits_truncate(permissions.bits() | other.bits())
 }
 
+/// Retain only the permissions present in both.
+pub const fn intersect(permissions: Permissions, other: Permissions) -> Permissions {
+    Permissions::from_bits_truncate(permissions.bits() & other.bits())
+}
+
 /// Remove permissions from another.
 pub const fn remove(permissions: Permissions, other: Permissions) -> Permissions {
     Permissions::from_bits_truncate(permissions.bits() & !other.bits())
@@ -37,6 +42,16 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_intersect() {
+        let actual = super::intersect(
+            Permissions::BAN_MEMBERS | Permissions::KICK_MEMBERS,
+            Permissions::BAN_MEMBERS | Permissions::CONNECT,
+        );
+
+        assert_eq!(actual, Permissions::BAN_MEMBERS);
+    }
+
     #[test]
     fn test_remove() {
         let actual = super::remove(
@@ -104,8 +104,8 @@ mod bitops;
 mod preset;
 
 use self::preset::{
-    PERMISSIONS_MESSAGING, PERMISSIONS_ROOT_ONLY, PERMISSIONS_STAGE_OMIT, PERMISSIONS_TEXT_OMIT,
-    PERMISSIONS_VOICE_OMIT,
+    PERMISSIONS_COMMUNICATION_DISABLED, PERMISSIONS_MESSAGING, PERMISSIONS_ROOT_ONLY,
+    PERMISSIONS_STAGE_OMIT, PERMISSIONS_TEXT_OMIT, PERMISSIONS_VOICE_OMIT,
 };
 use twilight_model::{
     channel::{
@@ -126,6 +126,8 @@ use twilight_model::{
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[must_use = "calculators aren't useful if you don't calculate permissions"]
 pub struct PermissionCalculator<'a> {
+    /// Whether the member's communication is disabled, i.e. they're timed out.
+    communication_disabled: bool,
     /// Permissions of the `@everyone` role for the guild.
     everyone_role: Permissions,
     /// ID of the guild.
@@ -157,6 +159,7 @@ impl<'a> PermissionCalculator<'a> {
         member_roles: &'a [(RoleId, Permissions)],
     ) -> Self {
         Self {
+            communication_disabled: false,
             everyone_role,
             guild_id,
             owner_id: UserId(0),
@@ -165,6 +168,23 @@ impl<'a> PermissionCalculator<'a> {
         }
     }
 
+    /// Configure whether the member is currently timed out.
+    ///
+    /// This should be `true` if the member's `communication_disabled_until`
+    /// timestamp is in the future. Timed out members only retain the
+    /// [View Channel] and [Read Message History] permissions, unless they are
+    /// the owner of the guild or have the [Administrator] permission.
+    ///
+    /// [Administrator]: twilight_model::guild::Permissions::ADMINISTRATOR
+    /// [Read Message History]: twilight_model::guild::Permissions::READ_MESSAGE_HISTORY
+    /// [View Channel]: twilight_model::guild::Permissions::VIEW_CHANNEL
+    #[must_use = "calculators should be used to calculate permissions"]
+    pub const fn communication_disabled(mut self, communication_disabled: bool) -> Self {
+        self.communication_disabled = communication_disabled;
+
+        self
+    }
+
     /// Configure the ID of the owner of the guild.
     ///
     /// This should be used if you don't want to manually take the user ID and
@@ -221,6 +241,12 @@ impl<'a> PermissionCalculator<'a> {
             idx += 1;
         }
 
+        // Timed out members lose all permissions other than being able to view
+        // channels and read their history.
+        if self.communication_disabled {
+            permissions = bitops::intersect(permissions, PERMISSIONS_COMMUNICATION_DISABLED);
+        }
+
         permissions
     }
 
@@ -322,6 +348,7 @@ impl<'a> PermissionCalculator<'a> {
     /// - [Manage Emojis]
     /// - [Manage Guild]
     /// - [Manage Nicknames]
+    /// - [Moderate Members]
     /// - [View Audit Log]
     /// - [View Guild Insights]
     ///
@@ -348,6 +375,7 @@ impl<'a> PermissionCalculator<'a> {
     /// [Manage Nicknames]: twilight_model::guild::Permissions::MANAGE_NICKNAMES
     /// [Manage Webhooks]: twilight_model::guild::Permissions::MANAGE_WEBHOOKS
     /// [Mention Everyone]: twilight_model::guild::Permissions::MENTION_EVERYONE
+    /// [Moderate Members]: twilight_model::guild::Permissions::MODERATE_MEMBERS
     /// [Move Members]: twilight_model::guild::Permissions::MOVE_MEMBERS
     /// [Mute Members]: twilight_model::guild::Permissions::MUTE_MEMBERS
     /// [Priority Speaker]: twilight_model::guild::Permissions::PRIORITY_SPEAKER
@@ -386,6 +414,12 @@ impl<'a> PermissionCalculator<'a> {
             self.user_id,
         );
 
+        // Permission overwrites can't grant timed out members any permissions
+        // beyond the ones they retain on the root level.
+        if self.communication_disabled {
+            permissions = bitops::intersect(permissions, PERMISSIONS_COMMUNICATION_DISABLED);
+        }
+
         // If the permission set is empty then we don't need to do any removals.
         if permissions.is_empty() {
             return permissions;
@@ -649,6 +683,43 @@ mod tests {
         assert!(calc.in_channel(ChannelType::GuildText, &[]).is_all());
     }
 
+    /// Test that timed out members only retain the permissions to view channels
+    /// and read their history, both on the root level and in channels.
+    #[test]
+    fn test_communication_disabled() {
+        let guild_id = GuildId(1);
+        let user_id = UserId(2);
+        let everyone_role = Permissions::READ_MESSAGE_HISTORY
+            | Permissions::SEND_MESSAGES
+            | Permissions::VIEW_CHANNEL;
+        let roles = &[(RoleId(3), Permissions::KICK_MEMBERS)];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::ADD_REACTIONS,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(user_id),
+        }];
+
+        let calculator = PermissionCalculator::new(guild_id, user_id, everyone_role, roles)
+            .communication_disabled(true);
+
+        let expected = Permissions::READ_MESSAGE_HISTORY | Permissions::VIEW_CHANNEL;
+        assert_eq!(expected, calculator.root());
+        assert_eq!(
+            expected,
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildText, overwrites)
+        );
+
+        // Owners and administrators aren't affected by timeouts.
+        assert!(calculator.owner_id(user_id).root().is_all());
+
+        let admin_roles = &[(RoleId(3), Permissions::ADMINISTRATOR)];
+        let calculator = PermissionCalculator::new(guild_id, user_id, everyone_role, admin_roles)
+            .communication_disabled(true);
+        assert!(calculator.root().is_all());
+    }
+
     /// Test that guild-level permissions are removed in the permissions for a
     /// channel of any type.
     #[test]
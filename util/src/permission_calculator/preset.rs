@@ -12,6 +12,12 @@ pub const PERMISSIONS_MESSAGING: Permissions = Permissions::from_bits_truncate(
         | Permissions::SEND_TTS_MESSAGES.bits(),
 );
 
+/// Permissions a member retains while their communication is disabled, i.e.
+/// while they're timed out.
+pub const PERMISSIONS_COMMUNICATION_DISABLED: Permissions = Permissions::from_bits_truncate(
+    Permissions::READ_MESSAGE_HISTORY.bits() | Permissions::VIEW_CHANNEL.bits(),
+);
+
 /// Permissions associated with a guild only at the root level (i.e. not channel
 /// related).
 pub const PERMISSIONS_ROOT_ONLY: Permissions = Permissions::from_bits_truncate(
@@ -22,6 +28,7 @@ pub const PERMISSIONS_ROOT_ONLY: Permissions = Permissions::from_bits_truncate(
         | Permissions::MANAGE_EMOJIS.bits()
         | Permissions::MANAGE_GUILD.bits()
         | Permissions::MANAGE_NICKNAMES.bits()
+        | Permissions::MODERATE_MEMBERS.bits()
         | Permissions::VIEW_AUDIT_LOG.bits()
         | Permissions::VIEW_GUILD_INSIGHTS.bits(),
 );
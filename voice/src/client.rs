@@ -0,0 +1,228 @@
+//! Combine gateway events into the information to connect to voice channels.
+
+use crate::connection::ConnectionInfo;
+use dashmap::DashMap;
+use twilight_model::{
+    gateway::event::Event,
+    id::{GuildId, UserId},
+};
+
+/// Voice server of a guild, from a `VoiceServerUpdate`.
+#[derive(Clone, Debug)]
+struct VoiceServer {
+    endpoint: String,
+    token: String,
+}
+
+/// Tracker of the voice sessions of the current user.
+///
+/// Joining a voice channel is done by sending an `UpdateVoiceState` command
+/// over the main gateway, after which Discord sends a `VoiceStateUpdate`
+/// event with the user's session and a `VoiceServerUpdate` event with the
+/// voice server to connect to. Processing these events returns the
+/// [`ConnectionInfo`] to connect with once both have been received.
+///
+/// # Examples
+///
+/// Connect to voice channels once their information is complete:
+///
+/// ```no_run
+/// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures_util::stream::StreamExt;
+/// use std::env;
+/// use twilight_gateway::{Intents, Shard};
+/// use twilight_model::id::UserId;
+/// use twilight_voice::{Connection, Voice};
+///
+/// let token = env::var("DISCORD_TOKEN")?;
+/// let (shard, mut events) = Shard::new(token, Intents::GUILD_VOICE_STATES);
+/// shard.start().await?;
+///
+/// let voice = Voice::new(UserId(123));
+///
+/// while let Some(event) = events.next().await {
+///     if let Some(info) = voice.process(&event) {
+///         let connection = Connection::connect(info).await?;
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct Voice {
+    servers: DashMap<GuildId, VoiceServer>,
+    sessions: DashMap<GuildId, String>,
+    user_id: UserId,
+}
+
+impl Voice {
+    /// Create a new tracker for the voice sessions of a user.
+    pub fn new(user_id: UserId) -> Self {
+        Self {
+            servers: DashMap::new(),
+            sessions: DashMap::new(),
+            user_id,
+        }
+    }
+
+    /// Return the ID of the user whose voice sessions are tracked.
+    pub const fn user_id(&self) -> UserId {
+        self.user_id
+    }
+
+    /// Process an event, returning the information to connect to a voice
+    /// channel if it changed.
+    ///
+    /// The information is returned when a voice server is assigned to a
+    /// guild the user has a session in, or the user's session changes in a
+    /// guild with a voice server. Any event can be provided, but only the
+    /// `VoiceServerUpdate` and `VoiceStateUpdate` events are used.
+    pub fn process(&self, event: &Event) -> Option<ConnectionInfo> {
+        let guild_id = match event {
+            Event::VoiceServerUpdate(update) => {
+                let guild_id = update.guild_id?;
+
+                // The endpoint is null while the voice server is unavailable,
+                // with another update being sent once a new one is
+                // allocated.
+                if let Some(endpoint) = update.endpoint.clone() {
+                    self.servers.insert(
+                        guild_id,
+                        VoiceServer {
+                            endpoint,
+                            token: update.token.clone(),
+                        },
+                    );
+                } else {
+                    self.servers.remove(&guild_id);
+
+                    return None;
+                }
+
+                guild_id
+            }
+            Event::VoiceStateUpdate(update) => {
+                let state = &update.0;
+
+                if state.user_id != self.user_id {
+                    return None;
+                }
+
+                let guild_id = state.guild_id?;
+
+                if state.channel_id.is_none() {
+                    self.servers.remove(&guild_id);
+                    self.sessions.remove(&guild_id);
+
+                    return None;
+                }
+
+                let previous = self.sessions.insert(guild_id, state.session_id.clone());
+
+                // Changes of mute and deaf states keep the session.
+                if previous.as_deref() == Some(state.session_id.as_str()) {
+                    return None;
+                }
+
+                guild_id
+            }
+            _ => return None,
+        };
+
+        self.info(guild_id)
+    }
+
+    /// Return the information to connect to the voice channel of a guild, if
+    /// both the user's session and the voice server are known.
+    pub fn info(&self, guild_id: GuildId) -> Option<ConnectionInfo> {
+        let server = self.servers.get(&guild_id)?;
+        let session_id = self.sessions.get(&guild_id)?;
+
+        Some(ConnectionInfo {
+            endpoint: server.endpoint.clone(),
+            guild_id,
+            session_id: session_id.clone(),
+            token: server.token.clone(),
+            user_id: self.user_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Voice;
+    use crate::ConnectionInfo;
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+    use twilight_model::{
+        gateway::{
+            event::Event,
+            payload::{VoiceServerUpdate, VoiceStateUpdate},
+        },
+        id::{ChannelId, GuildId, UserId},
+        voice::VoiceState,
+    };
+
+    assert_impl_all!(Voice: Debug, Send, Sync);
+
+    fn state(user_id: u64, channel_id: Option<u64>, session_id: &str, mute: bool) -> Event {
+        Event::VoiceStateUpdate(Box::new(VoiceStateUpdate(VoiceState {
+            channel_id: channel_id.map(ChannelId),
+            deaf: false,
+            guild_id: Some(GuildId(1)),
+            member: None,
+            mute,
+            self_deaf: false,
+            self_mute: false,
+            self_stream: false,
+            session_id: session_id.to_owned(),
+            suppress: false,
+            token: None,
+            user_id: UserId(user_id),
+            request_to_speak_timestamp: None,
+        })))
+    }
+
+    fn server(endpoint: Option<&str>) -> Event {
+        Event::VoiceServerUpdate(VoiceServerUpdate {
+            channel_id: None,
+            endpoint: endpoint.map(ToOwned::to_owned),
+            guild_id: Some(GuildId(1)),
+            token: "token".to_owned(),
+        })
+    }
+
+    #[test]
+    fn test_process() {
+        let voice = Voice::new(UserId(2));
+
+        assert!(voice
+            .process(&state(2, Some(3), "session", false))
+            .is_none());
+        assert!(voice.process(&server(None)).is_none());
+        assert_eq!(
+            Some(ConnectionInfo {
+                endpoint: "voice.discord.media".to_owned(),
+                guild_id: GuildId(1),
+                session_id: "session".to_owned(),
+                token: "token".to_owned(),
+                user_id: UserId(2),
+            }),
+            voice.process(&server(Some("voice.discord.media")))
+        );
+
+        // Other users and unchanged sessions are ignored.
+        assert!(voice.process(&state(4, Some(3), "other", false)).is_none());
+        assert!(voice.process(&state(2, Some(3), "session", true)).is_none());
+
+        assert_eq!(
+            "new",
+            voice
+                .process(&state(2, Some(3), "new", false))
+                .unwrap()
+                .session_id
+        );
+
+        assert!(voice.process(&state(2, None, "new", false)).is_none());
+        assert!(voice.info(GuildId(1)).is_none());
+    }
+}
@@ -0,0 +1,1023 @@
+//! Connections to voice channels, sending Opus audio.
+//!
+//! A [`Connection`] performs the handshake with the voice gateway, discovers
+//! the external address of its UDP socket, and sends encrypted audio over it.
+//! The connection to the voice gateway is maintained in the background,
+//! sending heartbeats and resuming the session when the connection is lost or
+//! a heartbeat is not acknowledged before the next one is due.
+
+use crate::{
+    model::{
+        Identify, IncomingPayload, OpCode, OutgoingPayload, Resume, SelectProtocol,
+        SelectProtocolData, Speaking, ENCRYPTION_MODE, VOICE_GATEWAY_VERSION,
+    },
+    udp::{ip_discovery_request, ip_discovery_response, RtpSender, IP_DISCOVERY_LEN},
+};
+use futures_util::{sink::SinkExt, stream::StreamExt};
+use serde::Serialize;
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    net::{TcpStream, UdpSocket},
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    time::{self as tokio_time, Instant, Interval},
+};
+use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use twilight_model::id::{GuildId, UserId};
+
+/// Opus frame of silence.
+///
+/// Five of these are sent when the user stops speaking to avoid
+/// interpolation of the audio sent last.
+pub const SILENCE_FRAME: [u8; 3] = [0xF8, 0xFF, 0xFE];
+
+/// Maximum duration to wait for each step of the handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of IP discovery requests to send before giving up.
+const IP_DISCOVERY_ATTEMPTS: u8 = 3;
+
+/// Maximum duration to wait for the response to an IP discovery request.
+const IP_DISCOVERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Number of attempts to resume a session before giving up.
+const RESUME_ATTEMPTS: u32 = 5;
+
+type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Connecting to a voice channel failed.
+#[derive(Debug)]
+pub struct ConnectionError {
+    kind: ConnectionErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl ConnectionError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &ConnectionErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(self) -> (ConnectionErrorType, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for ConnectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            ConnectionErrorType::Closed { code } => {
+                f.write_str("voice gateway closed the connection")?;
+
+                if let Some(code) = code {
+                    f.write_str(" with code ")?;
+                    Display::fmt(code, f)?;
+                }
+
+                Ok(())
+            }
+            ConnectionErrorType::Connecting => {
+                f.write_str("failed to connect to the voice gateway")
+            }
+            ConnectionErrorType::Deserializing { payload } => {
+                f.write_str("voice gateway payload could not be deserialized: ")?;
+
+                f.write_str(payload)
+            }
+            ConnectionErrorType::EncryptionModeUnsupported { modes } => {
+                f.write_str("voice server does not support the ")?;
+                f.write_str(ENCRYPTION_MODE)?;
+                f.write_str(" encryption mode, only ")?;
+
+                f.write_str(&modes.join(", "))
+            }
+            ConnectionErrorType::IpDiscovery => f.write_str("failed to discover the external ip"),
+            ConnectionErrorType::SecretKeyInvalid => {
+                f.write_str("voice server sent a secret key that is not 32 bytes long")
+            }
+            ConnectionErrorType::Sending => {
+                f.write_str("failed to send a payload to the voice gateway")
+            }
+            ConnectionErrorType::TimedOut => f.write_str("voice gateway did not respond in time"),
+        }
+    }
+}
+
+impl Error for ConnectionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`ConnectionError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConnectionErrorType {
+    /// Voice gateway closed the connection during the handshake.
+    Closed {
+        /// Close code, if the voice gateway sent one.
+        code: Option<u16>,
+    },
+    /// Connecting to the voice gateway failed.
+    Connecting,
+    /// Payload received from the voice gateway could not be deserialized.
+    Deserializing {
+        /// Payload as received.
+        payload: String,
+    },
+    /// Voice server does not support [`ENCRYPTION_MODE`].
+    EncryptionModeUnsupported {
+        /// Encryption modes the voice server supports.
+        modes: Vec<String>,
+    },
+    /// Discovering the external address of the UDP socket failed.
+    IpDiscovery,
+    /// Secret key sent by the voice server is not 32 bytes long.
+    SecretKeyInvalid,
+    /// Sending a payload to the voice gateway failed.
+    Sending,
+    /// Voice gateway or voice server did not respond in time.
+    TimedOut,
+}
+
+/// Sending audio failed.
+#[derive(Debug)]
+pub struct SendError {
+    kind: SendErrorType,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl SendError {
+    /// Immutable reference to the type of error that occurred.
+    #[must_use = "retrieving the type has no effect if left unused"]
+    pub const fn kind(&self) -> &SendErrorType {
+        &self.kind
+    }
+
+    /// Consume the error, returning the source error if there is any.
+    #[must_use = "consuming the error and retrieving the source has no effect if left unused"]
+    pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync>> {
+        self.source
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use = "consuming the error into its parts has no effect if left unused"]
+    pub fn into_parts(self) -> (SendErrorType, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for SendError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.kind {
+            SendErrorType::Closed => f.write_str("connection to the voice gateway is closed"),
+            SendErrorType::Encrypting => f.write_str("failed to encrypt the audio"),
+            SendErrorType::Sending => f.write_str("failed to send the audio"),
+        }
+    }
+}
+
+impl Error for SendError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Type of [`SendError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SendErrorType {
+    /// Connection to the voice gateway is closed and could not be resumed.
+    Closed,
+    /// Encrypting the audio failed.
+    Encrypting,
+    /// Sending the audio over the UDP socket failed.
+    Sending,
+}
+
+/// Information required to connect to a voice channel.
+///
+/// This is combined from the `VoiceStateUpdate` and `VoiceServerUpdate`
+/// events received after joining a voice channel, such as via
+/// [`Voice::process`].
+///
+/// [`Voice::process`]: crate::Voice::process
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ConnectionInfo {
+    /// Endpoint of the voice gateway.
+    ///
+    /// Endpoints without a `ws://` or `wss://` scheme are connected to via
+    /// `wss://`.
+    pub endpoint: String,
+    /// ID of the guild of the voice channel.
+    pub guild_id: GuildId,
+    /// ID of the gateway session of the user.
+    pub session_id: String,
+    /// Token of the voice session.
+    pub token: String,
+    /// ID of the user.
+    pub user_id: UserId,
+}
+
+impl ConnectionInfo {
+    /// URL of the voice gateway, including the version.
+    fn url(&self) -> String {
+        let endpoint = self.endpoint.trim_end_matches('/');
+
+        if endpoint.starts_with("ws://") || endpoint.starts_with("wss://") {
+            format!("{}/?v={}", endpoint, VOICE_GATEWAY_VERSION)
+        } else {
+            format!("wss://{}/?v={}", endpoint, VOICE_GATEWAY_VERSION)
+        }
+    }
+}
+
+/// Connection to a voice channel.
+///
+/// Audio is sent as Opus frames of 20 milliseconds of stereo audio at
+/// 48 kHz, which should be sent in order from one task every 20
+/// milliseconds.
+///
+/// Dropping the connection disconnects from the voice gateway. Leaving the
+/// voice channel is done via the main gateway.
+///
+/// # Examples
+///
+/// Connect once both halves of the voice information have been received and
+/// send a frame:
+///
+/// ```no_run
+/// # async fn run(event: twilight_model::gateway::event::Event) -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::id::UserId;
+/// use twilight_voice::{Connection, Voice};
+///
+/// let voice = Voice::new(UserId(123));
+///
+/// if let Some(info) = voice.process(&event) {
+///     let connection = Connection::connect(info).await?;
+///
+///     # let frame = [0; 3];
+///     connection.send_opus(&frame).await?;
+///     connection.stop_speaking().await?;
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct Connection {
+    /// Sender of payloads to the task maintaining the voice gateway
+    /// connection.
+    commands: UnboundedSender<String>,
+    info: ConnectionInfo,
+    rtp: Mutex<RtpSender>,
+    socket: UdpSocket,
+    speaking: AtomicBool,
+    ssrc: u32,
+}
+
+impl Connection {
+    /// Connect to a voice channel.
+    ///
+    /// Resolves once the handshake is complete and audio can be sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConnectionErrorType::Connecting`] error type if connecting
+    /// to the voice gateway failed.
+    ///
+    /// Returns a [`ConnectionErrorType::Closed`] error type if the voice
+    /// gateway closed the connection during the handshake, such as because
+    /// the token is invalid.
+    ///
+    /// Returns a [`ConnectionErrorType::EncryptionModeUnsupported`] error
+    /// type if the voice server does not support [`ENCRYPTION_MODE`].
+    ///
+    /// Returns a [`ConnectionErrorType::IpDiscovery`] error type if
+    /// discovering the external address of the UDP socket failed.
+    ///
+    /// Returns a [`ConnectionErrorType::TimedOut`] error type if the voice
+    /// gateway or server did not respond in time.
+    pub async fn connect(info: ConnectionInfo) -> Result<Self, ConnectionError> {
+        tracing::debug!(guild_id = %info.guild_id, "connecting to {}", info.endpoint);
+
+        let (mut stream, heartbeat_interval) = connect(&info).await?;
+
+        send(
+            &mut stream,
+            OpCode::Identify,
+            Identify {
+                server_id: info.guild_id,
+                session_id: info.session_id.clone(),
+                token: info.token.clone(),
+                user_id: info.user_id,
+            },
+        )
+        .await?;
+
+        let ready = receive(&mut stream, |payload| match payload {
+            IncomingPayload::Ready(ready) => Some(ready),
+            _ => None,
+        })
+        .await?;
+
+        if !ready.modes.iter().any(|mode| mode == ENCRYPTION_MODE) {
+            return Err(ConnectionError {
+                kind: ConnectionErrorType::EncryptionModeUnsupported { modes: ready.modes },
+                source: None,
+            });
+        }
+
+        let socket = UdpSocket::bind(("0.0.0.0", 0))
+            .await
+            .map_err(|source| ConnectionError {
+                kind: ConnectionErrorType::IpDiscovery,
+                source: Some(Box::new(source)),
+            })?;
+        socket
+            .connect((ready.ip.as_str(), ready.port))
+            .await
+            .map_err(|source| ConnectionError {
+                kind: ConnectionErrorType::IpDiscovery,
+                source: Some(Box::new(source)),
+            })?;
+
+        let (address, port) = discover_ip(&socket, ready.ssrc).await?;
+        tracing::debug!(guild_id = %info.guild_id, "discovered external address {}:{}", address, port);
+
+        send(
+            &mut stream,
+            OpCode::SelectProtocol,
+            SelectProtocol {
+                data: SelectProtocolData {
+                    address,
+                    mode: ENCRYPTION_MODE.to_owned(),
+                    port,
+                },
+                protocol: "udp".to_owned(),
+            },
+        )
+        .await?;
+
+        let description = receive(&mut stream, |payload| match payload {
+            IncomingPayload::SessionDescription(description) => Some(description),
+            _ => None,
+        })
+        .await?;
+
+        let secret_key =
+            <[u8; 32]>::try_from(description.secret_key.as_slice()).map_err(|source| {
+                ConnectionError {
+                    kind: ConnectionErrorType::SecretKeyInvalid,
+                    source: Some(Box::new(source)),
+                }
+            })?;
+
+        let (commands, commands_rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(
+            Runner {
+                commands: commands_rx,
+                heartbeat_interval,
+                heartbeat_nonce: None,
+                info: info.clone(),
+                stream,
+            }
+            .run(),
+        );
+
+        tracing::debug!(guild_id = %info.guild_id, "connected to {}", info.endpoint);
+
+        Ok(Self {
+            commands,
+            info,
+            rtp: Mutex::new(RtpSender::new(&secret_key, ready.ssrc)),
+            socket,
+            speaking: AtomicBool::new(false),
+            ssrc: ready.ssrc,
+        })
+    }
+
+    /// Return an immutable reference to the information used to connect.
+    pub const fn info(&self) -> &ConnectionInfo {
+        &self.info
+    }
+
+    /// Return the synchronization source identifying the audio sent.
+    pub const fn ssrc(&self) -> u32 {
+        self.ssrc
+    }
+
+    /// Whether the connection to the voice gateway is closed.
+    ///
+    /// This is the case if the session could not be resumed after losing the
+    /// connection or missing a heartbeat acknowledgement, or the voice gateway
+    /// closed the connection because the
+    /// session is no longer valid, such as because the user was
+    /// disconnected from the voice channel.
+    pub fn is_closed(&self) -> bool {
+        self.commands.is_closed()
+    }
+
+    /// Send an Opus frame.
+    ///
+    /// Marks the user as speaking if it is not already.
+    ///
+    /// Frames are sent immediately, so the caller must pace them: each frame
+    /// holds 20 milliseconds of audio and must be sent 20 milliseconds after
+    /// the previous one, such as by ticking an [`Interval`] with a period of
+    /// 20 milliseconds.
+    ///
+    /// [`Interval`]: tokio::time::Interval
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SendErrorType::Closed`] error type if the connection to
+    /// the voice gateway is closed.
+    ///
+    /// Returns a [`SendErrorType::Encrypting`] error type if encrypting the
+    /// frame failed.
+    ///
+    /// Returns a [`SendErrorType::Sending`] error type if sending the frame
+    /// failed.
+    pub async fn send_opus(&self, frame: &[u8]) -> Result<(), SendError> {
+        if !self.speaking.swap(true, Ordering::Relaxed) {
+            self.set_speaking(true)?;
+        }
+
+        self.send_frame(frame).await
+    }
+
+    /// Stop speaking, sending five frames of silence.
+    ///
+    /// Does nothing if the user is not speaking.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`send_opus`].
+    ///
+    /// [`send_opus`]: Self::send_opus
+    pub async fn stop_speaking(&self) -> Result<(), SendError> {
+        if !self.speaking.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        for _ in 0..5 {
+            self.send_frame(&SILENCE_FRAME).await?;
+        }
+
+        self.set_speaking(false)
+    }
+
+    /// Send an Opus frame without marking the user as speaking.
+    async fn send_frame(&self, frame: &[u8]) -> Result<(), SendError> {
+        if self.is_closed() {
+            return Err(SendError {
+                kind: SendErrorType::Closed,
+                source: None,
+            });
+        }
+
+        let packet = self
+            .rtp
+            .lock()
+            .expect("rtp sender poisoned")
+            .packet(frame)
+            .map_err(|source| SendError {
+                kind: SendErrorType::Encrypting,
+                source: Some(Box::new(source)),
+            })?;
+
+        self.socket
+            .send(&packet)
+            .await
+            .map_err(|source| SendError {
+                kind: SendErrorType::Sending,
+                source: Some(Box::new(source)),
+            })?;
+
+        Ok(())
+    }
+
+    fn set_speaking(&self, speaking: bool) -> Result<(), SendError> {
+        let payload = payload(
+            OpCode::Speaking,
+            Speaking {
+                delay: 0,
+                speaking: u8::from(speaking),
+                ssrc: self.ssrc,
+                user_id: None,
+            },
+        );
+
+        self.commands.send(payload).map_err(|_| SendError {
+            kind: SendErrorType::Closed,
+            source: None,
+        })
+    }
+}
+
+/// Task maintaining the connection to the voice gateway.
+struct Runner {
+    /// Receiver of payloads to send, until the connection is dropped.
+    commands: UnboundedReceiver<String>,
+    heartbeat_interval: Duration,
+    /// Nonce of the last heartbeat sent, until it is acknowledged.
+    heartbeat_nonce: Option<u64>,
+    info: ConnectionInfo,
+    stream: WebSocket,
+}
+
+impl Runner {
+    async fn run(mut self) {
+        let mut heartbeat = heartbeater(self.heartbeat_interval);
+
+        loop {
+            let lost = tokio::select! {
+                command = self.commands.recv() => {
+                    if let Some(payload) = command {
+                        self.send(payload).await
+                    } else {
+                        tracing::debug!(guild_id = %self.info.guild_id, "connection dropped, disconnecting");
+                        let _res = self.stream.close(None).await;
+
+                        return;
+                    }
+                }
+                _ = heartbeat.tick() => {
+                    // The connection is a zombie if the last heartbeat was
+                    // not acknowledged before the next one is due.
+                    if let Some(nonce) = self.heartbeat_nonce {
+                        tracing::debug!(guild_id = %self.info.guild_id, nonce, "heartbeat not acknowledged, resuming");
+
+                        true
+                    } else {
+                        let nonce = SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map_or(0, |duration| duration.as_millis() as u64);
+                        self.heartbeat_nonce = Some(nonce);
+
+                        self.send(payload(OpCode::Heartbeat, nonce)).await
+                    }
+                }
+                message = self.stream.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        self.process(&text);
+
+                        false
+                    }
+                    Some(Ok(Message::Close(frame))) => {
+                        let code = frame.map(|frame| u16::from(frame.code));
+                        tracing::debug!(guild_id = %self.info.guild_id, ?code, "voice gateway closed the connection");
+
+                        if matches!(code, Some(code) if is_fatal(code)) {
+                            return;
+                        }
+
+                        true
+                    }
+                    Some(Ok(_)) => false,
+                    Some(Err(source)) => {
+                        tracing::debug!(guild_id = %self.info.guild_id, "voice gateway connection errored: {}", source);
+
+                        true
+                    }
+                    None => true,
+                },
+            };
+
+            if lost {
+                if !self.resume().await {
+                    return;
+                }
+
+                heartbeat = heartbeater(self.heartbeat_interval);
+                self.heartbeat_nonce = None;
+            }
+        }
+    }
+
+    /// Send a payload, returning whether the connection was lost.
+    async fn send(&mut self, payload: String) -> bool {
+        if let Err(source) = self.stream.send(Message::Text(payload)).await {
+            tracing::debug!(guild_id = %self.info.guild_id, "failed to send payload: {}", source);
+
+            return true;
+        }
+
+        false
+    }
+
+    fn process(&mut self, text: &str) {
+        match IncomingPayload::from_json(text) {
+            Ok(Some(IncomingPayload::HeartbeatAck(nonce))) => {
+                if self.heartbeat_nonce == Some(nonce) {
+                    tracing::trace!(guild_id = %self.info.guild_id, nonce, "heartbeat acknowledged");
+                    self.heartbeat_nonce = None;
+                } else {
+                    tracing::debug!(guild_id = %self.info.guild_id, nonce, "acknowledged heartbeat was not the last sent");
+                }
+            }
+            Ok(Some(payload)) => {
+                tracing::debug!(guild_id = %self.info.guild_id, "received payload: {:?}", payload);
+            }
+            Ok(None) => {
+                tracing::trace!(guild_id = %self.info.guild_id, "received unknown payload: {}", text);
+            }
+            Err(source) => {
+                tracing::warn!(guild_id = %self.info.guild_id, "failed to deserialize payload {}: {}", text, source);
+            }
+        }
+    }
+
+    /// Resume the session, returning whether it was resumed.
+    async fn resume(&mut self) -> bool {
+        let mut delay = Duration::from_secs(1);
+
+        for attempt in 1..=RESUME_ATTEMPTS {
+            match self.try_resume().await {
+                Ok(()) => {
+                    tracing::debug!(guild_id = %self.info.guild_id, "resumed voice session");
+
+                    return true;
+                }
+                Err(source) => {
+                    tracing::warn!(guild_id = %self.info.guild_id, attempt, "failed to resume voice session: {}", source);
+
+                    if matches!(source.kind(), ConnectionErrorType::Closed { code: Some(code) } if is_fatal(*code))
+                    {
+                        return false;
+                    }
+                }
+            }
+
+            tokio_time::sleep(delay).await;
+            delay *= 2;
+        }
+
+        false
+    }
+
+    async fn try_resume(&mut self) -> Result<(), ConnectionError> {
+        let (mut stream, heartbeat_interval) = connect(&self.info).await?;
+
+        send(
+            &mut stream,
+            OpCode::Resume,
+            Resume {
+                server_id: self.info.guild_id,
+                session_id: self.info.session_id.clone(),
+                token: self.info.token.clone(),
+            },
+        )
+        .await?;
+
+        receive(&mut stream, |payload| match payload {
+            IncomingPayload::Resumed => Some(()),
+            _ => None,
+        })
+        .await?;
+
+        self.heartbeat_interval = heartbeat_interval;
+        self.stream = stream;
+
+        Ok(())
+    }
+}
+
+/// Whether a close code means that the session can not be resumed.
+///
+/// Every close code of the voice gateway other than 4015, meaning that the
+/// voice server crashed, requires a new session.
+const fn is_fatal(code: u16) -> bool {
+    matches!(code, 4001..=4014 | 4016)
+}
+
+fn heartbeater(interval: Duration) -> Interval {
+    tokio_time::interval_at(Instant::now() + interval, interval)
+}
+
+/// Connect to the voice gateway, returning the stream and the heartbeat
+/// interval sent in its hello.
+async fn connect(info: &ConnectionInfo) -> Result<(WebSocket, Duration), ConnectionError> {
+    let connecting = tokio_tungstenite::connect_async(info.url());
+
+    let (mut stream, _) = tokio_time::timeout(HANDSHAKE_TIMEOUT, connecting)
+        .await
+        .map_err(|source| ConnectionError {
+            kind: ConnectionErrorType::TimedOut,
+            source: Some(Box::new(source)),
+        })?
+        .map_err(|source| ConnectionError {
+            kind: ConnectionErrorType::Connecting,
+            source: Some(Box::new(source)),
+        })?;
+
+    let hello = receive(&mut stream, |payload| match payload {
+        IncomingPayload::Hello(hello) => Some(hello),
+        _ => None,
+    })
+    .await?;
+
+    Ok((
+        stream,
+        Duration::from_secs_f64(hello.heartbeat_interval / 1000.),
+    ))
+}
+
+/// Send IP discovery requests until a response is received.
+async fn discover_ip(socket: &UdpSocket, ssrc: u32) -> Result<(String, u16), ConnectionError> {
+    let request = ip_discovery_request(ssrc);
+    // Larger than a response to detect responses that are too long.
+    let mut buf = [0; IP_DISCOVERY_LEN + 1];
+
+    for _ in 0..IP_DISCOVERY_ATTEMPTS {
+        socket
+            .send(&request)
+            .await
+            .map_err(|source| ConnectionError {
+                kind: ConnectionErrorType::IpDiscovery,
+                source: Some(Box::new(source)),
+            })?;
+
+        match tokio_time::timeout(IP_DISCOVERY_TIMEOUT, socket.recv(&mut buf)).await {
+            Ok(Ok(len)) => {
+                return ip_discovery_response(&buf[..len]).ok_or(ConnectionError {
+                    kind: ConnectionErrorType::IpDiscovery,
+                    source: None,
+                })
+            }
+            Ok(Err(source)) => {
+                return Err(ConnectionError {
+                    kind: ConnectionErrorType::IpDiscovery,
+                    source: Some(Box::new(source)),
+                })
+            }
+            Err(_) => continue,
+        }
+    }
+
+    Err(ConnectionError {
+        kind: ConnectionErrorType::TimedOut,
+        source: None,
+    })
+}
+
+/// Serialize a payload to send to the voice gateway.
+fn payload(op: OpCode, d: impl Serialize) -> String {
+    serde_json::to_string(&OutgoingPayload { d, op }).expect("voice payloads are serializable")
+}
+
+async fn send(
+    stream: &mut WebSocket,
+    op: OpCode,
+    d: impl Serialize,
+) -> Result<(), ConnectionError> {
+    stream
+        .send(Message::Text(payload(op, d)))
+        .await
+        .map_err(|source| ConnectionError {
+            kind: ConnectionErrorType::Sending,
+            source: Some(Box::new(source)),
+        })
+}
+
+/// Receive payloads until one is accepted by the filter.
+async fn receive<T>(
+    stream: &mut WebSocket,
+    mut filter: impl FnMut(IncomingPayload) -> Option<T>,
+) -> Result<T, ConnectionError> {
+    let receiving = async {
+        loop {
+            let text = match stream.next().await {
+                Some(Ok(Message::Text(text))) => text,
+                Some(Ok(Message::Close(frame))) => {
+                    return Err(ConnectionError {
+                        kind: ConnectionErrorType::Closed {
+                            code: frame.map(|frame| u16::from(frame.code)),
+                        },
+                        source: None,
+                    })
+                }
+                Some(Ok(_)) => continue,
+                Some(Err(source)) => {
+                    return Err(ConnectionError {
+                        kind: ConnectionErrorType::Closed { code: None },
+                        source: Some(Box::new(source)),
+                    })
+                }
+                None => {
+                    return Err(ConnectionError {
+                        kind: ConnectionErrorType::Closed { code: None },
+                        source: None,
+                    })
+                }
+            };
+
+            let payload = match IncomingPayload::from_json(&text) {
+                Ok(payload) => payload,
+                Err(source) => {
+                    return Err(ConnectionError {
+                        kind: ConnectionErrorType::Deserializing { payload: text },
+                        source: Some(Box::new(source)),
+                    })
+                }
+            };
+
+            if let Some(value) = payload.and_then(&mut filter) {
+                return Ok(value);
+            }
+        }
+    };
+
+    tokio_time::timeout(HANDSHAKE_TIMEOUT, receiving)
+        .await
+        .map_err(|source| ConnectionError {
+            kind: ConnectionErrorType::TimedOut,
+            source: Some(Box::new(source)),
+        })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Connection, ConnectionError, ConnectionErrorType, ConnectionInfo, SendError, SendErrorType,
+    };
+    use crate::server::{Server, SECRET_KEY, SSRC};
+    use serde_json::{json, Value};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{error::Error, fmt::Debug, time::Duration};
+    use twilight_model::id::{GuildId, UserId};
+    use xsalsa20poly1305::{
+        aead::{Aead, NewAead},
+        Key, Nonce, XSalsa20Poly1305,
+    };
+
+    assert_fields!(ConnectionErrorType::Closed: code);
+    assert_fields!(ConnectionErrorType::Deserializing: payload);
+    assert_fields!(ConnectionErrorType::EncryptionModeUnsupported: modes);
+    assert_impl_all!(ConnectionErrorType: Debug, Send, Sync);
+    assert_impl_all!(ConnectionError: Error, Send, Sync);
+    assert_impl_all!(SendErrorType: Debug, Send, Sync);
+    assert_impl_all!(SendError: Error, Send, Sync);
+    assert_impl_all!(Connection: Debug, Send, Sync);
+    assert_impl_all!(ConnectionInfo: Clone, Debug, Eq, Send, Sync);
+
+    fn info(server: &Server) -> ConnectionInfo {
+        ConnectionInfo {
+            endpoint: server.endpoint(),
+            guild_id: GuildId(1),
+            session_id: "session".to_owned(),
+            token: "token".to_owned(),
+            user_id: UserId(2),
+        }
+    }
+
+    /// Wait for the next payload sent to the server with the opcode.
+    async fn payload(server: &mut Server, op: u64) -> Value {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let payload = server.payload().await;
+
+                if payload["op"] == op {
+                    return payload["d"].clone();
+                }
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_url() {
+        let mut info = ConnectionInfo {
+            endpoint: "voice.discord.media:443".to_owned(),
+            guild_id: GuildId(1),
+            session_id: "session".to_owned(),
+            token: "token".to_owned(),
+            user_id: UserId(2),
+        };
+        assert_eq!("wss://voice.discord.media:443/?v=4", info.url());
+
+        info.endpoint = "ws://127.0.0.1:1234/".to_owned();
+        assert_eq!("ws://127.0.0.1:1234/?v=4", info.url());
+    }
+
+    #[tokio::test]
+    async fn test_send_opus() {
+        let mut server = Server::start(None).await;
+        let connection = Connection::connect(info(&server)).await.unwrap();
+        assert_eq!(SSRC, connection.ssrc());
+
+        assert_eq!(
+            json!({
+                "server_id": "1",
+                "session_id": "session",
+                "token": "token",
+                "user_id": "2",
+            }),
+            payload(&mut server, 0).await
+        );
+        let select = payload(&mut server, 1).await;
+        assert_eq!("udp", select["protocol"]);
+        assert_eq!("127.0.0.1", select["data"]["address"]);
+        assert_eq!("xsalsa20_poly1305", select["data"]["mode"]);
+
+        connection.send_opus(&[1, 2, 3]).await.unwrap();
+        connection.send_opus(&[4, 5]).await.unwrap();
+
+        assert_eq!(
+            json!({ "delay": 0, "speaking": 1, "ssrc": SSRC }),
+            payload(&mut server, 5).await
+        );
+
+        let cipher = XSalsa20Poly1305::new(Key::from_slice(&SECRET_KEY));
+
+        for (sequence, frame) in [[1, 2, 3].as_ref(), [4, 5].as_ref()].iter().enumerate() {
+            let packet = server.packet().await;
+            assert_eq!(sequence as u8, packet[3]);
+            assert_eq!(SSRC.to_be_bytes(), packet[8..12]);
+
+            let mut nonce = Nonce::default();
+            nonce[..12].copy_from_slice(&packet[..12]);
+            assert_eq!(*frame, &cipher.decrypt(&nonce, &packet[12..]).unwrap()[..]);
+        }
+
+        assert!(payload(&mut server, 3).await.is_u64());
+
+        connection.stop_speaking().await.unwrap();
+        assert_eq!(0, payload(&mut server, 5).await["speaking"]);
+    }
+
+    #[tokio::test]
+    async fn test_resume() {
+        let mut server = Server::start(Some(4015)).await;
+        let connection = Connection::connect(info(&server)).await.unwrap();
+
+        assert_eq!(
+            json!({
+                "server_id": "1",
+                "session_id": "session",
+                "token": "token",
+            }),
+            payload(&mut server, 7).await
+        );
+
+        connection.send_opus(&[1]).await.unwrap();
+        payload(&mut server, 5).await;
+        assert!(!connection.is_closed());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_ack_missed() {
+        let mut server = Server::start_without_acks().await;
+        let connection = Connection::connect(info(&server)).await.unwrap();
+
+        let heartbeat = payload(&mut server, 3).await;
+        assert!(heartbeat.is_u64());
+        assert_eq!("session", payload(&mut server, 7).await["session_id"]);
+
+        // Heartbeats of the resumed session are acknowledged.
+        payload(&mut server, 3).await;
+        payload(&mut server, 3).await;
+        assert!(!connection.is_closed());
+    }
+
+    #[tokio::test]
+    async fn test_closed_fatal() {
+        let server = Server::start(Some(4014)).await;
+        let connection = Connection::connect(info(&server)).await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !connection.is_closed() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            connection.send_opus(&[1]).await.unwrap_err().kind(),
+            SendErrorType::Closed
+        ));
+    }
+}
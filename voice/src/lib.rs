@@ -0,0 +1,134 @@
+//! # twilight-voice
+//!
+//! [![discord badge][]][discord link] [![github badge][]][github link] [![license badge][]][license link] ![rust badge]
+//!
+//! `twilight-voice` is an implementation of Discord's voice connections as
+//! part of the twilight ecosystem, sending audio to voice channels without an
+//! external audio server such as Lavalink.
+//!
+//! It includes a [tracker] combining the `VoiceStateUpdate` and
+//! `VoiceServerUpdate` events received over the main gateway into the
+//! information needed to connect, and a [connection] to a voice channel. The
+//! connection performs the handshake with the voice gateway, discovers the
+//! external address of its UDP socket, and sends Opus frames as RTP packets
+//! encrypted with the `xsalsa20_poly1305` mode. Heartbeats are sent in the
+//! background, and the session is resumed when the connection to the voice
+//! gateway is lost or stops acknowledging heartbeats.
+//!
+//! Encoding audio as Opus and pacing the frames every 20 milliseconds is left
+//! to the user.
+//!
+//! ## Features
+//!
+//! ### TLS
+//!
+//! `twilight-voice` has features to enable [`tokio-tungstenite`]'s TLS
+//! features. These features are mutually exclusive. `rustls` is enabled by
+//! default.
+//!
+//! #### `native`
+//!
+//! The `native` feature enables [`tokio-tungstenite`]'s `native-tls`
+//! feature.
+//!
+//! To enable `native`, do something like this in your `Cargo.toml`:
+//!
+//! ```toml
+//! [dependencies]
+//! twilight-voice = { default-features = false, features = ["native"], version = "0.5" }
+//! ```
+//!
+//! #### `rustls`
+//!
+//! The `rustls` feature enables [`tokio-tungstenite`]'s `rustls-tls` feature,
+//! which uses [`rustls`] as the TLS backend.
+//!
+//! This is enabled by default.
+//!
+//! ## Examples
+//!
+//! Join a voice channel, connect to it once the voice events have been
+//! received, and send audio:
+//!
+//! ```rust,no_run
+//! use futures_util::stream::StreamExt;
+//! use std::{env, error::Error};
+//! use twilight_gateway::{Event, Intents, Shard};
+//! use twilight_model::{
+//!     gateway::payload::UpdateVoiceState,
+//!     id::{ChannelId, GuildId, UserId},
+//! };
+//! use twilight_voice::{Connection, Voice};
+//!
+//! # fn frames() -> Vec<Vec<u8>> { Vec::new() }
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+//!     let token = env::var("DISCORD_TOKEN")?;
+//!     let user_id = UserId(env::var("USER_ID")?.parse()?);
+//!
+//!     let (shard, mut events) = Shard::new(token, Intents::GUILDS | Intents::GUILD_VOICE_STATES);
+//!     shard.start().await?;
+//!
+//!     let voice = Voice::new(user_id);
+//!
+//!     while let Some(event) = events.next().await {
+//!         if let Event::Ready(_) = event {
+//!             let join = UpdateVoiceState::new(GuildId(1), ChannelId(2), false, false);
+//!             shard.command(&join).await?;
+//!         }
+//!
+//!         if let Some(info) = voice.process(&event) {
+//!             let connection = Connection::connect(info).await?;
+//!             let mut interval = tokio::time::interval(std::time::Duration::from_millis(20));
+//!
+//!             // Opus frames of 20 milliseconds each.
+//!             for frame in frames() {
+//!                 interval.tick().await;
+//!                 connection.send_opus(&frame).await?;
+//!             }
+//!
+//!             connection.stop_speaking().await?;
+//!         }
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`rustls`]: https://crates.io/crates/rustls
+//! [`tokio-tungstenite`]: https://crates.io/crates/tokio-tungstenite
+//! [connection]: Connection
+//! [discord badge]: https://img.shields.io/discord/745809834183753828?color=%237289DA&label=discord%20server&logo=discord&style=for-the-badge
+//! [discord link]: https://discord.gg/7jj8n7D
+//! [github badge]: https://img.shields.io/badge/github-twilight-6f42c1.svg?style=for-the-badge&logo=github
+//! [github link]: https://github.com/twilight-rs/twilight
+//! [license badge]: https://img.shields.io/badge/license-ISC-blue.svg?style=for-the-badge&logo=pastebin
+//! [license link]: https://github.com/twilight-rs/twilight/blob/main/LICENSE.md
+//! [rust badge]: https://img.shields.io/badge/rust-1.49+-93450a.svg?style=for-the-badge&logo=rust
+//! [tracker]: Voice
+
+#![deny(
+    clippy::all,
+    clippy::missing_const_for_fn,
+    future_incompatible,
+    missing_docs,
+    nonstandard_style,
+    rust_2018_idioms,
+    broken_intra_doc_links,
+    unused,
+    warnings
+)]
+
+pub mod client;
+pub mod connection;
+pub mod model;
+
+mod udp;
+
+#[cfg(test)]
+mod server;
+
+pub use self::{
+    client::Voice,
+    connection::{Connection, ConnectionInfo},
+};
@@ -0,0 +1,282 @@
+//! Payloads sent and received over the voice gateway.
+//!
+//! Payloads are wrapped in an object containing their [`OpCode`] as `op` and
+//! their data as `d`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use twilight_model::id::{GuildId, UserId};
+
+/// Version of the voice gateway implemented.
+pub const VOICE_GATEWAY_VERSION: u8 = 4;
+
+/// Encryption mode used for the audio sent over UDP.
+pub const ENCRYPTION_MODE: &str = "xsalsa20_poly1305";
+
+/// Type of a voice gateway payload.
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, Hash, PartialEq, Serialize_repr)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum OpCode {
+    /// Begin a voice session.
+    Identify = 0,
+    /// Select the protocol and encryption mode of the audio connection.
+    SelectProtocol = 1,
+    /// Voice session was created.
+    Ready = 2,
+    /// Keep the connection alive.
+    Heartbeat = 3,
+    /// Description of the session, including the secret key.
+    SessionDescription = 4,
+    /// Indicate whether a user is speaking.
+    Speaking = 5,
+    /// Heartbeat was received.
+    HeartbeatAck = 6,
+    /// Resume a voice session.
+    Resume = 7,
+    /// Interval to send heartbeats at.
+    Hello = 8,
+    /// Voice session was resumed.
+    Resumed = 9,
+    /// A user left the voice channel.
+    ClientDisconnect = 13,
+}
+
+/// A user left the voice channel.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ClientDisconnect {
+    /// ID of the user.
+    pub user_id: UserId,
+}
+
+/// Interval to send heartbeats at, sent when connecting.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Hello {
+    /// Interval in milliseconds.
+    pub heartbeat_interval: f64,
+}
+
+/// Begin a voice session.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Identify {
+    /// ID of the guild of the voice channel.
+    pub server_id: GuildId,
+    /// ID of the gateway session of the user.
+    pub session_id: String,
+    /// Token of the voice session.
+    pub token: String,
+    /// ID of the user.
+    pub user_id: UserId,
+}
+
+/// Voice session was created.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Ready {
+    /// IP address of the voice server's UDP socket.
+    pub ip: String,
+    /// Encryption modes supported by the voice server.
+    pub modes: Vec<String>,
+    /// Port of the voice server's UDP socket.
+    pub port: u16,
+    /// Synchronization source identifying the audio sent by the user.
+    pub ssrc: u32,
+}
+
+/// Resume a voice session.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Resume {
+    /// ID of the guild of the voice channel.
+    pub server_id: GuildId,
+    /// ID of the gateway session of the user.
+    pub session_id: String,
+    /// Token of the voice session.
+    pub token: String,
+}
+
+/// Select the protocol and encryption mode of the audio connection.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SelectProtocol {
+    /// Details of the audio connection.
+    pub data: SelectProtocolData,
+    /// Protocol of the audio connection, which is always `udp`.
+    pub protocol: String,
+}
+
+/// Details of the audio connection selected via [`SelectProtocol`].
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SelectProtocolData {
+    /// External IP address of the user's UDP socket, as discovered via IP
+    /// discovery.
+    pub address: String,
+    /// Encryption mode, such as [`ENCRYPTION_MODE`].
+    pub mode: String,
+    /// External port of the user's UDP socket.
+    pub port: u16,
+}
+
+/// Description of the session, including the secret key.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SessionDescription {
+    /// Encryption mode selected.
+    pub mode: String,
+    /// Secret key to encrypt audio with.
+    pub secret_key: Vec<u8>,
+}
+
+/// Indicate whether a user is speaking.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Speaking {
+    /// Delay in milliseconds, which is always 0 for bots.
+    pub delay: u32,
+    /// Bitflags of the kind of speaking, with `1` being microphone audio and
+    /// `0` not speaking.
+    pub speaking: u8,
+    /// Synchronization source identifying the user's audio.
+    pub ssrc: u32,
+    /// ID of the user, only sent by the voice server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<UserId>,
+}
+
+/// Payload sent to the voice gateway.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct OutgoingPayload<T> {
+    /// Data of the payload.
+    pub d: T,
+    /// Type of the payload.
+    pub op: OpCode,
+}
+
+/// Payload received from the voice gateway.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum IncomingPayload {
+    /// A user left the voice channel.
+    ClientDisconnect(ClientDisconnect),
+    /// Heartbeat with the nonce was received.
+    HeartbeatAck(u64),
+    /// Interval to send heartbeats at.
+    Hello(Hello),
+    /// Voice session was created.
+    Ready(Ready),
+    /// Voice session was resumed.
+    Resumed,
+    /// Description of the session.
+    SessionDescription(SessionDescription),
+    /// A user started or stopped speaking.
+    Speaking(Speaking),
+}
+
+impl IncomingPayload {
+    /// Parse a payload from JSON.
+    ///
+    /// Returns `None` if the type of the payload is not known.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload is not valid JSON or its data does not
+    /// match its type.
+    pub fn from_json(json: &str) -> Result<Option<Self>, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            #[serde(default)]
+            d: Value,
+            op: u8,
+        }
+
+        let Raw { d, op } = serde_json::from_str(json)?;
+
+        Ok(Some(match op {
+            2 => Self::Ready(serde_json::from_value(d)?),
+            4 => Self::SessionDescription(serde_json::from_value(d)?),
+            5 => Self::Speaking(serde_json::from_value(d)?),
+            6 => Self::HeartbeatAck(serde_json::from_value(d)?),
+            8 => Self::Hello(serde_json::from_value(d)?),
+            9 => Self::Resumed,
+            13 => Self::ClientDisconnect(serde_json::from_value(d)?),
+            _ => return Ok(None),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Hello, Identify, IncomingPayload, OpCode, OutgoingPayload, Ready, Speaking};
+    use serde_json::json;
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+    use twilight_model::id::{GuildId, UserId};
+
+    assert_impl_all!(IncomingPayload: Clone, Debug, Send, Sync);
+    assert_impl_all!(OpCode: Clone, Copy, Debug, Send, Sync);
+
+    #[test]
+    fn test_outgoing() {
+        let payload = OutgoingPayload {
+            d: Identify {
+                server_id: GuildId(1),
+                session_id: "session".to_owned(),
+                token: "token".to_owned(),
+                user_id: UserId(2),
+            },
+            op: OpCode::Identify,
+        };
+
+        assert_eq!(
+            json!({
+                "d": {
+                    "server_id": "1",
+                    "session_id": "session",
+                    "token": "token",
+                    "user_id": "2",
+                },
+                "op": 0,
+            }),
+            serde_json::to_value(&payload).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_incoming() {
+        assert_eq!(
+            Some(IncomingPayload::Hello(Hello {
+                heartbeat_interval: 41_250.5,
+            })),
+            IncomingPayload::from_json(r#"{"op":8,"d":{"heartbeat_interval":41250.5}}"#).unwrap()
+        );
+        assert_eq!(
+            Some(IncomingPayload::Ready(Ready {
+                ip: "127.0.0.1".to_owned(),
+                modes: vec!["xsalsa20_poly1305".to_owned()],
+                port: 1234,
+                ssrc: 1,
+            })),
+            IncomingPayload::from_json(
+                r#"{"op":2,"d":{"ssrc":1,"ip":"127.0.0.1","port":1234,"modes":["xsalsa20_poly1305"],"heartbeat_interval":1}}"#
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            Some(IncomingPayload::Speaking(Speaking {
+                delay: 0,
+                speaking: 1,
+                ssrc: 2,
+                user_id: Some(UserId(3)),
+            })),
+            IncomingPayload::from_json(
+                r#"{"op":5,"d":{"speaking":1,"delay":0,"ssrc":2,"user_id":"3"}}"#
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            Some(IncomingPayload::Resumed),
+            IncomingPayload::from_json(r#"{"op":9,"d":null}"#).unwrap()
+        );
+        assert_eq!(
+            None,
+            IncomingPayload::from_json(r#"{"op":12,"d":{}}"#).unwrap()
+        );
+        assert!(IncomingPayload::from_json(r#"{"op":2,"d":{}}"#).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Local stand-in for a voice server, used in tests.
+//!
+//! Performs the voice gateway handshake with each connection, answers IP
+//! discovery requests, and records the payloads and audio packets it
+//! receives.
+
+use crate::{
+    model::{Hello, OpCode, OutgoingPayload, Ready, SessionDescription, ENCRYPTION_MODE},
+    udp::IP_DISCOVERY_LEN,
+};
+use futures_util::{sink::SinkExt, stream::StreamExt};
+use serde::Serialize;
+use serde_json::Value;
+use std::net::SocketAddr;
+use tokio::{
+    net::{TcpListener, TcpStream, UdpSocket},
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+};
+use tokio_tungstenite::{
+    tungstenite::{
+        protocol::{frame::coding::CloseCode, CloseFrame},
+        Message,
+    },
+    WebSocketStream,
+};
+
+/// Secret key sent to clients.
+pub const SECRET_KEY: [u8; 32] = [3; 32];
+
+/// Synchronization source assigned to clients.
+pub const SSRC: u32 = 42;
+
+pub struct Server {
+    address: SocketAddr,
+    packets: UnboundedReceiver<Vec<u8>>,
+    payloads: UnboundedReceiver<Value>,
+}
+
+impl Server {
+    /// Start the server.
+    ///
+    /// If a close code is provided, the first connection is closed with it
+    /// once the handshake is complete.
+    pub async fn start(close_code: Option<u16>) -> Self {
+        Self::start_with(close_code, true).await
+    }
+
+    /// Start the server, not acknowledging the heartbeats of the first
+    /// connection.
+    pub async fn start_without_acks() -> Self {
+        Self::start_with(None, false).await
+    }
+
+    async fn start_with(close_code: Option<u16>, ack_first: bool) -> Self {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let socket = UdpSocket::bind(("127.0.0.1", 0)).await.unwrap();
+        let udp_port = socket.local_addr().unwrap().port();
+
+        let (packets_tx, packets) = mpsc::unbounded_channel();
+        let (payloads_tx, payloads) = mpsc::unbounded_channel();
+
+        tokio::spawn(udp(socket, packets_tx));
+        tokio::spawn(async move {
+            let mut close_code = close_code;
+            let mut ack = ack_first;
+
+            // Connections are handled concurrently, as clients may resume
+            // before closing their previous connection.
+            while let Ok((stream, _)) = listener.accept().await {
+                let stream = tokio_tungstenite::accept_async(stream).await.unwrap();
+                tokio::spawn(gateway(
+                    stream,
+                    payloads_tx.clone(),
+                    udp_port,
+                    close_code.take(),
+                    ack,
+                ));
+                ack = true;
+            }
+        });
+
+        Self {
+            address,
+            packets,
+            payloads,
+        }
+    }
+
+    pub fn endpoint(&self) -> String {
+        format!("ws://{}", self.address)
+    }
+
+    /// Next audio packet received.
+    pub async fn packet(&mut self) -> Vec<u8> {
+        self.packets.recv().await.unwrap()
+    }
+
+    /// Next payload received by the voice gateway.
+    pub async fn payload(&mut self) -> Value {
+        self.payloads.recv().await.unwrap()
+    }
+}
+
+fn payload(op: OpCode, d: impl Serialize) -> Message {
+    Message::Text(serde_json::to_string(&OutgoingPayload { d, op }).unwrap())
+}
+
+async fn gateway(
+    mut stream: WebSocketStream<TcpStream>,
+    payloads: UnboundedSender<Value>,
+    udp_port: u16,
+    close_code: Option<u16>,
+    ack: bool,
+) {
+    let hello = Hello {
+        heartbeat_interval: 100.,
+    };
+
+    if stream.send(payload(OpCode::Hello, hello)).await.is_err() {
+        return;
+    }
+
+    while let Some(Ok(message)) = stream.next().await {
+        let text = match message {
+            Message::Text(text) => text,
+            _ => continue,
+        };
+
+        let value = serde_json::from_str::<Value>(&text).unwrap();
+        let op = value["op"].as_u64().unwrap();
+        let d = value["d"].clone();
+        let _res = payloads.send(value);
+
+        let response = match op {
+            0 => payload(
+                OpCode::Ready,
+                Ready {
+                    ip: "127.0.0.1".to_owned(),
+                    modes: vec!["aead_aes256_gcm".to_owned(), ENCRYPTION_MODE.to_owned()],
+                    port: udp_port,
+                    ssrc: SSRC,
+                },
+            ),
+            1 => payload(
+                OpCode::SessionDescription,
+                SessionDescription {
+                    mode: ENCRYPTION_MODE.to_owned(),
+                    secret_key: SECRET_KEY.to_vec(),
+                },
+            ),
+            3 if ack => payload(OpCode::HeartbeatAck, d),
+            7 => payload(OpCode::Resumed, Value::Null),
+            _ => continue,
+        };
+
+        if stream.send(response).await.is_err() {
+            return;
+        }
+
+        if let (1, Some(code)) = (op, close_code) {
+            let frame = CloseFrame {
+                code: CloseCode::from(code),
+                reason: "".into(),
+            };
+            let _res = stream.send(Message::Close(Some(frame))).await;
+
+            return;
+        }
+    }
+}
+
+async fn udp(socket: UdpSocket, packets: UnboundedSender<Vec<u8>>) {
+    let mut buf = [0; 1500];
+
+    while let Ok((len, peer)) = socket.recv_from(&mut buf).await {
+        let packet = &buf[..len];
+
+        // Answer IP discovery requests with the address of the peer.
+        if len == IP_DISCOVERY_LEN && packet[0..2] == 1u16.to_be_bytes() {
+            let mut response = [0; IP_DISCOVERY_LEN];
+            response[0..2].copy_from_slice(&2u16.to_be_bytes());
+            response[2..4].copy_from_slice(&70u16.to_be_bytes());
+            response[4..8].copy_from_slice(&packet[4..8]);
+            let ip = peer.ip().to_string();
+            response[8..8 + ip.len()].copy_from_slice(ip.as_bytes());
+            response[72..].copy_from_slice(&peer.port().to_be_bytes());
+
+            let _res = socket.send_to(&response, peer).await;
+
+            continue;
+        }
+
+        let _res = packets.send(packet.to_vec());
+    }
+}
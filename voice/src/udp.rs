@@ -0,0 +1,161 @@
+//! Packets sent and received over the UDP audio connection.
+
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use xsalsa20poly1305::{
+    aead::{Aead, Error as AeadError, NewAead},
+    Key, Nonce, XSalsa20Poly1305,
+};
+
+/// Length of an IP discovery request and response.
+pub(crate) const IP_DISCOVERY_LEN: usize = 74;
+
+/// Length of the header of an RTP packet.
+pub(crate) const RTP_HEADER_LEN: usize = 12;
+
+/// Number of samples per channel in one Opus frame of 20 milliseconds at
+/// 48 kHz, by which the RTP timestamp advances with each frame.
+const FRAME_SAMPLES: u32 = 960;
+
+/// Version 2 of RTP without padding, extension, or contributing sources.
+const RTP_VERSION: u8 = 0x80;
+
+/// Dynamic RTP payload type used for Opus.
+const RTP_PAYLOAD_TYPE: u8 = 0x78;
+
+/// Create an IP discovery request for a synchronization source.
+pub(crate) fn ip_discovery_request(ssrc: u32) -> [u8; IP_DISCOVERY_LEN] {
+    let mut packet = [0; IP_DISCOVERY_LEN];
+    // Type of the packet, 1 being a request.
+    packet[0..2].copy_from_slice(&1u16.to_be_bytes());
+    // Length of the packet, excluding the type and length.
+    packet[2..4].copy_from_slice(&70u16.to_be_bytes());
+    packet[4..8].copy_from_slice(&ssrc.to_be_bytes());
+
+    packet
+}
+
+/// Parse the external address and port from an IP discovery response.
+///
+/// Returns `None` if the packet is not a valid response.
+pub(crate) fn ip_discovery_response(packet: &[u8]) -> Option<(String, u16)> {
+    // Type of the packet, 2 being a response.
+    if packet.len() != IP_DISCOVERY_LEN || packet[0..2] != 2u16.to_be_bytes() {
+        return None;
+    }
+
+    // The address is null-terminated.
+    let address = &packet[8..72];
+    let end = address.iter().position(|byte| *byte == 0)?;
+    let address = std::str::from_utf8(&address[..end]).ok()?;
+
+    let port = u16::from_be_bytes([packet[72], packet[73]]);
+
+    Some((address.to_owned(), port))
+}
+
+/// Builder of encrypted RTP packets for a synchronization source.
+pub(crate) struct RtpSender {
+    cipher: XSalsa20Poly1305,
+    sequence: u16,
+    ssrc: u32,
+    timestamp: u32,
+}
+
+impl RtpSender {
+    pub(crate) fn new(secret_key: &[u8; 32], ssrc: u32) -> Self {
+        Self {
+            cipher: XSalsa20Poly1305::new(Key::from_slice(secret_key)),
+            sequence: 0,
+            ssrc,
+            timestamp: 0,
+        }
+    }
+
+    /// Create the encrypted packet of the next Opus frame.
+    ///
+    /// The nonce is the RTP header padded with zeroes, and the encrypted
+    /// frame is prefixed with its authentication tag.
+    pub(crate) fn packet(&mut self, frame: &[u8]) -> Result<Vec<u8>, AeadError> {
+        let mut header = [0; RTP_HEADER_LEN];
+        header[0] = RTP_VERSION;
+        header[1] = RTP_PAYLOAD_TYPE;
+        header[2..4].copy_from_slice(&self.sequence.to_be_bytes());
+        header[4..8].copy_from_slice(&self.timestamp.to_be_bytes());
+        header[8..12].copy_from_slice(&self.ssrc.to_be_bytes());
+
+        let mut nonce = Nonce::default();
+        nonce[..RTP_HEADER_LEN].copy_from_slice(&header);
+
+        let encrypted = self.cipher.encrypt(&nonce, frame)?;
+
+        self.sequence = self.sequence.wrapping_add(1);
+        self.timestamp = self.timestamp.wrapping_add(FRAME_SAMPLES);
+
+        let mut packet = Vec::with_capacity(RTP_HEADER_LEN + encrypted.len());
+        packet.extend_from_slice(&header);
+        packet.extend_from_slice(&encrypted);
+
+        Ok(packet)
+    }
+}
+
+impl Debug for RtpSender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("RtpSender")
+            .field("sequence", &self.sequence)
+            .field("ssrc", &self.ssrc)
+            .field("timestamp", &self.timestamp)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ip_discovery_request, ip_discovery_response, RtpSender, IP_DISCOVERY_LEN, RTP_HEADER_LEN,
+    };
+    use xsalsa20poly1305::{
+        aead::{Aead, NewAead},
+        Key, Nonce, XSalsa20Poly1305,
+    };
+
+    #[test]
+    fn test_ip_discovery() {
+        let request = ip_discovery_request(0x0102_0304);
+        assert_eq!([0, 1, 0, 70, 1, 2, 3, 4], request[..8]);
+        assert!(request[8..].iter().all(|byte| *byte == 0));
+
+        let mut response = [0; IP_DISCOVERY_LEN];
+        response[1] = 2;
+        response[8..17].copy_from_slice(b"127.0.0.1");
+        response[72..].copy_from_slice(&50_000u16.to_be_bytes());
+
+        assert_eq!(
+            Some(("127.0.0.1".to_owned(), 50_000)),
+            ip_discovery_response(&response)
+        );
+        assert!(ip_discovery_response(&request).is_none());
+        assert!(ip_discovery_response(&response[..10]).is_none());
+    }
+
+    #[test]
+    fn test_rtp_packet() {
+        let key = [7; 32];
+        let mut sender = RtpSender::new(&key, 42);
+
+        sender.packet(&[0; 3]).unwrap();
+        let packet = sender.packet(&[1, 2, 3]).unwrap();
+
+        let header = &packet[..RTP_HEADER_LEN];
+        assert_eq!([0x80, 0x78, 0, 1, 0, 0, 3, 192, 0, 0, 0, 42], header);
+
+        let mut nonce = Nonce::default();
+        nonce[..RTP_HEADER_LEN].copy_from_slice(header);
+        let cipher = XSalsa20Poly1305::new(Key::from_slice(&key));
+
+        assert_eq!(
+            vec![1, 2, 3],
+            cipher.decrypt(&nonce, &packet[RTP_HEADER_LEN..]).unwrap()
+        );
+    }
+}